@@ -0,0 +1,483 @@
+//! Model of accelerator device such as GPU or FPGA.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use dslab_core::cast;
+use dslab_core::component::Id;
+use dslab_core::context::SimulationContext;
+use dslab_core::event::{Event, EventId};
+use dslab_core::handler::EventHandler;
+
+use dslab_models::throughput_sharing::{FairThroughputSharingModel, ThroughputSharingModel};
+
+// STRUCTS -------------------------------------------------------------------------------------------------------------
+
+/// Partition of accelerator device with dedicated compute and memory resources (similar to MIG in NVIDIA GPUs).
+#[derive(Clone, Debug, Serialize)]
+pub struct Partition {
+    /// Partition name.
+    pub name: String,
+    /// Kernel execution speed in flop/s.
+    pub speed: f64,
+    /// Device memory size.
+    pub memory: u64,
+}
+
+impl Partition {
+    /// Creates a new partition.
+    pub fn new(name: &str, speed: f64, memory: u64) -> Self {
+        Self {
+            name: name.to_string(),
+            speed,
+            memory,
+        }
+    }
+}
+
+/// Reason for kernel failure.
+#[derive(Clone, Debug, Serialize)]
+pub enum FailReason {
+    /// None of the suitable partitions has enough memory to run the kernel.
+    NotEnoughMemory {
+        /// Requested amount of device memory.
+        requested_memory: u64,
+        /// Maximum memory size among the suitable partitions.
+        max_memory: u64,
+    },
+    /// The requested partition doesn't exist.
+    UnknownPartition {
+        /// Index of the requested partition.
+        partition: usize,
+    },
+}
+
+/// Direction of data transfer over the host-device link.
+#[derive(Clone, Copy, Debug, Serialize)]
+enum Direction {
+    ToDevice,
+    ToHost,
+}
+
+struct KernelInfo {
+    flops: f64,
+    memory: u64,
+    input_size: f64,
+    output_size: f64,
+    requested_partition: Option<usize>,
+    partition: usize,
+    requester: Id,
+}
+
+struct PartitionState {
+    partition: Partition,
+    memory_available: u64,
+    // number of kernels which hold the partition resources, including the ones transferring the data
+    kernel_count: usize,
+    throughput_model: FairThroughputSharingModel<u64>,
+    next_event: EventId,
+}
+
+struct Link {
+    throughput_model: FairThroughputSharingModel<u64>,
+    next_event: EventId,
+}
+
+// EVENTS --------------------------------------------------------------------------------------------------------------
+
+/// Request to run a kernel.
+#[derive(Clone, Serialize)]
+pub struct KernelRequest {
+    /// Kernel size.
+    pub flops: f64,
+    /// Device memory needed for a kernel.
+    pub memory: u64,
+    /// Size of input data transferred from host to device before the kernel execution.
+    pub input_size: f64,
+    /// Size of output data transferred from device to host after the kernel execution.
+    pub output_size: f64,
+    /// Requested partition, or `None` to use any suitable partition.
+    pub partition: Option<usize>,
+    /// Id of simulation component to inform about the kernel progress.
+    pub requester: Id,
+}
+
+/// Kernel has acquired the device resources, the transfer of its input data is started.
+#[derive(Clone, Serialize)]
+pub struct KernelStarted {
+    /// Id of the kernel.
+    pub id: u64,
+    /// Partition used by the kernel.
+    pub partition: usize,
+}
+
+/// Kernel is finished and its output data is transferred to host.
+#[derive(Clone, Serialize)]
+pub struct KernelFinished {
+    /// Id of the kernel.
+    pub id: u64,
+}
+
+/// Kernel is failed.
+#[derive(Clone, Serialize)]
+pub struct KernelFailed {
+    /// Id of the kernel.
+    pub id: u64,
+    /// Reason for failure.
+    pub reason: FailReason,
+}
+
+#[derive(Clone, Serialize)]
+struct InternalTransferStarted {
+    kernel: u64,
+    direction: Direction,
+}
+
+#[derive(Clone, Serialize)]
+struct InternalTransferFinished {
+    direction: Direction,
+}
+
+#[derive(Clone, Serialize)]
+struct InternalKernelFinished {
+    partition: usize,
+}
+
+// MODEL ---------------------------------------------------------------------------------------------------------------
+
+/// Models accelerator device (e.g. GPU or FPGA) attached to a host via PCIe-like link.
+///
+/// The kernel execution consists of three stages: the transfer of input data from host to device, the kernel
+/// execution and the transfer of output data from device to host. The device memory needed for a kernel is held
+/// during all these stages. Each transfer takes the link latency plus the time needed to transfer the data,
+/// while the link bandwidth is fairly shared between the concurrent transfers in the same direction.
+///
+/// The device can be split into [partitions](Partition) with dedicated compute and memory resources.
+/// By default, the whole device forms a single partition. Each partition runs one kernel at a time, unless
+/// the time-slicing is enabled, in which case the kernels running on a partition fairly share its speed
+/// and are limited only by the partition memory.
+///
+/// The kernels which can't be started due to lack of resources are queued. Each time the resources are released,
+/// the queued kernels are started in the order of their submission if there is a suitable partition. Among several
+/// suitable partitions the one with the smallest memory is used.
+pub struct Accelerator {
+    speed: f64,
+    memory: u64,
+    bandwidth: f64,
+    latency: f64,
+    time_slicing: bool,
+    partitions: Vec<PartitionState>,
+    to_device: Link,
+    to_host: Link,
+    kernels: HashMap<u64, KernelInfo>,
+    queue: Vec<u64>,
+    ctx: SimulationContext,
+}
+
+impl Accelerator {
+    /// Creates a new accelerator with the given speed, device memory size, and link bandwidth and latency.
+    pub fn new(speed: f64, memory: u64, bandwidth: f64, latency: f64, ctx: SimulationContext) -> Self {
+        Self {
+            speed,
+            memory,
+            bandwidth,
+            latency,
+            time_slicing: false,
+            partitions: vec![Self::make_partition(Partition::new("default", speed, memory))],
+            to_device: Self::make_link(bandwidth),
+            to_host: Self::make_link(bandwidth),
+            kernels: HashMap::new(),
+            queue: Vec::new(),
+            ctx,
+        }
+    }
+
+    /// Splits the device into the given partitions.
+    ///
+    /// Panics if the total speed or memory of the partitions exceeds the device speed or memory.
+    pub fn with_partitions(mut self, partitions: Vec<Partition>) -> Self {
+        assert!(!partitions.is_empty(), "At least one partition must be specified");
+        assert!(
+            partitions.iter().map(|p| p.speed).sum::<f64>() <= self.speed * (1. + 1e-9),
+            "Total speed of partitions exceeds the device speed"
+        );
+        assert!(
+            partitions.iter().map(|p| p.memory).sum::<u64>() <= self.memory,
+            "Total memory of partitions exceeds the device memory"
+        );
+        self.partitions = partitions.into_iter().map(Self::make_partition).collect();
+        self
+    }
+
+    /// Enables the time-slicing, which allows to run multiple kernels on a partition concurrently.
+    pub fn with_time_slicing(mut self) -> Self {
+        self.time_slicing = true;
+        self
+    }
+
+    fn make_partition(partition: Partition) -> PartitionState {
+        PartitionState {
+            memory_available: partition.memory,
+            kernel_count: 0,
+            throughput_model: FairThroughputSharingModel::with_fixed_throughput(partition.speed),
+            next_event: 0,
+            partition,
+        }
+    }
+
+    fn make_link(bandwidth: f64) -> Link {
+        Link {
+            throughput_model: FairThroughputSharingModel::with_fixed_throughput(bandwidth),
+            next_event: 0,
+        }
+    }
+
+    /// Returns the device speed.
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Returns the total amount of device memory.
+    pub fn memory_total(&self) -> u64 {
+        self.memory
+    }
+
+    /// Returns the link bandwidth.
+    pub fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    /// Returns the link latency.
+    pub fn latency(&self) -> f64 {
+        self.latency
+    }
+
+    /// Returns whether the time-slicing is enabled.
+    pub fn time_slicing(&self) -> bool {
+        self.time_slicing
+    }
+
+    /// Returns the device partitions.
+    pub fn partitions(&self) -> Vec<&Partition> {
+        self.partitions.iter().map(|p| &p.partition).collect()
+    }
+
+    /// Returns the amount of available memory in the given partition.
+    pub fn memory_available(&self, partition: usize) -> u64 {
+        self.partitions[partition].memory_available
+    }
+
+    /// Returns the number of kernels which hold the device resources.
+    pub fn running_count(&self) -> usize {
+        self.partitions.iter().map(|p| p.kernel_count).sum()
+    }
+
+    /// Returns the number of queued kernels.
+    pub fn queue_length(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns the kernel completion time on the given partition without queueing and contention.
+    pub fn estimate_time(&self, flops: f64, input_size: f64, output_size: f64, partition: usize) -> f64 {
+        let transfer_time = |size: f64| {
+            if size > 0. {
+                self.latency + size / self.bandwidth
+            } else {
+                0.
+            }
+        };
+        transfer_time(input_size) + flops / self.partitions[partition].partition.speed + transfer_time(output_size)
+    }
+
+    /// Starts kernel with given parameters on any suitable partition and returns kernel id.
+    pub fn run(&mut self, flops: f64, memory: u64, input_size: f64, output_size: f64, requester: Id) -> u64 {
+        self.submit(KernelRequest {
+            flops,
+            memory,
+            input_size,
+            output_size,
+            partition: None,
+            requester,
+        })
+    }
+
+    /// Starts kernel with given request and returns kernel id.
+    ///
+    /// In contrast to [`run`](Self::run), allows to specify the partition.
+    pub fn submit(&mut self, request: KernelRequest) -> u64 {
+        self.ctx.emit_self_now(request)
+    }
+
+    /// Selects the partition with the smallest memory among the ones which can run the kernel now.
+    fn find_partition(&self, kernel: &KernelInfo) -> Option<usize> {
+        (0..self.partitions.len())
+            .filter(|&p| kernel.requested_partition.is_none_or(|requested| requested == p))
+            .filter(|&p| {
+                let state = &self.partitions[p];
+                state.memory_available >= kernel.memory && (self.time_slicing || state.kernel_count == 0)
+            })
+            .min_by_key(|&p| self.partitions[p].partition.memory)
+    }
+
+    /// Starts the queued kernels which can be run now.
+    fn process_queue(&mut self) {
+        let mut i = 0;
+        while i < self.queue.len() {
+            let id = self.queue[i];
+            if let Some(partition) = self.find_partition(&self.kernels[&id]) {
+                self.queue.remove(i);
+                self.start_kernel(id, partition);
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    fn start_kernel(&mut self, id: u64, partition: usize) {
+        let kernel = self.kernels.get_mut(&id).unwrap();
+        kernel.partition = partition;
+        let state = &mut self.partitions[partition];
+        state.memory_available -= kernel.memory;
+        state.kernel_count += 1;
+        self.ctx.emit_now(KernelStarted { id, partition }, kernel.requester);
+        let input_size = kernel.input_size;
+        self.start_transfer(id, Direction::ToDevice, input_size);
+    }
+
+    fn start_transfer(&mut self, id: u64, direction: Direction, size: f64) {
+        if size > 0. {
+            self.ctx
+                .emit_self(InternalTransferStarted { kernel: id, direction }, self.latency);
+        } else {
+            self.on_transfer_finished(id, direction);
+        }
+    }
+
+    fn on_transfer_finished(&mut self, id: u64, direction: Direction) {
+        match direction {
+            Direction::ToDevice => self.start_execution(id),
+            Direction::ToHost => self.finish_kernel(id),
+        }
+    }
+
+    fn start_execution(&mut self, id: u64) {
+        let kernel = &self.kernels[&id];
+        if kernel.flops > 0. {
+            let state = &mut self.partitions[kernel.partition];
+            self.ctx.cancel_event(state.next_event);
+            state.throughput_model.insert(id, kernel.flops, &mut self.ctx);
+            Self::schedule_kernel_completion(kernel.partition, state, &mut self.ctx);
+        } else {
+            let output_size = kernel.output_size;
+            self.start_transfer(id, Direction::ToHost, output_size);
+        }
+    }
+
+    fn finish_kernel(&mut self, id: u64) {
+        let kernel = self.kernels.remove(&id).unwrap();
+        let state = &mut self.partitions[kernel.partition];
+        state.memory_available += kernel.memory;
+        state.kernel_count -= 1;
+        self.ctx.emit_now(KernelFinished { id }, kernel.requester);
+        self.process_queue();
+    }
+
+    fn schedule_kernel_completion(partition: usize, state: &mut PartitionState, ctx: &mut SimulationContext) {
+        if let Some((time, _)) = state.throughput_model.peek() {
+            state.next_event = ctx.emit_self(InternalKernelFinished { partition }, time - ctx.time());
+        }
+    }
+
+    fn schedule_transfer_completion(direction: Direction, link: &mut Link, ctx: &mut SimulationContext) {
+        if let Some((time, _)) = link.throughput_model.peek() {
+            link.next_event = ctx.emit_self(InternalTransferFinished { direction }, time - ctx.time());
+        }
+    }
+}
+
+impl EventHandler for Accelerator {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            KernelRequest {
+                flops,
+                memory,
+                input_size,
+                output_size,
+                partition,
+                requester,
+            } => {
+                let fail_reason = match partition {
+                    Some(partition) if partition >= self.partitions.len() => {
+                        Some(FailReason::UnknownPartition { partition })
+                    }
+                    _ => {
+                        let max_memory = self
+                            .partitions
+                            .iter()
+                            .enumerate()
+                            .filter(|(p, _)| partition.is_none_or(|requested| requested == *p))
+                            .map(|(_, state)| state.partition.memory)
+                            .max()
+                            .unwrap();
+                        if max_memory < memory {
+                            Some(FailReason::NotEnoughMemory {
+                                requested_memory: memory,
+                                max_memory,
+                            })
+                        } else {
+                            None
+                        }
+                    }
+                };
+                if let Some(reason) = fail_reason {
+                    self.ctx.emit_now(KernelFailed { id: event.id, reason }, requester);
+                } else {
+                    self.kernels.insert(
+                        event.id,
+                        KernelInfo {
+                            flops,
+                            memory,
+                            input_size,
+                            output_size,
+                            requested_partition: partition,
+                            partition: 0,
+                            requester,
+                        },
+                    );
+                    self.queue.push(event.id);
+                    self.process_queue();
+                }
+            }
+            InternalTransferStarted { kernel, direction } => {
+                let size = match direction {
+                    Direction::ToDevice => self.kernels[&kernel].input_size,
+                    Direction::ToHost => self.kernels[&kernel].output_size,
+                };
+                let link = match direction {
+                    Direction::ToDevice => &mut self.to_device,
+                    Direction::ToHost => &mut self.to_host,
+                };
+                self.ctx.cancel_event(link.next_event);
+                link.throughput_model.insert(kernel, size, &mut self.ctx);
+                Self::schedule_transfer_completion(direction, link, &mut self.ctx);
+            }
+            InternalTransferFinished { direction } => {
+                let link = match direction {
+                    Direction::ToDevice => &mut self.to_device,
+                    Direction::ToHost => &mut self.to_host,
+                };
+                let (_, kernel) = link.throughput_model.pop().unwrap();
+                Self::schedule_transfer_completion(direction, link, &mut self.ctx);
+                self.on_transfer_finished(kernel, direction);
+            }
+            InternalKernelFinished { partition } => {
+                let state = &mut self.partitions[partition];
+                let (_, kernel) = state.throughput_model.pop().unwrap();
+                Self::schedule_kernel_completion(partition, state, &mut self.ctx);
+                let output_size = self.kernels[&kernel].output_size;
+                self.start_transfer(kernel, Direction::ToHost, output_size);
+            }
+        })
+    }
+}
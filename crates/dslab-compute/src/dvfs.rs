@@ -0,0 +1,131 @@
+//! Dynamic voltage and frequency scaling (DVFS) of computing resources.
+
+use dslab_models::power::host::{HostPowerModel, HostState};
+
+/// Tolerance used when looking up the P-state by frequency.
+const FREQUENCY_EPSILON: f64 = 1e-9;
+
+/// Performance state (P-state) of computing resource.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PState {
+    /// CPU frequency, e.g. in GHz.
+    pub frequency: f64,
+    /// Core speed relative to the nominal resource speed.
+    pub speed: f64,
+}
+
+impl PState {
+    /// Creates P-state with the given frequency and relative speed.
+    pub fn new(frequency: f64, speed: f64) -> Self {
+        Self { frequency, speed }
+    }
+}
+
+/// Manages the P-state of computing resource and tracks its energy consumption.
+///
+/// The resource speed is multiplied by the speed of the current P-state.
+/// The resource power is computed by the host power model from the current resource utilization and the relative
+/// frequency of the current P-state, which is 0 for the lowest frequency and 1 for the highest frequency
+/// (see [`DvfsAwareCpuPowerModel`](dslab_models::power::cpu_models::dvfs::DvfsAwareCpuPowerModel)).
+/// The energy consumption is computed by integrating the power over time.
+pub struct DvfsControl {
+    p_states: Vec<PState>,
+    current: usize,
+    power_model: HostPowerModel,
+    utilization: f64,
+    energy_consumed: f64,
+    prev_time: f64,
+}
+
+impl DvfsControl {
+    /// Creates DVFS control with the given P-states and power model.
+    ///
+    /// Initially the resource is in the P-state with the highest frequency.
+    pub fn new(mut p_states: Vec<PState>, power_model: HostPowerModel) -> Self {
+        assert!(!p_states.is_empty(), "At least one P-state must be specified");
+        for p_state in p_states.iter() {
+            assert!(
+                p_state.frequency > 0. && p_state.speed > 0.,
+                "P-state frequency and speed must be positive"
+            );
+        }
+        p_states.sort_by(|a, b| a.frequency.total_cmp(&b.frequency));
+        let current = p_states.len() - 1;
+        Self {
+            p_states,
+            current,
+            power_model,
+            utilization: 0.,
+            energy_consumed: 0.,
+            prev_time: 0.,
+        }
+    }
+
+    /// Returns the P-states sorted by frequency.
+    pub fn p_states(&self) -> &[PState] {
+        &self.p_states
+    }
+
+    /// Returns the current P-state.
+    pub fn p_state(&self) -> &PState {
+        &self.p_states[self.current]
+    }
+
+    /// Returns the index of the current P-state.
+    pub fn p_state_index(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the index of the P-state with the given frequency.
+    pub fn find_p_state(&self, frequency: f64) -> Option<usize> {
+        self.p_states
+            .iter()
+            .position(|p| (p.frequency - frequency).abs() < FREQUENCY_EPSILON)
+    }
+
+    /// Returns the frequency of the current P-state relative to the range of P-state frequencies.
+    pub fn relative_frequency(&self) -> f64 {
+        let min_frequency = self.p_states[0].frequency;
+        let max_frequency = self.p_states[self.p_states.len() - 1].frequency;
+        if max_frequency > min_frequency {
+            (self.p_state().frequency - min_frequency) / (max_frequency - min_frequency)
+        } else {
+            1.
+        }
+    }
+
+    /// Returns the current resource power consumption.
+    pub fn power(&self) -> f64 {
+        self.power_model
+            .get_power(HostState::cpu_util_freq(self.utilization, self.relative_frequency()))
+    }
+
+    /// Returns the total energy consumption up to the given time.
+    pub fn energy_consumed(&self, time: f64) -> f64 {
+        self.energy_consumed + (time - self.prev_time) * self.power()
+    }
+
+    fn advance(&mut self, time: f64) {
+        self.energy_consumed = self.energy_consumed(time);
+        self.prev_time = time;
+    }
+
+    /// Starts tracking the energy consumption at the given time.
+    pub(crate) fn start(&mut self, time: f64) {
+        self.energy_consumed = 0.;
+        self.prev_time = time;
+    }
+
+    /// Updates the resource utilization at the given time.
+    pub(crate) fn set_utilization(&mut self, utilization: f64, time: f64) {
+        self.advance(time);
+        self.utilization = utilization;
+    }
+
+    /// Switches to the P-state with the given index at the given time.
+    pub(crate) fn set_p_state(&mut self, index: usize, time: f64) {
+        assert!(index < self.p_states.len(), "Unknown P-state {}", index);
+        self.advance(time);
+        self.current = index;
+    }
+}
@@ -1,5 +1,9 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../readme.md")]
 
+pub mod accelerator;
+pub mod dvfs;
 pub mod multicore;
+pub mod queue;
 pub mod singlecore;
+pub mod thermal;
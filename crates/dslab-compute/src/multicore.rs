@@ -7,8 +7,14 @@ use serde::Serialize;
 use dslab_core::cast;
 use dslab_core::component::Id;
 use dslab_core::context::SimulationContext;
-use dslab_core::event::Event;
+use dslab_core::event::{Event, EventId};
 use dslab_core::handler::EventHandler;
+pub use dslab_models::interference::model::{InterferenceModel, MemoryProfile};
+use dslab_models::power::cpu::CpuPowerModel;
+
+use crate::dvfs::DvfsControl;
+use crate::queue::{QueuePolicy, QueuedComputation, ResourceState, RunningComputationInfo};
+use crate::thermal::{ThermalCheck, ThermalControl};
 
 // STRUCTS -------------------------------------------------------------------------------------------------------------
 
@@ -28,6 +34,84 @@ impl Allocation {
     }
 }
 
+/// Group of identical cores within a resource.
+///
+/// A resource with multiple core groups models heterogeneous cores, e.g. performance and efficiency cores.
+#[derive(Clone)]
+pub struct CoreGroup {
+    /// Name of the core type.
+    pub name: String,
+    /// Number of cores.
+    pub cores: u32,
+    /// Core speed in flop/s.
+    pub speed: f64,
+    /// Optional power model of the group, which is provided with the group utilization.
+    /// It is not used when the resource power is tracked by [`DvfsControl`].
+    pub power_model: Option<Box<dyn CpuPowerModel>>,
+}
+
+impl CoreGroup {
+    /// Creates a new core group without power model.
+    pub fn new(name: &str, cores: u32, speed: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            cores,
+            speed,
+            power_model: None,
+        }
+    }
+
+    /// Sets the power model of the group.
+    pub fn with_power_model(mut self, power_model: Box<dyn CpuPowerModel>) -> Self {
+        self.power_model = Some(power_model);
+        self
+    }
+}
+
+/// Policy used to select the core group for a computation which doesn't request a specific core group.
+///
+/// Each computation runs on the cores from a single group. Only the groups with enough available cores
+/// are considered, ties are resolved in favor of the group with the lowest index.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub enum CoreGroupPolicy {
+    /// Selects the group providing the shortest computation execution time.
+    #[default]
+    Fastest,
+    /// Selects the group with the slowest cores, keeping the fast cores for other computations.
+    Slowest,
+    /// Selects the group with the largest number of available cores.
+    MostAvailable,
+}
+
+impl CoreGroupPolicy {
+    /// Returns the core group and the number of cores for the computation given the current core speed
+    /// and the number of available cores in each group, or `None` if no group has enough available cores.
+    ///
+    /// If the computation requests a specific core group, only this group is considered.
+    pub fn select(
+        &self,
+        speeds: &[f64],
+        cores_available: &[u32],
+        computation: &QueuedComputation,
+    ) -> Option<(usize, u32)> {
+        let candidates = (0..speeds.len())
+            .filter(|&group| computation.core_group.is_none_or(|requested| requested == group))
+            .filter(|&group| cores_available[group] >= computation.min_cores)
+            .map(|group| (group, cores_available[group].min(computation.max_cores)));
+        match self {
+            CoreGroupPolicy::Fastest => candidates.min_by(|a, b| {
+                computation
+                    .estimated_time(a.1, speeds[a.0])
+                    .total_cmp(&computation.estimated_time(b.1, speeds[b.0]))
+            }),
+            CoreGroupPolicy::Slowest => candidates.min_by(|a, b| speeds[a.0].total_cmp(&speeds[b.0])),
+            CoreGroupPolicy::MostAvailable => {
+                candidates.min_by_key(|&(group, _)| std::cmp::Reverse(cores_available[group]))
+            }
+        }
+    }
+}
+
 /// Function from `[1, max_cores]` to `[1, +inf]` describing the dependency
 /// between the number of cores used for running a task and achieved parallel speedup.
 #[derive(Clone, Copy, Debug, Serialize)]
@@ -73,22 +157,35 @@ pub enum FailReason {
         /// Requested amount of memory.
         requested_memory: u64,
     },
+    /// Computation can't be resumed since it is not suspended.
+    NotSuspended,
 }
 
 #[derive(Debug)]
 struct RunningComputation {
+    core_group: usize,
+    requested_core_group: Option<usize>,
     cores: u32,
     memory: u64,
+    min_cores: u32,
+    max_cores: u32,
+    cores_dependency: CoresDependency,
     requester: Id,
+    memory_profile: MemoryProfile,
+    // speed factor due to interference with other running computations
+    interference_factor: f64,
+    speedup: f64,
+    remaining_flops: f64,
+    last_update: f64,
+    finish_event: EventId,
 }
 
 impl RunningComputation {
-    fn new(cores: u32, memory: u64, requester: Id) -> Self {
-        RunningComputation {
-            cores,
-            memory,
-            requester,
-        }
+    /// Updates the remaining flops according to the progress made since the last update.
+    fn update_progress(&mut self, speed: f64, time: f64) {
+        self.remaining_flops -= (time - self.last_update) * speed * self.speedup;
+        self.remaining_flops = self.remaining_flops.max(0.);
+        self.last_update = time;
     }
 }
 
@@ -107,6 +204,12 @@ pub struct CompRequest {
     pub max_cores: u32,
     /// Defines the dependence of parallel speedup on the number of used cores.
     pub cores_dependency: CoresDependency,
+    /// Computation priority, used only by the local queue policies.
+    pub priority: i64,
+    /// Requested core group, or `None` to let the resource select the core group using its [`CoreGroupPolicy`].
+    pub core_group: Option<usize>,
+    /// Memory intensity of the computation, used only by the interference model.
+    pub memory_profile: MemoryProfile,
     /// Id of simulation component to inform about the computation progress.
     pub requester: Id,
 }
@@ -116,8 +219,10 @@ pub struct CompRequest {
 pub struct CompStarted {
     /// Id of the computation.
     pub id: u64,
+    /// Core group used by the computation.
+    pub core_group: usize,
     /// Number of cores allocated to the computation.
-    /// Equals to the minimum between the number of available cores
+    /// Equals to the minimum between the number of available cores in the core group
     /// and the maximum number of cores for the computation.
     pub cores: u32,
 }
@@ -138,6 +243,67 @@ pub struct CompFailed {
     pub reason: FailReason,
 }
 
+/// Request to cancel a computation.
+#[derive(Clone, Serialize)]
+pub struct CancelRequest {
+    /// Id of the computation.
+    pub id: u64,
+}
+
+/// Computation is canceled.
+#[derive(Clone, Serialize)]
+pub struct CompCanceled {
+    /// Id of the computation.
+    pub id: u64,
+    /// Amount of computations which were not performed.
+    pub remaining_flops: f64,
+}
+
+/// Request to suspend a computation.
+#[derive(Clone, Serialize)]
+pub struct SuspendRequest {
+    /// Id of the computation.
+    pub id: u64,
+}
+
+/// Computation is suspended.
+#[derive(Clone, Serialize)]
+pub struct CompSuspended {
+    /// Id of the computation.
+    pub id: u64,
+    /// Amount of computations remaining to be performed.
+    pub remaining_flops: f64,
+}
+
+/// Request to resume a suspended computation.
+#[derive(Clone, Serialize)]
+pub struct ResumeRequest {
+    /// Id of the computation.
+    pub id: u64,
+}
+
+/// Computation is resumed.
+#[derive(Clone, Serialize)]
+pub struct CompResumed {
+    /// Id of the computation.
+    pub id: u64,
+    /// Core group used by the computation.
+    pub core_group: usize,
+    /// Number of cores allocated to the computation.
+    /// Equals to the minimum between the number of available cores in the core group
+    /// and the maximum number of cores for the computation.
+    pub cores: u32,
+}
+
+/// Computation can't be resumed, it stays in its current state.
+#[derive(Clone, Serialize)]
+pub struct CompResumeFailed {
+    /// Id of the computation.
+    pub id: u64,
+    /// Reason for failure.
+    pub reason: FailReason,
+}
+
 /// Request to allocate resources.
 #[derive(Clone, Serialize)]
 pub struct AllocationRequest {
@@ -197,37 +363,220 @@ pub struct DeallocationFailed {
 /// Each core can only be used by one computation. The cores allocation for each computation is computed
 /// upon the request arrival and is not changed afterwards.
 /// This model also supports the manual allocation and release of cores and memory.
+///
+/// A running computation can be canceled, which releases its cores and memory, or suspended, which releases
+/// its cores but keeps its memory. Upon resumption the cores allocation of suspended computation is computed
+/// again, so it can differ from the original one.
+///
+/// By default, the computation request fails if there are not enough resources to start it. When the local queue
+/// is enabled, such requests are queued and started later according to the [`QueuePolicy`].
+///
+/// The resource cores can be heterogeneous, in which case they are organized into [core groups](CoreGroup)
+/// with their own speed and power model. Each computation runs on the cores from a single group, which is either
+/// specified in the request or selected by the [`CoreGroupPolicy`]. The manually allocated cores are taken from
+/// the groups in their order.
+///
+/// The core speed can be adjusted according to the resource temperature by using [`ThermalControl`]
+/// and by switching the resource P-state with [`DvfsControl`], which also tracks the energy consumption
+/// of the whole resource instead of the power models of core groups.
+/// In this case the remaining time of running computations is updated upon each speed change.
+///
+/// The computations running on the resource can slow down each other due to contention for shared memory bandwidth
+/// and cache. When the [`InterferenceModel`] is set, each computation declares its [`MemoryProfile`], and the speed
+/// of running computations is recomputed from their profiles each time a computation starts or stops running.
 pub struct Compute {
     speed: f64,
+    speed_factor: f64,
+    core_groups: Vec<CoreGroup>,
+    core_group_policy: CoreGroupPolicy,
+    group_cores_available: Vec<u32>,
+    core_groups_power: f64,
+    core_groups_energy: f64,
+    core_groups_prev_time: f64,
     cores_total: u32,
     cores_available: u32,
     memory_total: u64,
     memory_available: u64,
     computations: HashMap<u64, RunningComputation>,
+    suspended_computations: HashMap<u64, RunningComputation>,
     allocations: HashMap<Id, Allocation>,
+    allocated_group_cores: HashMap<Id, Vec<u32>>,
+    queue_policy: Option<Box<dyn QueuePolicy>>,
+    queue: Vec<QueuedComputation>,
+    dequeued_count: u64,
+    total_wait_time: f64,
+    max_wait_time: f64,
+    thermal_control: Option<ThermalControl>,
+    dvfs_control: Option<DvfsControl>,
+    interference_model: Option<Box<dyn InterferenceModel>>,
     ctx: SimulationContext,
 }
 
 impl Compute {
     /// Creates a new computing resource.
     pub fn new(speed: f64, cores: u32, memory: u64, ctx: SimulationContext) -> Self {
-        Self {
+        Self::from_core_groups(vec![CoreGroup::new("default", cores, speed)], memory, ctx)
+    }
+
+    /// Creates a new computing resource with heterogeneous cores organized into the given core groups.
+    pub fn from_core_groups(core_groups: Vec<CoreGroup>, memory: u64, ctx: SimulationContext) -> Self {
+        assert!(!core_groups.is_empty(), "At least one core group must be specified");
+        let speed = core_groups.iter().map(|g| g.speed).fold(0., f64::max);
+        let cores: u32 = core_groups.iter().map(|g| g.cores).sum();
+        let group_cores_available = core_groups.iter().map(|g| g.cores).collect();
+        let mut compute = Self {
             speed,
+            speed_factor: 1.,
+            core_groups,
+            core_group_policy: CoreGroupPolicy::default(),
+            group_cores_available,
+            core_groups_power: 0.,
+            core_groups_energy: 0.,
+            core_groups_prev_time: ctx.time(),
             cores_total: cores,
             cores_available: cores,
             memory_total: memory,
             memory_available: memory,
             computations: HashMap::new(),
+            suspended_computations: HashMap::new(),
             allocations: HashMap::new(),
+            allocated_group_cores: HashMap::new(),
+            queue_policy: None,
+            queue: Vec::new(),
+            dequeued_count: 0,
+            total_wait_time: 0.,
+            max_wait_time: 0.,
+            thermal_control: None,
+            dvfs_control: None,
+            interference_model: None,
             ctx,
+        };
+        compute.update_core_groups_power();
+        compute
+    }
+
+    /// Sets the policy used to select the core group for computations.
+    pub fn with_core_group_policy(mut self, policy: CoreGroupPolicy) -> Self {
+        self.core_group_policy = policy;
+        self
+    }
+
+    /// Enables the thermal control of the resource.
+    pub fn with_thermal_control(mut self, thermal_control: ThermalControl) -> Self {
+        self.thermal_control = Some(thermal_control);
+        self.update_thermal_frequency();
+        self.update_utilization();
+        self
+    }
+
+    /// Enables the DVFS control of the resource.
+    pub fn with_dvfs_control(mut self, mut dvfs_control: DvfsControl) -> Self {
+        dvfs_control.start(self.ctx.time());
+        self.dvfs_control = Some(dvfs_control);
+        self.update_thermal_frequency();
+        self.update_speed_factor();
+        self.update_utilization();
+        self
+    }
+
+    /// Enables the interference between running computations using the given model.
+    pub fn with_interference_model(mut self, model: Box<dyn InterferenceModel>) -> Self {
+        self.interference_model = Some(model);
+        self
+    }
+
+    /// Enables the local queue with the given policy.
+    ///
+    /// The requests which can't be started due to lack of resources are queued instead of being failed,
+    /// except for the requests exceeding the total resource capacity.
+    pub fn with_queue(mut self, policy: Box<dyn QueuePolicy>) -> Self {
+        self.queue_policy = Some(policy);
+        self
+    }
+
+    /// Returns the thermal control of the resource, if enabled.
+    pub fn thermal_control(&self) -> Option<&ThermalControl> {
+        self.thermal_control.as_ref()
+    }
+
+    /// Returns the DVFS control of the resource, if enabled.
+    pub fn dvfs_control(&self) -> Option<&DvfsControl> {
+        self.dvfs_control.as_ref()
+    }
+
+    /// Switches the resource to the P-state with the given frequency.
+    ///
+    /// The remaining time of running computations is rescaled according to the P-state speed.
+    /// Panics if DVFS control is not enabled or there is no P-state with such frequency.
+    pub fn set_frequency(&mut self, frequency: f64) {
+        let dvfs_control = self.dvfs_control.as_ref().expect("DVFS control is not enabled");
+        let index = dvfs_control
+            .find_p_state(frequency)
+            .unwrap_or_else(|| panic!("There is no P-state with frequency {}", frequency));
+        self.set_p_state(index);
+    }
+
+    /// Switches the resource to the P-state with the given index (P-states are sorted by frequency).
+    ///
+    /// The remaining time of running computations is rescaled according to the P-state speed.
+    /// If thermal control is enabled, the resource power passed to the thermal model is updated as well.
+    /// Panics if DVFS control is not enabled.
+    pub fn set_p_state(&mut self, index: usize) {
+        let time = self.ctx.time();
+        let dvfs_control = self.dvfs_control.as_mut().expect("DVFS control is not enabled");
+        dvfs_control.set_p_state(index, time);
+        self.update_thermal_frequency();
+        self.update_speed_factor();
+    }
+
+    /// Returns the total resource energy consumption.
+    ///
+    /// If DVFS control is enabled, the energy is tracked by it for the whole resource. Otherwise, the energy is
+    /// computed from the power models of core groups. Returns zero if neither is used.
+    pub fn energy_consumed(&self) -> f64 {
+        match self.dvfs_control.as_ref() {
+            Some(dvfs_control) => dvfs_control.energy_consumed(self.ctx.time()),
+            None => self.core_groups_energy + (self.ctx.time() - self.core_groups_prev_time) * self.core_groups_power,
         }
     }
 
-    /// Returns the core speed.
+    /// Returns the core speed (the speed of the fastest core group if the cores are heterogeneous).
     pub fn speed(&self) -> f64 {
         self.speed
     }
 
+    /// Returns the current core speed, which can differ from the nominal speed due to thermal throttling
+    /// or the current P-state.
+    pub fn current_speed(&self) -> f64 {
+        self.speed * self.speed_factor
+    }
+
+    /// Returns the current speed factor of running computation due to interference with other computations,
+    /// or `None` if there is no running computation with such id.
+    pub fn interference_factor(&self, id: u64) -> Option<f64> {
+        self.computations.get(&id).map(|c| c.interference_factor)
+    }
+
+    /// Returns the core groups of the resource.
+    pub fn core_groups(&self) -> &[CoreGroup] {
+        &self.core_groups
+    }
+
+    /// Returns the index of the core group with the given name.
+    pub fn find_core_group(&self, name: &str) -> Option<usize> {
+        self.core_groups.iter().position(|g| g.name == name)
+    }
+
+    /// Returns the number of available cores in the given core group.
+    pub fn core_group_cores_available(&self, group: usize) -> u32 {
+        self.group_cores_available[group]
+    }
+
+    /// Returns the current core speed in the given core group.
+    pub fn core_group_speed(&self, group: usize) -> f64 {
+        self.core_groups[group].speed * self.speed_factor
+    }
+
     /// Returns the total number of cores.
     pub fn cores_total(&self) -> u32 {
         self.cores_total
@@ -264,11 +613,78 @@ impl Compute {
             min_cores,
             max_cores,
             cores_dependency,
+            priority: 0,
+            core_group: None,
+            memory_profile: MemoryProfile::default(),
             requester,
         };
+        self.submit(request)
+    }
+
+    /// Starts computation with given request and returns computation id.
+    ///
+    /// In contrast to [`run`](Self::run), allows to specify the computation priority, core group and memory profile.
+    pub fn submit(&mut self, request: CompRequest) -> u64 {
         self.ctx.emit_self_now(request)
     }
 
+    /// Requests cancellation of computation with given id.
+    ///
+    /// Upon cancellation the computation cores and memory are released and the requester receives [`CompCanceled`].
+    /// Queued computations can be canceled as well.
+    /// The request is ignored if the computation is already completed.
+    pub fn cancel(&mut self, id: u64) {
+        self.ctx.emit_self_now(CancelRequest { id });
+    }
+
+    /// Requests suspension of running computation with given id.
+    ///
+    /// Upon suspension the computation cores are released while its memory is kept,
+    /// and the requester receives [`CompSuspended`].
+    /// The request is ignored if the computation is already completed or suspended.
+    pub fn suspend(&mut self, id: u64) {
+        self.ctx.emit_self_now(SuspendRequest { id });
+    }
+
+    /// Requests resumption of suspended computation with given id.
+    ///
+    /// The requester receives [`CompResumed`] if there are enough available cores, otherwise [`CompResumeFailed`].
+    /// If the computation is running or queued, the requester receives [`CompResumeFailed`] with
+    /// [`FailReason::NotSuspended`]. The request is ignored if the computation is already completed.
+    pub fn resume(&mut self, id: u64) {
+        self.ctx.emit_self_now(ResumeRequest { id });
+    }
+
+    /// Returns the number of suspended computations.
+    pub fn suspended_count(&self) -> usize {
+        self.suspended_computations.len()
+    }
+
+    /// Returns the number of computations in the local queue.
+    pub fn queue_length(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns the computations in the local queue in the order of their submission.
+    pub fn queued_computations(&self) -> &[QueuedComputation] {
+        &self.queue
+    }
+
+    /// Returns the average waiting time in the local queue among the computations started from the queue
+    /// (including the ones started immediately upon submission).
+    pub fn average_wait_time(&self) -> f64 {
+        if self.dequeued_count > 0 {
+            self.total_wait_time / self.dequeued_count as f64
+        } else {
+            0.
+        }
+    }
+
+    /// Returns the maximum waiting time in the local queue among the computations started from the queue.
+    pub fn max_wait_time(&self) -> f64 {
+        self.max_wait_time
+    }
+
     /// Requests resource allocation with given parameters and returns allocation id.
     pub fn allocate(&mut self, cores: u32, memory: u64, requester: Id) -> u64 {
         let request = AllocationRequest {
@@ -286,6 +702,209 @@ impl Compute {
         };
         self.ctx.emit_self_now(request)
     }
+
+    fn update_utilization(&mut self) {
+        let utilization = (self.cores_total - self.cores_available) as f64 / self.cores_total as f64;
+        if let Some(thermal_control) = self.thermal_control.as_mut() {
+            thermal_control.set_utilization(utilization, &mut self.ctx);
+        }
+        if let Some(dvfs_control) = self.dvfs_control.as_mut() {
+            dvfs_control.set_utilization(utilization, self.ctx.time());
+        }
+        self.update_core_groups_power();
+    }
+
+    /// Passes the relative frequency of the current P-state to the thermal control.
+    fn update_thermal_frequency(&mut self) {
+        let frequency = self.dvfs_control.as_ref().map(|c| c.relative_frequency());
+        if let Some(thermal_control) = self.thermal_control.as_mut() {
+            thermal_control.set_frequency(frequency, &mut self.ctx);
+        }
+    }
+
+    /// Accounts the energy consumed by core groups since the last update and recomputes their power.
+    ///
+    /// The power models of core groups are not used when the resource power is tracked by the DVFS control.
+    fn update_core_groups_power(&mut self) {
+        let time = self.ctx.time();
+        self.core_groups_energy += (time - self.core_groups_prev_time) * self.core_groups_power;
+        self.core_groups_prev_time = time;
+        if self.dvfs_control.is_some() {
+            self.core_groups_power = 0.;
+            return;
+        }
+        self.core_groups_power = self
+            .core_groups
+            .iter()
+            .zip(self.group_cores_available.iter())
+            .filter_map(|(group, &available)| {
+                group.power_model.as_ref().map(|model| {
+                    let utilization = (group.cores - available) as f64 / group.cores as f64;
+                    model.get_power(utilization, None, None)
+                })
+            })
+            .sum();
+    }
+
+    fn core_group_speeds(&self) -> Vec<f64> {
+        self.core_groups.iter().map(|g| g.speed * self.speed_factor).collect()
+    }
+
+    fn select_core_group(&self, computation: &QueuedComputation, cores_available: &[u32]) -> Option<(usize, u32)> {
+        self.core_group_policy
+            .select(&self.core_group_speeds(), cores_available, computation)
+    }
+
+    fn start_computation(&mut self, computation: &QueuedComputation, core_group: usize, cores: u32) {
+        self.memory_available -= computation.memory;
+        self.cores_available -= cores;
+        self.group_cores_available[core_group] -= cores;
+        self.ctx.emit_now(
+            CompStarted {
+                id: computation.id,
+                core_group,
+                cores,
+            },
+            computation.requester,
+        );
+
+        let mut running_computation = RunningComputation {
+            core_group,
+            requested_core_group: computation.core_group,
+            cores,
+            memory: computation.memory,
+            min_cores: computation.min_cores,
+            max_cores: computation.max_cores,
+            cores_dependency: computation.cores_dependency,
+            requester: computation.requester,
+            memory_profile: computation.memory_profile,
+            interference_factor: 1.,
+            speedup: computation.cores_dependency.speedup(cores),
+            remaining_flops: computation.flops,
+            last_update: self.ctx.time(),
+            finish_event: 0,
+        };
+        let compute_time = computation.estimated_time(cores, self.core_group_speed(core_group));
+        running_computation.finish_event = self.ctx.emit_self(CompFinished { id: computation.id }, compute_time);
+        self.computations.insert(computation.id, running_computation);
+        self.update_utilization();
+        self.update_interference();
+    }
+
+    /// Releases the cores used by the computation.
+    fn release_cores(&mut self, core_group: usize, cores: u32) {
+        self.cores_available += cores;
+        self.group_cores_available[core_group] += cores;
+    }
+
+    /// Starts the queued computations selected by the queue policy.
+    fn process_queue(&mut self) {
+        if self.queue.is_empty() || self.queue_policy.is_none() {
+            return;
+        }
+        let time = self.ctx.time();
+        let speeds = self.core_group_speeds();
+        let state = ResourceState {
+            time,
+            running: self
+                .computations
+                .values()
+                .map(|c| RunningComputationInfo {
+                    core_group: c.core_group,
+                    cores: c.cores,
+                    memory: c.memory,
+                    finish_time: c.last_update
+                        + c.remaining_flops / (speeds[c.core_group] * c.interference_factor) / c.speedup,
+                })
+                .collect(),
+            speeds,
+            cores_available: self.group_cores_available.clone(),
+            memory_available: self.memory_available,
+            core_group_policy: self.core_group_policy,
+        };
+        let selected = self.queue_policy.as_mut().unwrap().select(&self.queue, &state);
+        for (id, core_group, cores) in selected {
+            let pos = self
+                .queue
+                .iter()
+                .position(|c| c.id == id)
+                .expect("Queue policy selected unknown computation");
+            let computation = self.queue.remove(pos);
+            assert!(
+                cores > 0
+                    && cores >= computation.min_cores
+                    && cores <= computation.max_cores
+                    && core_group < self.core_groups.len()
+                    && computation.core_group.is_none_or(|requested| requested == core_group)
+                    && cores <= self.group_cores_available[core_group]
+                    && computation.memory <= self.memory_available,
+                "Queue policy selected computation which doesn't fit into available resources"
+            );
+            let wait_time = time - computation.submit_time;
+            self.dequeued_count += 1;
+            self.total_wait_time += wait_time;
+            self.max_wait_time = self.max_wait_time.max(wait_time);
+            self.start_computation(&computation, core_group, cores);
+        }
+    }
+
+    /// Updates the core speed factor from the thermal and DVFS controls
+    /// and reschedules the completion of running computations.
+    fn update_speed_factor(&mut self) {
+        let thermal_factor = self.thermal_control.as_ref().map_or(1., |c| c.level().speed);
+        let dvfs_factor = self.dvfs_control.as_ref().map_or(1., |c| c.p_state().speed);
+        let old_speeds = self.core_group_speeds();
+        self.speed_factor = thermal_factor * dvfs_factor;
+        let new_speeds = self.core_group_speeds();
+        let time = self.ctx.time();
+        for (&id, computation) in self.computations.iter_mut() {
+            computation.update_progress(
+                old_speeds[computation.core_group] * computation.interference_factor,
+                time,
+            );
+            self.ctx.cancel_event(computation.finish_event);
+            computation.finish_event = self.ctx.emit_self(
+                CompFinished { id },
+                computation.remaining_flops
+                    / (new_speeds[computation.core_group] * computation.interference_factor)
+                    / computation.speedup,
+            );
+        }
+    }
+
+    /// Recomputes the speed of running computations using the interference model
+    /// and reschedules the completion of computations whose speed has changed.
+    fn update_interference(&mut self) {
+        let Some(model) = self.interference_model.as_ref() else {
+            return;
+        };
+        let mut ids = self.computations.keys().copied().collect::<Vec<_>>();
+        ids.sort();
+        let workloads = ids
+            .iter()
+            .map(|id| {
+                let computation = &self.computations[id];
+                (computation.memory_profile, computation.cores as f64)
+            })
+            .collect::<Vec<_>>();
+        let factors = model.speed_factors(&workloads);
+        let speeds = self.core_group_speeds();
+        let time = self.ctx.time();
+        for (id, factor) in ids.into_iter().zip(factors) {
+            let computation = self.computations.get_mut(&id).unwrap();
+            if factor == computation.interference_factor {
+                continue;
+            }
+            let speed = speeds[computation.core_group];
+            computation.update_progress(speed * computation.interference_factor, time);
+            computation.interference_factor = factor;
+            self.ctx.cancel_event(computation.finish_event);
+            computation.finish_event = self.ctx.emit_self(
+                CompFinished { id },
+                computation.remaining_flops / (speed * factor) / computation.speedup,
+            );
+        }
+    }
 }
 
 impl EventHandler for Compute {
@@ -296,15 +915,49 @@ impl EventHandler for Compute {
                 memory,
                 min_cores,
                 max_cores,
-                ref cores_dependency,
+                cores_dependency,
+                priority,
+                core_group,
+                memory_profile,
                 requester,
             } => {
-                if self.memory_available < memory || self.cores_available < min_cores {
+                let computation = QueuedComputation {
+                    id: event.id,
+                    flops,
+                    memory,
+                    min_cores,
+                    max_cores,
+                    cores_dependency,
+                    priority,
+                    core_group,
+                    memory_profile,
+                    requester,
+                    submit_time: self.ctx.time(),
+                };
+                let group_cores_total = self.core_groups.iter().map(|g| g.cores).collect::<Vec<_>>();
+                let fits_total =
+                    self.memory_total >= memory && self.select_core_group(&computation, &group_cores_total).is_some();
+                let selection = if self.memory_available >= memory {
+                    self.select_core_group(&computation, &self.group_cores_available)
+                } else {
+                    None
+                };
+                if self.queue_policy.is_some() && fits_total {
+                    self.queue.push(computation);
+                    self.process_queue();
+                } else if let Some((core_group, cores)) = selection {
+                    self.start_computation(&computation, core_group, cores);
+                } else {
+                    let available_cores = match core_group {
+                        Some(group) if group < self.core_groups.len() => self.group_cores_available[group],
+                        Some(_) => 0,
+                        None => self.cores_available,
+                    };
                     self.ctx.emit_now(
                         CompFailed {
                             id: event.id,
                             reason: FailReason::NotEnoughResources {
-                                available_cores: self.cores_available,
+                                available_cores,
                                 available_memory: self.memory_available,
                                 requested_cores: min_cores,
                                 requested_memory: memory,
@@ -312,18 +965,6 @@ impl EventHandler for Compute {
                         },
                         requester,
                     );
-                } else {
-                    let cores = self.cores_available.min(max_cores);
-                    self.memory_available -= memory;
-                    self.cores_available -= cores;
-                    self.ctx.emit_now(CompStarted { id: event.id, cores }, requester);
-
-                    let speedup = cores_dependency.speedup(cores);
-
-                    let compute_time = flops / self.speed / speedup;
-                    self.ctx.emit_self(CompFinished { id: event.id }, compute_time);
-                    self.computations
-                        .insert(event.id, RunningComputation::new(cores, memory, requester));
                 }
             }
             CompFinished { id } => {
@@ -332,8 +973,137 @@ impl EventHandler for Compute {
                     .remove(&id)
                     .expect("Unexpected CompFinished event in Compute");
                 self.memory_available += running_computation.memory;
-                self.cores_available += running_computation.cores;
+                self.release_cores(running_computation.core_group, running_computation.cores);
                 self.ctx.emit(CompFinished { id }, running_computation.requester, 0.);
+                self.update_utilization();
+                self.update_interference();
+                self.process_queue();
+            }
+            CancelRequest { id } => {
+                if let Some(mut computation) = self.computations.remove(&id) {
+                    computation.update_progress(
+                        self.core_group_speed(computation.core_group) * computation.interference_factor,
+                        self.ctx.time(),
+                    );
+                    self.ctx.cancel_event(computation.finish_event);
+                    self.release_cores(computation.core_group, computation.cores);
+                    self.memory_available += computation.memory;
+                    self.ctx.emit_now(
+                        CompCanceled {
+                            id,
+                            remaining_flops: computation.remaining_flops,
+                        },
+                        computation.requester,
+                    );
+                    self.update_utilization();
+                    self.update_interference();
+                    self.process_queue();
+                } else if let Some(computation) = self.suspended_computations.remove(&id) {
+                    self.memory_available += computation.memory;
+                    self.ctx.emit_now(
+                        CompCanceled {
+                            id,
+                            remaining_flops: computation.remaining_flops,
+                        },
+                        computation.requester,
+                    );
+                    self.process_queue();
+                } else if let Some(pos) = self.queue.iter().position(|c| c.id == id) {
+                    let computation = self.queue.remove(pos);
+                    self.ctx.emit_now(
+                        CompCanceled {
+                            id,
+                            remaining_flops: computation.flops,
+                        },
+                        computation.requester,
+                    );
+                    // removal of the blocked computation can allow other computations to start
+                    self.process_queue();
+                }
+            }
+            SuspendRequest { id } => {
+                if let Some(mut computation) = self.computations.remove(&id) {
+                    computation.update_progress(
+                        self.core_group_speed(computation.core_group) * computation.interference_factor,
+                        self.ctx.time(),
+                    );
+                    self.ctx.cancel_event(computation.finish_event);
+                    self.release_cores(computation.core_group, computation.cores);
+                    computation.cores = 0;
+                    self.ctx.emit_now(
+                        CompSuspended {
+                            id,
+                            remaining_flops: computation.remaining_flops,
+                        },
+                        computation.requester,
+                    );
+                    self.suspended_computations.insert(id, computation);
+                    self.update_utilization();
+                    self.update_interference();
+                    self.process_queue();
+                }
+            }
+            ResumeRequest { id } => {
+                if let Some(computation) = self.suspended_computations.get(&id) {
+                    // the core group is selected again, unless it was requested explicitly
+                    let request = QueuedComputation {
+                        id,
+                        flops: computation.remaining_flops,
+                        memory: 0,
+                        min_cores: computation.min_cores,
+                        max_cores: computation.max_cores,
+                        cores_dependency: computation.cores_dependency,
+                        priority: 0,
+                        core_group: computation.requested_core_group,
+                        memory_profile: computation.memory_profile,
+                        requester: computation.requester,
+                        submit_time: self.ctx.time(),
+                    };
+                    if let Some((core_group, cores)) = self.select_core_group(&request, &self.group_cores_available) {
+                        let mut computation = self.suspended_computations.remove(&id).unwrap();
+                        self.cores_available -= cores;
+                        self.group_cores_available[core_group] -= cores;
+                        computation.core_group = core_group;
+                        computation.cores = cores;
+                        computation.speedup = computation.cores_dependency.speedup(cores);
+                        computation.interference_factor = 1.;
+                        computation.last_update = self.ctx.time();
+                        let compute_time =
+                            computation.remaining_flops / self.core_group_speed(core_group) / computation.speedup;
+                        computation.finish_event = self.ctx.emit_self(CompFinished { id }, compute_time);
+                        self.ctx
+                            .emit_now(CompResumed { id, core_group, cores }, computation.requester);
+                        self.computations.insert(id, computation);
+                        self.update_utilization();
+                        self.update_interference();
+                    } else {
+                        self.ctx.emit_now(
+                            CompResumeFailed {
+                                id,
+                                reason: FailReason::NotEnoughResources {
+                                    available_cores: self.cores_available,
+                                    available_memory: self.memory_available,
+                                    requested_cores: computation.min_cores,
+                                    requested_memory: 0,
+                                },
+                            },
+                            computation.requester,
+                        );
+                    }
+                } else if let Some(requester) = self
+                    .computations
+                    .get(&id)
+                    .map(|c| c.requester)
+                    .or_else(|| self.queue.iter().find(|c| c.id == id).map(|c| c.requester))
+                {
+                    self.ctx.emit_now(
+                        CompResumeFailed {
+                            id,
+                            reason: FailReason::NotSuspended,
+                        },
+                        requester,
+                    );
+                }
             }
             AllocationRequest { allocation, requester } => {
                 if self.memory_available < allocation.memory || self.cores_available < allocation.cores {
@@ -356,9 +1126,22 @@ impl EventHandler for Compute {
                         .or_insert_with(|| Allocation::new(0, 0));
                     current_allocation.cores += allocation.cores;
                     current_allocation.memory += allocation.memory;
+                    // take the cores from the groups in their order
+                    let group_cores = self
+                        .allocated_group_cores
+                        .entry(requester)
+                        .or_insert_with(|| vec![0; self.core_groups.len()]);
+                    let mut remaining = allocation.cores;
+                    for (group, available) in self.group_cores_available.iter_mut().enumerate() {
+                        let cores = remaining.min(*available);
+                        *available -= cores;
+                        group_cores[group] += cores;
+                        remaining -= cores;
+                    }
                     self.cores_available -= allocation.cores;
                     self.memory_available -= allocation.memory;
                     self.ctx.emit(AllocationSuccess { id: event.id }, requester, 0.);
+                    self.update_utilization();
                 }
             }
             DeallocationRequest { allocation, requester } => {
@@ -369,6 +1152,16 @@ impl EventHandler for Compute {
                 if current_allocation.cores >= allocation.cores && current_allocation.memory >= allocation.memory {
                     current_allocation.cores -= allocation.cores;
                     current_allocation.memory -= allocation.memory;
+                    // release the cores from the groups in reverse order
+                    if let Some(group_cores) = self.allocated_group_cores.get_mut(&requester) {
+                        let mut remaining = allocation.cores;
+                        for (group, allocated) in group_cores.iter_mut().enumerate().rev() {
+                            let cores = remaining.min(*allocated);
+                            *allocated -= cores;
+                            self.group_cores_available[group] += cores;
+                            remaining -= cores;
+                        }
+                    }
                     self.cores_available += allocation.cores;
                     self.memory_available += allocation.memory;
                     self.ctx.emit(DeallocationSuccess { id: event.id }, requester, 0.);
@@ -388,6 +1181,15 @@ impl EventHandler for Compute {
                 }
                 if current_allocation.cores == 0 && current_allocation.memory == 0 {
                     self.allocations.remove(&requester);
+                    self.allocated_group_cores.remove(&requester);
+                }
+                self.update_utilization();
+                self.process_queue();
+            }
+            ThermalCheck {} => {
+                let thermal_control = self.thermal_control.as_mut().unwrap();
+                if thermal_control.on_check(&mut self.ctx) {
+                    self.update_speed_factor();
                 }
             }
         })
@@ -0,0 +1,290 @@
+//! Local queueing of computation requests in [`multicore`](crate::multicore) model.
+//!
+//! When the local queue is enabled, the requests which can't be started immediately due to lack of resources
+//! are queued instead of being failed. The queued requests are started according to the queue policy
+//! each time the resources are released.
+//!
+//! For resources with multiple core groups, each computation is placed into a single core group selected by
+//! the [`CoreGroupPolicy`] of the resource, unless the core group is specified in the request.
+
+use dslab_core::component::Id;
+use dslab_models::interference::model::MemoryProfile;
+
+use crate::multicore::{CoreGroupPolicy, CoresDependency};
+
+/// Computation request waiting in the local queue.
+#[derive(Clone, Debug)]
+pub struct QueuedComputation {
+    /// Id of the computation.
+    pub id: u64,
+    /// Total computation size.
+    pub flops: f64,
+    /// Total memory needed for a computation.
+    pub memory: u64,
+    /// Minimum number of used cores.
+    pub min_cores: u32,
+    /// Maximum number of used cores.
+    pub max_cores: u32,
+    /// Defines the dependence of parallel speedup on the number of used cores.
+    pub cores_dependency: CoresDependency,
+    /// Computation priority.
+    pub priority: i64,
+    /// Requested core group, or `None` if the core group is selected by the resource.
+    pub core_group: Option<usize>,
+    /// Memory intensity of the computation.
+    pub memory_profile: MemoryProfile,
+    /// Id of simulation component to inform about the computation progress.
+    pub requester: Id,
+    /// Time when the computation was queued.
+    pub submit_time: f64,
+}
+
+impl QueuedComputation {
+    /// Returns the core group and the number of cores allocated to the computation if it is started with the given
+    /// available resources (the number of available cores is specified per core group),
+    /// or `None` if the resources are not sufficient.
+    pub fn fit(&self, state: &ResourceState, cores_available: &[u32], memory_available: u64) -> Option<(usize, u32)> {
+        if memory_available >= self.memory {
+            state.core_group_policy.select(&state.speeds, cores_available, self)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the computation execution time when using the given number of cores with the given core speed.
+    pub fn estimated_time(&self, cores: u32, speed: f64) -> f64 {
+        self.flops / speed / self.cores_dependency.speedup(cores)
+    }
+}
+
+/// Running computation as seen by the queue policy.
+#[derive(Clone, Debug)]
+pub struct RunningComputationInfo {
+    /// Used core group.
+    pub core_group: usize,
+    /// Number of used cores.
+    pub cores: u32,
+    /// Amount of used memory.
+    pub memory: u64,
+    /// Expected completion time assuming the current core speed and interference.
+    pub finish_time: f64,
+}
+
+/// Resource state passed to the queue policy.
+#[derive(Clone, Debug)]
+pub struct ResourceState {
+    /// Current time.
+    pub time: f64,
+    /// Current core speed in each core group.
+    pub speeds: Vec<f64>,
+    /// Currently available number of cores in each core group.
+    pub cores_available: Vec<u32>,
+    /// Currently available amount of memory.
+    pub memory_available: u64,
+    /// Policy used to select the core group of computations.
+    pub core_group_policy: CoreGroupPolicy,
+    /// Currently running computations.
+    pub running: Vec<RunningComputationInfo>,
+}
+
+/// Policy which decides when the queued computations are started.
+pub trait QueuePolicy {
+    /// Selects the queued computations to start at the current time.
+    ///
+    /// The queue is ordered by the computation submission time. Returns the ids of selected computations along with
+    /// the core group and the number of cores allocated to each of them. The selected computations must fit into
+    /// the available resources.
+    fn select(&mut self, queue: &[QueuedComputation], state: &ResourceState) -> Vec<(u64, usize, u32)>;
+}
+
+/// Starts the computations in the given order until the first one which doesn't fit into the available resources.
+fn select_in_order<'a, I>(order: I, state: &ResourceState) -> Vec<(u64, usize, u32)>
+where
+    I: Iterator<Item = &'a QueuedComputation>,
+{
+    let mut cores_available = state.cores_available.clone();
+    let mut memory_available = state.memory_available;
+    let mut selected = Vec::new();
+    for computation in order {
+        if let Some((group, cores)) = computation.fit(state, &cores_available, memory_available) {
+            cores_available[group] -= cores;
+            memory_available -= computation.memory;
+            selected.push((computation.id, group, cores));
+        } else {
+            break;
+        }
+    }
+    selected
+}
+
+/// Starts the computations in the order of their submission.
+pub struct FifoPolicy {}
+
+impl FifoPolicy {
+    /// Creates FIFO policy.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for FifoPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QueuePolicy for FifoPolicy {
+    fn select(&mut self, queue: &[QueuedComputation], state: &ResourceState) -> Vec<(u64, usize, u32)> {
+        select_in_order(queue.iter(), state)
+    }
+}
+
+/// Starts the computations in the order of their size (flops), the smallest one first.
+pub struct ShortestJobFirstPolicy {}
+
+impl ShortestJobFirstPolicy {
+    /// Creates shortest-job-first policy.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for ShortestJobFirstPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QueuePolicy for ShortestJobFirstPolicy {
+    fn select(&mut self, queue: &[QueuedComputation], state: &ResourceState) -> Vec<(u64, usize, u32)> {
+        let mut order = queue.iter().collect::<Vec<_>>();
+        // stable sort preserves the submission order of computations with equal size
+        order.sort_by(|a, b| a.flops.total_cmp(&b.flops));
+        select_in_order(order.into_iter(), state)
+    }
+}
+
+/// Starts the computations in the order of their priority, the highest priority first.
+pub struct PriorityPolicy {}
+
+impl PriorityPolicy {
+    /// Creates priority policy.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for PriorityPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QueuePolicy for PriorityPolicy {
+    fn select(&mut self, queue: &[QueuedComputation], state: &ResourceState) -> Vec<(u64, usize, u32)> {
+        let mut order = queue.iter().collect::<Vec<_>>();
+        order.sort_by_key(|c| std::cmp::Reverse(c.priority));
+        select_in_order(order.into_iter(), state)
+    }
+}
+
+/// FIFO policy with EASY backfilling.
+///
+/// When the first queued computation doesn't fit into the available resources, it gets a reservation at the earliest
+/// time when enough resources for its `min_cores` and memory are released by the running computations (shadow time).
+/// The following computations can be started ahead of it if they either complete before the shadow time
+/// or use only the resources which are not needed for the reservation. In the latter case the number of allocated
+/// cores can be reduced down to `min_cores` of the backfilled computation.
+///
+/// The completion times are estimated using the current core speed. For resources with multiple core groups,
+/// the reservation is made in the core group selected for the blocked computation at the shadow time.
+/// If the blocked computation can't get a reservation, because the resources it needs are held by manual
+/// allocations or suspended computations, no computations are backfilled.
+pub struct EasyBackfillingPolicy {}
+
+impl EasyBackfillingPolicy {
+    /// Creates EASY backfilling policy.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for EasyBackfillingPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QueuePolicy for EasyBackfillingPolicy {
+    fn select(&mut self, queue: &[QueuedComputation], state: &ResourceState) -> Vec<(u64, usize, u32)> {
+        let mut selected = select_in_order(queue.iter(), state);
+        if selected.len() == queue.len() {
+            return selected;
+        }
+        let mut cores_available = state.cores_available.clone();
+        let mut memory_available = state.memory_available;
+        let mut running = state.running.clone();
+        for (computation, &(_, group, cores)) in queue.iter().zip(selected.iter()) {
+            cores_available[group] -= cores;
+            memory_available -= computation.memory;
+            running.push(RunningComputationInfo {
+                core_group: group,
+                cores,
+                memory: computation.memory,
+                finish_time: state.time + computation.estimated_time(cores, state.speeds[group]),
+            });
+        }
+
+        // compute the reservation for the first blocked computation
+        let head = &queue[selected.len()];
+        running.sort_by(|a, b| a.finish_time.total_cmp(&b.finish_time));
+        let mut shadow_time = f64::INFINITY;
+        let mut extra_cores = vec![u32::MAX; cores_available.len()];
+        let mut extra_memory = u64::MAX;
+        let mut cores = cores_available.clone();
+        let mut memory = memory_available;
+        for computation in running.iter() {
+            cores[computation.core_group] += computation.cores;
+            memory += computation.memory;
+            if let Some((group, _)) = head.fit(state, &cores, memory) {
+                shadow_time = computation.finish_time;
+                cores[group] -= head.min_cores;
+                extra_cores = cores;
+                extra_memory = memory - head.memory;
+                break;
+            }
+        }
+        if shadow_time == f64::INFINITY {
+            // the resources held by allocations or suspended computations prevent the reservation,
+            // backfilling without it could delay the blocked computation indefinitely
+            return selected;
+        }
+
+        // backfill the following computations
+        for computation in queue[selected.len() + 1..].iter() {
+            if let Some((group, cores)) = computation.fit(state, &cores_available, memory_available) {
+                if state.time + computation.estimated_time(cores, state.speeds[group]) <= shadow_time {
+                    cores_available[group] -= cores;
+                    memory_available -= computation.memory;
+                    selected.push((computation.id, group, cores));
+                    continue;
+                }
+                let extra_cores_available = cores_available
+                    .iter()
+                    .zip(extra_cores.iter())
+                    .map(|(&available, &extra)| available.min(extra))
+                    .collect::<Vec<_>>();
+                if let Some((group, cores)) =
+                    computation.fit(state, &extra_cores_available, memory_available.min(extra_memory))
+                {
+                    cores_available[group] -= cores;
+                    memory_available -= computation.memory;
+                    extra_cores[group] -= cores;
+                    extra_memory -= computation.memory;
+                    selected.push((computation.id, group, cores));
+                }
+            }
+        }
+        selected
+    }
+}
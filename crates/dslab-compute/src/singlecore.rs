@@ -1,5 +1,7 @@
 //! Model of computing resource with a single core.
 
+use std::collections::HashMap;
+
 use serde::Serialize;
 
 use dslab_core::cast;
@@ -8,7 +10,12 @@ use dslab_core::context::SimulationContext;
 use dslab_core::event::Event;
 use dslab_core::handler::EventHandler;
 
-use dslab_models::throughput_sharing::{FairThroughputSharingModel, ThroughputSharingModel};
+use dslab_models::throughput_sharing::{
+    ActivityId, DynamicThroughputSharingModel, FairThroughputSharingModel, ThroughputSharingModel,
+};
+
+use crate::dvfs::DvfsControl;
+use crate::thermal::{ThermalCheck, ThermalControl};
 
 // STRUCTS -------------------------------------------------------------------------------------------------------------
 
@@ -20,6 +27,17 @@ pub enum FailReason {
         /// Amount of currently available memory.
         available_memory: u64,
     },
+    /// Computation can't be resumed since it is not suspended.
+    NotSuspended,
+}
+
+/// Computation tracked by the resource until its completion or cancellation.
+struct ComputationInfo {
+    activity_id: ActivityId,
+    flops: f64,
+    memory: u64,
+    requester: Id,
+    suspended: bool,
 }
 
 #[derive(Serialize, Clone)]
@@ -76,6 +94,61 @@ pub struct CompFailed {
     pub reason: FailReason,
 }
 
+/// Request to cancel a computation.
+#[derive(Clone, Serialize)]
+pub struct CancelRequest {
+    /// Id of the computation.
+    pub id: u64,
+}
+
+/// Computation is canceled.
+#[derive(Clone, Serialize)]
+pub struct CompCanceled {
+    /// Id of the computation.
+    pub id: u64,
+    /// Amount of computations which were not performed.
+    pub remaining_flops: f64,
+}
+
+/// Request to suspend a computation.
+#[derive(Clone, Serialize)]
+pub struct SuspendRequest {
+    /// Id of the computation.
+    pub id: u64,
+}
+
+/// Computation is suspended.
+#[derive(Clone, Serialize)]
+pub struct CompSuspended {
+    /// Id of the computation.
+    pub id: u64,
+    /// Amount of computations remaining to be performed.
+    pub remaining_flops: f64,
+}
+
+/// Request to resume a suspended computation.
+#[derive(Clone, Serialize)]
+pub struct ResumeRequest {
+    /// Id of the computation.
+    pub id: u64,
+}
+
+/// Computation is resumed.
+#[derive(Clone, Serialize)]
+pub struct CompResumed {
+    /// Id of the computation.
+    pub id: u64,
+}
+
+/// Computation can't be resumed, it stays in its current state.
+#[derive(Clone, Serialize)]
+pub struct CompResumeFailed {
+    /// Id of the computation.
+    pub id: u64,
+    /// Reason for failure.
+    pub reason: FailReason,
+}
+
 // MODEL ---------------------------------------------------------------------------------------------------------------
 
 /// Models computing resource with a single "core" supporting concurrent execution
@@ -85,6 +158,12 @@ pub struct CompFailed {
 /// The task completion time is determined by the amount of computations and the core share.
 /// Each time a task is completed or a new task is submitted, the core shares and completion
 /// times of all running tasks are updated accordingly.
+///
+/// A running computation can be canceled, which releases its memory, or suspended, which keeps its memory
+/// but excludes it from the core sharing until it is resumed.
+///
+/// The core speed can be adjusted according to the resource temperature by using [`ThermalControl`]
+/// and by switching the resource P-state with [`DvfsControl`], which also tracks the energy consumption.
 pub struct Compute {
     #[allow(dead_code)]
     speed: f64,
@@ -92,7 +171,11 @@ pub struct Compute {
     memory_total: u64,
     memory_available: u64,
     throughput_model: FairThroughputSharingModel<RunningComputation>,
+    computations: HashMap<u64, ComputationInfo>,
+    running_count: usize,
     next_event: u64,
+    thermal_control: Option<ThermalControl>,
+    dvfs_control: Option<DvfsControl>,
     ctx: SimulationContext,
 }
 
@@ -104,11 +187,72 @@ impl Compute {
             memory_total: memory,
             memory_available: memory,
             throughput_model: FairThroughputSharingModel::with_fixed_throughput(speed),
+            computations: HashMap::new(),
+            running_count: 0,
             next_event: 0,
+            thermal_control: None,
+            dvfs_control: None,
             ctx,
         }
     }
 
+    /// Enables the thermal control of the resource.
+    pub fn with_thermal_control(mut self, thermal_control: ThermalControl) -> Self {
+        self.thermal_control = Some(thermal_control);
+        self.update_utilization();
+        self
+    }
+
+    /// Returns the thermal control of the resource, if enabled.
+    pub fn thermal_control(&self) -> Option<&ThermalControl> {
+        self.thermal_control.as_ref()
+    }
+
+    /// Enables the DVFS control of the resource.
+    pub fn with_dvfs_control(mut self, mut dvfs_control: DvfsControl) -> Self {
+        dvfs_control.start(self.ctx.time());
+        self.dvfs_control = Some(dvfs_control);
+        self.update_speed_factor();
+        self.update_utilization();
+        self
+    }
+
+    /// Returns the DVFS control of the resource, if enabled.
+    pub fn dvfs_control(&self) -> Option<&DvfsControl> {
+        self.dvfs_control.as_ref()
+    }
+
+    /// Switches the resource to the P-state with the given frequency.
+    ///
+    /// The remaining time of running computations is rescaled according to the P-state speed.
+    /// Panics if DVFS control is not enabled or there is no P-state with such frequency.
+    pub fn set_frequency(&mut self, frequency: f64) {
+        let dvfs_control = self.dvfs_control.as_ref().expect("DVFS control is not enabled");
+        let index = dvfs_control
+            .find_p_state(frequency)
+            .unwrap_or_else(|| panic!("There is no P-state with frequency {}", frequency));
+        self.set_p_state(index);
+    }
+
+    /// Switches the resource to the P-state with the given index (P-states are sorted by frequency).
+    ///
+    /// The remaining time of running computations is rescaled according to the P-state speed.
+    /// Panics if DVFS control is not enabled.
+    pub fn set_p_state(&mut self, index: usize) {
+        let time = self.ctx.time();
+        let dvfs_control = self.dvfs_control.as_mut().expect("DVFS control is not enabled");
+        dvfs_control.set_p_state(index, time);
+        self.update_speed_factor();
+    }
+
+    /// Returns the total resource energy consumption tracked by the DVFS control,
+    /// or zero if DVFS control is not enabled.
+    pub fn energy_consumed(&self) -> f64 {
+        self.dvfs_control
+            .as_ref()
+            .map_or(0., |dvfs_control| dvfs_control.energy_consumed(self.ctx.time()))
+    }
+
     /// Starts computation with given parameters and returns computation id.
     pub fn run(&mut self, flops: f64, memory: u64, requester: Id) -> u64 {
         let request = CompRequest {
@@ -118,6 +262,70 @@ impl Compute {
         };
         self.ctx.emit_self_now(request)
     }
+
+    /// Requests cancellation of computation with given id.
+    ///
+    /// Upon cancellation the computation memory is released and the requester receives [`CompCanceled`].
+    /// The request is ignored if the computation is already completed.
+    pub fn cancel(&mut self, id: u64) {
+        self.ctx.emit_self_now(CancelRequest { id });
+    }
+
+    /// Requests suspension of running computation with given id.
+    ///
+    /// The suspended computation keeps its memory, and the requester receives [`CompSuspended`].
+    /// The request is ignored if the computation is already completed or suspended.
+    pub fn suspend(&mut self, id: u64) {
+        self.ctx.emit_self_now(SuspendRequest { id });
+    }
+
+    /// Requests resumption of suspended computation with given id.
+    ///
+    /// The requester receives [`CompResumed`], or [`CompResumeFailed`] if the computation is running.
+    /// The request is ignored if the computation is already completed.
+    pub fn resume(&mut self, id: u64) {
+        self.ctx.emit_self_now(ResumeRequest { id });
+    }
+
+    fn remaining_flops(&self, computation: &ComputationInfo) -> f64 {
+        let progress = self
+            .throughput_model
+            .progress(computation.activity_id, &self.ctx)
+            .unwrap();
+        (1. - progress) * computation.flops
+    }
+
+    fn schedule_next_completion(&mut self) {
+        if let Some((time, computation)) = self.throughput_model.peek() {
+            self.next_event = self.ctx.emit_self(
+                InternalCompFinished {
+                    computation: computation.clone(),
+                },
+                time - self.ctx.time(),
+            );
+        }
+    }
+
+    fn update_utilization(&mut self) {
+        let utilization = if self.running_count > 0 { 1. } else { 0. };
+        if let Some(thermal_control) = self.thermal_control.as_mut() {
+            thermal_control.set_utilization(utilization, &mut self.ctx);
+        }
+        if let Some(dvfs_control) = self.dvfs_control.as_mut() {
+            dvfs_control.set_utilization(utilization, self.ctx.time());
+        }
+    }
+
+    /// Updates the core speed from the thermal and DVFS controls
+    /// and reschedules the completion of running computations.
+    fn update_speed_factor(&mut self) {
+        let thermal_factor = self.thermal_control.as_ref().map_or(1., |c| c.level().speed);
+        let dvfs_factor = self.dvfs_control.as_ref().map_or(1., |c| c.p_state().speed);
+        self.throughput_model
+            .set_capacity(thermal_factor * dvfs_factor, &mut self.ctx);
+        self.ctx.cancel_event(self.next_event);
+        self.schedule_next_completion();
+    }
 }
 
 impl EventHandler for Compute {
@@ -141,19 +349,24 @@ impl EventHandler for Compute {
                 } else {
                     self.memory_available -= memory;
                     self.ctx.cancel_event(self.next_event);
-                    self.throughput_model.insert(
+                    let activity_id = self.throughput_model.insert(
                         RunningComputation::new(event.id, memory, requester),
                         flops,
                         &mut self.ctx,
                     );
-                    if let Some((time, computation)) = self.throughput_model.peek() {
-                        self.next_event = self.ctx.emit_self(
-                            InternalCompFinished {
-                                computation: computation.clone(),
-                            },
-                            time - self.ctx.time(),
-                        );
-                    }
+                    self.computations.insert(
+                        event.id,
+                        ComputationInfo {
+                            activity_id,
+                            flops,
+                            memory,
+                            requester,
+                            suspended: false,
+                        },
+                    );
+                    self.running_count += 1;
+                    self.schedule_next_completion();
+                    self.update_utilization();
                 }
             }
             InternalCompFinished { computation } => {
@@ -162,18 +375,68 @@ impl EventHandler for Compute {
                     computation.id == next_computation.id,
                     "Got unexpected InternalCompFinished event"
                 );
+                self.computations.remove(&computation.id);
                 self.memory_available += computation.memory;
+                self.running_count -= 1;
                 self.ctx
                     .emit_now(CompFinished { id: computation.id }, computation.requester);
-                if let Some((time, computation)) = self.throughput_model.peek() {
-                    self.next_event = self.ctx.emit_self(
-                        InternalCompFinished {
-                            computation: computation.clone(),
+                self.schedule_next_completion();
+                self.update_utilization();
+            }
+            CancelRequest { id } => {
+                if let Some(computation) = self.computations.remove(&id) {
+                    let remaining_flops = self.remaining_flops(&computation);
+                    self.ctx.cancel_event(self.next_event);
+                    self.throughput_model.remove(computation.activity_id, &mut self.ctx);
+                    self.memory_available += computation.memory;
+                    if !computation.suspended {
+                        self.running_count -= 1;
+                    }
+                    self.ctx
+                        .emit_now(CompCanceled { id, remaining_flops }, computation.requester);
+                    self.schedule_next_completion();
+                    self.update_utilization();
+                }
+            }
+            SuspendRequest { id } => {
+                if let Some(computation) = self.computations.get(&id).filter(|c| !c.suspended) {
+                    let remaining_flops = self.remaining_flops(computation);
+                    let (activity_id, requester) = (computation.activity_id, computation.requester);
+                    self.ctx.cancel_event(self.next_event);
+                    self.throughput_model.suspend(activity_id, &mut self.ctx);
+                    self.computations.get_mut(&id).unwrap().suspended = true;
+                    self.running_count -= 1;
+                    self.ctx.emit_now(CompSuspended { id, remaining_flops }, requester);
+                    self.schedule_next_completion();
+                    self.update_utilization();
+                }
+            }
+            ResumeRequest { id } => {
+                if let Some(computation) = self.computations.get_mut(&id).filter(|c| c.suspended) {
+                    computation.suspended = false;
+                    let (activity_id, requester) = (computation.activity_id, computation.requester);
+                    self.ctx.cancel_event(self.next_event);
+                    self.throughput_model.resume(activity_id, &mut self.ctx);
+                    self.running_count += 1;
+                    self.ctx.emit_now(CompResumed { id }, requester);
+                    self.schedule_next_completion();
+                    self.update_utilization();
+                } else if let Some(computation) = self.computations.get(&id) {
+                    self.ctx.emit_now(
+                        CompResumeFailed {
+                            id,
+                            reason: FailReason::NotSuspended,
                         },
-                        time - self.ctx.time(),
+                        computation.requester,
                     );
                 }
             }
+            ThermalCheck {} => {
+                let thermal_control = self.thermal_control.as_mut().unwrap();
+                if thermal_control.on_check(&mut self.ctx) {
+                    self.update_speed_factor();
+                }
+            }
         })
     }
 }
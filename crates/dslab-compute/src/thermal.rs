@@ -0,0 +1,99 @@
+//! Thermal control of computing resources.
+
+use serde::Serialize;
+
+use dslab_core::context::SimulationContext;
+use dslab_core::event::EventId;
+
+use dslab_models::power::host::{HostPowerModel, HostState};
+use dslab_models::thermal::governor::{PerformanceLevel, ThermalGovernor};
+use dslab_models::thermal::model::ThermalModel;
+
+/// Internal event used to check the resource temperature by the thermal governor.
+#[derive(Clone, Serialize)]
+pub(crate) struct ThermalCheck {}
+
+/// Tracks the temperature of computing resource and adjusts its speed using the thermal governor.
+///
+/// The resource power is computed by the host power model from the current resource utilization
+/// and the CPU frequency and state of the current performance level. If the resource frequency is also set
+/// by the current P-state (see [`DvfsControl`](crate::dvfs::DvfsControl)), the lower of two frequencies is used. The power is passed to the thermal model,
+/// and the thermal governor switches the performance level when the temperature crosses the thresholds.
+/// The resource speed is multiplied by the speed of the current performance level.
+pub struct ThermalControl {
+    thermal_model: Box<dyn ThermalModel>,
+    governor: ThermalGovernor,
+    power_model: HostPowerModel,
+    utilization: f64,
+    frequency: Option<f64>,
+    next_check: Option<EventId>,
+}
+
+impl ThermalControl {
+    /// Creates thermal control with the given thermal model, governor and power model.
+    pub fn new(thermal_model: Box<dyn ThermalModel>, governor: ThermalGovernor, power_model: HostPowerModel) -> Self {
+        Self {
+            thermal_model,
+            governor,
+            power_model,
+            utilization: 0.,
+            frequency: None,
+            next_check: None,
+        }
+    }
+
+    /// Returns the resource temperature at the given time.
+    pub fn temperature(&self, time: f64) -> f64 {
+        self.thermal_model.temperature(time)
+    }
+
+    /// Returns the current performance level.
+    pub fn level(&self) -> &PerformanceLevel {
+        self.governor.level()
+    }
+
+    /// Returns the current resource power consumption.
+    pub fn power(&self) -> f64 {
+        let level = self.governor.level();
+        let cpu_freq = match (level.cpu_freq, self.frequency) {
+            (Some(level_freq), Some(freq)) => Some(level_freq.min(freq)),
+            (level_freq, freq) => level_freq.or(freq),
+        };
+        self.power_model.get_power(HostState {
+            cpu_util: Some(self.utilization),
+            cpu_freq,
+            cpu_state: level.cpu_state,
+            ..Default::default()
+        })
+    }
+
+    /// Updates the resource utilization at the current time.
+    pub(crate) fn set_utilization(&mut self, utilization: f64, ctx: &mut SimulationContext) {
+        self.utilization = utilization;
+        self.update_power(ctx);
+    }
+
+    /// Updates the relative resource frequency set by the current P-state at the current time.
+    pub(crate) fn set_frequency(&mut self, frequency: Option<f64>, ctx: &mut SimulationContext) {
+        self.frequency = frequency;
+        self.update_power(ctx);
+    }
+
+    /// Processes the [`ThermalCheck`] event, returns true if the performance level is changed.
+    pub(crate) fn on_check(&mut self, ctx: &mut SimulationContext) -> bool {
+        self.next_check = None;
+        let changed = self.governor.update(self.thermal_model.temperature(ctx.time()));
+        self.update_power(ctx);
+        changed
+    }
+
+    fn update_power(&mut self, ctx: &mut SimulationContext) {
+        self.thermal_model.set_power(self.power(), ctx.time());
+        if let Some(event_id) = self.next_check.take() {
+            ctx.cancel_event(event_id);
+        }
+        if let Some(time) = self.governor.next_check_time(self.thermal_model.as_ref(), ctx.time()) {
+            self.next_check = Some(ctx.emit_self(ThermalCheck {}, time - ctx.time()));
+        }
+    }
+}
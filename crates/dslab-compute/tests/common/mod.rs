@@ -0,0 +1,79 @@
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dslab_core::cast;
+use dslab_core::component::Id;
+use dslab_core::event::Event;
+use dslab_core::handler::EventHandler;
+use dslab_core::simulation::Simulation;
+
+use dslab_compute::multicore::*;
+
+pub fn assert_float_eq(x: f64, y: f64, eps: f64) {
+    assert!((x - y).abs() < eps, "Values do not match: {:.15} vs {:.15}", x, y);
+}
+
+/// Records the events received from the compute resource.
+#[derive(Default)]
+pub struct Recorder {
+    pub events: Vec<(f64, &'static str, u64)>,
+}
+
+impl Recorder {
+    /// Returns the time of the first event of the given kind for the given computation.
+    pub fn time_of(&self, kind: &str, id: u64) -> Option<f64> {
+        self.events
+            .iter()
+            .find(|(_, k, i)| *k == kind && *i == id)
+            .map(|(time, _, _)| *time)
+    }
+}
+
+impl EventHandler for Recorder {
+    fn on(&mut self, event: Event) {
+        let time = event.time;
+        cast!(match event.data {
+            CompStarted { id, .. } => {
+                self.events.push((time, "started", id));
+            }
+            CompFinished { id } => {
+                self.events.push((time, "finished", id));
+            }
+            CompFailed { id, .. } => {
+                self.events.push((time, "failed", id));
+            }
+            CompCanceled { id, .. } => {
+                self.events.push((time, "canceled", id));
+            }
+            CompSuspended { id, .. } => {
+                self.events.push((time, "suspended", id));
+            }
+            CompResumed { id, .. } => {
+                self.events.push((time, "resumed", id));
+            }
+            CompResumeFailed { id, .. } => {
+                self.events.push((time, "resume_failed", id));
+            }
+            AllocationSuccess { id } => {
+                self.events.push((time, "allocated", id));
+            }
+            DeallocationSuccess { id } => {
+                self.events.push((time, "deallocated", id));
+            }
+        })
+    }
+}
+
+/// Creates simulation with the given compute resource and an event recorder.
+pub fn setup(
+    make_compute: impl FnOnce(&mut Simulation) -> Compute,
+) -> (Simulation, Rc<RefCell<Compute>>, Rc<RefCell<Recorder>>, Id) {
+    let mut sim = Simulation::new(123);
+    let recorder = Rc::new(RefCell::new(Recorder::default()));
+    let recorder_id = sim.add_handler("recorder", recorder.clone());
+    let compute = Rc::new(RefCell::new(make_compute(&mut sim)));
+    sim.add_handler("compute", compute.clone());
+    (sim, compute, recorder, recorder_id)
+}
@@ -0,0 +1,92 @@
+mod common;
+use common::{assert_float_eq, setup};
+
+use dslab_core::simulation::Simulation;
+use dslab_models::power::cpu_models::dvfs::DvfsAwareCpuPowerModel;
+use dslab_models::power::host::{HostPowerModel, HostPowerModelBuilder};
+use dslab_models::thermal::governor::{PerformanceLevel, ThermalGovernor};
+use dslab_models::thermal::rc::RcThermalModel;
+
+use dslab_compute::dvfs::{DvfsControl, PState};
+use dslab_compute::multicore::*;
+use dslab_compute::thermal::ThermalControl;
+
+// P(util, freq) = 10 + 20 * util + 40 * util * freq
+fn power_model() -> HostPowerModel {
+    HostPowerModelBuilder::new()
+        .cpu(Box::new(DvfsAwareCpuPowerModel::new(10., 20., 40.)))
+        .build()
+}
+
+fn dvfs_control() -> DvfsControl {
+    DvfsControl::new(vec![PState::new(2., 1.), PState::new(1., 0.5)], power_model())
+}
+
+fn make_compute(sim: &mut Simulation) -> Compute {
+    Compute::new(1., 2, 100, sim.create_context("compute")).with_dvfs_control(dvfs_control())
+}
+
+#[test]
+fn test_set_frequency_rescales_running_computation() {
+    let (mut sim, compute, recorder, recorder_id) = setup(make_compute);
+    let id = compute
+        .borrow_mut()
+        .run(4., 10, 2, 2, CoresDependency::Linear, recorder_id);
+    sim.step_for_duration(1.);
+    compute.borrow_mut().set_frequency(1.);
+    assert_float_eq(compute.borrow().current_speed(), 0.5, 1e-9);
+    sim.step_until_no_events();
+
+    // 2 flops are done at the full speed, the remaining 2 flops are computed at the half speed
+    assert_float_eq(recorder.borrow().time_of("finished", id).unwrap(), 3., 1e-9);
+}
+
+#[test]
+fn test_energy_across_p_states() {
+    let (mut sim, compute, _recorder, recorder_id) = setup(make_compute);
+    compute
+        .borrow_mut()
+        .run(4., 10, 2, 2, CoresDependency::Linear, recorder_id);
+    sim.step_for_duration(1.);
+    // fully utilized at the highest frequency
+    assert_float_eq(compute.borrow().energy_consumed(), 70., 1e-9);
+    compute.borrow_mut().set_frequency(1.);
+    sim.step_until_no_events();
+    // fully utilized at the lowest frequency for two more seconds
+    assert_float_eq(sim.time(), 3., 1e-9);
+    assert_float_eq(compute.borrow().energy_consumed(), 70. + 2. * 30., 1e-9);
+    compute.borrow_mut().set_frequency(2.);
+    sim.step_for_duration(1.);
+    // idle power doesn't depend on the frequency
+    assert_float_eq(compute.borrow().energy_consumed(), 70. + 2. * 30. + 10., 1e-9);
+}
+
+#[test]
+fn test_dvfs_energy_replaces_core_group_power() {
+    let (mut sim, compute, _recorder, recorder_id) = setup(|sim| {
+        let group =
+            CoreGroup::new("default", 2, 1.).with_power_model(Box::new(DvfsAwareCpuPowerModel::new(5., 5., 5.)));
+        Compute::from_core_groups(vec![group], 100, sim.create_context("compute")).with_dvfs_control(dvfs_control())
+    });
+    compute
+        .borrow_mut()
+        .run(4., 10, 2, 2, CoresDependency::Linear, recorder_id);
+    sim.step_until_no_events();
+    assert_float_eq(compute.borrow().energy_consumed(), 2. * 70., 1e-9);
+}
+
+#[test]
+fn test_p_state_feeds_thermal_power() {
+    let (mut sim, compute, _recorder, recorder_id) = setup(|sim| {
+        let governor = ThermalGovernor::new(vec![PerformanceLevel::new(1., None, None)], 1000., 900.);
+        let thermal_control = ThermalControl::new(Box::new(RcThermalModel::new(1., 1., 25.)), governor, power_model());
+        make_compute(sim).with_thermal_control(thermal_control)
+    });
+    compute
+        .borrow_mut()
+        .run(4., 10, 2, 2, CoresDependency::Linear, recorder_id);
+    sim.step_for_duration(1.);
+    assert_float_eq(compute.borrow().thermal_control().unwrap().power(), 70., 1e-9);
+    compute.borrow_mut().set_frequency(1.);
+    assert_float_eq(compute.borrow().thermal_control().unwrap().power(), 30., 1e-9);
+}
@@ -0,0 +1,64 @@
+mod common;
+use common::{assert_float_eq, setup};
+
+use dslab_core::component::Id;
+use dslab_models::interference::bandwidth::BandwidthInterferenceModel;
+
+use dslab_compute::multicore::*;
+
+fn memory_bound_request(flops: f64, requester: Id) -> CompRequest {
+    CompRequest {
+        flops,
+        memory: 10,
+        min_cores: 2,
+        max_cores: 2,
+        cores_dependency: CoresDependency::Linear,
+        priority: 0,
+        core_group: None,
+        // 2 cores consume 4 GB/s, which is the whole node bandwidth
+        memory_profile: MemoryProfile::new(2., 0., 1.),
+        requester,
+    }
+}
+
+#[test]
+fn test_interference_slows_down_and_releases() {
+    let (mut sim, compute, recorder, recorder_id) = setup(|sim| {
+        Compute::new(1., 4, 100, sim.create_context("compute"))
+            .with_interference_model(Box::new(BandwidthInterferenceModel::new(4.)))
+    });
+    let short = compute.borrow_mut().submit(memory_bound_request(2., recorder_id));
+    let long = compute.borrow_mut().submit(memory_bound_request(6., recorder_id));
+    sim.step_for_duration(0.5);
+    // the bandwidth is oversubscribed twice, so both computations run at the half speed
+    assert_float_eq(compute.borrow().interference_factor(short).unwrap(), 0.5, 1e-9);
+    assert_float_eq(compute.borrow().interference_factor(long).unwrap(), 0.5, 1e-9);
+
+    sim.step_for_duration(2.);
+    // the survivor gets the whole bandwidth after the short computation is finished
+    assert!(compute.borrow().interference_factor(short).is_none());
+    assert_float_eq(compute.borrow().interference_factor(long).unwrap(), 1., 1e-9);
+    sim.step_until_no_events();
+
+    let recorder = recorder.borrow();
+    assert_float_eq(recorder.time_of("finished", short).unwrap(), 2., 1e-9);
+    // 2 flops are done at the half speed until time 2, the remaining 4 flops are done at the full speed
+    assert_float_eq(recorder.time_of("finished", long).unwrap(), 4., 1e-9);
+}
+
+#[test]
+fn test_interference_ignores_compute_bound_computations() {
+    let (mut sim, compute, recorder, recorder_id) = setup(|sim| {
+        Compute::new(1., 4, 100, sim.create_context("compute"))
+            .with_interference_model(Box::new(BandwidthInterferenceModel::new(4.)))
+    });
+    let memory_bound = compute.borrow_mut().submit(memory_bound_request(2., recorder_id));
+    let compute_bound = compute
+        .borrow_mut()
+        .run(2., 10, 2, 2, CoresDependency::Linear, recorder_id);
+    sim.step_until_no_events();
+
+    let recorder = recorder.borrow();
+    assert_float_eq(recorder.time_of("finished", memory_bound).unwrap(), 1., 1e-9);
+    assert_float_eq(recorder.time_of("finished", compute_bound).unwrap(), 1., 1e-9);
+}
@@ -0,0 +1,194 @@
+mod common;
+use common::{assert_float_eq, setup};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dslab_core::cast;
+use dslab_core::event::Event;
+use dslab_core::handler::EventHandler;
+use dslab_core::simulation::Simulation;
+
+use dslab_compute::multicore::*;
+use dslab_compute::queue::FifoPolicy;
+use dslab_compute::singlecore;
+use dslab_compute::singlecore::{
+    CompCanceled as SinglecoreCanceled, CompFinished as SinglecoreFinished, CompResumeFailed as SinglecoreResumeFailed,
+    CompResumed as SinglecoreResumed, CompStarted as SinglecoreStarted, CompSuspended as SinglecoreSuspended,
+};
+
+#[test]
+fn test_cancel_queued_computation() {
+    let (mut sim, compute, recorder, recorder_id) =
+        setup(|sim| Compute::new(1., 2, 100, sim.create_context("compute")).with_queue(Box::new(FifoPolicy::new())));
+    let first = compute
+        .borrow_mut()
+        .run(4., 0, 2, 2, CoresDependency::Linear, recorder_id);
+    let second = compute
+        .borrow_mut()
+        .run(2., 0, 2, 2, CoresDependency::Linear, recorder_id);
+    compute.borrow_mut().cancel(second);
+    sim.step_until_no_events();
+
+    let recorder = recorder.borrow();
+    assert_float_eq(recorder.time_of("canceled", second).unwrap(), 0., 1e-9);
+    assert!(recorder.time_of("started", second).is_none());
+    assert_float_eq(recorder.time_of("finished", first).unwrap(), 2., 1e-9);
+    assert_eq!(compute.borrow().queue_length(), 0);
+}
+
+#[test]
+fn test_cancel_running_computation_starts_waiting_one() {
+    let (mut sim, compute, recorder, recorder_id) =
+        setup(|sim| Compute::new(1., 2, 100, sim.create_context("compute")).with_queue(Box::new(FifoPolicy::new())));
+    let first = compute
+        .borrow_mut()
+        .run(4., 10, 2, 2, CoresDependency::Linear, recorder_id);
+    let second = compute
+        .borrow_mut()
+        .run(2., 10, 2, 2, CoresDependency::Linear, recorder_id);
+    sim.step_for_duration(1.);
+    compute.borrow_mut().cancel(first);
+    sim.step_until_no_events();
+
+    let recorder = recorder.borrow();
+    assert_float_eq(recorder.time_of("canceled", first).unwrap(), 1., 1e-9);
+    assert!(recorder.time_of("finished", first).is_none());
+    assert_float_eq(recorder.time_of("started", second).unwrap(), 1., 1e-9);
+    assert_float_eq(recorder.time_of("finished", second).unwrap(), 2., 1e-9);
+    assert_eq!(compute.borrow().cores_available(), 2);
+    assert_eq!(compute.borrow().memory_available(), 100);
+}
+
+#[test]
+fn test_suspend_and_resume() {
+    let (mut sim, compute, recorder, recorder_id) =
+        setup(|sim| Compute::new(1., 2, 100, sim.create_context("compute")));
+    let id = compute
+        .borrow_mut()
+        .run(4., 10, 2, 2, CoresDependency::Linear, recorder_id);
+    sim.step_for_duration(1.);
+    compute.borrow_mut().suspend(id);
+    sim.step_for_duration(2.);
+    assert_eq!(compute.borrow().suspended_count(), 1);
+    assert_eq!(compute.borrow().cores_available(), 2);
+    assert_eq!(compute.borrow().memory_available(), 90);
+    compute.borrow_mut().resume(id);
+    sim.step_until_no_events();
+
+    // the completion time is shifted by the suspended interval
+    let recorder = recorder.borrow();
+    assert_float_eq(recorder.time_of("suspended", id).unwrap(), 1., 1e-9);
+    assert_float_eq(recorder.time_of("resumed", id).unwrap(), 3., 1e-9);
+    assert_float_eq(recorder.time_of("finished", id).unwrap(), 4., 1e-9);
+    assert_eq!(compute.borrow().suspended_count(), 0);
+}
+
+#[test]
+fn test_resume_not_suspended_computation() {
+    let (mut sim, compute, recorder, recorder_id) =
+        setup(|sim| Compute::new(1., 2, 100, sim.create_context("compute")));
+    let id = compute
+        .borrow_mut()
+        .run(4., 10, 2, 2, CoresDependency::Linear, recorder_id);
+    compute.borrow_mut().resume(id);
+    sim.step_until_no_events();
+
+    let recorder = recorder.borrow();
+    assert_float_eq(recorder.time_of("resume_failed", id).unwrap(), 0., 1e-9);
+    assert!(recorder.time_of("resumed", id).is_none());
+    assert_float_eq(recorder.time_of("finished", id).unwrap(), 2., 1e-9);
+}
+
+#[derive(Default)]
+struct SinglecoreRecorder {
+    events: Vec<(f64, &'static str, u64)>,
+}
+
+impl SinglecoreRecorder {
+    fn time_of(&self, kind: &str, id: u64) -> Option<f64> {
+        self.events
+            .iter()
+            .find(|(_, k, i)| *k == kind && *i == id)
+            .map(|(time, _, _)| *time)
+    }
+}
+
+impl EventHandler for SinglecoreRecorder {
+    fn on(&mut self, event: Event) {
+        let time = event.time;
+        cast!(match event.data {
+            SinglecoreStarted { id, .. } => {
+                self.events.push((time, "started", id));
+            }
+            SinglecoreFinished { id } => {
+                self.events.push((time, "finished", id));
+            }
+            SinglecoreCanceled { id, .. } => {
+                self.events.push((time, "canceled", id));
+            }
+            SinglecoreSuspended { id, .. } => {
+                self.events.push((time, "suspended", id));
+            }
+            SinglecoreResumed { id } => {
+                self.events.push((time, "resumed", id));
+            }
+            SinglecoreResumeFailed { id, .. } => {
+                self.events.push((time, "resume_failed", id));
+            }
+        })
+    }
+}
+
+fn setup_singlecore() -> (
+    Simulation,
+    Rc<RefCell<singlecore::Compute>>,
+    Rc<RefCell<SinglecoreRecorder>>,
+    u32,
+) {
+    let mut sim = Simulation::new(123);
+    let recorder = Rc::new(RefCell::new(SinglecoreRecorder::default()));
+    let recorder_id = sim.add_handler("recorder", recorder.clone());
+    let compute = Rc::new(RefCell::new(singlecore::Compute::new(
+        1.,
+        100,
+        sim.create_context("compute"),
+    )));
+    sim.add_handler("compute", compute.clone());
+    (sim, compute, recorder, recorder_id)
+}
+
+#[test]
+fn test_singlecore_cancel_running_computation() {
+    let (mut sim, compute, recorder, recorder_id) = setup_singlecore();
+    let first = compute.borrow_mut().run(2., 10, recorder_id);
+    let second = compute.borrow_mut().run(2., 10, recorder_id);
+    sim.step_for_duration(1.);
+    compute.borrow_mut().cancel(first);
+    sim.step_until_no_events();
+
+    // the second computation gets the whole core after the first one is canceled
+    let recorder = recorder.borrow();
+    assert_float_eq(recorder.time_of("canceled", first).unwrap(), 1., 1e-9);
+    assert!(recorder.time_of("finished", first).is_none());
+    assert_float_eq(recorder.time_of("finished", second).unwrap(), 2.5, 1e-9);
+}
+
+#[test]
+fn test_singlecore_suspend_and_resume() {
+    let (mut sim, compute, recorder, recorder_id) = setup_singlecore();
+    let id = compute.borrow_mut().run(2., 10, recorder_id);
+    sim.step_for_duration(1.);
+    compute.borrow_mut().suspend(id);
+    sim.step_for_duration(2.);
+    compute.borrow_mut().resume(id);
+    compute.borrow_mut().resume(id);
+    sim.step_until_no_events();
+
+    let recorder = recorder.borrow();
+    assert_float_eq(recorder.time_of("suspended", id).unwrap(), 1., 1e-9);
+    assert_float_eq(recorder.time_of("resumed", id).unwrap(), 3., 1e-9);
+    // the second request arrives when the computation is already running
+    assert_float_eq(recorder.time_of("resume_failed", id).unwrap(), 3., 1e-9);
+    assert_float_eq(recorder.time_of("finished", id).unwrap(), 4., 1e-9);
+}
@@ -0,0 +1,131 @@
+mod common;
+use common::{assert_float_eq, setup};
+
+use dslab_compute::multicore::*;
+use dslab_compute::queue::*;
+use dslab_models::interference::model::MemoryProfile;
+
+fn computation(id: u64, flops: f64, cores: u32, priority: i64) -> QueuedComputation {
+    QueuedComputation {
+        id,
+        flops,
+        memory: 0,
+        min_cores: cores,
+        max_cores: cores,
+        cores_dependency: CoresDependency::Linear,
+        priority,
+        core_group: None,
+        memory_profile: MemoryProfile::default(),
+        requester: 0,
+        submit_time: 0.,
+    }
+}
+
+fn resource_state(cores_available: u32, running: Vec<RunningComputationInfo>) -> ResourceState {
+    ResourceState {
+        time: 0.,
+        speeds: vec![1.],
+        cores_available: vec![cores_available],
+        memory_available: 100,
+        core_group_policy: CoreGroupPolicy::default(),
+        running,
+    }
+}
+
+fn selected_ids(selected: Vec<(u64, usize, u32)>) -> Vec<u64> {
+    selected.into_iter().map(|(id, _, _)| id).collect()
+}
+
+#[test]
+fn test_policy_ordering() {
+    let queue = vec![
+        computation(0, 30., 2, 0),
+        computation(1, 10., 2, 1),
+        computation(2, 20., 2, 5),
+    ];
+    let state = resource_state(4, Vec::new());
+    assert_eq!(selected_ids(FifoPolicy::new().select(&queue, &state)), vec![0, 1]);
+    assert_eq!(
+        selected_ids(ShortestJobFirstPolicy::new().select(&queue, &state)),
+        vec![1, 2]
+    );
+    assert_eq!(selected_ids(PriorityPolicy::new().select(&queue, &state)), vec![2, 1]);
+    // the policies stop at the first computation which doesn't fit
+    let queue = vec![computation(0, 1., 4, 0), computation(1, 1., 1, 0)];
+    assert!(FifoPolicy::new()
+        .select(&queue, &resource_state(2, Vec::new()))
+        .is_empty());
+}
+
+#[test]
+fn test_backfilling_does_not_delay_head() {
+    // two of four cores are busy until time 10, the head needs all four cores
+    let running = vec![RunningComputationInfo {
+        core_group: 0,
+        cores: 2,
+        memory: 0,
+        finish_time: 10.,
+    }];
+    let queue = vec![
+        computation(0, 4., 4, 0),
+        // would run until time 10 on two cores, which are needed for the head at time 10
+        computation(1, 40., 2, 0),
+        // completes before time 10
+        computation(2, 10., 2, 0),
+    ];
+    let selected = EasyBackfillingPolicy::new().select(&queue, &resource_state(2, running));
+    assert_eq!(selected, vec![(2, 0, 2)]);
+}
+
+#[test]
+fn test_backfilling_without_reservation() {
+    // two of four cores are held by a manual allocation, so the head can't get a reservation
+    let queue = vec![computation(0, 4., 4, 0), computation(1, 2., 2, 0)];
+    let selected = EasyBackfillingPolicy::new().select(&queue, &resource_state(2, Vec::new()));
+    assert!(selected.is_empty());
+}
+
+#[test]
+fn test_head_is_not_starved_by_allocation() {
+    let (mut sim, compute, recorder, recorder_id) = setup(|sim| {
+        Compute::new(1., 4, 100, sim.create_context("compute")).with_queue(Box::new(EasyBackfillingPolicy::new()))
+    });
+    compute.borrow_mut().allocate(2, 0, recorder_id);
+    sim.step_until_no_events();
+    let head = compute
+        .borrow_mut()
+        .run(4., 0, 4, 4, CoresDependency::Linear, recorder_id);
+    let small = compute
+        .borrow_mut()
+        .run(2., 0, 2, 2, CoresDependency::Linear, recorder_id);
+    sim.step_for_duration(5.);
+    compute.borrow_mut().deallocate(2, 0, recorder_id);
+    sim.step_until_no_events();
+
+    let recorder = recorder.borrow();
+    assert_float_eq(recorder.time_of("started", head).unwrap(), 5., 1e-9);
+    assert_float_eq(recorder.time_of("finished", head).unwrap(), 6., 1e-9);
+    assert_float_eq(recorder.time_of("started", small).unwrap(), 6., 1e-9);
+}
+
+#[test]
+fn test_wait_time_stats() {
+    let (mut sim, compute, recorder, recorder_id) =
+        setup(|sim| Compute::new(1., 2, 100, sim.create_context("compute")).with_queue(Box::new(FifoPolicy::new())));
+    let ids = (0..3)
+        .map(|_| {
+            compute
+                .borrow_mut()
+                .run(2., 0, 2, 2, CoresDependency::Linear, recorder_id)
+        })
+        .collect::<Vec<_>>();
+    sim.step_until_no_events();
+
+    let recorder = recorder.borrow();
+    for (i, id) in ids.into_iter().enumerate() {
+        assert_float_eq(recorder.time_of("started", id).unwrap(), i as f64, 1e-9);
+    }
+    assert_eq!(compute.borrow().queue_length(), 0);
+    assert_float_eq(compute.borrow().average_wait_time(), 1., 1e-9);
+    assert_float_eq(compute.borrow().max_wait_time(), 2., 1e-9);
+}
@@ -0,0 +1,53 @@
+mod common;
+use common::{assert_float_eq, setup};
+
+use dslab_models::power::cpu_models::dvfs::DvfsAwareCpuPowerModel;
+use dslab_models::power::host::HostPowerModelBuilder;
+use dslab_models::thermal::governor::{PerformanceLevel, ThermalGovernor};
+use dslab_models::thermal::rc::RcThermalModel;
+
+use dslab_compute::multicore::*;
+use dslab_compute::thermal::ThermalControl;
+
+#[test]
+fn test_thermal_throttling_slows_down_computation() {
+    let (mut sim, compute, recorder, recorder_id) = setup(|sim| {
+        // the resource consumes 100 W under full load, so its temperature is T(t) = 100 * (1 - exp(-t))
+        let power_model = HostPowerModelBuilder::new()
+            .cpu(Box::new(DvfsAwareCpuPowerModel::new(0., 100., 0.)))
+            .build();
+        let governor = ThermalGovernor::new(
+            vec![
+                PerformanceLevel::new(1., None, None),
+                PerformanceLevel::new(0.5, None, None),
+            ],
+            50.,
+            40.,
+        );
+        let thermal_control = ThermalControl::new(Box::new(RcThermalModel::new(1., 1., 0.)), governor, power_model);
+        Compute::new(1., 2, 100, sim.create_context("compute")).with_thermal_control(thermal_control)
+    });
+    let id = compute
+        .borrow_mut()
+        .run(2., 10, 2, 2, CoresDependency::Linear, recorder_id);
+    sim.step_for_duration(0.9);
+    {
+        let compute = compute.borrow();
+        let thermal_control = compute.thermal_control().unwrap();
+        assert!(thermal_control.temperature(sim.time()) > 50.);
+        assert_eq!(thermal_control.level().speed, 0.5);
+        assert_float_eq(compute.current_speed(), 0.5, 1e-9);
+    }
+    sim.step_until_no_events();
+
+    // the trip temperature is reached at ln(2) and the remaining work is done at the half speed,
+    // so the computation finishes at ln(2) + (2 - 2 * ln(2)) instead of 1
+    let throttle_time = 2f64.ln();
+    assert_float_eq(
+        recorder.borrow().time_of("finished", id).unwrap(),
+        throttle_time + (2. - 2. * throttle_time),
+        1e-6,
+    );
+    // the idle resource cools down to the release temperature and restores the full speed
+    assert_float_eq(compute.borrow().current_speed(), 1., 1e-9);
+}
@@ -193,6 +193,11 @@ impl DAG {
         self.tasks[task_id].resource_restriction = Some(restriction);
     }
 
+    /// Sets the cost of task execution on accelerator.
+    pub fn set_accelerator_cost(&mut self, task_id: usize, cost: AcceleratorCost) {
+        self.tasks[task_id].accelerator = Some(cost);
+    }
+
     /// Sets data item as output of the specified task.
     ///
     /// The data item must not have producer, i.e. it must be among the DAG inputs.
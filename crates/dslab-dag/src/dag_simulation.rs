@@ -1,14 +1,15 @@
 //! Simulation configuration and execution.
 
 use std::cell::RefCell;
+use std::collections::BTreeSet;
 use std::rc::Rc;
 
-use dslab_compute::multicore::{Compute, CoresDependency};
+use dslab_compute::multicore::{Compute, CoreGroup, CoresDependency};
 use dslab_core::simulation::Simulation;
 
 use crate::dag::DAG;
 use crate::network::NetworkConfig;
-use crate::resource::{Resource, ResourceConfig};
+use crate::resource::{AcceleratorConfig, CoreGroupConfig, Resource, ResourceConfig};
 use crate::runner::{Config, DAGRunner, Start};
 use crate::scheduler::Scheduler;
 use crate::task::ResourceRestriction;
@@ -47,6 +48,40 @@ impl DagSimulation {
             speed,
             cores,
             memory,
+            core_groups: Vec::new(),
+            accelerator: None,
+        });
+    }
+
+    /// Adds a resource with provided parameters and an attached accelerator.
+    pub fn add_resource_with_accelerator(
+        &mut self,
+        name: &str,
+        speed: f64,
+        cores: u32,
+        memory: u64,
+        accelerator: AcceleratorConfig,
+    ) {
+        self.resource_configs.push(ResourceConfig {
+            name: name.to_string(),
+            speed,
+            cores,
+            memory,
+            core_groups: Vec::new(),
+            accelerator: Some(accelerator),
+        });
+    }
+
+    /// Adds a resource with heterogeneous cores organized into provided core groups.
+    pub fn add_resource_with_core_groups(&mut self, name: &str, core_groups: Vec<CoreGroupConfig>, memory: u64) {
+        assert!(!core_groups.is_empty(), "At least one core group must be specified");
+        self.resource_configs.push(ResourceConfig {
+            name: name.to_string(),
+            speed: core_groups.iter().map(|g| g.speed).fold(0., f64::max),
+            cores: core_groups.iter().map(|g| g.cores).sum(),
+            memory,
+            core_groups,
+            accelerator: None,
         });
     }
 
@@ -62,27 +97,41 @@ impl DagSimulation {
             .resource_configs
             .iter()
             .map(|r| {
-                let compute = Rc::new(RefCell::new(Compute::new(
-                    r.speed,
-                    r.cores,
+                let core_groups = r.effective_core_groups();
+                let compute = Rc::new(RefCell::new(Compute::from_core_groups(
+                    core_groups
+                        .iter()
+                        .map(|g| CoreGroup::new(&g.name, g.cores, g.speed))
+                        .collect(),
                     r.memory,
                     self.sim.create_context(&r.name),
                 )));
                 let id = self.sim.add_handler(&r.name, compute.clone());
+                let speed = compute.borrow().speed();
+                let cores = compute.borrow().cores_total();
+                let accelerator = r.accelerator.as_ref().map(|config| {
+                    let name = format!("{}-accelerator", r.name);
+                    let accelerator = Rc::new(RefCell::new(config.make_accelerator(self.sim.create_context(&name))));
+                    self.sim.add_handler(&name, accelerator.clone());
+                    accelerator
+                });
                 Resource {
                     id,
                     name: r.name.clone(),
                     compute,
-                    speed: r.speed,
-                    cores: r.cores,
-                    cores_available: r.cores,
+                    speed,
+                    cores,
+                    cores_available: cores,
                     memory: r.memory,
                     memory_available: r.memory,
+                    core_groups,
+                    accelerator,
                 }
             })
             .collect::<Vec<_>>();
 
         self.add_input_output_tasks(&mut dag);
+        self.restrict_accelerated_tasks(&mut dag);
 
         let runner = Rc::new(RefCell::new(DAGRunner::new(
             dag,
@@ -134,6 +183,33 @@ impl DagSimulation {
         self.sim.time()
     }
 
+    /// Restricts the tasks which require accelerator to the resources with accelerator.
+    ///
+    /// The restriction is intersected with the existing task restriction.
+    /// Panics if there is no resource with accelerator the task is allowed to run on.
+    fn restrict_accelerated_tasks(&self, dag: &mut DAG) {
+        for task_id in 0..dag.get_tasks().len() {
+            let task = dag.get_task(task_id);
+            if !task.accelerator.as_ref().is_some_and(|a| a.required) {
+                continue;
+            }
+            let allowed_resources = self
+                .resource_configs
+                .iter()
+                .enumerate()
+                .filter(|(i, r)| r.accelerator.is_some() && r.name != "master" && task.is_allowed_on(*i))
+                .map(|(i, _)| i)
+                .collect::<BTreeSet<_>>();
+            if allowed_resources.is_empty() {
+                panic!(
+                    "Task {} requires accelerator, but it isn't allowed to run on any resource with accelerator",
+                    task.name
+                );
+            }
+            dag.set_resource_restriction(task_id, ResourceRestriction::Only(allowed_resources));
+        }
+    }
+
     fn add_input_output_tasks(&mut self, dag: &mut DAG) {
         let master_resource = self.resource_configs.iter().position(|r| r.name == "master").unwrap();
 
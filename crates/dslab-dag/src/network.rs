@@ -8,7 +8,11 @@ use serde::{Deserialize, Serialize};
 
 use dslab_core::context::SimulationContext;
 use dslab_core::Id;
-use dslab_network::models::{ConstantBandwidthNetworkModel, SharedBandwidthNetworkModel, TopologyAwareNetworkModel};
+use dslab_network::models::tcp_aware::{LV08_TCP_WINDOW, LV08_WEIGHT_S};
+use dslab_network::models::{
+    ConstantBandwidthNetworkModel, SharedBandwidthNetworkModel, TcpAwareNetworkModel, TopologyAwareNetworkModel,
+};
+use dslab_network::platform::Platform;
 use dslab_network::{Link, Network};
 
 use crate::resource::Resource;
@@ -45,6 +49,18 @@ pub enum NetworkConfig {
         /// Links latency in μs.
         link_latency: f64,
     },
+    /// Topology-aware model with topology imported from SimGrid platform file.
+    ///
+    /// Resources are located on the platform hosts with the same names.
+    SimGridPlatform {
+        /// Path to platform file.
+        file: String,
+        /// Name of the platform host where the master is located.
+        master_host: String,
+        /// Whether to use [`TcpAwareNetworkModel`] with LV08 parameters instead of [`TopologyAwareNetworkModel`].
+        #[serde(default)]
+        tcp_aware: bool,
+    },
 }
 
 impl NetworkConfig {
@@ -73,7 +89,32 @@ impl NetworkConfig {
         }
     }
 
+    /// Creates network config with [`TopologyAwareNetworkModel`] using the topology from SimGrid platform file.
+    ///
+    /// Resources are located on the platform hosts with the same names, the master is located on `master_host`.
+    pub fn simgrid_platform(file: &str, master_host: &str) -> Self {
+        NetworkConfig::SimGridPlatform {
+            file: file.to_string(),
+            master_host: master_host.to_string(),
+            tcp_aware: false,
+        }
+    }
+
+    /// Creates network config with [`TcpAwareNetworkModel`] using the topology from SimGrid platform file.
+    ///
+    /// The model uses parameters of SimGrid's LV08 model, so the results should be close to SimGrid predictions
+    /// for the same platform. Resources are located as in [`Self::simgrid_platform`].
+    pub fn simgrid_platform_tcp_aware(file: &str, master_host: &str) -> Self {
+        NetworkConfig::SimGridPlatform {
+            file: file.to_string(),
+            master_host: master_host.to_string(),
+            tcp_aware: true,
+        }
+    }
+
     /// Creates network model based on stored parameters.
+    ///
+    /// In case of SimGrid platform, the platform file is parsed here and its nodes and links are added to the network.
     pub fn make_network(&self, ctx: SimulationContext) -> Network {
         match self {
             NetworkConfig::ConstantBandwidth { bandwidth, latency } => {
@@ -95,13 +136,54 @@ impl NetworkConfig {
                 )
             }
             NetworkConfig::TopologyAware { .. } => Network::new(Box::new(TopologyAwareNetworkModel::new()), ctx),
+            NetworkConfig::SimGridPlatform { file, tcp_aware, .. } => {
+                let mut platform = Platform::from_simgrid_xml(file);
+                platform.scale_bandwidth(1e-6); // convert to MB/s since data item sizes are in MB
+                let mut network = if *tcp_aware {
+                    Network::new(
+                        Box::new(
+                            TcpAwareNetworkModel::new()
+                                .with_routing(platform.routing())
+                                // convert to MB since data item sizes are in MB
+                                .with_weight_s(LV08_WEIGHT_S * 1e-6)
+                                .with_tcp_window(LV08_TCP_WINDOW * 1e-6),
+                        ),
+                        ctx,
+                    )
+                } else {
+                    Network::new(
+                        Box::new(TopologyAwareNetworkModel::new().with_routing(platform.routing())),
+                        ctx,
+                    )
+                };
+                platform.add_to_network(&mut network, |_| {
+                    Box::new(ConstantBandwidthNetworkModel::new(100000., 0.))
+                });
+                network.init_topology();
+                network
+            }
         }
     }
 
-    /// Adds network nodes and links (in case of topology-aware network model).
+    /// Adds network nodes and links (in case of topology-aware network model) and sets the locations of resources.
+    ///
+    /// The network must be created by [`Self::make_network`] with the same config.
     pub fn init_network(&self, network: Rc<RefCell<Network>>, runner_id: Id, resources: &[Resource]) {
         let mut network = network.borrow_mut();
 
+        if let NetworkConfig::SimGridPlatform { master_host, .. } = self {
+            for resource in resources.iter() {
+                let host = if resource.name == "master" {
+                    master_host
+                } else {
+                    &resource.name
+                };
+                network.set_location(resource.id, host);
+            }
+            network.set_location(runner_id, master_host);
+            return;
+        }
+
         // Add nodes
         for (host_name, id) in resources
             .iter()
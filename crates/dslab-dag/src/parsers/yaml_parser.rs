@@ -8,6 +8,7 @@ use dslab_compute::multicore::CoresDependency;
 
 use crate::dag::DAG;
 use crate::parsers::config::ParserConfig;
+use crate::task::AcceleratorCost;
 
 fn one() -> u32 {
     1
@@ -23,6 +24,17 @@ struct DataItem {
     size: f64,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct Accelerator {
+    // expected unit: Gflops
+    flops: f64,
+    #[serde(default = "zero")]
+    // expected unit: MB
+    memory: u64,
+    #[serde(default)]
+    required: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct Task {
     name: String,
@@ -39,6 +51,7 @@ struct Task {
     #[serde(default)]
     inputs: Vec<String>,
     outputs: Vec<DataItem>,
+    accelerator: Option<Accelerator>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -78,6 +91,16 @@ impl DAG {
                     _ => CoresDependency::Linear,
                 },
             );
+            if let Some(accelerator) = &task.accelerator {
+                dag.set_accelerator_cost(
+                    task_id,
+                    AcceleratorCost {
+                        flops: accelerator.flops,
+                        memory: if !config.ignore_memory { accelerator.memory } else { 0 },
+                        required: accelerator.required,
+                    },
+                );
+            }
             for output in task.outputs.iter() {
                 data_items.insert(
                     output.name.clone(),
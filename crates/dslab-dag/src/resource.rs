@@ -1,45 +1,199 @@
 //! Resource model.
 
 use std::cell::RefCell;
+use std::ops::Range;
 use std::path::Path;
 use std::rc::Rc;
 
 use serde::{Deserialize, Serialize};
 
 use dslab_core::component::Id;
+use dslab_core::context::SimulationContext;
 
+use dslab_compute::accelerator::{Accelerator, Partition};
 use dslab_compute::multicore::*;
+use dslab_network::platform::Platform;
+
+use crate::task::Task;
 
 /// Represents a computing resource that can execute DAG tasks.
 ///
 /// Described by the number of CPU cores, their speed in flop/s and amount of memory.
+/// The cores can be heterogeneous, in which case they are organized into core groups with different speed.
+/// The cores of each group occupy a contiguous range of core indices, and each task runs on the cores from
+/// a single group.
 ///
 /// Supports execution of parallel tasks. The modeling of task execution is implemented by means of the
 /// [multicore](https://github.com/osukhoroslov/dslab/tree/main/crates/dslab-compute/src/multicore.rs)
 /// compute model from the dslab-compute crate.
+///
+/// The resource can also have an accelerator (e.g. GPU), which is used to run the tasks with accelerator cost.
+/// Such tasks still occupy the allocated cores and memory of the resource during their execution.
 #[derive(Clone)]
 pub struct Resource {
     pub id: Id,
     pub name: String,
     pub compute: Rc<RefCell<Compute>>,
-    /// CPU speed in Gflop/s.
+    /// CPU speed in Gflop/s (the speed of the fastest core group if the cores are heterogeneous).
     pub speed: f64,
     pub cores: u32,
     pub cores_available: u32,
     /// Memory size in MB.
     pub memory: u64,
     pub memory_available: u64,
+    /// Core groups in the order of their core indices, contains a single group if the cores are homogeneous.
+    pub core_groups: Vec<CoreGroupConfig>,
+    pub accelerator: Option<Rc<RefCell<Accelerator>>>,
+}
+
+impl Resource {
+    /// Returns the index of the core group containing the given core.
+    pub fn core_group(&self, core: u32) -> usize {
+        let mut end = 0;
+        for (group, core_group) in self.core_groups.iter().enumerate() {
+            end += core_group.cores;
+            if core < end {
+                return group;
+            }
+        }
+        panic!("Core {} is out of range for resource {}", core, self.name);
+    }
+
+    /// Returns the maximum number of cores which can be used by a task, i.e. the size of the largest core group.
+    pub fn max_task_cores(&self) -> u32 {
+        self.core_groups.iter().map(|g| g.cores).max().unwrap()
+    }
+
+    /// Returns the range of core indices occupied by the given core group.
+    pub fn core_group_cores(&self, group: usize) -> Range<u32> {
+        let start = self.core_groups[..group].iter().map(|g| g.cores).sum();
+        start..start + self.core_groups[group].cores
+    }
+
+    /// Returns whether the task is executed on the accelerator of this resource,
+    /// i.e. the task has accelerator cost and the accelerator has enough memory for it.
+    pub fn runs_on_accelerator(&self, task: &Task) -> bool {
+        match (task.accelerator.as_ref(), self.accelerator.as_ref()) {
+            (Some(cost), Some(accelerator)) => accelerator
+                .borrow()
+                .partitions()
+                .iter()
+                .any(|p| p.memory >= cost.memory),
+            _ => false,
+        }
+    }
+
+    /// Returns the task execution time on the accelerator without queueing and contention,
+    /// or `None` if the task is not executed on the accelerator of this resource.
+    pub fn accelerator_time(&self, task: &Task, input_size: f64, output_size: f64) -> Option<f64> {
+        if !self.runs_on_accelerator(task) {
+            return None;
+        }
+        let cost = task.accelerator.as_ref().unwrap();
+        let accelerator = self.accelerator.as_ref().unwrap().borrow();
+        accelerator
+            .partitions()
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.memory >= cost.memory)
+            .map(|(i, _)| accelerator.estimate_time(cost.flops, input_size, output_size, i))
+            .min_by(|a, b| a.total_cmp(b))
+    }
+}
+
+/// Contains parameters of a group of identical cores within a resource.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CoreGroupConfig {
+    pub name: String,
+    pub cores: u32,
+    /// CPU speed in Gflop/s.
+    pub speed: f64,
+}
+
+/// Contains parameters of a partition of accelerator device.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PartitionConfig {
+    pub name: String,
+    /// Partition speed in Gflop/s.
+    pub speed: f64,
+    /// Partition memory size in MB.
+    pub memory: u64,
+}
+
+/// Contains parameters of accelerator device (e.g. GPU) attached to a resource.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AcceleratorConfig {
+    /// Device speed in Gflop/s.
+    pub speed: f64,
+    /// Device memory size in MB.
+    pub memory: u64,
+    /// Bandwidth of host-device link in MB/s.
+    pub bandwidth: f64,
+    /// Latency of host-device link in seconds.
+    #[serde(default)]
+    pub latency: f64,
+    /// Allows multiple tasks to share a device partition concurrently.
+    #[serde(default)]
+    pub time_slicing: bool,
+    /// Device partitions, empty if the whole device is used as a single partition.
+    #[serde(default)]
+    pub partitions: Vec<PartitionConfig>,
+}
+
+impl AcceleratorConfig {
+    /// Creates accelerator model instance with the given context.
+    pub fn make_accelerator(&self, ctx: SimulationContext) -> Accelerator {
+        let mut accelerator = Accelerator::new(self.speed, self.memory, self.bandwidth, self.latency, ctx);
+        if !self.partitions.is_empty() {
+            accelerator = accelerator.with_partitions(
+                self.partitions
+                    .iter()
+                    .map(|p| Partition::new(&p.name, p.speed, p.memory))
+                    .collect(),
+            );
+        }
+        if self.time_slicing {
+            accelerator = accelerator.with_time_slicing();
+        }
+        accelerator
+    }
 }
 
 /// Contains parameters of computing resource, can be used later to create a compute resource instance.
+///
+/// The resource with heterogeneous cores is described by the list of core groups,
+/// in which case `speed` and `cores` are ignored and can be omitted.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ResourceConfig {
     pub name: String,
     /// CPU speed in Gflop/s.
+    #[serde(default)]
     pub speed: f64,
+    #[serde(default)]
     pub cores: u32,
     /// Memory size in MB.
     pub memory: u64,
+    /// Core groups of the resource with heterogeneous cores, empty if the cores are homogeneous.
+    #[serde(default)]
+    pub core_groups: Vec<CoreGroupConfig>,
+    /// Accelerator attached to the resource.
+    #[serde(default)]
+    pub accelerator: Option<AcceleratorConfig>,
+}
+
+impl ResourceConfig {
+    /// Returns the resource core groups, or a single group with `speed` and `cores` if the cores are homogeneous.
+    pub fn effective_core_groups(&self) -> Vec<CoreGroupConfig> {
+        if self.core_groups.is_empty() {
+            vec![CoreGroupConfig {
+                name: "default".to_string(),
+                cores: self.cores,
+                speed: self.speed,
+            }]
+        } else {
+            self.core_groups.clone()
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -51,10 +205,40 @@ struct Resources {
 ///
 /// Configuration file example:
 /// <https://github.com/osukhoroslov/dslab/blob/main/examples/dag-demo/systems/cluster-het-4-32cores.yaml>
+///
+/// Example with heterogeneous cores:
+/// <https://github.com/osukhoroslov/dslab/blob/main/examples/dag-demo/systems/cluster-hybrid-4-48cores.yaml>
 pub fn read_resource_configs<P: AsRef<Path>>(file: P) -> Vec<ResourceConfig> {
     let resources: Resources = serde_yaml::from_str(
         &std::fs::read_to_string(&file).unwrap_or_else(|_| panic!("Can't read file {}", file.as_ref().display())),
     )
     .unwrap_or_else(|_| panic!("Can't parse YAML from file {}", file.as_ref().display()));
-    resources.resources
+    let mut resources = resources.resources;
+    for resource in resources.iter_mut().filter(|r| !r.core_groups.is_empty()) {
+        resource.speed = resource.core_groups.iter().map(|g| g.speed).fold(0., f64::max);
+        resource.cores = resource.core_groups.iter().map(|g| g.cores).sum();
+    }
+    resources
+}
+
+/// Creates resource configurations for the hosts of imported network platform.
+///
+/// Host speed is converted to Gflop/s. Memory size in MB is read from the `memory` host property if it is present,
+/// otherwise `default_memory` is used.
+pub fn resource_configs_from_platform(platform: &Platform, default_memory: u64) -> Vec<ResourceConfig> {
+    platform
+        .hosts
+        .iter()
+        .map(|host| ResourceConfig {
+            name: host.name.clone(),
+            speed: host.speed / 1e9,
+            cores: host.cores,
+            core_groups: Vec::new(),
+            accelerator: None,
+            memory: host
+                .properties
+                .get("memory")
+                .map_or(default_memory, |m| m.parse().expect("Can't parse host memory")),
+        })
+        .collect()
 }
@@ -9,6 +9,7 @@ use serde::Serialize;
 
 use strum::IntoEnumIterator;
 
+use dslab_compute::accelerator::{KernelFinished, KernelStarted};
 use dslab_compute::multicore::*;
 use dslab_core::cast;
 use dslab_core::component::Id;
@@ -288,7 +289,7 @@ impl DAGRunner {
             );
             return;
         }
-        if need_cores > self.resources[resource].compute.borrow().cores_total() {
+        if need_cores > self.resources[resource].max_task_cores() {
             log_error!(
                 self.ctx,
                 "Wrong action, resource {} doesn't have enough cores",
@@ -304,6 +305,16 @@ impl DAGRunner {
             );
             return;
         }
+        if task.accelerator.as_ref().is_some_and(|a| a.required) && !self.resources[resource].runs_on_accelerator(task)
+        {
+            log_error!(
+                self.ctx,
+                "Wrong action, task {} requires accelerator which resource {} doesn't have or can't fit",
+                task_id,
+                resource
+            );
+            return;
+        }
         if need_cores < task.min_cores || task.max_cores < need_cores {
             log_error!(
                 self.ctx,
@@ -383,6 +394,18 @@ impl DAGRunner {
                         log_error!(self.ctx, "Wrong action, cores list {:?} contains same cores", cores);
                         return;
                     }
+                    let resource_info = &self.resources[resource];
+                    if cores
+                        .iter()
+                        .any(|&core| resource_info.core_group(core) != resource_info.core_group(cores[0]))
+                    {
+                        log_error!(
+                            self.ctx,
+                            "Wrong action, cores list {:?} contains cores from different core groups",
+                            cores
+                        );
+                        return;
+                    }
                     self.process_schedule_action(task, resource, cores.len() as u32, cores, expected_span);
                 }
                 Action::TransferData { data_item, from, to } => {
@@ -432,11 +455,11 @@ impl DAGRunner {
             }
 
             for (action_id, need_cores) in needed_cores.into_iter() {
-                let mut ready_cores = ready_cores.remove(&action_id).unwrap();
-                ready_cores.truncate(need_cores as usize);
-                if ready_cores.len() < need_cores as usize {
-                    continue;
-                }
+                let ready_cores = ready_cores.remove(&action_id).unwrap();
+                let ready_cores = match select_group_cores(&self.resources[resource_idx], ready_cores, need_cores) {
+                    Some(cores) => cores,
+                    None => continue,
+                };
                 let task_id = task_ids.remove(&action_id).unwrap();
                 let task = self.dag.get_task(task_id);
                 let resource = &mut self.resources[resource_idx];
@@ -626,15 +649,32 @@ impl DAGRunner {
     fn start_task(&mut self, task_id: usize) {
         let task = self.dag.get_task(task_id);
         let location = *self.task_location.get(&task_id).unwrap();
-        let cores = self.task_cores.get(&task_id).unwrap().len() as u32;
-        let computation_id = self.resources[location].compute.borrow_mut().run(
-            task.flops,
-            task.memory,
-            cores,
-            cores,
-            task.cores_dependency,
-            self.id,
-        );
+        let task_cores = self.task_cores.get(&task_id).unwrap();
+        let cores = task_cores.len() as u32;
+        let core_group = self.resources[location].core_group(task_cores[0]);
+        let computation_id = if self.resources[location].runs_on_accelerator(task) {
+            let cost = task.accelerator.as_ref().unwrap();
+            let data_size = |items: &Vec<usize>| items.iter().map(|&id| self.dag.get_data_item(id).size).sum();
+            self.resources[location].accelerator.as_ref().unwrap().borrow_mut().run(
+                cost.flops,
+                cost.memory,
+                data_size(&task.inputs),
+                data_size(&task.outputs),
+                self.id,
+            )
+        } else {
+            self.resources[location].compute.borrow_mut().submit(CompRequest {
+                flops: task.flops,
+                memory: task.memory,
+                min_cores: cores,
+                max_cores: cores,
+                cores_dependency: task.cores_dependency,
+                priority: 0,
+                core_group: Some(core_group),
+                memory_profile: MemoryProfile::default(),
+                requester: self.id,
+            })
+        };
         self.computations.insert(computation_id, task_id);
 
         self.run_stats
@@ -710,6 +750,27 @@ impl DAGRunner {
 #[derive(Clone, Serialize)]
 pub struct Start {}
 
+/// Selects the given number of cores from a single core group of the resource,
+/// preferring the fastest group among the ones with enough cores.
+fn select_group_cores(resource: &Resource, cores: Vec<u32>, need_cores: u32) -> Option<Vec<u32>> {
+    let mut group_cores: BTreeMap<usize, Vec<u32>> = BTreeMap::new();
+    for core in cores {
+        group_cores.entry(resource.core_group(core)).or_default().push(core);
+    }
+    group_cores
+        .into_iter()
+        .filter(|(_, cores)| cores.len() >= need_cores as usize)
+        .min_by(|(a, _), (b, _)| {
+            resource.core_groups[*b]
+                .speed
+                .total_cmp(&resource.core_groups[*a].speed)
+        })
+        .map(|(_, mut cores)| {
+            cores.truncate(need_cores as usize);
+            cores
+        })
+}
+
 impl EventHandler for DAGRunner {
     fn on(&mut self, event: Event) {
         cast!(match event.data {
@@ -721,6 +782,11 @@ impl EventHandler for DAGRunner {
                 let task_id = self.computations.remove(&id).unwrap();
                 self.on_task_completed(task_id);
             }
+            KernelStarted { .. } => {}
+            KernelFinished { id } => {
+                let task_id = self.computations.remove(&id).unwrap();
+                self.on_task_completed(task_id);
+            }
             DataTransferCompleted { dt } => {
                 self.on_data_transfer_completed(dt.id);
             }
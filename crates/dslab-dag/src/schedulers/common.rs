@@ -144,26 +144,54 @@ pub fn evaluate_assignment(
             .max_by(|a, b| a.total_cmp(b))
             .unwrap_or(0.),
     };
-    let task_exec_time = dag.get_task(task_id).flops
-        / resources[resource].speed
-        / dag.get_task(task_id).cores_dependency.speedup(need_cores)
-        + download_time;
+    // the task with accelerator cost runs on the accelerator if the resource has one,
+    // its contention with other tasks using the accelerator is not taken into account
+    let task = dag.get_task(task_id);
+    let data_size = |items: &Vec<usize>| items.iter().map(|&id| dag.get_data_item(id).size).sum();
+    let accelerator_time =
+        resources[resource].accelerator_time(task, data_size(&task.inputs), data_size(&task.outputs));
+    if accelerator_time.is_none() && task.accelerator.as_ref().is_some_and(|a| a.required) {
+        return None;
+    }
+
+    // each core group is evaluated separately, since the task runs on the cores from a single group
+    let mut best: Option<(f64, f64, Vec<u32>)> = None;
+    for (group, core_group) in resources[resource].core_groups.iter().enumerate() {
+        if core_group.cores < need_cores {
+            continue;
+        }
+        let group_cores = resources[resource].core_group_cores(group);
+        let task_exec_time = accelerator_time
+            .unwrap_or_else(|| task.flops / core_group.speed / task.cores_dependency.speedup(need_cores))
+            + download_time;
 
-    let (start_time, cores) = find_earliest_slot(
-        &scheduled_tasks[resource],
-        start_time,
-        task_exec_time,
-        need_cores,
-        need_memory,
-        resources[resource].compute.borrow().memory_total(),
-        &memory_usage[resource],
-    );
+        let (start_time, cores) = find_earliest_slot(
+            &scheduled_tasks[resource][group_cores.start as usize..group_cores.end as usize],
+            start_time,
+            task_exec_time,
+            need_cores,
+            need_memory,
+            resources[resource].compute.borrow().memory_total(),
+            &memory_usage[resource],
+        );
 
-    assert!(cores.len() >= need_cores as usize);
+        assert!(cores.len() >= need_cores as usize);
 
-    let cores = cores.iter().take(need_cores as usize).cloned().collect::<Vec<_>>();
+        let cores = cores
+            .iter()
+            .take(need_cores as usize)
+            .map(|&core| group_cores.start + core)
+            .collect::<Vec<_>>();
 
-    Some((start_time, start_time + task_exec_time, cores))
+        let finish_time = start_time + task_exec_time;
+        if best
+            .as_ref()
+            .is_none_or(|(_, best_finish, _)| finish_time < *best_finish)
+        {
+            best = Some((start_time, finish_time, cores));
+        }
+    }
+    best
 }
 
 fn find_earliest_slot(
@@ -19,6 +19,8 @@ struct Resource {
     id: usize,
     cores: u32,
     cores_available: u32,
+    // tasks can't use more cores than there are in the largest core group
+    max_task_cores: u32,
     memory: u64,
     memory_available: u64,
     speed: f64,
@@ -122,6 +124,7 @@ impl DynamicListScheduler {
                 id,
                 cores: resource.cores,
                 cores_available: resource.cores_available,
+                max_task_cores: resource.max_task_cores(),
                 memory: resource.memory,
                 memory_available: resource.memory_available,
                 speed: resource.speed,
@@ -277,7 +280,9 @@ impl DynamicListScheduler {
                 CoresCriterion::Efficiency90 => get_max_cores_for_efficiency(0.9),
                 CoresCriterion::Efficiency50 => get_max_cores_for_efficiency(0.5),
             };
-            let cores = cores.clamp(dag.get_task(task).min_cores, dag.get_task(task).max_cores);
+            let cores = cores
+                .min(resources[best_resource].max_task_cores)
+                .clamp(dag.get_task(task).min_cores, dag.get_task(task).max_cores);
 
             resources[best_resource].cores_available -= cores;
             resources[best_resource].memory_available -= dag.get_task(task).memory;
@@ -321,6 +326,7 @@ impl DynamicListScheduler {
                 id,
                 cores: resource.cores,
                 cores_available: resource.cores_available,
+                max_task_cores: resource.max_task_cores(),
                 memory: resource.memory,
                 memory_available: resource.memory_available,
                 speed: resource.speed,
@@ -403,7 +409,7 @@ impl DynamicListScheduler {
                     CoresCriterion::Efficiency90 => get_max_cores_for_efficiency(0.9),
                     CoresCriterion::Efficiency50 => get_max_cores_for_efficiency(0.5),
                 };
-                let cores = cores.clamp(task.min_cores, task.max_cores);
+                let cores = cores.min(resource.max_task_cores).clamp(task.min_cores, task.max_cores);
 
                 resource.cores_available -= cores;
                 resource.memory_available -= task.memory;
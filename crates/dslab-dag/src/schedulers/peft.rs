@@ -53,6 +53,8 @@ impl PeftScheduler {
         let avg_net_time = system.avg_net_time(ctx.id(), &config.data_transfer_mode);
 
         // optimistic cost table
+        // (resource speed corresponds to the fastest core group, so the estimate stays optimistic for resources
+        // with heterogeneous cores, while the EFT part of PEFT evaluates each core group separately)
         let mut oct = vec![vec![0.0; resources.len()]; task_count];
         for task_id in topsort(dag).into_iter().rev() {
             for resource_id in 0..resources.len() {
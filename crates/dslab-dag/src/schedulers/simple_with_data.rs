@@ -12,6 +12,7 @@ use crate::task::*;
 
 struct Resource {
     cores_available: u32,
+    max_task_cores: u32,
     memory_available: u64,
     id: Id,
 }
@@ -31,6 +32,7 @@ impl SimpleDataScheduler {
             .iter()
             .map(|resource| Resource {
                 cores_available: resource.cores_available,
+                max_task_cores: resource.max_task_cores(),
                 memory_available: resource.memory_available,
                 id: resource.id,
             })
@@ -43,10 +45,15 @@ impl SimpleDataScheduler {
                 if !task.is_allowed_on(i) {
                     continue;
                 }
-                if resource.cores_available < task.min_cores || resource.memory_available < task.memory {
+                if resource.cores_available.min(resource.max_task_cores) < task.min_cores
+                    || resource.memory_available < task.memory
+                {
                     continue;
                 }
-                let cores = resource.cores_available.min(task.max_cores);
+                let cores = resource
+                    .cores_available
+                    .min(resource.max_task_cores)
+                    .min(task.max_cores);
                 resource.cores_available -= cores;
                 resource.memory_available -= task.memory;
                 for &data_item in task.inputs.iter() {
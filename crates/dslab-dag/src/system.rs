@@ -15,11 +15,13 @@ pub struct System<'a> {
 
 impl System<'_> {
     /// Returns average time over all resources for executing one flop.
+    ///
+    /// For resources with heterogeneous cores the time is averaged over their core groups.
     pub fn avg_flop_time(&self) -> f64 {
         self.resources
             .iter()
             .filter(|r| r.name != "master")
-            .map(|r| 1. / r.speed)
+            .map(|r| r.core_groups.iter().map(|g| 1. / g.speed).sum::<f64>() / r.core_groups.len() as f64)
             .sum::<f64>()
             / (self.resources.len() - 1) as f64
     }
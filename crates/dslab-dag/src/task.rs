@@ -41,12 +41,27 @@ impl ResourceRestriction {
     }
 }
 
+/// Describes the alternative execution of a task on accelerator (e.g. GPU).
+#[derive(Clone, Debug)]
+pub struct AcceleratorCost {
+    /// The amount of computations performed on accelerator in Gflops.
+    pub flops: f64,
+    /// Accelerator memory demand in MB.
+    pub memory: u64,
+    /// Whether the task can run only on resources with accelerator.
+    pub required: bool,
+}
+
 /// Represents a DAG task.
 ///
 /// Described by the amount of computations in flops, the minimum and maximum number of used cores, and the amount of
 /// used memory. Also has a function which defines the dependence of parallel speedup on the number of used cores.
 ///
 /// Each task can consume (as task inputs) and produce (as task inputs) one or more data items.
+///
+/// The task can also have an accelerator cost, in which case it is executed on the accelerator when it is placed
+/// on a resource with accelerator. Its inputs are transferred to the accelerator before the execution and its outputs
+/// are transferred back after the execution.
 #[derive(Clone, Debug)]
 pub struct Task {
     pub name: String,
@@ -62,6 +77,7 @@ pub struct Task {
     pub outputs: Vec<usize>,
     pub(crate) ready_inputs: usize,
     pub resource_restriction: Option<ResourceRestriction>,
+    pub accelerator: Option<AcceleratorCost>,
 }
 
 impl Task {
@@ -86,6 +102,7 @@ impl Task {
             outputs: Vec::new(),
             ready_inputs: 0,
             resource_restriction: None,
+            accelerator: None,
         }
     }
 
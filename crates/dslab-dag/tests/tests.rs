@@ -11,7 +11,7 @@ use dslab_dag::dag::DAG;
 use dslab_dag::dag_simulation::DagSimulation;
 use dslab_dag::data_item::DataTransferMode;
 use dslab_dag::network::NetworkConfig;
-use dslab_dag::resource::ResourceConfig;
+use dslab_dag::resource::{AcceleratorConfig, CoreGroupConfig, ResourceConfig};
 use dslab_dag::runner::Config;
 use dslab_dag::scheduler::Scheduler;
 use dslab_dag::schedulers::dls::DlsScheduler;
@@ -19,6 +19,7 @@ use dslab_dag::schedulers::heft::HeftScheduler;
 use dslab_dag::schedulers::lookahead::LookaheadScheduler;
 use dslab_dag::schedulers::peft::PeftScheduler;
 use dslab_dag::schedulers::simple_scheduler::SimpleScheduler;
+use dslab_dag::task::AcceleratorCost;
 
 const PRECISION: f64 = 1. / ((1 << 20) as f64);
 
@@ -93,6 +94,8 @@ fn gen_resources(rng: &mut Pcg64, num_resources: usize, infinite_memory: bool) -
             name: i.to_string(),
             speed: rng.gen_range::<u64, _>(1..1_000_000_000) as f64,
             cores: rng.gen_range(1..10),
+            core_groups: Vec::new(),
+            accelerator: None,
             memory: if infinite_memory {
                 1_u64 << 60
             } else {
@@ -394,3 +397,182 @@ fn test_fork_join() {
     let result = sim.time();
     assert_float_eq(result, correct_result, EPSILON);
 }
+
+#[test]
+fn test_core_groups() {
+    let mut dag = DAG::new();
+    for i in 0..6 {
+        dag.add_task(&i.to_string(), 20., 32, 1, 1, CoresDependency::Linear);
+    }
+
+    // tasks finish earlier when all of them run on two fast cores than when slow cores are also used
+    let correct_result = 3. * 20. / 10.;
+
+    let schedulers: Vec<Rc<RefCell<dyn Scheduler>>> = vec![
+        Rc::new(RefCell::new(HeftScheduler::new())),
+        Rc::new(RefCell::new(PeftScheduler::new())),
+    ];
+    for scheduler in schedulers {
+        let mut sim = DagSimulation::new(
+            123,
+            Vec::new(),
+            NetworkConfig::constant(10., 0.),
+            scheduler,
+            Config {
+                data_transfer_mode: DataTransferMode::Direct,
+            },
+        );
+        sim.add_resource_with_core_groups(
+            "0",
+            vec![
+                CoreGroupConfig {
+                    name: "fast".to_string(),
+                    cores: 2,
+                    speed: 10.,
+                },
+                CoreGroupConfig {
+                    name: "slow".to_string(),
+                    cores: 4,
+                    speed: 2.,
+                },
+            ],
+            1024,
+        );
+        let runner = sim.init(dag.clone());
+        sim.step_until_no_events();
+        assert!(runner.borrow().is_completed());
+
+        let result = sim.time();
+        assert_float_eq(result, correct_result, EPSILON);
+    }
+}
+
+#[test]
+fn test_accelerator() {
+    let mut dag = DAG::new();
+    let a = dag.add_task("a", 100., 0, 1, 1, CoresDependency::Linear);
+    dag.set_accelerator_cost(
+        a,
+        AcceleratorCost {
+            flops: 100.,
+            memory: 8,
+            required: false,
+        },
+    );
+    let data = dag.add_task_output(a, "data", 10.);
+    let b = dag.add_task("b", 100., 0, 1, 1, CoresDependency::Linear);
+    dag.set_accelerator_cost(
+        b,
+        AcceleratorCost {
+            flops: 200.,
+            memory: 8,
+            required: true,
+        },
+    );
+    dag.add_data_dependency(data, b);
+
+    // a: 1 s of execution and 1 s of output transfer over the accelerator link,
+    // b: 1 s of input transfer and 2 s of execution
+    let correct_result = 5.;
+
+    let mut sim = DagSimulation::new(
+        123,
+        Vec::new(),
+        NetworkConfig::constant(10., 0.),
+        Rc::new(RefCell::new(HeftScheduler::new())),
+        Config {
+            data_transfer_mode: DataTransferMode::Direct,
+        },
+    );
+    sim.add_resource("cpu", 10., 1, 1024);
+    sim.add_resource_with_accelerator(
+        "gpu",
+        10.,
+        1,
+        1024,
+        AcceleratorConfig {
+            speed: 100.,
+            memory: 16,
+            bandwidth: 10.,
+            latency: 0.,
+            time_slicing: false,
+            partitions: Vec::new(),
+        },
+    );
+    let runner = sim.init(dag);
+    sim.step_until_no_events();
+    assert!(runner.borrow().is_completed());
+
+    let result = sim.time();
+    assert_float_eq(result, correct_result, EPSILON);
+}
+
+#[test]
+#[should_panic(expected = "Task a requires accelerator")]
+fn test_required_accelerator_without_resources() {
+    let mut dag = DAG::new();
+    let a = dag.add_task("a", 100., 0, 1, 1, CoresDependency::Linear);
+    dag.set_accelerator_cost(
+        a,
+        AcceleratorCost {
+            flops: 100.,
+            memory: 8,
+            required: true,
+        },
+    );
+
+    let mut sim = DagSimulation::new(
+        123,
+        Vec::new(),
+        NetworkConfig::constant(10., 0.),
+        Rc::new(RefCell::new(HeftScheduler::new())),
+        Config {
+            data_transfer_mode: DataTransferMode::Direct,
+        },
+    );
+    sim.add_resource("cpu", 10., 1, 1024);
+    sim.init(dag);
+}
+
+#[test]
+fn test_simgrid_platform() {
+    let platform = r#"<?xml version='1.0'?>
+<!DOCTYPE platform SYSTEM "https://simgrid.org/simgrid.dtd">
+<platform version="4.1">
+    <zone id="zone0" routing="Full">
+        <host id="master-host" speed="1Gf" />
+        <host id="worker" speed="1Gf" />
+        <link id="link" latency="0" bandwidth="10MBps"/>
+        <route src="master-host" dst="worker">
+            <link_ctn id="link" />
+        </route>
+    </zone>
+</platform>"#;
+    let file = std::env::temp_dir().join("dslab-dag-test-platform.xml");
+    std::fs::write(&file, platform).unwrap();
+
+    let mut dag = DAG::new();
+    let input = dag.add_data_item("input", 10.);
+    let task = dag.add_task("task", 100., 0, 1, 1, CoresDependency::Linear);
+    dag.add_data_dependency(input, task);
+
+    // 1 s of input transfer over the 10 MB/s link and 1 s of execution
+    let correct_result = 2.;
+
+    let mut sim = DagSimulation::new(
+        123,
+        Vec::new(),
+        NetworkConfig::simgrid_platform(file.to_str().unwrap(), "master-host"),
+        Rc::new(RefCell::new(HeftScheduler::new())),
+        Config {
+            data_transfer_mode: DataTransferMode::Direct,
+        },
+    );
+    sim.add_resource("worker", 100., 1, 1024);
+    let runner = sim.init(dag);
+    sim.step_until_no_events();
+    assert!(runner.borrow().is_completed());
+
+    let result = sim.time();
+    assert_float_eq(result, correct_result, EPSILON);
+}
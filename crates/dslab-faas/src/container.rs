@@ -48,6 +48,8 @@ pub struct Container {
     pub end_event: Option<EventId>,
     /// CPU share of the container.
     pub cpu_share: f64,
+    /// Whether the container is deployed with accelerator.
+    pub accelerated: bool,
 }
 
 impl Container {
@@ -157,9 +159,17 @@ impl ContainerManager {
     }
 
     /// Tries to deploy a new container for given app.
+    ///
+    /// If the app can use accelerator, the container is deployed with accelerator when possible.
     pub fn try_deploy(&mut self, app: &Application, time: f64) -> Option<(usize, f64)> {
+        if let Some(resources) = app.get_accelerated_resources() {
+            if self.resources.can_allocate(resources) {
+                let id = self.deploy_container(app, resources.clone(), true, time);
+                return Some((id, app.get_deployment_time()));
+            }
+        }
         if self.resources.can_allocate(app.get_resources()) {
-            let id = self.deploy_container(app, time);
+            let id = self.deploy_container(app, app.get_resources().clone(), false, time);
             return Some((id, app.get_deployment_time()));
         }
         None
@@ -206,7 +216,13 @@ impl ContainerManager {
     }
 
     /// Deploys a new container for given application.
-    fn deploy_container(&mut self, app: &Application, time: f64) -> usize {
+    fn deploy_container(
+        &mut self,
+        app: &Application,
+        resources: ResourceConsumer,
+        accelerated: bool,
+        time: f64,
+    ) -> usize {
         let cont_id = self.container_counter.increment();
         let container = Container {
             status: ContainerStatus::Deploying,
@@ -215,11 +231,12 @@ impl ContainerManager {
             deployment_time: app.get_deployment_time(),
             app_id: app.id,
             invocations: Default::default(),
-            resources: app.get_resources().clone(),
+            resources,
             started_invocations: 0,
             end_event: None,
             last_change: time,
             cpu_share: app.get_cpu_share(),
+            accelerated,
         };
         self.resources.allocate(&container.resources);
         self.containers.insert(cont_id, container);
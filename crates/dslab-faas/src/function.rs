@@ -1,6 +1,28 @@
 //! Function and application models.
 use crate::resource::ResourceConsumer;
 
+/// Describes the execution of application functions on an accelerator (e.g. GPU).
+///
+/// Accelerators are modeled as host resources (e.g. the number of GPUs or the amount of GPU memory), which are
+/// allocated to the container in addition to the application resources. The invocations running on such container
+/// have their duration divided by the speedup and increased by the time of data transfer to and from the accelerator.
+///
+/// Unlike the accelerator device model from `dslab-compute`, this profile doesn't simulate kernel execution and
+/// data transfers. FaaS invocations are described by their durations rather than by the amount of computations
+/// and transferred data, and invocations don't contend for the container resources, so the effect of accelerator
+/// reduces to the fixed speedup and transfer time.
+#[derive(Clone)]
+pub struct AcceleratorProfile {
+    /// Accelerator resources required by a container in addition to the application resources.
+    pub resources: ResourceConsumer,
+    /// Ratio of the invocation duration on CPU to its duration on accelerator.
+    pub speedup: f64,
+    /// Time of data transfer between host and accelerator added to each invocation.
+    pub transfer_time: f64,
+    /// Whether the functions can run only on accelerator.
+    pub required: bool,
+}
+
 /// An application shares a common container image.
 /// Functions from the same application can be executed on the same container (limited by `concurrent_invocations` field).
 pub struct Application {
@@ -10,6 +32,8 @@ pub struct Application {
     container_deployment_time: f64,
     container_cpu_share: f64,
     container_resources: ResourceConsumer,
+    accelerator: Option<AcceleratorProfile>,
+    accelerated_resources: ResourceConsumer,
 }
 
 impl Application {
@@ -26,9 +50,21 @@ impl Application {
             container_deployment_time,
             container_cpu_share,
             container_resources,
+            accelerator: None,
+            accelerated_resources: ResourceConsumer::new_empty(),
         }
     }
 
+    /// Allows the containers of this application to use accelerator according to the given profile.
+    ///
+    /// If the accelerator is not required, the containers are deployed with accelerator when possible
+    /// and without it otherwise.
+    pub fn with_accelerator(mut self, accelerator: AcceleratorProfile) -> Self {
+        self.accelerated_resources = self.container_resources.merge(&accelerator.resources);
+        self.accelerator = Some(accelerator);
+        self
+    }
+
     /// Returns maximum possible number of invocations that can be run simultaneously on one container of this application.
     pub fn get_concurrent_invocations(&self) -> usize {
         self.concurrent_invocations
@@ -45,8 +81,29 @@ impl Application {
     }
 
     /// Returns resources required by containers of this application.
+    ///
+    /// Includes the accelerator resources if the accelerator is required.
     pub fn get_resources(&self) -> &ResourceConsumer {
-        &self.container_resources
+        match &self.accelerator {
+            Some(accelerator) if accelerator.required => &self.accelerated_resources,
+            _ => &self.container_resources,
+        }
+    }
+
+    /// Returns accelerator profile of this application if it can use accelerator.
+    pub fn get_accelerator(&self) -> Option<&AcceleratorProfile> {
+        self.accelerator.as_ref()
+    }
+
+    /// Returns resources required by containers of this application deployed with accelerator.
+    pub fn get_accelerated_resources(&self) -> Option<&ResourceConsumer> {
+        self.accelerator.as_ref().map(|_| &self.accelerated_resources)
+    }
+
+    /// Returns the duration of invocation with the given CPU duration on container deployed with accelerator.
+    pub fn get_accelerated_duration(&self, duration: f64) -> f64 {
+        let accelerator = self.accelerator.as_ref().unwrap();
+        duration / accelerator.speedup + accelerator.transfer_time
     }
 }
 
@@ -18,7 +18,7 @@ use crate::container::{ContainerManager, ContainerStatus};
 use crate::cpu::{Cpu, CpuPolicy};
 use crate::event::{ContainerEndEvent, ContainerStartEvent, IdleDeployEvent, InvocationEndEvent};
 use crate::function::{Application, FunctionRegistry};
-use crate::invocation::{InvocationRegistry, InvocationStatus};
+use crate::invocation::{Invocation, InvocationRegistry, InvocationStatus};
 use crate::invoker::{Invoker, InvokerDecision};
 use crate::resource::{ResourceConsumer, ResourceProvider};
 use crate::simulation::HandlerId;
@@ -199,9 +199,18 @@ impl Host {
         invocation.start_time = Some(time);
         invocation.status = InvocationStatus::Running;
         invocation.container_id = Some(cont_id);
+        if container.accelerated {
+            Self::accelerate_invocation(invocation, &self.function_registry.borrow());
+        }
         self.cpu.on_new_invocation(invocation, container, time);
     }
 
+    /// Replaces the invocation duration with its duration on accelerator.
+    fn accelerate_invocation(invocation: &mut Invocation, function_registry: &FunctionRegistry) {
+        let app = function_registry.get_app(invocation.app_id).unwrap();
+        invocation.duration = app.get_accelerated_duration(invocation.duration);
+    }
+
     fn on_container_start(&mut self, id: usize, time: f64) {
         if let Some(invocations) = self.container_manager.take_reservations(id) {
             for invocation in invocations {
@@ -329,6 +338,9 @@ impl Host {
                 invocation.status = InvocationStatus::Running;
                 invocation.start_time = Some(time);
                 let container = self.container_manager.get_container_mut(req.container_id).unwrap();
+                if container.accelerated {
+                    Self::accelerate_invocation(invocation, &self.function_registry.borrow());
+                }
                 self.cpu.on_new_invocation(invocation, container, time);
             } else {
                 invocation.status = InvocationStatus::WaitingForContainer;
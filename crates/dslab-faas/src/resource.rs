@@ -167,6 +167,19 @@ impl ResourceConsumer {
         }
     }
 
+    /// Returns a consumer which requires the resources of both this and the other consumer.
+    pub fn merge(&self, other: &ResourceConsumer) -> Self {
+        let mut resources = self.resources.clone();
+        for (id, req) in other.iter() {
+            if let Some(own) = resources.get_mut(id) {
+                own.quantity += req.quantity;
+            } else {
+                resources.insert(id, req.clone());
+            }
+        }
+        Self { resources }
+    }
+
     /// Iterates over resource requirements of the consumer.
     pub fn iter(&self) -> VecMapIterator<ResourceRequirement> {
         self.resources.iter()
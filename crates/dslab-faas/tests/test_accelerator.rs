@@ -0,0 +1,55 @@
+mod common;
+use common::assert_float_eq;
+
+use std::boxed::Box;
+
+use dslab_core::simulation::Simulation;
+use dslab_faas::coldstart::FixedTimeColdStartPolicy;
+use dslab_faas::config::Config;
+use dslab_faas::cpu::IgnoredCpuPolicy;
+use dslab_faas::function::{AcceleratorProfile, Application};
+use dslab_faas::resource::{ResourceConsumer, ResourceProvider};
+use dslab_faas::simulation::ServerlessSimulation;
+
+#[test]
+fn test_accelerator() {
+    let config = Config {
+        coldstart_policy: Box::new(FixedTimeColdStartPolicy::new(10.0, 0.0, false)),
+        cpu_policy: Box::new(IgnoredCpuPolicy::default()),
+        ..Default::default()
+    };
+    let mut sim = ServerlessSimulation::new(Simulation::new(1), config);
+    let host_mem = sim.create_resource("mem", 4);
+    let host_gpu = sim.create_resource("gpu", 1);
+    sim.add_host(None, ResourceProvider::new(vec![host_mem, host_gpu]), 4);
+    let fn_mem = sim.create_resource_requirement("mem", 1);
+    let fn_gpu = sim.create_resource_requirement("gpu", 1);
+    let f = sim.add_app_with_single_function(
+        Application::new(1, 0., 1., ResourceConsumer::new(vec![fn_mem])).with_accelerator(AcceleratorProfile {
+            resources: ResourceConsumer::new(vec![fn_gpu.clone()]),
+            speedup: 4.,
+            transfer_time: 0.5,
+            required: false,
+        }),
+    );
+    let fn_mem = sim.create_resource_requirement("mem", 1);
+    let g = sim.add_app_with_single_function(
+        Application::new(1, 0., 1., ResourceConsumer::new(vec![fn_mem])).with_accelerator(AcceleratorProfile {
+            resources: ResourceConsumer::new(vec![fn_gpu]),
+            speedup: 2.,
+            transfer_time: 0.,
+            required: true,
+        }),
+    );
+    // the first invocation gets the only accelerator, the second one falls back to CPU
+    sim.send_invocation_request(f, 4.0, 0.0);
+    sim.send_invocation_request(f, 4.0, 0.0);
+    // waits for the accelerator to be released by the first container
+    sim.send_invocation_request(g, 4.0, 0.0);
+    sim.step_until_no_events();
+    let invocations = sim.get_invocations(0..3);
+    assert_float_eq(invocations[0].finish_time.unwrap(), 1.5, 1e-9);
+    assert_float_eq(invocations[1].finish_time.unwrap(), 4.0, 1e-9);
+    assert_float_eq(invocations[2].start_time.unwrap(), 11.5, 1e-9);
+    assert_float_eq(invocations[2].finish_time.unwrap(), 13.5, 1e-9);
+}
@@ -0,0 +1,84 @@
+//! Carbon meter calculates the host carbon emissions and attributes them to the VMs running on the host.
+
+use std::collections::HashMap;
+
+use dslab_models::carbon::intensity::CarbonIntensityTrace;
+use dslab_models::carbon::meter::CarbonMeter;
+
+/// Host carbon meter structure.
+///
+/// The host energy consumption and emissions are attributed to the VMs proportionally to their power shares,
+/// which are passed on each update and are used until the next update.
+#[derive(Debug, Clone)]
+pub struct HostCarbonMeter {
+    meter: CarbonMeter,
+    vm_shares: Vec<(u32, f64)>,
+    vm_energy_consumed: HashMap<u32, f64>,
+    vm_emissions: HashMap<u32, f64>,
+}
+
+impl HostCarbonMeter {
+    /// Creates meter with the given carbon intensity trace and PUE, which starts accounting at the given time.
+    pub fn new(intensity: CarbonIntensityTrace, pue: f64, time: f64) -> Self {
+        Self {
+            meter: CarbonMeter::new(intensity).with_pue(pue).with_start_time(time),
+            vm_shares: Vec::new(),
+            vm_energy_consumed: HashMap::new(),
+            vm_emissions: HashMap::new(),
+        }
+    }
+
+    /// Invoked each time the host power consumption is changed to update the energy consumption and emissions.
+    ///
+    /// The `vm_shares` specify the fractions of the host power attributed to each VM, which should sum to 1
+    /// or be empty if the host is idle.
+    pub fn update(&mut self, time: f64, power: f64, vm_shares: Vec<(u32, f64)>) {
+        let energy_consumed = self.meter.energy_consumed();
+        let emissions = self.meter.emissions();
+        self.meter.update(time, power);
+        let energy_delta = self.meter.energy_consumed() - energy_consumed;
+        let emissions_delta = self.meter.emissions() - emissions;
+        for (vm_id, share) in self.vm_shares.iter() {
+            *self.vm_energy_consumed.entry(*vm_id).or_default() += share * energy_delta;
+            *self.vm_emissions.entry(*vm_id).or_default() += share * emissions_delta;
+        }
+        self.vm_shares = vm_shares;
+    }
+
+    /// Replaces the carbon intensity trace starting from the given time, keeping the accumulated values.
+    pub fn set_intensity(
+        &mut self,
+        intensity: CarbonIntensityTrace,
+        time: f64,
+        power: f64,
+        vm_shares: Vec<(u32, f64)>,
+    ) {
+        self.update(time, power, vm_shares);
+        self.meter.set_intensity(intensity, time);
+    }
+
+    /// Returns the power usage effectiveness of the host facility.
+    pub fn pue(&self) -> f64 {
+        self.meter.pue()
+    }
+
+    /// Returns the total host energy consumption since the start of accounting.
+    pub fn energy_consumed(&self) -> f64 {
+        self.meter.energy_consumed()
+    }
+
+    /// Returns the total host carbon emissions in gCO2 including the facility overhead (PUE).
+    pub fn emissions(&self) -> f64 {
+        self.meter.emissions()
+    }
+
+    /// Returns the host energy consumption attributed to the specified VM.
+    pub fn vm_energy_consumed(&self, vm_id: u32) -> f64 {
+        self.vm_energy_consumed.get(&vm_id).copied().unwrap_or(0.)
+    }
+
+    /// Returns the host carbon emissions attributed to the specified VM.
+    pub fn vm_emissions(&self, vm_id: u32) -> f64 {
+        self.vm_emissions.get(&vm_id).copied().unwrap_or(0.)
+    }
+}
@@ -12,8 +12,11 @@ use dslab_core::cast;
 use dslab_core::context::SimulationContext;
 use dslab_core::event::Event;
 use dslab_core::handler::EventHandler;
+use dslab_models::carbon::intensity::CarbonIntensityTrace;
+use dslab_models::interference::model::InterferenceModel;
 use dslab_models::power::host::{HostPowerModel, HostState};
 
+use crate::core::carbon_meter::HostCarbonMeter;
 use crate::core::common::AllocationVerdict;
 use crate::core::config::sim_config::SimulationConfig;
 use crate::core::energy_meter::EnergyMeter;
@@ -33,7 +36,10 @@ use crate::core::vm_api::VmAPI;
 /// and migration, and reports the VM status changes to VM API component. Host manager periodically computes its
 /// current load, as the sum of loads produced by currently running VMs, and reports it to the monitoring component.
 /// Host manager also records the total energy consumption of the host computed using the power model
-/// defined as a function of CPU load.
+/// defined as a function of CPU load, and optionally the carbon emissions caused by this consumption.
+/// If the interference model is set, host also computes the slowdown of VMs due to contention for shared memory
+/// bandwidth and cache, which grows when more VMs are packed on the host (e.g. with VM overcommit),
+/// and accounts it in the SLAV metric.
 pub struct HostManager {
     pub id: u32,
     pub rack_id: Option<u32>,
@@ -55,6 +61,7 @@ pub struct HostManager {
     recently_removed_vms: Vec<u32>,
     recent_vm_status_changes: HashMap<u32, VmStatus>,
     energy_meter: EnergyMeter,
+    carbon_meter: Option<HostCarbonMeter>,
 
     monitoring_id: u32,
     placement_store_id: u32,
@@ -63,6 +70,7 @@ pub struct HostManager {
     allow_vm_overcommit: bool,
     power_model: HostPowerModel,
     slav_metric: Box<dyn HostSLAVMetric>,
+    interference_model: Option<Box<dyn InterferenceModel>>,
 
     ctx: SimulationContext,
     logger: Rc<RefCell<Box<dyn Logger>>>,
@@ -103,12 +111,14 @@ impl HostManager {
             recently_removed_vms: Vec::new(),
             recent_vm_status_changes: HashMap::new(),
             energy_meter: EnergyMeter::new(),
+            carbon_meter: None,
             monitoring_id,
             placement_store_id,
             vm_api,
             allow_vm_overcommit,
             power_model,
             slav_metric,
+            interference_model: None,
             ctx,
             logger,
             sim_config,
@@ -152,9 +162,8 @@ impl HostManager {
         self.recently_added_vms.push(vm.id);
         self.vms.insert(vm.id);
         let cpu_load = self.cpu_load(time);
-        let power = self.current_power(cpu_load);
-        self.energy_meter.update(time, power);
-        self.slav_metric.update(time, cpu_load);
+        self.update_power(time, cpu_load);
+        self.slav_metric.update(time, self.effective_cpu_load(time));
     }
 
     /// Releases resources when VM is deleted, updates resource and energy consumption.
@@ -178,9 +187,8 @@ impl HostManager {
         self.vms.remove(&vm.id);
         self.recently_removed_vms.push(vm.id);
         let cpu_load = self.cpu_load(time);
-        let power = self.current_power(cpu_load);
-        self.energy_meter.update(time, power);
-        self.slav_metric.update(time, cpu_load);
+        self.update_power(time, cpu_load);
+        self.slav_metric.update(time, self.effective_cpu_load(time));
     }
 
     /// Returns the host CPU capacity.
@@ -223,6 +231,55 @@ impl HostManager {
         memory_used / self.memory_total as f64
     }
 
+    /// Sets the model used to compute the slowdown of VMs due to interference.
+    pub fn set_interference_model(&mut self, model: Box<dyn InterferenceModel>) {
+        self.interference_model = Some(model);
+    }
+
+    /// Returns the current speed factors (from 0 to 1) of the active VMs due to interference.
+    ///
+    /// The interference model is provided with the memory profile and the number of used vCPUs of each VM,
+    /// which is computed from its current CPU load. All factors are 1 if the interference model is not set.
+    pub fn vm_speed_factors(&self, time: f64) -> Vec<(u32, f64)> {
+        let vm_api = self.vm_api.borrow();
+        let mut vm_ids = self.vms.iter().copied().collect::<Vec<_>>();
+        vm_ids.sort();
+        let Some(model) = self.interference_model.as_ref() else {
+            return vm_ids.into_iter().map(|vm_id| (vm_id, 1.)).collect();
+        };
+        let workloads = vm_ids
+            .iter()
+            .map(|vm_id| {
+                let vm = vm_api.get_vm(*vm_id);
+                let vm = vm.borrow();
+                (vm.memory_profile(), vm.cpu_usage as f64 * vm.get_cpu_load(time))
+            })
+            .collect::<Vec<_>>();
+        vm_ids.into_iter().zip(model.speed_factors(&workloads)).collect()
+    }
+
+    /// Returns the CPU load (used/total) required to run the active VMs at their nominal speed.
+    ///
+    /// The CPU usage of each VM is divided by its speed factor due to interference, so the host can be overloaded
+    /// from the point of view of VMs even if its actual CPU load is below 1. This load is used to update
+    /// the SLAV metric, while the power consumption depends on the actual CPU load.
+    pub fn effective_cpu_load(&self, time: f64) -> f64 {
+        if self.interference_model.is_none() {
+            return self.cpu_load(time);
+        }
+        let vm_api = self.vm_api.borrow();
+        let cpu_used: f64 = self
+            .vm_speed_factors(time)
+            .into_iter()
+            .map(|(vm_id, factor)| {
+                let vm = vm_api.get_vm(vm_id);
+                let vm = vm.borrow();
+                vm.cpu_usage as f64 * vm.get_cpu_load(time) / factor
+            })
+            .sum();
+        cpu_used / self.cpu_total as f64
+    }
+
     /// Returns the current power consumption.
     pub fn current_power(&self, cpu_load: f64) -> f64 {
         // CPU utilization is capped by 100%
@@ -230,18 +287,105 @@ impl HostManager {
         self.power_model.get_power(HostState::cpu_util(cpu_util))
     }
 
+    /// Returns the fractions of the host power attributed to the active VMs.
+    ///
+    /// The power is split proportionally to the CPU usage of VMs, or proportionally to the allocated vCPUs
+    /// if the VMs do not use CPU.
+    fn vm_power_shares(&self, time: f64) -> Vec<(u32, f64)> {
+        let vm_api = self.vm_api.borrow();
+        let mut cpu_used = Vec::with_capacity(self.vms.len());
+        for vm_id in &self.vms {
+            let vm = vm_api.get_vm(*vm_id);
+            let vm = vm.borrow();
+            cpu_used.push((*vm_id, vm.cpu_usage as f64 * vm.get_cpu_load(time), vm.cpu_usage as f64));
+        }
+        let total_used: f64 = cpu_used.iter().map(|(_, used, _)| used).sum();
+        let total_allocated: f64 = cpu_used.iter().map(|(_, _, allocated)| allocated).sum();
+        if total_used > 0. {
+            cpu_used
+                .into_iter()
+                .map(|(id, used, _)| (id, used / total_used))
+                .collect()
+        } else if total_allocated > 0. {
+            cpu_used
+                .into_iter()
+                .map(|(id, _, allocated)| (id, allocated / total_allocated))
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Updates the energy consumption and emissions using the current CPU load.
+    fn update_power(&mut self, time: f64, cpu_load: f64) {
+        let power = self.current_power(cpu_load);
+        self.energy_meter.update(time, power);
+        let vm_shares = self.carbon_meter.as_ref().map(|_| self.vm_power_shares(time));
+        if let (Some(carbon_meter), Some(vm_shares)) = (self.carbon_meter.as_mut(), vm_shares) {
+            carbon_meter.update(time, power, vm_shares);
+        }
+    }
+
     /// Returns the total energy consumption.
     pub fn get_energy_consumed(&mut self, time: f64) -> f64 {
         let cpu_load = self.cpu_load(time);
-        let power = self.current_power(cpu_load);
-        self.energy_meter.update(time, power);
+        self.update_power(time, cpu_load);
         self.energy_meter.energy_consumed()
     }
 
+    /// Enables accounting of carbon emissions caused by the host energy consumption starting from the given time.
+    ///
+    /// The emissions are computed using the carbon intensity of the host location and PUE of the host facility.
+    /// If the accounting is already enabled (i.e. the host is relocated), the emissions accumulated so far are kept
+    /// and the new carbon intensity is used from the given time, while the passed PUE must remain the same.
+    pub fn enable_carbon_accounting(&mut self, intensity: CarbonIntensityTrace, pue: f64, time: f64) {
+        let cpu_load = self.cpu_load(time);
+        self.update_power(time, cpu_load);
+        let power = self.current_power(cpu_load);
+        let vm_shares = self.vm_power_shares(time);
+        if let Some(carbon_meter) = self.carbon_meter.as_mut() {
+            assert_eq!(carbon_meter.pue(), pue, "PUE of the host can't be changed");
+            carbon_meter.set_intensity(intensity, time, power, vm_shares);
+        } else {
+            let mut carbon_meter = HostCarbonMeter::new(intensity, pue, time);
+            carbon_meter.update(time, power, vm_shares);
+            self.carbon_meter = Some(carbon_meter);
+        }
+    }
+
+    /// Returns true if the accounting of carbon emissions is enabled.
+    pub fn carbon_accounting_enabled(&self) -> bool {
+        self.carbon_meter.is_some()
+    }
+
+    /// Returns the total carbon emissions in gCO2 or zero if the carbon accounting is not enabled.
+    pub fn get_emissions(&mut self, time: f64) -> f64 {
+        let cpu_load = self.cpu_load(time);
+        self.update_power(time, cpu_load);
+        self.carbon_meter.as_ref().map_or(0., |meter| meter.emissions())
+    }
+
+    /// Returns the host energy consumption attributed to the specified VM
+    /// or zero if the carbon accounting is not enabled.
+    pub fn get_vm_energy_consumed(&mut self, vm_id: u32, time: f64) -> f64 {
+        let cpu_load = self.cpu_load(time);
+        self.update_power(time, cpu_load);
+        self.carbon_meter
+            .as_ref()
+            .map_or(0., |meter| meter.vm_energy_consumed(vm_id))
+    }
+
+    /// Returns the host carbon emissions in gCO2 attributed to the specified VM
+    /// or zero if the carbon accounting is not enabled.
+    pub fn get_vm_emissions(&mut self, vm_id: u32, time: f64) -> f64 {
+        let cpu_load = self.cpu_load(time);
+        self.update_power(time, cpu_load);
+        self.carbon_meter.as_ref().map_or(0., |meter| meter.vm_emissions(vm_id))
+    }
+
     /// Returns the total SLAV value.
     pub fn get_accumulated_slav(&mut self, time: f64) -> f64 {
-        let cpu_load = self.cpu_load(time);
-        self.slav_metric.update(time, cpu_load);
+        self.slav_metric.update(time, self.effective_cpu_load(time));
         self.slav_metric.value()
     }
 
@@ -379,9 +523,8 @@ impl HostManager {
             .log_trace(&self.ctx, format!("host #{} sends it`s data to monitoring", self.id));
         let time = self.ctx.time();
         let cpu_load = self.cpu_load(time);
-        let power = self.current_power(cpu_load);
-        self.energy_meter.update(time, power);
-        self.slav_metric.update(time, cpu_load);
+        self.update_power(time, cpu_load);
+        self.slav_metric.update(time, self.effective_cpu_load(time));
 
         self.ctx.emit(
             HostStateUpdate {
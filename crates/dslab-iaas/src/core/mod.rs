@@ -1,3 +1,4 @@
+pub mod carbon_meter;
 pub mod common;
 pub mod config;
 pub mod energy_meter;
@@ -6,6 +6,8 @@ use std::rc::Rc;
 use serde::ser::{SerializeStruct, Serializer};
 use serde::Serialize;
 
+use dslab_models::interference::model::MemoryProfile;
+
 use crate::core::config::sim_config::SimulationConfig;
 use crate::core::load_model::{ConstantLoadModel, LoadModel};
 
@@ -38,6 +40,8 @@ pub struct ResourceConsumer {
     pub memory_usage: u64,
     pub cpu_load_model: Box<dyn LoadModel>,
     pub memory_load_model: Box<dyn LoadModel>,
+    /// Memory intensity of VM workload, used by the host interference model.
+    pub memory_profile: MemoryProfile,
 }
 
 impl ResourceConsumer {
@@ -53,6 +57,7 @@ impl ResourceConsumer {
             memory_usage,
             cpu_load_model,
             memory_load_model,
+            memory_profile: MemoryProfile::default(),
         }
     }
 
@@ -63,6 +68,7 @@ impl ResourceConsumer {
             memory_usage,
             cpu_load_model: Box::new(ConstantLoadModel::new(1.0)),
             memory_load_model: Box::new(ConstantLoadModel::new(1.0)),
+            memory_profile: MemoryProfile::default(),
         }
     }
 
@@ -73,8 +79,15 @@ impl ResourceConsumer {
             memory_usage,
             cpu_load_model: Box::new(ConstantLoadModel::new(cpu_load)),
             memory_load_model: Box::new(ConstantLoadModel::new(memory_load)),
+            memory_profile: MemoryProfile::default(),
         }
     }
+
+    /// Sets the memory intensity of VM workload.
+    pub fn with_memory_profile(mut self, memory_profile: MemoryProfile) -> Self {
+        self.memory_profile = memory_profile;
+        self
+    }
 }
 
 /// Represents virtual machine (VM).
@@ -92,6 +105,7 @@ pub struct VirtualMachine {
     start_time: f64,
     cpu_load_model: Box<dyn LoadModel>,
     memory_load_model: Box<dyn LoadModel>,
+    memory_profile: MemoryProfile,
     sim_config: Rc<SimulationConfig>,
 }
 
@@ -124,6 +138,7 @@ impl VirtualMachine {
             start_time: -1.,
             cpu_load_model: resource_consumer.cpu_load_model,
             memory_load_model: resource_consumer.memory_load_model,
+            memory_profile: resource_consumer.memory_profile,
             sim_config,
         }
     }
@@ -167,4 +182,9 @@ impl VirtualMachine {
     pub fn get_memory_load(&self, time: f64) -> f64 {
         self.memory_load_model.get_resource_load(time, time - self.start_time)
     }
+
+    /// Returns the memory intensity of VM workload.
+    pub fn memory_profile(&self) -> MemoryProfile {
+        self.memory_profile
+    }
 }
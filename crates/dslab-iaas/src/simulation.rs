@@ -10,8 +10,11 @@ use sugars::{rc, refcell};
 use dslab_core::context::SimulationContext;
 use dslab_core::simulation::Simulation;
 use dslab_core::Id;
+use dslab_models::carbon::intensity::{load_carbon_intensity_traces, CarbonIntensityTrace};
+use dslab_models::interference::model::InterferenceModel;
 use dslab_models::power::cpu_models::linear::LinearCpuPowerModel;
 use dslab_models::power::host::{HostPowerModel, HostPowerModelBuilder};
+use dslab_network::platform::Platform;
 
 use crate::core::config::sim_config::SimulationConfig;
 use crate::core::events::allocation::{AllocationRequest, MigrationRequest};
@@ -52,6 +55,9 @@ pub struct CloudSimulation {
     components: HashMap<u32, Rc<RefCell<dyn CustomComponent>>>,
     host_power_model: HostPowerModel,
     slav_metric: Box<dyn HostSLAVMetric>,
+    carbon_intensity: BTreeMap<String, CarbonIntensityTrace>,
+    pue: f64,
+    vm_workloads: HashMap<u32, String>,
     batch_mode: bool,
     batch_buffer: Vec<VMSpawnRequest>,
     logger: Rc<RefCell<Box<dyn Logger>>>,
@@ -100,6 +106,9 @@ impl CloudSimulation {
                 .cpu(Box::new(LinearCpuPowerModel::new(0.4, 1.)))
                 .build(),
             slav_metric: Box::new(OverloadTimeFraction::new()),
+            carbon_intensity: BTreeMap::new(),
+            pue: 1.,
+            vm_workloads: HashMap::new(),
             batch_mode: false,
             batch_buffer: Vec::new(),
             logger,
@@ -212,6 +221,24 @@ impl CloudSimulation {
         self.add_host_internal(name, cpu_total, memory_total, Some(rack_id))
     }
 
+    /// Creates hosts for the hosts of imported network platform, and returns their IDs in the platform order.
+    ///
+    /// The host CPU capacity is set to the number of platform host cores, since host speed is not modeled here.
+    /// Memory capacity is read from the `memory` host property if it is present, otherwise `default_memory` is used.
+    pub fn add_hosts_from_platform(&mut self, platform: &Platform, default_memory: u64) -> Vec<u32> {
+        platform
+            .hosts
+            .iter()
+            .map(|host| {
+                let memory = host.properties.get("memory").map_or(default_memory, |m| {
+                    m.parse()
+                        .unwrap_or_else(|_| panic!("Can't parse memory of host {}", host.name))
+                });
+                self.add_host(&host.name, host.cores, memory)
+            })
+            .collect()
+    }
+
     /// Creates new scheduler with specified name and VM placement algorithm, and returns the scheduler ID.
     pub fn add_scheduler(&mut self, name: &str, vm_placement_algorithm: VMPlacementAlgorithm) -> u32 {
         // create scheduler using current state from placement store
@@ -417,6 +444,136 @@ impl CloudSimulation {
         self.slav_metric = slav_metric;
     }
 
+    /// Sets the model used to compute the slowdown of VMs on the specified host due to contention for shared memory
+    /// bandwidth and cache. The memory intensity of VMs is specified in their [`ResourceConsumer`].
+    pub fn set_host_interference_model(&mut self, host_id: u32, model: Box<dyn InterferenceModel>) {
+        self.host(host_id).borrow_mut().set_interference_model(model);
+    }
+
+    /// Returns the current speed factor (from 0 to 1) of the specified VM due to interference with other VMs
+    /// on the same host, or `None` if the VM is not placed on any host.
+    pub fn vm_speed_factor(&mut self, vm_id: u32) -> Option<f64> {
+        let time = self.current_time();
+        let host_id = self.vm_location(vm_id)?;
+        self.host(host_id)
+            .borrow()
+            .vm_speed_factors(time)
+            .into_iter()
+            .find(|(id, _)| *id == vm_id)
+            .map(|(_, factor)| factor)
+    }
+
+    /// Sets the grid carbon intensity trace for the specified location.
+    pub fn add_carbon_intensity(&mut self, location: &str, intensity: CarbonIntensityTrace) {
+        self.carbon_intensity.insert(location.to_string(), intensity);
+    }
+
+    /// Loads the grid carbon intensity traces for multiple locations from CSV file
+    /// with `location`, `time` and `intensity` columns (see [`load_carbon_intensity_traces`]).
+    pub fn load_carbon_intensity(&mut self, path: &str) {
+        self.carbon_intensity.extend(load_carbon_intensity_traces(path));
+    }
+
+    /// Sets the power usage effectiveness (PUE) of the facility used in carbon accounting.
+    ///
+    /// Must be called before setting host locations.
+    pub fn set_pue(&mut self, pue: f64) {
+        assert!(pue >= 1., "PUE must be at least 1, got {}", pue);
+        assert!(
+            self.hosts
+                .values()
+                .all(|host| !host.borrow().carbon_accounting_enabled()),
+            "PUE must be set before setting host locations"
+        );
+        self.pue = pue;
+    }
+
+    /// Sets the location of specified host and enables accounting of the host carbon emissions
+    /// using the carbon intensity trace of this location starting from the current time.
+    ///
+    /// If the host location was set before, the emissions accumulated at the previous location are kept.
+    pub fn set_host_location(&mut self, host_id: u32, location: &str) {
+        let intensity = self
+            .carbon_intensity
+            .get(location)
+            .unwrap_or_else(|| panic!("Carbon intensity for location {} is not specified", location))
+            .clone();
+        let time = self.current_time();
+        self.host(host_id)
+            .borrow_mut()
+            .enable_carbon_accounting(intensity, self.pue, time);
+    }
+
+    /// Assigns the specified VM to the workload, so that its emissions are included in the workload emissions.
+    pub fn set_vm_workload(&mut self, vm_id: u32, workload: &str) {
+        self.vm_workloads.insert(vm_id, workload.to_string());
+    }
+
+    /// Returns the carbon emissions in gCO2 of the specified host.
+    ///
+    /// Returns zero if the host location is not set.
+    pub fn host_emissions(&mut self, host_id: u32) -> f64 {
+        let time = self.current_time();
+        self.host(host_id).borrow_mut().get_emissions(time)
+    }
+
+    /// Returns the total carbon emissions in gCO2 of all hosts.
+    pub fn total_emissions(&mut self) -> f64 {
+        let time = self.current_time();
+        self.hosts
+            .values()
+            .map(|host| host.borrow_mut().get_emissions(time))
+            .sum()
+    }
+
+    /// Returns the energy consumption of hosts attributed to the specified VM.
+    ///
+    /// The energy consumption of host is split between its VMs proportionally to their CPU usage.
+    /// Only the hosts with location set are accounted.
+    pub fn vm_energy_consumed(&mut self, vm_id: u32) -> f64 {
+        let time = self.current_time();
+        self.hosts
+            .values()
+            .map(|host| host.borrow_mut().get_vm_energy_consumed(vm_id, time))
+            .sum()
+    }
+
+    /// Returns the carbon emissions in gCO2 of hosts attributed to the specified VM.
+    ///
+    /// The emissions of host are split between its VMs proportionally to their CPU usage.
+    /// Only the hosts with location set are accounted.
+    pub fn vm_emissions(&mut self, vm_id: u32) -> f64 {
+        let time = self.current_time();
+        self.hosts
+            .values()
+            .map(|host| host.borrow_mut().get_vm_emissions(vm_id, time))
+            .sum()
+    }
+
+    /// Returns the energy consumption attributed to the VMs of the specified workload.
+    pub fn workload_energy_consumed(&mut self, workload: &str) -> f64 {
+        self.workload_vms(workload)
+            .into_iter()
+            .map(|vm_id| self.vm_energy_consumed(vm_id))
+            .sum()
+    }
+
+    /// Returns the carbon emissions in gCO2 attributed to the VMs of the specified workload.
+    pub fn workload_emissions(&mut self, workload: &str) -> f64 {
+        self.workload_vms(workload)
+            .into_iter()
+            .map(|vm_id| self.vm_emissions(vm_id))
+            .sum()
+    }
+
+    fn workload_vms(&self, workload: &str) -> Vec<u32> {
+        self.vm_workloads
+            .iter()
+            .filter(|(_, vm_workload)| vm_workload.as_str() == workload)
+            .map(|(vm_id, _)| *vm_id)
+            .collect()
+    }
+
     /// Returns the reference to monitoring component (provides actual host load).
     pub fn monitoring(&self) -> Rc<RefCell<Monitoring>> {
         self.monitoring.clone()
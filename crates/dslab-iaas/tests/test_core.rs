@@ -1,7 +1,11 @@
 use dslab_core::simulation::Simulation;
 
+use dslab_models::carbon::intensity::CarbonIntensityTrace;
+use dslab_models::interference::bandwidth::BandwidthInterferenceModel;
+use dslab_models::interference::model::MemoryProfile;
 use dslab_models::power::cpu_models::constant::ConstantCpuPowerModel;
 use dslab_models::power::host::HostPowerModelBuilder;
+use dslab_network::platform::Platform;
 
 use dslab_iaas::core::common::Allocation;
 use dslab_iaas::core::config::sim_config::SimulationConfig;
@@ -454,3 +458,195 @@ fn test_batch_request() {
     assert_eq!(cloud_sim.vm_location(vm_ids[1]), Some(h));
     assert_eq!(cloud_sim.vm_location(vm_ids[2]), Some(h));
 }
+
+#[test]
+// Using default linear power model (0.4 + 0.6 * CPU load) and PUE of 1.5.
+// Host h1 runs VMs 1 and 2 using 10 and 5 vCPUs out of 30 during 3 seconds, then power consumption is 0.7.
+// Host h2 runs VM 3 using 20 vCPUs out of 30 during 3 seconds, then power consumption is 0.8.
+// The carbon intensity is 100 for h1 and 400 for h2 until 2 seconds, then 100.
+// The host emissions are split between VMs proportionally to their CPU usage.
+fn test_carbon_emissions() {
+    let sim = Simulation::new(123);
+    let sim_config = SimulationConfig::from_file(&name_wrapper("config_zero_latency.yaml"));
+    let mut cloud_sim = CloudSimulation::new(sim, sim_config);
+    cloud_sim.add_carbon_intensity("north", CarbonIntensityTrace::constant(100.));
+    cloud_sim.add_carbon_intensity("south", CarbonIntensityTrace::new(vec![(0., 400.), (2., 100.)]));
+    cloud_sim.set_pue(1.5);
+
+    let h1 = cloud_sim.add_host("h1", 30, 30);
+    let h2 = cloud_sim.add_host("h2", 30, 30);
+    let h3 = cloud_sim.add_host("h3", 30, 30);
+    cloud_sim.set_host_location(h1, "north");
+    cloud_sim.set_host_location(h2, "south");
+
+    let vm1 = cloud_sim.spawn_vm_on_host(ResourceConsumer::with_full_load(10, 10), 3.0, None, h1);
+    let vm2 = cloud_sim.spawn_vm_on_host(ResourceConsumer::with_const_load(10, 10, 0.5, 0.5), 3.0, None, h1);
+    let vm3 = cloud_sim.spawn_vm_on_host(ResourceConsumer::with_full_load(20, 20), 3.0, None, h2);
+    cloud_sim.set_vm_workload(vm1, "w");
+    cloud_sim.set_vm_workload(vm3, "w");
+
+    cloud_sim.step_for_duration(10.);
+    assert_eq!(cloud_sim.current_time(), 10.);
+
+    // emissions = PUE * power * time * intensity / 3.6e6
+    let kwh = 3.6e6;
+    let h1_emissions = 1.5 * 100. * (0.7 * 3. + 0.4 * 7.) / kwh;
+    let h2_emissions = 1.5 * (0.8 * 2. * 400. + 0.8 * 100. + 0.4 * 7. * 100.) / kwh;
+    assert!((cloud_sim.host_emissions(h1) - h1_emissions).abs() < 1e-12);
+    assert!((cloud_sim.host_emissions(h2) - h2_emissions).abs() < 1e-12);
+    assert_eq!(cloud_sim.host_emissions(h3), 0.);
+    assert!((cloud_sim.total_emissions() - h1_emissions - h2_emissions).abs() < 1e-12);
+
+    assert!((cloud_sim.vm_energy_consumed(vm1) - 0.7 * 3. * 2. / 3.).abs() < 1e-12);
+    assert!((cloud_sim.vm_energy_consumed(vm2) - 0.7 * 3. / 3.).abs() < 1e-12);
+    assert!((cloud_sim.vm_energy_consumed(vm3) - 0.8 * 3.).abs() < 1e-12);
+    let vm1_emissions = 1.5 * 100. * 0.7 * 3. * 2. / 3. / kwh;
+    let vm3_emissions = 1.5 * (0.8 * 2. * 400. + 0.8 * 100.) / kwh;
+    assert!((cloud_sim.vm_emissions(vm1) - vm1_emissions).abs() < 1e-12);
+    assert!((cloud_sim.vm_emissions(vm3) - vm3_emissions).abs() < 1e-12);
+
+    assert!((cloud_sim.workload_energy_consumed("w") - (0.7 * 3. * 2. / 3. + 0.8 * 3.)).abs() < 1e-12);
+    assert!((cloud_sim.workload_emissions("w") - vm1_emissions - vm3_emissions).abs() < 1e-12);
+}
+
+#[test]
+// The idle host consumes 0.4 W, its emissions before relocation are kept.
+fn test_host_relocation() {
+    let sim = Simulation::new(123);
+    let sim_config = SimulationConfig::from_file(&name_wrapper("config_zero_latency.yaml"));
+    let mut cloud_sim = CloudSimulation::new(sim, sim_config);
+    cloud_sim.add_carbon_intensity("north", CarbonIntensityTrace::constant(100.));
+    cloud_sim.add_carbon_intensity("south", CarbonIntensityTrace::constant(400.));
+
+    let h = cloud_sim.add_host("h", 30, 30);
+    cloud_sim.set_host_location(h, "north");
+    cloud_sim.step_for_duration(2.);
+    cloud_sim.set_host_location(h, "south");
+    cloud_sim.step_for_duration(3.);
+    assert_eq!(cloud_sim.current_time(), 5.);
+
+    let emissions = 0.4 * (2. * 100. + 3. * 400.) / 3.6e6;
+    assert!((cloud_sim.host_emissions(h) - emissions).abs() < 1e-12);
+}
+
+#[test]
+#[should_panic(expected = "PUE must be set before setting host locations")]
+fn test_pue_after_host_location() {
+    let sim = Simulation::new(123);
+    let sim_config = SimulationConfig::from_file(&name_wrapper("config_zero_latency.yaml"));
+    let mut cloud_sim = CloudSimulation::new(sim, sim_config);
+    cloud_sim.add_carbon_intensity("north", CarbonIntensityTrace::constant(100.));
+
+    let h = cloud_sim.add_host("h", 30, 30);
+    cloud_sim.set_host_location(h, "north");
+    cloud_sim.set_pue(1.5);
+}
+
+#[test]
+// Both VMs use 10 vCPUs at full load and consume 1 GB/s of memory bandwidth per vCPU,
+// so the host bandwidth of 10 GB/s is oversubscribed twice while they run together.
+fn test_vm_interference() {
+    let sim = Simulation::new(123);
+    let sim_config = SimulationConfig::from_file(&name_wrapper("config_zero_latency.yaml"));
+    let mut cloud_sim = CloudSimulation::new(sim, sim_config);
+
+    let h = cloud_sim.add_host("h", 30, 30);
+    cloud_sim.set_host_interference_model(h, Box::new(BandwidthInterferenceModel::new(10.)));
+
+    let profile = MemoryProfile::new(1., 0., 1.);
+    let vm1 = cloud_sim.spawn_vm_on_host(
+        ResourceConsumer::with_full_load(10, 10).with_memory_profile(profile),
+        5.0,
+        None,
+        h,
+    );
+    let vm2 = cloud_sim.spawn_vm_on_host(
+        ResourceConsumer::with_full_load(10, 10).with_memory_profile(profile),
+        2.0,
+        None,
+        h,
+    );
+    let vm3 = cloud_sim.spawn_vm_on_host(ResourceConsumer::with_full_load(10, 10), 5.0, None, h);
+
+    cloud_sim.step_for_duration(1.);
+    assert_eq!(cloud_sim.vm_speed_factor(vm1), Some(0.5));
+    assert_eq!(cloud_sim.vm_speed_factor(vm2), Some(0.5));
+    assert_eq!(cloud_sim.vm_speed_factor(vm3), Some(1.));
+
+    cloud_sim.step_for_duration(2.);
+    assert_eq!(cloud_sim.vm_speed_factor(vm1), Some(1.));
+    assert_eq!(cloud_sim.vm_speed_factor(vm2), None);
+}
+
+#[test]
+// Hosts are created from the platform hosts, the second host gets the default memory capacity.
+fn test_hosts_from_platform() {
+    let platform = Platform::from_simgrid_xml_str(
+        r#"<?xml version='1.0'?>
+<!DOCTYPE platform SYSTEM "https://simgrid.org/simgrid.dtd">
+<platform version="4.1">
+    <zone id="zone0" routing="Full">
+        <host id="h1" speed="1Gf" core="16">
+            <prop id="memory" value="64" />
+        </host>
+        <host id="h2" speed="2Gf" core="8" />
+        <link id="link" latency="0" bandwidth="10MBps"/>
+        <route src="h1" dst="h2">
+            <link_ctn id="link" />
+        </route>
+    </zone>
+</platform>"#,
+    );
+
+    let sim = Simulation::new(123);
+    let sim_config = SimulationConfig::from_file(&name_wrapper("config_zero_latency.yaml"));
+    let mut cloud_sim = CloudSimulation::new(sim, sim_config);
+
+    let hosts = cloud_sim.add_hosts_from_platform(&platform, 32);
+    assert_eq!(hosts.len(), 2);
+    assert_eq!(cloud_sim.host(hosts[0]).borrow().cpu_total(), 16);
+    assert_eq!(cloud_sim.host(hosts[0]).borrow().memory_total(), 64);
+    assert_eq!(cloud_sim.host(hosts[1]).borrow().cpu_total(), 8);
+    assert_eq!(cloud_sim.host(hosts[1]).borrow().memory_total(), 32);
+
+    let s = cloud_sim.add_scheduler("s", VMPlacementAlgorithm::single(FirstFit::new()));
+    let vm = cloud_sim.spawn_vm_now(ResourceConsumer::with_full_load(12, 48), 10.0, None, s);
+    cloud_sim.step_for_duration(1.);
+    assert_eq!(cloud_sim.vm_location(vm), Some(hosts[0]));
+}
+
+#[test]
+// The actual CPU load is 30 / 40, but the two memory-bound VMs run at the half speed while they run together,
+// so the host is overloaded for 2 seconds out of 5 from the point of view of VMs.
+fn test_vm_interference_slav() {
+    let sim = Simulation::new(123);
+    let sim_config = SimulationConfig::from_file(&name_wrapper("config_zero_latency.yaml"));
+    let mut cloud_sim = CloudSimulation::new(sim, sim_config);
+    cloud_sim.set_slav_metric(Box::new(OverloadTimeFraction::new()));
+
+    let h = cloud_sim.add_host("h", 40, 40);
+    cloud_sim.set_host_interference_model(h, Box::new(BandwidthInterferenceModel::new(10.)));
+
+    let profile = MemoryProfile::new(1., 0., 1.);
+    cloud_sim.spawn_vm_on_host(
+        ResourceConsumer::with_full_load(10, 10).with_memory_profile(profile),
+        5.0,
+        None,
+        h,
+    );
+    cloud_sim.spawn_vm_on_host(
+        ResourceConsumer::with_full_load(10, 10).with_memory_profile(profile),
+        2.0,
+        None,
+        h,
+    );
+    cloud_sim.spawn_vm_on_host(ResourceConsumer::with_full_load(10, 10), 5.0, None, h);
+
+    cloud_sim.step_for_duration(1.);
+    assert!((cloud_sim.host(h).borrow().cpu_load(1.) - 0.75).abs() < 1e-12);
+    assert!((cloud_sim.host(h).borrow().effective_cpu_load(1.) - 1.25).abs() < 1e-12);
+
+    cloud_sim.step_for_duration(9.);
+    let end_time = cloud_sim.current_time();
+    assert!((cloud_sim.host(h).borrow_mut().get_accumulated_slav(end_time) - 0.4).abs() < 1e-12);
+}
@@ -0,0 +1,121 @@
+//! Carbon intensity of electricity grid.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::rc::Rc;
+
+use serde::Deserialize;
+
+/// Number of Joules in one kWh.
+const JOULES_PER_KWH: f64 = 3.6e6;
+
+#[derive(Deserialize)]
+struct IntensityRecord {
+    time: f64,
+    intensity: f64,
+}
+
+#[derive(Deserialize)]
+struct LocationIntensityRecord {
+    location: String,
+    time: f64,
+    intensity: f64,
+}
+
+/// Time series of grid carbon intensity in gCO2/kWh.
+///
+/// The intensity is piecewise constant: the value specified at some time is used until the next specified time.
+/// The first value is also used before its time. The series is shared between clones of the trace.
+#[derive(Clone, Debug)]
+pub struct CarbonIntensityTrace {
+    points: Rc<Vec<(f64, f64)>>,
+}
+
+impl CarbonIntensityTrace {
+    /// Creates trace from the given `(time, intensity)` points.
+    pub fn new(mut points: Vec<(f64, f64)>) -> Self {
+        assert!(
+            !points.is_empty(),
+            "Carbon intensity trace must contain at least one point"
+        );
+        for (_, intensity) in points.iter() {
+            assert!(
+                *intensity >= 0.,
+                "Carbon intensity must be non-negative, got {}",
+                intensity
+            );
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {
+            points: Rc::new(points),
+        }
+    }
+
+    /// Creates trace with the constant intensity.
+    pub fn constant(intensity: f64) -> Self {
+        Self::new(vec![(0., intensity)])
+    }
+
+    /// Loads trace from CSV file with `time` and `intensity` columns.
+    pub fn from_csv<P: AsRef<Path>>(file: P) -> Self {
+        let mut reader =
+            csv::Reader::from_path(&file).unwrap_or_else(|_| panic!("Can't read file {}", file.as_ref().display()));
+        let points = reader
+            .deserialize()
+            .map(|record| {
+                let record: IntensityRecord =
+                    record.unwrap_or_else(|e| panic!("Can't parse carbon intensity record: {}", e));
+                (record.time, record.intensity)
+            })
+            .collect();
+        Self::new(points)
+    }
+
+    /// Returns the carbon intensity at the given time.
+    pub fn intensity(&self, time: f64) -> f64 {
+        let pos = self.points.partition_point(|(t, _)| *t <= time);
+        self.points[pos.saturating_sub(1)].1
+    }
+
+    /// Returns the integral of the carbon intensity over the given time interval in gCO2 * s / kWh.
+    pub fn integral(&self, from: f64, to: f64) -> f64 {
+        if to <= from {
+            return 0.;
+        }
+        let mut result = 0.;
+        let mut time = from;
+        let mut pos = self.points.partition_point(|(t, _)| *t <= from);
+        while time < to {
+            let next_time = self.points.get(pos).map_or(to, |(t, _)| t.min(to));
+            result += self.points[pos.saturating_sub(1)].1 * (next_time - time);
+            time = next_time;
+            pos += 1;
+        }
+        result
+    }
+
+    /// Returns the emissions in gCO2 caused by the constant power consumption (in Watts)
+    /// over the given time interval (in seconds).
+    pub fn emissions(&self, power: f64, from: f64, to: f64) -> f64 {
+        power * self.integral(from, to) / JOULES_PER_KWH
+    }
+}
+
+/// Loads traces for multiple locations from CSV file with `location`, `time` and `intensity` columns.
+pub fn load_carbon_intensity_traces<P: AsRef<Path>>(file: P) -> BTreeMap<String, CarbonIntensityTrace> {
+    let mut reader =
+        csv::Reader::from_path(&file).unwrap_or_else(|_| panic!("Can't read file {}", file.as_ref().display()));
+    let mut points: BTreeMap<String, Vec<(f64, f64)>> = BTreeMap::new();
+    for record in reader.deserialize() {
+        let record: LocationIntensityRecord =
+            record.unwrap_or_else(|e| panic!("Can't parse carbon intensity record: {}", e));
+        points
+            .entry(record.location)
+            .or_default()
+            .push((record.time, record.intensity));
+    }
+    points
+        .into_iter()
+        .map(|(location, points)| (location, CarbonIntensityTrace::new(points)))
+        .collect()
+}
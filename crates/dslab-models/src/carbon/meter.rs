@@ -0,0 +1,80 @@
+//! Carbon meter.
+
+use crate::carbon::intensity::CarbonIntensityTrace;
+
+/// Calculates the energy consumption and carbon emissions of a device with piecewise constant power consumption.
+///
+/// The emissions account for the power usage effectiveness (PUE) of the facility hosting the device,
+/// i.e. the consumed energy is multiplied by PUE before applying the carbon intensity.
+#[derive(Clone, Debug)]
+pub struct CarbonMeter {
+    intensity: CarbonIntensityTrace,
+    pue: f64,
+    energy_consumed: f64,
+    emissions: f64,
+    current_power: f64,
+    prev_time: f64,
+}
+
+impl CarbonMeter {
+    /// Creates meter with the given carbon intensity trace and PUE equal to 1.
+    pub fn new(intensity: CarbonIntensityTrace) -> Self {
+        Self {
+            intensity,
+            pue: 1.,
+            energy_consumed: 0.,
+            emissions: 0.,
+            current_power: 0.,
+            prev_time: 0.,
+        }
+    }
+
+    /// Sets the power usage effectiveness of the facility.
+    pub fn with_pue(mut self, pue: f64) -> Self {
+        assert!(pue >= 1., "PUE must be at least 1, got {}", pue);
+        self.pue = pue;
+        self
+    }
+
+    /// Sets the time from which the energy consumption and emissions are accounted.
+    pub fn with_start_time(mut self, time: f64) -> Self {
+        self.prev_time = time;
+        self
+    }
+
+    /// Returns the power usage effectiveness of the facility.
+    pub fn pue(&self) -> f64 {
+        self.pue
+    }
+
+    /// Invoked each time the device power consumption is changed to update the energy consumption and emissions.
+    pub fn update(&mut self, time: f64, power: f64) {
+        self.energy_consumed += (time - self.prev_time) * self.current_power;
+        self.emissions += self.pue * self.intensity.emissions(self.current_power, self.prev_time, time);
+        self.current_power = power;
+        self.prev_time = time;
+    }
+
+    /// Replaces the carbon intensity trace starting from the given time, e.g. when the device is relocated.
+    ///
+    /// The energy consumption and emissions accumulated before this time are preserved.
+    pub fn set_intensity(&mut self, intensity: CarbonIntensityTrace, time: f64) {
+        self.update(time, self.current_power);
+        self.intensity = intensity;
+    }
+
+    /// Returns the total energy consumption of the device in Joules.
+    pub fn energy_consumed(&self) -> f64 {
+        self.energy_consumed
+    }
+
+    /// Returns the total energy consumption of the device including the facility overhead (multiplied by PUE).
+    pub fn facility_energy_consumed(&self) -> f64 {
+        self.energy_consumed * self.pue
+    }
+
+    /// Returns the total carbon emissions in gCO2.
+    pub fn emissions(&self) -> f64 {
+        self.emissions
+    }
+}
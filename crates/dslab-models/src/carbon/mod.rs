@@ -0,0 +1,10 @@
+//! Carbon emissions accounting.
+//!
+//! The emissions caused by the energy consumption are computed using the carbon intensity of electricity grid,
+//! which varies over time and depends on the location of the consumer.
+
+pub mod intensity;
+pub mod meter;
+
+#[cfg(test)]
+mod tests;
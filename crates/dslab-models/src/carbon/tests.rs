@@ -0,0 +1,83 @@
+use std::io::Write;
+
+use approx::assert_abs_diff_eq;
+
+use crate::carbon::intensity::{load_carbon_intensity_traces, CarbonIntensityTrace};
+use crate::carbon::meter::CarbonMeter;
+
+#[test]
+fn test_intensity_trace() {
+    let trace = CarbonIntensityTrace::new(vec![(3600., 200.), (0., 100.), (7200., 50.)]);
+    assert_eq!(trace.intensity(-1.), 100.);
+    assert_eq!(trace.intensity(0.), 100.);
+    assert_eq!(trace.intensity(3599.), 100.);
+    assert_eq!(trace.intensity(3600.), 200.);
+    assert_eq!(trace.intensity(1e9), 50.);
+
+    assert_eq!(trace.integral(0., 3600.), 100. * 3600.);
+    assert_eq!(trace.integral(1800., 5400.), 100. * 1800. + 200. * 1800.);
+    assert_eq!(trace.integral(-100., 10800.), 100. * 3700. + 200. * 3600. + 50. * 3600.);
+    assert_eq!(trace.integral(5000., 5000.), 0.);
+
+    // 1 kW during 1 hour = 1 kWh
+    assert_abs_diff_eq!(trace.emissions(1000., 0., 3600.), 100., epsilon = 1e-9);
+    assert_abs_diff_eq!(trace.emissions(1000., 1800., 5400.), 150., epsilon = 1e-9);
+}
+
+#[test]
+fn test_carbon_meter() {
+    let trace = CarbonIntensityTrace::new(vec![(0., 100.), (3600., 300.)]);
+    let mut meter = CarbonMeter::new(trace).with_pue(1.5);
+    meter.update(0., 1000.);
+    meter.update(1800., 2000.);
+    meter.update(5400., 0.);
+    meter.update(10000., 500.);
+
+    assert_abs_diff_eq!(meter.energy_consumed(), 1000. * 1800. + 2000. * 3600., epsilon = 1e-6);
+    assert_abs_diff_eq!(
+        meter.facility_energy_consumed(),
+        1.5 * (1000. * 1800. + 2000. * 3600.),
+        epsilon = 1e-6
+    );
+    // 0.5 kWh at 100 g/kWh, 1 kWh at 100 g/kWh, 1 kWh at 300 g/kWh, all multiplied by PUE
+    assert_abs_diff_eq!(meter.emissions(), 1.5 * (50. + 100. + 300.), epsilon = 1e-9);
+}
+
+#[test]
+fn test_carbon_meter_start_time() {
+    let mut meter = CarbonMeter::new(CarbonIntensityTrace::constant(400.)).with_start_time(3600.);
+    meter.update(3600., 100.);
+    meter.update(7200., 0.);
+    assert_abs_diff_eq!(meter.energy_consumed(), 360000., epsilon = 1e-6);
+    assert_abs_diff_eq!(meter.emissions(), 40., epsilon = 1e-9);
+}
+
+#[test]
+fn test_carbon_meter_set_intensity() {
+    let mut meter = CarbonMeter::new(CarbonIntensityTrace::constant(100.));
+    meter.update(0., 1000.);
+    meter.set_intensity(CarbonIntensityTrace::constant(400.), 3600.);
+    meter.update(7200., 0.);
+    assert_abs_diff_eq!(meter.energy_consumed(), 1000. * 7200., epsilon = 1e-6);
+    assert_abs_diff_eq!(meter.emissions(), 100. + 400., epsilon = 1e-9);
+}
+
+#[test]
+fn test_load_traces() {
+    let path = std::env::temp_dir().join("dslab_models_test_carbon_intensity.csv");
+    let mut file = std::fs::File::create(&path).unwrap();
+    writeln!(file, "location,time,intensity").unwrap();
+    writeln!(file, "north,0,50").unwrap();
+    writeln!(file, "south,0,400").unwrap();
+    writeln!(file, "north,3600,80").unwrap();
+    writeln!(file, "south,3600,350").unwrap();
+    drop(file);
+
+    let traces = load_carbon_intensity_traces(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(traces.len(), 2);
+    assert_eq!(traces["north"].intensity(100.), 50.);
+    assert_eq!(traces["north"].intensity(4000.), 80.);
+    assert_eq!(traces["south"].intensity(100.), 400.);
+    assert_eq!(traces["south"].intensity(4000.), 350.);
+}
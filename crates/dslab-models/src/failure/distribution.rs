@@ -0,0 +1,117 @@
+//! Distributions of time to failure and repair time.
+
+use rand::distributions::Distribution;
+use rand::Rng;
+
+/// Distribution of a non-negative time interval, such as time to failure or repair time.
+///
+/// Can be sampled with [`SimulationContext::sample_from_distribution`](dslab_core::SimulationContext::sample_from_distribution).
+#[derive(Clone, Debug, PartialEq)]
+pub enum TimeDistribution {
+    /// Fixed interval.
+    Constant(f64),
+    /// Uniform distribution in `[min, max)`.
+    Uniform {
+        /// Minimum value.
+        min: f64,
+        /// Maximum value.
+        max: f64,
+    },
+    /// Exponential distribution with the given mean value (i.e. MTTF or MTTR).
+    Exponential {
+        /// Mean value.
+        mean: f64,
+    },
+    /// Weibull distribution with the given shape and scale parameters.
+    ///
+    /// The shape below 1 corresponds to decreasing failure rate (infant mortality),
+    /// the shape above 1 corresponds to increasing failure rate (wear-out).
+    Weibull {
+        /// Shape parameter.
+        shape: f64,
+        /// Scale parameter.
+        scale: f64,
+    },
+    /// Log-normal distribution, often used to model repair times.
+    LogNormal {
+        /// Mean of the underlying normal distribution.
+        mu: f64,
+        /// Standard deviation of the underlying normal distribution.
+        sigma: f64,
+    },
+}
+
+impl TimeDistribution {
+    /// Checks the distribution parameters and panics if they are invalid.
+    pub fn validate(&self) {
+        match *self {
+            Self::Constant(value) => assert!(value >= 0., "Constant time must be non-negative"),
+            Self::Uniform { min, max } => assert!(
+                min >= 0. && min <= max,
+                "Uniform distribution bounds must satisfy 0 <= min <= max"
+            ),
+            Self::Exponential { mean } => assert!(mean > 0., "Exponential distribution mean must be positive"),
+            Self::Weibull { shape, scale } => assert!(
+                shape > 0. && scale > 0.,
+                "Weibull distribution parameters must be positive"
+            ),
+            Self::LogNormal { sigma, .. } => assert!(sigma >= 0., "Log-normal distribution sigma must be non-negative"),
+        }
+    }
+
+    /// Returns the mean value of the distribution.
+    pub fn mean(&self) -> f64 {
+        match *self {
+            Self::Constant(value) => value,
+            Self::Uniform { min, max } => (min + max) / 2.,
+            Self::Exponential { mean } => mean,
+            Self::Weibull { shape, scale } => scale * gamma(1. + 1. / shape),
+            Self::LogNormal { mu, sigma } => (mu + sigma * sigma / 2.).exp(),
+        }
+    }
+}
+
+impl Distribution<f64> for TimeDistribution {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match *self {
+            Self::Constant(value) => value,
+            Self::Uniform { min, max } => min + (max - min) * rng.gen::<f64>(),
+            // 1 - u is used to avoid taking the logarithm of zero
+            Self::Exponential { mean } => -mean * (1. - rng.gen::<f64>()).ln(),
+            Self::Weibull { shape, scale } => scale * (-(1. - rng.gen::<f64>()).ln()).powf(1. / shape),
+            Self::LogNormal { mu, sigma } => {
+                // Box-Muller transform
+                let u1 = 1. - rng.gen::<f64>();
+                let u2 = rng.gen::<f64>();
+                let z = (-2. * u1.ln()).sqrt() * (2. * std::f64::consts::PI * u2).cos();
+                (mu + sigma * z).exp()
+            }
+        }
+    }
+}
+
+/// Computes the gamma function using the Lanczos approximation.
+fn gamma(x: f64) -> f64 {
+    const COEFFS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        std::f64::consts::PI / ((std::f64::consts::PI * x).sin() * gamma(1. - x))
+    } else {
+        let x = x - 1.;
+        let t = x + 7.5;
+        let sum = COEFFS[1..]
+            .iter()
+            .enumerate()
+            .fold(COEFFS[0], |acc, (i, c)| acc + c / (x + i as f64 + 1.));
+        (2. * std::f64::consts::PI).sqrt() * t.powf(x + 0.5) * (-t).exp() * sum
+    }
+}
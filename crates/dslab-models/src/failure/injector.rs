@@ -0,0 +1,219 @@
+//! Failure injector component.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use serde::Serialize;
+
+use dslab_core::{cast, Event, EventHandler, SimulationContext};
+
+use crate::failure::distribution::TimeDistribution;
+use crate::failure::trace::FailureTrace;
+
+/// Component which can be crashed and recovered by [`FailureInjector`].
+pub trait FailureTarget {
+    /// Invoked when the component fails.
+    fn crash(&mut self, time: f64);
+    /// Invoked when the component is repaired.
+    fn recover(&mut self, time: f64);
+}
+
+/// Internal event used to start the failure of the source targets.
+#[derive(Clone, Serialize)]
+struct FailureStarted {
+    source: usize,
+}
+
+/// Internal event used to end the failure of the source targets.
+#[derive(Clone, Serialize)]
+struct FailureEnded {
+    source: usize,
+}
+
+struct TargetInfo {
+    target: Rc<RefCell<dyn FailureTarget>>,
+    // number of currently active failures affecting the target
+    active_failures: u32,
+    failure_count: u32,
+    down_since: f64,
+    downtime: f64,
+}
+
+/// Source of failures affecting one or more targets simultaneously.
+struct FailureSource {
+    targets: Vec<usize>,
+    // alternating failure and repair process, not set for trace records
+    process: Option<(TimeDistribution, TimeDistribution)>,
+}
+
+/// Injects failures into the registered targets and tracks their availability.
+///
+/// The failures are produced by independent failure processes of individual targets, by correlated failures
+/// of target groups (e.g. sharing a rack or a power domain) and by replaying failure traces.
+/// A target is crashed when the first of its failures starts and is recovered when all its failures end,
+/// so overlapping failures from different sources are handled correctly.
+pub struct FailureInjector {
+    targets: Vec<TargetInfo>,
+    target_ids: HashMap<String, usize>,
+    sources: Vec<FailureSource>,
+    ctx: SimulationContext,
+}
+
+impl FailureInjector {
+    /// Creates failure injector.
+    pub fn new(ctx: SimulationContext) -> Self {
+        Self {
+            targets: Vec::new(),
+            target_ids: HashMap::new(),
+            sources: Vec::new(),
+            ctx,
+        }
+    }
+
+    /// Registers target with the given name, which is used to refer to it in failure processes and traces.
+    pub fn add_target(&mut self, name: &str, target: Rc<RefCell<dyn FailureTarget>>) {
+        assert!(
+            !self.target_ids.contains_key(name),
+            "Failure target {} is already registered",
+            name
+        );
+        self.target_ids.insert(name.to_string(), self.targets.len());
+        self.targets.push(TargetInfo {
+            target,
+            active_failures: 0,
+            failure_count: 0,
+            down_since: 0.,
+            downtime: 0.,
+        });
+    }
+
+    fn target_id(&self, name: &str) -> usize {
+        *self
+            .target_ids
+            .get(name)
+            .unwrap_or_else(|| panic!("Unknown failure target {}", name))
+    }
+
+    /// Adds independent failure process for the target with the given distributions of time to failure
+    /// and repair time. The first time to failure is counted from the current time.
+    pub fn add_failure_process(&mut self, target: &str, ttf: TimeDistribution, repair_time: TimeDistribution) {
+        self.add_correlated_failures(&[target], ttf, repair_time);
+    }
+
+    /// Adds failure process for the group of targets, which fail and recover simultaneously,
+    /// e.g. due to a failure of the shared rack switch or power supply.
+    pub fn add_correlated_failures(&mut self, targets: &[&str], ttf: TimeDistribution, repair_time: TimeDistribution) {
+        assert!(!targets.is_empty(), "Failure group must contain at least one target");
+        ttf.validate();
+        repair_time.validate();
+        let targets = targets.iter().map(|name| self.target_id(name)).collect();
+        let source = self.sources.len();
+        let delay = self.ctx.sample_from_distribution(&ttf);
+        self.sources.push(FailureSource {
+            targets,
+            process: Some((ttf, repair_time)),
+        });
+        self.ctx.emit_self(FailureStarted { source }, delay);
+    }
+
+    /// Schedules the failures from the trace, the trace node names should match the registered target names.
+    ///
+    /// The trace times are treated as absolute simulation times. The failures which have already ended are skipped,
+    /// while the targets of failures which are still in progress are crashed immediately.
+    pub fn add_failure_trace(&mut self, trace: &FailureTrace) {
+        let now = self.ctx.time();
+        for record in trace.records() {
+            let target = self.target_id(&record.node);
+            if record.end_time <= now {
+                continue;
+            }
+            let source = self.sources.len();
+            self.sources.push(FailureSource {
+                targets: vec![target],
+                process: None,
+            });
+            if record.start_time < now {
+                self.start_failure(source);
+            } else {
+                self.ctx.emit_self(FailureStarted { source }, record.start_time - now);
+            }
+            self.ctx.emit_self(FailureEnded { source }, record.end_time - now);
+        }
+    }
+
+    /// Returns whether the target is currently available.
+    pub fn is_up(&self, target: &str) -> bool {
+        self.targets[self.target_id(target)].active_failures == 0
+    }
+
+    /// Returns the number of target failures so far.
+    pub fn failure_count(&self, target: &str) -> u32 {
+        self.targets[self.target_id(target)].failure_count
+    }
+
+    /// Returns the total time during which the target was unavailable.
+    pub fn downtime(&self, target: &str) -> f64 {
+        let info = &self.targets[self.target_id(target)];
+        if info.active_failures > 0 {
+            info.downtime + self.ctx.time() - info.down_since
+        } else {
+            info.downtime
+        }
+    }
+
+    /// Returns the fraction of time since the simulation start during which the target was available.
+    pub fn availability(&self, target: &str) -> f64 {
+        let time = self.ctx.time();
+        if time == 0. {
+            return if self.is_up(target) { 1. } else { 0. };
+        }
+        1. - self.downtime(target) / time
+    }
+
+    fn start_failure(&mut self, source: usize) {
+        let time = self.ctx.time();
+        for &id in self.sources[source].targets.iter() {
+            let info = &mut self.targets[id];
+            info.active_failures += 1;
+            if info.active_failures == 1 {
+                info.failure_count += 1;
+                info.down_since = time;
+                info.target.borrow_mut().crash(time);
+            }
+        }
+        if let Some((_, repair_time)) = &self.sources[source].process {
+            let delay = self.ctx.sample_from_distribution(repair_time);
+            self.ctx.emit_self(FailureEnded { source }, delay);
+        }
+    }
+
+    fn end_failure(&mut self, source: usize) {
+        let time = self.ctx.time();
+        for &id in self.sources[source].targets.iter() {
+            let info = &mut self.targets[id];
+            info.active_failures -= 1;
+            if info.active_failures == 0 {
+                info.downtime += time - info.down_since;
+                info.target.borrow_mut().recover(time);
+            }
+        }
+        if let Some((ttf, _)) = &self.sources[source].process {
+            let delay = self.ctx.sample_from_distribution(ttf);
+            self.ctx.emit_self(FailureStarted { source }, delay);
+        }
+    }
+}
+
+impl EventHandler for FailureInjector {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            FailureStarted { source } => {
+                self.start_failure(source);
+            }
+            FailureEnded { source } => {
+                self.end_failure(source);
+            }
+        })
+    }
+}
@@ -0,0 +1,11 @@
+//! Failure and repair models.
+//!
+//! Provides the distributions of time to failure and repair time, the replay of failure traces
+//! and the failure injector component, which crashes and recovers the registered targets.
+
+pub mod distribution;
+pub mod injector;
+pub mod trace;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,193 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use approx::assert_abs_diff_eq;
+
+use dslab_core::Simulation;
+
+use crate::failure::distribution::TimeDistribution;
+use crate::failure::injector::{FailureInjector, FailureTarget};
+use crate::failure::trace::{FailureRecord, FailureTrace};
+
+#[derive(Default)]
+struct Node {
+    events: Vec<(bool, f64)>,
+}
+
+impl FailureTarget for Node {
+    fn crash(&mut self, time: f64) {
+        self.events.push((false, time));
+    }
+
+    fn recover(&mut self, time: f64) {
+        self.events.push((true, time));
+    }
+}
+
+fn make_injector(sim: &mut Simulation, node_count: usize) -> (Rc<RefCell<FailureInjector>>, Vec<Rc<RefCell<Node>>>) {
+    let injector = Rc::new(RefCell::new(FailureInjector::new(sim.create_context("failures"))));
+    sim.add_handler("failures", injector.clone());
+    let mut nodes = Vec::new();
+    for i in 0..node_count {
+        let node = Rc::new(RefCell::new(Node::default()));
+        injector.borrow_mut().add_target(&format!("node{}", i), node.clone());
+        nodes.push(node);
+    }
+    (injector, nodes)
+}
+
+#[test]
+fn test_distributions() {
+    let mut sim = Simulation::new(123);
+    let ctx = sim.create_context("test");
+    let distributions = [
+        TimeDistribution::Uniform { min: 1., max: 3. },
+        TimeDistribution::Exponential { mean: 10. },
+        TimeDistribution::Weibull { shape: 0.7, scale: 5. },
+        TimeDistribution::Weibull { shape: 2., scale: 5. },
+        TimeDistribution::LogNormal { mu: 1., sigma: 0.5 },
+    ];
+    let n = 100000;
+    for dist in distributions.iter() {
+        let mut sum = 0.;
+        for _ in 0..n {
+            let value = ctx.sample_from_distribution(dist);
+            assert!(value >= 0.);
+            sum += value;
+        }
+        let mean = sum / n as f64;
+        assert_abs_diff_eq!(mean, dist.mean(), epsilon = dist.mean() * 0.02);
+    }
+    assert_eq!(ctx.sample_from_distribution(&TimeDistribution::Constant(4.)), 4.);
+    // Weibull with shape 1 is exponential
+    assert_abs_diff_eq!(
+        TimeDistribution::Weibull { shape: 1., scale: 5. }.mean(),
+        5.,
+        epsilon = 1e-9
+    );
+}
+
+#[test]
+fn test_failure_process() {
+    let mut sim = Simulation::new(123);
+    let (injector, nodes) = make_injector(&mut sim, 1);
+    injector
+        .borrow_mut()
+        .add_failure_process("node0", TimeDistribution::Constant(10.), TimeDistribution::Constant(2.));
+
+    sim.step_until_time(11.);
+    assert!(!injector.borrow().is_up("node0"));
+    assert_eq!(injector.borrow().downtime("node0"), 1.);
+
+    sim.step_until_time(36.);
+    assert!(injector.borrow().is_up("node0"));
+    assert_eq!(injector.borrow().failure_count("node0"), 3);
+    assert_eq!(injector.borrow().downtime("node0"), 6.);
+    assert_abs_diff_eq!(injector.borrow().availability("node0"), 30. / 36., epsilon = 1e-9);
+    assert_eq!(
+        nodes[0].borrow().events,
+        vec![
+            (false, 10.),
+            (true, 12.),
+            (false, 22.),
+            (true, 24.),
+            (false, 34.),
+            (true, 36.)
+        ]
+    );
+}
+
+#[test]
+fn test_correlated_failures() {
+    let mut sim = Simulation::new(123);
+    let (injector, nodes) = make_injector(&mut sim, 3);
+    injector
+        .borrow_mut()
+        .add_failure_process("node0", TimeDistribution::Constant(5.), TimeDistribution::Constant(10.));
+    injector.borrow_mut().add_correlated_failures(
+        &["node0", "node1"],
+        TimeDistribution::Constant(10.),
+        TimeDistribution::Constant(2.),
+    );
+
+    sim.step_until_time(13.);
+    // node0 is still down due to its own failure
+    assert!(!injector.borrow().is_up("node0"));
+    assert!(injector.borrow().is_up("node1"));
+    assert!(injector.borrow().is_up("node2"));
+    assert_eq!(nodes[0].borrow().events, vec![(false, 5.)]);
+    assert_eq!(nodes[1].borrow().events, vec![(false, 10.), (true, 12.)]);
+    assert!(nodes[2].borrow().events.is_empty());
+
+    sim.step_until_time(15.);
+    assert!(injector.borrow().is_up("node0"));
+    assert_eq!(nodes[0].borrow().events, vec![(false, 5.), (true, 15.)]);
+    assert_eq!(injector.borrow().failure_count("node0"), 1);
+    assert_eq!(injector.borrow().downtime("node0"), 10.);
+}
+
+#[test]
+fn test_fta_trace() {
+    let data = "\
+event_id\tcomponent_id\tnode_id\tplatform_id\tnode_name\tevent_type\tevent_start_time\tevent_stop_time\tevent_end_reason
+1\t0\t1\t0\tn1\t0\t1000000100\t1000000150\t1
+2\t0\t1\t0\tn1\t1\t1000000150\t1000000300\t0
+3\t0\t2\t0\tn2\t0\t1000000120.5\t1000000130\t1
+4\t0\t3\t0\tn3\t0\t1000000200\t1000000210\t1
+5\t0\t1\t0\tn1\t0\t1000000300\t1000000310\t1
+";
+    let trace = FailureTrace::parse_fta(data);
+    assert_eq!(trace.records().len(), 4);
+    assert_eq!(
+        trace.records()[1],
+        FailureRecord {
+            node: "2".to_string(),
+            start_time: 1000000120.5,
+            end_time: 1000000130.,
+        }
+    );
+
+    let trace = trace
+        .shifted_to(100.)
+        .map_nodes(|node| (node != "3").then(|| format!("node{}", node.parse::<u32>().unwrap() - 1)));
+    assert_eq!(trace.records().len(), 3);
+
+    let mut sim = Simulation::new(123);
+    let (injector, nodes) = make_injector(&mut sim, 2);
+    injector.borrow_mut().add_failure_trace(&trace);
+    sim.step_until_no_events();
+    assert_eq!(sim.time(), 310.);
+    assert_eq!(
+        nodes[0].borrow().events,
+        vec![(false, 100.), (true, 150.), (false, 300.), (true, 310.)]
+    );
+    assert_eq!(nodes[1].borrow().events, vec![(false, 120.5), (true, 130.)]);
+    assert_eq!(injector.borrow().downtime("node0"), 60.);
+    assert_eq!(injector.borrow().downtime("node1"), 9.5);
+}
+
+#[test]
+fn test_trace_loaded_during_failure() {
+    let trace = FailureTrace::new(
+        [("node0", 10., 20.), ("node1", 30., 60.), ("node0", 70., 80.)]
+            .into_iter()
+            .map(|(node, start_time, end_time)| FailureRecord {
+                node: node.to_string(),
+                start_time,
+                end_time,
+            })
+            .collect(),
+    );
+
+    let mut sim = Simulation::new(123);
+    let (injector, nodes) = make_injector(&mut sim, 2);
+    sim.step_for_duration(50.);
+    injector.borrow_mut().add_failure_trace(&trace);
+    // the ended failure is skipped, while the failure in progress crashes the target immediately
+    assert!(injector.borrow().is_up("node0"));
+    assert!(!injector.borrow().is_up("node1"));
+    sim.step_until_no_events();
+    assert_eq!(nodes[0].borrow().events, vec![(false, 70.), (true, 80.)]);
+    assert_eq!(nodes[1].borrow().events, vec![(false, 50.), (true, 60.)]);
+    assert_eq!(injector.borrow().downtime("node1"), 10.);
+}
@@ -0,0 +1,127 @@
+//! Failure traces.
+
+use std::path::Path;
+
+/// Single period of component unavailability.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FailureRecord {
+    /// Name of the failed component.
+    pub node: String,
+    /// Time of failure.
+    pub start_time: f64,
+    /// Time of recovery.
+    pub end_time: f64,
+}
+
+/// Trace of component failures, which can be replayed by [`FailureInjector`](crate::failure::injector::FailureInjector).
+#[derive(Clone, Debug, Default)]
+pub struct FailureTrace {
+    records: Vec<FailureRecord>,
+}
+
+impl FailureTrace {
+    /// Creates trace from the given records.
+    pub fn new(mut records: Vec<FailureRecord>) -> Self {
+        for record in records.iter() {
+            assert!(
+                record.start_time <= record.end_time,
+                "Failure of {} ends before it starts",
+                record.node
+            );
+        }
+        records.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+        Self { records }
+    }
+
+    /// Loads trace from the event trace file in the Failure Trace Archive (FTA) format.
+    ///
+    /// See [`FailureTrace::parse_fta`] for details.
+    pub fn from_fta<P: AsRef<Path>>(path: P) -> Self {
+        let data =
+            std::fs::read_to_string(&path).unwrap_or_else(|_| panic!("Can't read file {}", path.as_ref().display()));
+        Self::parse_fta(&data)
+    }
+
+    /// Parses the contents of the event trace file in the Failure Trace Archive (FTA) format.
+    ///
+    /// Each line contains tab-separated fields `event_id`, `component_id`, `node_id`, `platform_id`, `node_name`,
+    /// `event_type`, `event_start_time`, `event_stop_time` and optional `event_end_reason`.
+    /// Only the unavailability events (`event_type` = 0) are used, the failed components are identified by `node_id`.
+    /// Header and comment lines are skipped.
+    pub fn parse_fta(data: &str) -> Self {
+        let mut records = Vec::new();
+        for line in data.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line.split('\t').map(|f| f.trim()).collect::<Vec<_>>();
+            if fields.len() < 8 {
+                panic!("Invalid FTA event record: {}", line);
+            }
+            let event_type = match fields[5].parse::<u32>() {
+                Ok(event_type) => event_type,
+                // header line
+                Err(_) => continue,
+            };
+            if event_type != 0 {
+                continue;
+            }
+            let parse_time = |s: &str| {
+                s.parse::<f64>()
+                    .unwrap_or_else(|_| panic!("Invalid time in FTA event record: {}", line))
+            };
+            records.push(FailureRecord {
+                node: fields[2].to_string(),
+                start_time: parse_time(fields[6]),
+                end_time: parse_time(fields[7]),
+            });
+        }
+        Self::new(records)
+    }
+
+    /// Returns the trace records sorted by failure time.
+    pub fn records(&self) -> &[FailureRecord] {
+        &self.records
+    }
+
+    /// Returns the time of the first failure in the trace.
+    pub fn start_time(&self) -> Option<f64> {
+        self.records.first().map(|r| r.start_time)
+    }
+
+    /// Shifts the trace so that the first failure occurs at the given time.
+    ///
+    /// Useful for traces with absolute timestamps, such as FTA traces using Unix time.
+    pub fn shifted_to(mut self, time: f64) -> Self {
+        if let Some(start_time) = self.start_time() {
+            let delta = time - start_time;
+            for record in self.records.iter_mut() {
+                record.start_time += delta;
+                record.end_time += delta;
+            }
+        }
+        self
+    }
+
+    /// Renames the trace nodes using the given function, the records of nodes mapped to `None` are removed.
+    ///
+    /// Useful to map the trace nodes to the simulated components.
+    pub fn map_nodes<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        self.records = self
+            .records
+            .into_iter()
+            .filter_map(|record| {
+                f(&record.node).map(|node| FailureRecord {
+                    node,
+                    start_time: record.start_time,
+                    end_time: record.end_time,
+                })
+            })
+            .collect();
+        self
+    }
+}
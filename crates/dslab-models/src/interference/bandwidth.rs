@@ -0,0 +1,71 @@
+//! Memory bandwidth and cache contention model.
+
+use crate::interference::model::{InterferenceModel, MemoryProfile};
+
+/// Models the contention for shared memory bandwidth and last-level cache.
+///
+/// When the total cache footprint of workloads exceeds the cache size, the fraction of footprint which doesn't fit
+/// into the cache `m = 1 - cache_size / total_footprint` causes additional cache misses, which increase
+/// the memory traffic of each workload using the cache by a factor of `1 + miss_penalty * m`.
+/// When the total memory traffic exceeds the node bandwidth, the bandwidth is shared proportionally
+/// and memory accesses are slowed down by the ratio `r` of the total traffic to the bandwidth.
+///
+/// The execution time of a workload with sensitivity `s` is multiplied by `(1 - s) + s * f * r`,
+/// where `f` is its traffic increase factor due to cache misses, and the speed factor is the inverse of it.
+#[derive(Clone)]
+pub struct BandwidthInterferenceModel {
+    bandwidth: f64,
+    cache_size: f64,
+    miss_penalty: f64,
+}
+
+impl BandwidthInterferenceModel {
+    /// Creates model with the given node memory bandwidth and without cache contention.
+    pub fn new(bandwidth: f64) -> Self {
+        Self {
+            bandwidth,
+            cache_size: f64::INFINITY,
+            miss_penalty: 0.,
+        }
+    }
+
+    /// Enables cache contention with the given cache size and increase of memory traffic when the cache is fully
+    /// oversubscribed.
+    pub fn with_cache(mut self, cache_size: f64, miss_penalty: f64) -> Self {
+        self.cache_size = cache_size;
+        self.miss_penalty = miss_penalty;
+        self
+    }
+}
+
+impl InterferenceModel for BandwidthInterferenceModel {
+    fn speed_factors(&self, workloads: &[(MemoryProfile, f64)]) -> Vec<f64> {
+        let total_cache: f64 = workloads.iter().map(|(profile, _)| profile.cache).sum();
+        let miss_fraction = if total_cache > self.cache_size {
+            1. - self.cache_size / total_cache
+        } else {
+            0.
+        };
+        let traffic_factors = workloads
+            .iter()
+            .map(|(profile, _)| {
+                if profile.cache > 0. {
+                    1. + self.miss_penalty * miss_fraction
+                } else {
+                    1.
+                }
+            })
+            .collect::<Vec<_>>();
+        let total_traffic: f64 = workloads
+            .iter()
+            .zip(traffic_factors.iter())
+            .map(|((profile, cores), factor)| profile.bandwidth * cores * factor)
+            .sum();
+        let contention = (total_traffic / self.bandwidth).max(1.);
+        workloads
+            .iter()
+            .zip(traffic_factors.iter())
+            .map(|((profile, _), factor)| 1. / ((1. - profile.sensitivity) + profile.sensitivity * factor * contention))
+            .collect()
+    }
+}
@@ -0,0 +1,11 @@
+//! Models of performance interference between co-located workloads.
+//!
+//! The workloads running on the same node (e.g. computations on a multicore resource or VMs on a host)
+//! compete for shared memory bandwidth and last-level cache. An interference model computes the slowdown
+//! of each workload based on the memory profiles of all co-located workloads.
+
+pub mod bandwidth;
+pub mod model;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,44 @@
+//! Interference model trait.
+
+use dyn_clone::{clone_trait_object, DynClone};
+use serde::Serialize;
+
+/// Describes the memory intensity of a workload.
+///
+/// The default profile describes a workload which doesn't use memory bandwidth and cache,
+/// and therefore is not affected by interference.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct MemoryProfile {
+    /// Memory bandwidth consumed per used core when running without interference (e.g. in GB/s).
+    pub bandwidth: f64,
+    /// Last-level cache footprint of a workload (e.g. in MB).
+    pub cache: f64,
+    /// Fraction of the workload execution time which is bound by memory accesses, from 0 to 1.
+    /// Only this part of execution is slowed down by interference.
+    pub sensitivity: f64,
+}
+
+impl MemoryProfile {
+    /// Creates memory profile with the given bandwidth per core, cache footprint and sensitivity.
+    pub fn new(bandwidth: f64, cache: f64, sensitivity: f64) -> Self {
+        assert!(
+            (0. ..=1.).contains(&sensitivity),
+            "Sensitivity must be in range [0, 1], got {}",
+            sensitivity
+        );
+        Self {
+            bandwidth,
+            cache,
+            sensitivity,
+        }
+    }
+}
+
+/// A model for computing the slowdown of co-located workloads due to contention for shared memory resources.
+pub trait InterferenceModel: DynClone {
+    /// Returns the speed factor (from 0 to 1) of each workload given the memory profiles of all co-located workloads
+    /// and the number of cores used by each of them (can be fractional, e.g. for CPU shares).
+    fn speed_factors(&self, workloads: &[(MemoryProfile, f64)]) -> Vec<f64>;
+}
+
+clone_trait_object!(InterferenceModel);
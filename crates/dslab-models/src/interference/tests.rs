@@ -0,0 +1,42 @@
+use approx::assert_abs_diff_eq;
+
+use crate::interference::bandwidth::BandwidthInterferenceModel;
+use crate::interference::model::{InterferenceModel, MemoryProfile};
+
+#[test]
+fn test_bandwidth_contention() {
+    let model = BandwidthInterferenceModel::new(20.);
+    let memory_bound = MemoryProfile::new(5., 0., 0.5);
+    let compute_bound = MemoryProfile::default();
+
+    // the total traffic fits into the bandwidth
+    let factors = model.speed_factors(&[(memory_bound, 2.), (compute_bound, 4.)]);
+    assert_abs_diff_eq!(factors[0], 1., epsilon = 1e-12);
+    assert_abs_diff_eq!(factors[1], 1., epsilon = 1e-12);
+
+    // the total traffic is twice the bandwidth, only the memory-bound part is slowed down
+    let factors = model.speed_factors(&[(memory_bound, 4.), (memory_bound, 4.), (compute_bound, 4.)]);
+    assert_abs_diff_eq!(factors[0], 1. / 1.5, epsilon = 1e-12);
+    assert_abs_diff_eq!(factors[1], 1. / 1.5, epsilon = 1e-12);
+    assert_abs_diff_eq!(factors[2], 1., epsilon = 1e-12);
+}
+
+#[test]
+fn test_cache_contention() {
+    let model = BandwidthInterferenceModel::new(100.).with_cache(30., 2.);
+    let profile = MemoryProfile::new(10., 20., 1.);
+
+    let factors = model.speed_factors(&[(profile, 1.)]);
+    assert_abs_diff_eq!(factors[0], 1., epsilon = 1e-12);
+
+    // half of the total footprint doesn't fit into the cache, which doubles the memory traffic
+    let factors = model.speed_factors(&[(profile, 1.), (profile, 2.), (MemoryProfile::new(1., 0., 1.), 1.)]);
+    assert_abs_diff_eq!(factors[0], 1. / (1. + 2. * 0.25), epsilon = 1e-12);
+    assert_abs_diff_eq!(factors[1], 1. / (1. + 2. * 0.25), epsilon = 1e-12);
+    assert_abs_diff_eq!(factors[2], 1., epsilon = 1e-12);
+
+    // the increased traffic also saturates the bandwidth
+    let factors = model.speed_factors(&[(profile, 4.), (profile, 4.)]);
+    let contention = 8. * 10. * 1.5 / 100.;
+    assert_abs_diff_eq!(factors[0], 1. / (1.5 * contention), epsilon = 1e-12);
+}
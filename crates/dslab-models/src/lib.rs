@@ -1,5 +1,9 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+pub mod carbon;
+pub mod failure;
+pub mod interference;
 pub mod power;
+pub mod thermal;
 pub mod throughput_sharing;
@@ -0,0 +1,335 @@
+//! Fitting of CPU power models to power measurements.
+
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::power::cpu::CpuPowerModel;
+use crate::power::cpu_models::asymptotic::AsymptoticCpuPowerModel;
+use crate::power::cpu_models::cubic::CubicCpuPowerModel;
+use crate::power::cpu_models::empirical::EmpiricalCpuPowerModel;
+use crate::power::cpu_models::linear::LinearCpuPowerModel;
+use crate::power::cpu_models::mse::MseCpuPowerModel;
+use crate::power::cpu_models::square::SquareCpuPowerModel;
+
+/// Range of `tau` parameter values searched when fitting [`AsymptoticCpuPowerModel`].
+const TAU_RANGE: (f64, f64) = (0.01, 2.);
+/// Range of `r` parameter values searched when fitting [`MseCpuPowerModel`].
+const R_PARAM_RANGE: (f64, f64) = (1., 5.);
+/// Number of grid points used to locate the optimal shape parameter before its refinement.
+const GRID_SIZE: usize = 200;
+/// Number of golden-section search iterations used to refine the shape parameter.
+const REFINE_ITERATIONS: usize = 100;
+
+#[derive(Deserialize)]
+struct MeasurementRecord {
+    utilization: f64,
+    power: f64,
+}
+
+/// Power consumption measurements at different CPU utilization levels.
+#[derive(Clone, Debug)]
+pub struct CpuPowerMeasurements {
+    points: Vec<(f64, f64)>,
+}
+
+impl CpuPowerMeasurements {
+    /// Creates measurements from the given `(utilization, power)` points.
+    ///
+    /// The utilization is passed as a float in `[0, 1]` range, the power is passed in Watts.
+    pub fn new(mut points: Vec<(f64, f64)>) -> Self {
+        assert!(!points.is_empty(), "At least one measurement must be specified");
+        for (utilization, power) in points.iter() {
+            assert!(
+                (0. ..=1.).contains(utilization),
+                "Utilization must be in [0, 1] range, got {}",
+                utilization
+            );
+            assert!(*power >= 0., "Power must be non-negative, got {}", power);
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { points }
+    }
+
+    /// Loads measurements from CSV file with `utilization` and `power` columns.
+    ///
+    /// The utilization levels can be arbitrary values in `[0, 1]` range, the power is specified in Watts.
+    pub fn from_csv<P: AsRef<Path>>(file: P) -> Self {
+        let mut reader =
+            csv::Reader::from_path(&file).unwrap_or_else(|_| panic!("Can't read file {}", file.as_ref().display()));
+        let points = reader
+            .deserialize()
+            .map(|record| {
+                let record: MeasurementRecord =
+                    record.unwrap_or_else(|e| panic!("Can't parse power measurement record: {}", e));
+                (record.utilization, record.power)
+            })
+            .collect();
+        Self::new(points)
+    }
+
+    /// Loads measurements from [SPECpower_ssj2008](https://www.spec.org/power_ssj2008/results/) result file
+    /// in text format.
+    pub fn from_specpower<P: AsRef<Path>>(file: P) -> Self {
+        let text =
+            std::fs::read_to_string(&file).unwrap_or_else(|_| panic!("Can't read file {}", file.as_ref().display()));
+        Self::parse_specpower(&text)
+    }
+
+    /// Parses measurements from the contents of SPECpower_ssj2008 result file in text format.
+    ///
+    /// The measurements are read from the benchmark results summary table, which contains a row for each
+    /// target load level and the final row for the active idle state:
+    ///
+    /// ```text
+    ///   Target    Actual               Average       Performance
+    ///    Load      Load     ssj_ops   Active Power   to Power Ratio
+    ///    100%     99.6%   1,006,456        222          4,534
+    ///    ...
+    ///     10%     10.0%     101,112       98.0          1,032
+    ///   Active Idle               0       58.4              0
+    /// ```
+    ///
+    /// The actual load is used as the utilization level. Only the first table is read, so for multi-node results
+    /// the measurements correspond to the whole system.
+    pub fn parse_specpower(text: &str) -> Self {
+        let mut points = Vec::new();
+        for line in text.lines() {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            if tokens.len() >= 4 && tokens[0] == "Active" && tokens[1] == "Idle" {
+                points.push((0., parse_specpower_number(tokens[3])));
+                break;
+            }
+            if tokens.len() >= 4 && tokens[0].ends_with('%') && tokens[1].ends_with('%') {
+                let (Some(_), Some(load)) = (parse_percent(tokens[0]), parse_percent(tokens[1])) else {
+                    continue;
+                };
+                points.push((load.min(1.), parse_specpower_number(tokens[3])));
+            }
+        }
+        assert!(!points.is_empty(), "No power measurements found in SPECpower result");
+        Self::new(points)
+    }
+
+    /// Returns the measured `(utilization, power)` points sorted by utilization.
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// Creates [`EmpiricalCpuPowerModel`] using the power values at utilization levels from 0% to 100%
+    /// with 10% step, which are obtained by linear interpolation between the measurements.
+    pub fn to_empirical_model(&self) -> EmpiricalCpuPowerModel {
+        EmpiricalCpuPowerModel::new((0..=10).map(|i| self.interpolate(i as f64 / 10.)).collect())
+    }
+
+    /// Fits the parameters of the model of given type to the measurements using the least squares method.
+    pub fn fit(&self, model_type: CpuPowerModelType) -> FittedCpuPowerModel {
+        let fit = match model_type {
+            CpuPowerModelType::Linear => self.fit_shape(|u| u),
+            CpuPowerModelType::Square => self.fit_shape(|u| u.powi(2)),
+            CpuPowerModelType::Cubic => self.fit_shape(|u| u.powi(3)),
+            CpuPowerModelType::Asymptotic => self.fit_shape_param(TAU_RANGE, |u, tau| (1. + u - (-u / tau).exp()) / 2.),
+            CpuPowerModelType::Mse => self.fit_shape_param(R_PARAM_RANGE, |u, r| 2. * u - u.powf(r)),
+        };
+        let mut result = FittedCpuPowerModel {
+            model_type,
+            min_power: fit.offset,
+            max_power: fit.offset + fit.factor,
+            param: fit.param,
+            rmse: 0.,
+            max_error: 0.,
+        };
+        let model = result.model();
+        let errors: Vec<f64> = self
+            .points
+            .iter()
+            .map(|(utilization, power)| (model.get_power(*utilization, None, None) - power).abs())
+            .collect();
+        result.rmse = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+        result.max_error = errors.iter().cloned().fold(0., f64::max);
+        result
+    }
+
+    /// Fits all supported models to the measurements and returns them sorted by the fit error (RMSE).
+    pub fn fit_all(&self) -> Vec<FittedCpuPowerModel> {
+        let mut models: Vec<FittedCpuPowerModel> = CpuPowerModelType::ALL
+            .iter()
+            .map(|model_type| self.fit(*model_type))
+            .collect();
+        models.sort_by(|a, b| a.rmse.total_cmp(&b.rmse));
+        models
+    }
+
+    /// Fits all supported models to the measurements and returns the model with the lowest fit error (RMSE).
+    pub fn fit_best(&self) -> FittedCpuPowerModel {
+        self.fit_all().remove(0)
+    }
+
+    fn interpolate(&self, utilization: f64) -> f64 {
+        let pos = self.points.partition_point(|(u, _)| *u < utilization);
+        if pos == 0 {
+            return self.points[0].1;
+        }
+        if pos == self.points.len() {
+            return self.points[pos - 1].1;
+        }
+        let (u1, p1) = self.points[pos - 1];
+        let (u2, p2) = self.points[pos];
+        p1 + (p2 - p1) * (utilization - u1) / (u2 - u1)
+    }
+
+    /// Fits the model `P(u) = offset + factor * shape(u)` by linear least squares.
+    fn fit_shape<F: Fn(f64) -> f64>(&self, shape: F) -> ShapeFit {
+        let n = self.points.len() as f64;
+        let xs: Vec<f64> = self.points.iter().map(|(u, _)| shape(*u)).collect();
+        let mean_x = xs.iter().sum::<f64>() / n;
+        let mean_y = self.points.iter().map(|(_, p)| p).sum::<f64>() / n;
+        let mut cov = 0.;
+        let mut var = 0.;
+        for (x, (_, y)) in xs.iter().zip(self.points.iter()) {
+            cov += (x - mean_x) * (y - mean_y);
+            var += (x - mean_x).powi(2);
+        }
+        assert!(
+            var > 0.,
+            "At least two distinct utilization levels are required to fit power model"
+        );
+        let factor = cov / var;
+        let offset = mean_y - factor * mean_x;
+        let sse = xs
+            .iter()
+            .zip(self.points.iter())
+            .map(|(x, (_, y))| (offset + factor * x - y).powi(2))
+            .sum();
+        ShapeFit {
+            offset,
+            factor,
+            param: None,
+            sse,
+        }
+    }
+
+    /// Fits the model `P(u) = offset + factor * shape(u, param)` by searching for the parameter value
+    /// in the given range, which minimizes the error of linear least squares fit.
+    fn fit_shape_param<F: Fn(f64, f64) -> f64>(&self, range: (f64, f64), shape: F) -> ShapeFit {
+        let fit_with = |param: f64| ShapeFit {
+            param: Some(param),
+            ..self.fit_shape(|u| shape(u, param))
+        };
+        // coarse grid search followed by golden-section search around the best grid point
+        let step = (range.1 - range.0) / GRID_SIZE as f64;
+        let best_idx = (0..=GRID_SIZE)
+            .map(|i| (i, fit_with(range.0 + step * i as f64).sse))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap()
+            .0;
+        let mut lo = range.0 + step * best_idx.saturating_sub(1) as f64;
+        let mut hi = (range.0 + step * (best_idx + 1) as f64).min(range.1);
+        let ratio = (5f64.sqrt() - 1.) / 2.;
+        for _ in 0..REFINE_ITERATIONS {
+            let x1 = hi - ratio * (hi - lo);
+            let x2 = lo + ratio * (hi - lo);
+            if fit_with(x1).sse <= fit_with(x2).sse {
+                hi = x2;
+            } else {
+                lo = x1;
+            }
+        }
+        fit_with((lo + hi) / 2.)
+    }
+}
+
+struct ShapeFit {
+    offset: f64,
+    factor: f64,
+    param: Option<f64>,
+    sse: f64,
+}
+
+fn parse_percent(token: &str) -> Option<f64> {
+    token.strip_suffix('%')?.parse::<f64>().ok().map(|value| value / 100.)
+}
+
+fn parse_specpower_number(token: &str) -> f64 {
+    token
+        .replace(',', "")
+        .parse()
+        .unwrap_or_else(|_| panic!("Can't parse power value {} in SPECpower result", token))
+}
+
+/// Types of CPU power models supported by fitting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CpuPowerModelType {
+    /// [`LinearCpuPowerModel`]
+    Linear,
+    /// [`SquareCpuPowerModel`]
+    Square,
+    /// [`CubicCpuPowerModel`]
+    Cubic,
+    /// [`AsymptoticCpuPowerModel`]
+    Asymptotic,
+    /// [`MseCpuPowerModel`]
+    Mse,
+}
+
+impl CpuPowerModelType {
+    /// All supported model types.
+    pub const ALL: [CpuPowerModelType; 5] = [
+        CpuPowerModelType::Linear,
+        CpuPowerModelType::Square,
+        CpuPowerModelType::Cubic,
+        CpuPowerModelType::Asymptotic,
+        CpuPowerModelType::Mse,
+    ];
+}
+
+impl Display for CpuPowerModelType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CpuPowerModelType::Linear => write!(f, "linear"),
+            CpuPowerModelType::Square => write!(f, "square"),
+            CpuPowerModelType::Cubic => write!(f, "cubic"),
+            CpuPowerModelType::Asymptotic => write!(f, "asymptotic"),
+            CpuPowerModelType::Mse => write!(f, "mse"),
+        }
+    }
+}
+
+/// CPU power model fitted to the measurements.
+#[derive(Clone, Debug)]
+pub struct FittedCpuPowerModel {
+    /// Model type.
+    pub model_type: CpuPowerModelType,
+    /// Fitted minimum power consumption in Watts.
+    pub min_power: f64,
+    /// Fitted maximum power consumption in Watts.
+    pub max_power: f64,
+    /// Fitted shape parameter: `tau` for asymptotic model, `r` for MSE model and `None` for other models.
+    pub param: Option<f64>,
+    /// Root mean squared error of the model on the measurements in Watts.
+    pub rmse: f64,
+    /// Maximum absolute error of the model on the measurements in Watts.
+    pub max_error: f64,
+}
+
+impl FittedCpuPowerModel {
+    /// Creates the power model with the fitted parameters.
+    pub fn model(&self) -> Box<dyn CpuPowerModel> {
+        match self.model_type {
+            CpuPowerModelType::Linear => Box::new(LinearCpuPowerModel::new(self.min_power, self.max_power)),
+            CpuPowerModelType::Square => Box::new(SquareCpuPowerModel::new(self.min_power, self.max_power)),
+            CpuPowerModelType::Cubic => Box::new(CubicCpuPowerModel::new(self.min_power, self.max_power)),
+            CpuPowerModelType::Asymptotic => Box::new(AsymptoticCpuPowerModel::new(
+                self.min_power,
+                self.max_power,
+                self.param.unwrap(),
+            )),
+            CpuPowerModelType::Mse => Box::new(MseCpuPowerModel::new(
+                self.min_power,
+                self.max_power,
+                self.param.unwrap(),
+            )),
+        }
+    }
+}
@@ -3,6 +3,7 @@
 use crate::power::cpu::CpuPowerModel;
 use crate::power::hdd::{HddPowerModel, HddState};
 use crate::power::memory::MemoryPowerModel;
+use crate::power::ssd::{SsdPowerModel, SsdState};
 
 /// Stores host state properties essential to compute the current host power consumption.
 ///
@@ -26,6 +27,8 @@ pub struct HostState {
     pub memory_write_util: Option<f64>,
     /// Hard disk drive state.
     pub hdd_state: Option<HddState>,
+    /// Solid-state drive state.
+    pub ssd_state: Option<SsdState>,
 }
 
 impl HostState {
@@ -62,6 +65,7 @@ impl HostState {
 /// - CPU power consumption estimated using the provided CPU power model and idle power value
 /// - memory power consumption estimated using the provided memory power model
 /// - hard disk drive power consumption estimated using the provided HDD power model
+/// - solid-state drive power consumption estimated using the provided SSD power model
 /// - consumption of other host components modeled as a fixed value
 #[derive(Clone, Default)]
 pub struct HostPowerModel {
@@ -69,6 +73,7 @@ pub struct HostPowerModel {
     cpu_idle_power: Option<f64>,
     memory_power_model: Option<Box<dyn MemoryPowerModel>>,
     hard_drive_power_model: Option<Box<dyn HddPowerModel>>,
+    ssd_power_model: Option<Box<dyn SsdPowerModel>>,
     other_power: f64,
 }
 
@@ -96,6 +101,11 @@ impl HostPowerModel {
                 result += model.get_power(hdd_state);
             }
         }
+        if let Some(model) = &self.ssd_power_model {
+            if let Some(ssd_state) = host_state.ssd_state {
+                result += model.get_power(ssd_state);
+            }
+        }
         result += self.other_power;
         result
     }
@@ -108,6 +118,7 @@ pub struct HostPowerModelBuilder {
     cpu_idle_power: Option<f64>,
     memory_power_model: Option<Box<dyn MemoryPowerModel>>,
     hard_drive_power_model: Option<Box<dyn HddPowerModel>>,
+    ssd_power_model: Option<Box<dyn SsdPowerModel>>,
     other_power: f64,
 }
 
@@ -142,6 +153,12 @@ impl HostPowerModelBuilder {
         self
     }
 
+    /// Sets the SSD power model.
+    pub fn ssd(mut self, model: Box<dyn SsdPowerModel>) -> Self {
+        self.ssd_power_model = Some(model);
+        self
+    }
+
     /// Sets the power consumption of other host components.
     pub fn other(mut self, value: f64) -> Self {
         self.other_power = value;
@@ -155,6 +172,7 @@ impl HostPowerModelBuilder {
             cpu_idle_power: self.cpu_idle_power,
             memory_power_model: self.memory_power_model,
             hard_drive_power_model: self.hard_drive_power_model,
+            ssd_power_model: self.ssd_power_model,
             other_power: self.other_power,
         }
     }
@@ -1,10 +1,15 @@
 //! Power consumption models.
 
 pub mod cpu;
+pub mod cpu_fitting;
 pub mod cpu_models;
 pub mod hdd;
 pub mod hdd_models;
 pub mod host;
 pub mod memory;
 pub mod memory_models;
+pub mod network;
+pub mod network_models;
+pub mod ssd;
+pub mod ssd_models;
 mod tests;
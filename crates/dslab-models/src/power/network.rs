@@ -0,0 +1,27 @@
+//! Network device power model traits.
+
+use dyn_clone::{clone_trait_object, DynClone};
+
+/// A model for estimating the power consumption of network switch (or router) based on the utilization of its ports.
+///
+/// Only the active ports, i.e. the ports connected to the enabled links, are taken into account.
+/// See [Mahadevan P. et al. A power benchmarking framework for network devices (NETWORKING 2009)](https://doi.org/10.1007/978-3-642-01399-7_62).
+pub trait SwitchPowerModel: DynClone {
+    /// Returns the switch power consumption in Watts.
+    ///
+    /// The utilization of each active port should be passed as a float in 0.0-1.0 range.
+    fn get_power(&self, port_utilizations: &[f64]) -> f64;
+}
+
+clone_trait_object!(SwitchPowerModel);
+
+/// A model for estimating the power consumption of network link (e.g. transceivers at its ends) based on its
+/// utilization.
+pub trait LinkPowerModel: DynClone {
+    /// Returns the link power consumption in Watts.
+    ///
+    /// Link utilization should be passed as a float in 0.0-1.0 range.
+    fn get_power(&self, utilization: f64) -> f64;
+}
+
+clone_trait_object!(LinkPowerModel);
@@ -0,0 +1,45 @@
+//! Constant network device power models.
+
+use crate::power::network::{LinkPowerModel, SwitchPowerModel};
+
+/// A switch power model using a constant power consumption value independent of the ports state.
+#[derive(Clone)]
+pub struct ConstantSwitchPowerModel {
+    power: f64,
+}
+
+impl ConstantSwitchPowerModel {
+    /// Creates a constant power model.
+    ///
+    /// * `power` - The power consumption in Watts.
+    pub fn new(power: f64) -> Self {
+        Self { power }
+    }
+}
+
+impl SwitchPowerModel for ConstantSwitchPowerModel {
+    fn get_power(&self, _port_utilizations: &[f64]) -> f64 {
+        self.power
+    }
+}
+
+/// A link power model using a constant power consumption value independent of the link utilization.
+#[derive(Clone)]
+pub struct ConstantLinkPowerModel {
+    power: f64,
+}
+
+impl ConstantLinkPowerModel {
+    /// Creates a constant power model.
+    ///
+    /// * `power` - The power consumption in Watts.
+    pub fn new(power: f64) -> Self {
+        Self { power }
+    }
+}
+
+impl LinkPowerModel for ConstantLinkPowerModel {
+    fn get_power(&self, _utilization: f64) -> f64 {
+        self.power
+    }
+}
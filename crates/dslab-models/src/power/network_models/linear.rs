@@ -0,0 +1,66 @@
+//! Linear network device power models.
+
+use crate::power::network::{LinkPowerModel, SwitchPowerModel};
+
+/// A switch power model consisting of the static chassis power, the power of each active port
+/// and the rate-dependent power growing linearly with the port utilization.
+///
+/// See [Mahadevan P. et al. A power benchmarking framework for network devices (NETWORKING 2009)](https://doi.org/10.1007/978-3-642-01399-7_62).
+#[derive(Clone)]
+pub struct LinearSwitchPowerModel {
+    chassis_power: f64,
+    port_power: f64,
+    port_dynamic_power: f64,
+}
+
+impl LinearSwitchPowerModel {
+    /// Creates a linear switch power model.
+    ///
+    /// * `chassis_power` - The static power consumption of the switch chassis in Watts.
+    /// * `port_power` - The power consumption of each active port in Watts (at 0% utilization).
+    /// * `port_dynamic_power` - The additional power consumption of the port in Watts at 100% utilization.
+    pub fn new(chassis_power: f64, port_power: f64, port_dynamic_power: f64) -> Self {
+        Self {
+            chassis_power,
+            port_power,
+            port_dynamic_power,
+        }
+    }
+}
+
+impl SwitchPowerModel for LinearSwitchPowerModel {
+    fn get_power(&self, port_utilizations: &[f64]) -> f64 {
+        self.chassis_power
+            + port_utilizations
+                .iter()
+                .map(|utilization| self.port_power + self.port_dynamic_power * utilization)
+                .sum::<f64>()
+    }
+}
+
+/// A link power model based on linear interpolation between the idle and maximum power consumption values.
+#[derive(Clone)]
+pub struct LinearLinkPowerModel {
+    idle_power: f64,
+    factor: f64,
+}
+
+impl LinearLinkPowerModel {
+    /// Creates a linear link power model.
+    ///
+    /// * `idle_power` - The power consumption in Watts at 0% utilization.
+    /// * `max_power` - The power consumption in Watts at 100% utilization.
+    pub fn new(idle_power: f64, max_power: f64) -> Self {
+        assert!(max_power >= idle_power, "max_power should not be less than idle_power");
+        Self {
+            idle_power,
+            factor: max_power - idle_power,
+        }
+    }
+}
+
+impl LinkPowerModel for LinearLinkPowerModel {
+    fn get_power(&self, utilization: f64) -> f64 {
+        self.idle_power + self.factor * utilization
+    }
+}
@@ -0,0 +1,4 @@
+//! A collection of network device power models.
+
+pub mod constant;
+pub mod linear;
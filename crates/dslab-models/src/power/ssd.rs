@@ -0,0 +1,44 @@
+//! Solid-state drive power model trait.
+
+use dyn_clone::{clone_trait_object, DynClone};
+
+/// Power states of solid-state drive (SSD).
+///
+/// The states follow the NVMe power management model, where the drive has operational power states
+/// serving I/O requests and non-operational low-power states (e.g. PS3 and PS4) with entry and exit latencies.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SsdState {
+    /// The drive serves I/O requests with the given read and write throughput.
+    Active {
+        /// Current read throughput.
+        read_throughput: f64,
+        /// Current write throughput.
+        write_throughput: f64,
+    },
+    /// The drive is in operational power state but does not serve I/O requests.
+    Idle,
+    /// The drive is in non-operational low-power state with the given index (e.g. 0 for PS3 and 1 for PS4).
+    LowPower(usize),
+}
+
+/// Parameters of SSD non-operational low-power state.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SsdLowPowerState {
+    /// Power consumption in the state in Watts.
+    pub power: f64,
+    /// Time needed to enter the state in seconds.
+    pub entry_latency: f64,
+    /// Time needed to return from the state to the operational state in seconds.
+    pub exit_latency: f64,
+}
+
+/// A model for estimating the power consumption of solid-state drive (SSD) based on its state.
+pub trait SsdPowerModel: DynClone {
+    /// Returns the drive power consumption in Watts.
+    fn get_power(&self, state: SsdState) -> f64;
+
+    /// Returns the parameters of low-power state with the given index or `None` if there is no such state.
+    fn low_power_state(&self, index: usize) -> Option<SsdLowPowerState>;
+}
+
+clone_trait_object!(SsdPowerModel);
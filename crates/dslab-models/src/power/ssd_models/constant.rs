@@ -0,0 +1,30 @@
+//! Constant SSD power model.
+
+use crate::power::ssd::{SsdLowPowerState, SsdPowerModel, SsdState};
+
+/// A power model using a constant power consumption value independent of the drive state.
+///
+/// The model does not support low-power states.
+#[derive(Clone)]
+pub struct ConstantSsdPowerModel {
+    power: f64,
+}
+
+impl ConstantSsdPowerModel {
+    /// Creates a constant power model.
+    ///
+    /// * `power` - The power consumption in Watts.
+    pub fn new(power: f64) -> Self {
+        Self { power }
+    }
+}
+
+impl SsdPowerModel for ConstantSsdPowerModel {
+    fn get_power(&self, _state: SsdState) -> f64 {
+        self.power
+    }
+
+    fn low_power_state(&self, _index: usize) -> Option<SsdLowPowerState> {
+        None
+    }
+}
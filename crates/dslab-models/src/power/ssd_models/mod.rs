@@ -0,0 +1,4 @@
+//! A collection of SSD power models.
+
+pub mod constant;
+pub mod throughput;
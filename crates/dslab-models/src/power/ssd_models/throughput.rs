@@ -0,0 +1,94 @@
+//! Throughput-based SSD power model.
+
+use crate::power::ssd::{SsdLowPowerState, SsdPowerModel, SsdState};
+
+/// A power model where the power consumption of active drive grows linearly with its read and write throughput.
+///
+/// The power consumption of active drive is computed as
+/// `P = P_idle + (P_read - P_idle) * min(r / r_max, 1) + (P_write - P_idle) * min(w / w_max, 1)`,
+/// where `r` and `w` are the current read and write throughput, `r_max` and `w_max` are the maximum read
+/// and write throughput, and `P_read` and `P_write` are the power consumption values at the maximum read
+/// and write throughput respectively.
+///
+/// The model also supports non-operational low-power states (e.g. PS3 and PS4 in NVMe drives),
+/// which are indexed in the order of their addition.
+#[derive(Clone)]
+pub struct ThroughputSsdPowerModel {
+    idle_power: f64,
+    read_power: f64,
+    max_read_throughput: f64,
+    write_power: f64,
+    max_write_throughput: f64,
+    low_power_states: Vec<SsdLowPowerState>,
+}
+
+impl ThroughputSsdPowerModel {
+    /// Creates a throughput-based power model.
+    ///
+    /// * `idle_power` - The power consumption in Watts when the drive does not serve I/O requests.
+    /// * `read_power` - The power consumption in Watts at the maximum read throughput.
+    /// * `max_read_throughput` - The maximum read throughput.
+    /// * `write_power` - The power consumption in Watts at the maximum write throughput.
+    /// * `max_write_throughput` - The maximum write throughput.
+    pub fn new(
+        idle_power: f64,
+        read_power: f64,
+        max_read_throughput: f64,
+        write_power: f64,
+        max_write_throughput: f64,
+    ) -> Self {
+        assert!(
+            max_read_throughput > 0. && max_write_throughput > 0.,
+            "Maximum read and write throughput must be positive"
+        );
+        Self {
+            idle_power,
+            read_power,
+            max_read_throughput,
+            write_power,
+            max_write_throughput,
+            low_power_states: Vec::new(),
+        }
+    }
+
+    /// Adds non-operational low-power state with the given power consumption in Watts
+    /// and entry and exit latencies in seconds.
+    pub fn with_low_power_state(mut self, power: f64, entry_latency: f64, exit_latency: f64) -> Self {
+        assert!(
+            entry_latency >= 0. && exit_latency >= 0.,
+            "Entry and exit latencies must be non-negative"
+        );
+        self.low_power_states.push(SsdLowPowerState {
+            power,
+            entry_latency,
+            exit_latency,
+        });
+        self
+    }
+}
+
+impl SsdPowerModel for ThroughputSsdPowerModel {
+    fn get_power(&self, state: SsdState) -> f64 {
+        match state {
+            SsdState::Active {
+                read_throughput,
+                write_throughput,
+            } => {
+                self.idle_power
+                    + (self.read_power - self.idle_power) * (read_throughput / self.max_read_throughput).min(1.)
+                    + (self.write_power - self.idle_power) * (write_throughput / self.max_write_throughput).min(1.)
+            }
+            SsdState::Idle => self.idle_power,
+            SsdState::LowPower(index) => {
+                self.low_power_states
+                    .get(index)
+                    .unwrap_or_else(|| panic!("Unknown SSD low-power state {}", index))
+                    .power
+            }
+        }
+    }
+
+    fn low_power_state(&self, index: usize) -> Option<SsdLowPowerState> {
+        self.low_power_states.get(index).copied()
+    }
+}
@@ -3,8 +3,17 @@
 #[cfg(test)]
 mod test_cpu;
 
+#[cfg(test)]
+mod test_fitting;
+
 #[cfg(test)]
 mod test_hdd;
 
 #[cfg(test)]
 mod test_memory;
+
+#[cfg(test)]
+mod test_network;
+
+#[cfg(test)]
+mod test_ssd;
@@ -0,0 +1,171 @@
+//! Tests for fitting of CPU power models.
+
+use std::io::Write;
+
+use approx::assert_abs_diff_eq;
+
+use crate::power::cpu::CpuPowerModel;
+use crate::power::cpu_fitting::{CpuPowerMeasurements, CpuPowerModelType};
+use crate::power::cpu_models::asymptotic::AsymptoticCpuPowerModel;
+use crate::power::cpu_models::cubic::CubicCpuPowerModel;
+use crate::power::cpu_models::linear::LinearCpuPowerModel;
+use crate::power::cpu_models::mse::MseCpuPowerModel;
+use crate::power::cpu_models::square::SquareCpuPowerModel;
+
+const SPECPOWER_RESULT: &str = "
+                                 SPECpower_ssj2008
+                Copyright 2007-2011 Standard Performance Evaluation Corporation
+
+IBM Corporation System x3550 M3                     ssj_ops/watt = 2,927
+
+====================================================================================================
+Benchmark Results Summary
+====================================================================================================
+                  Performance                 Power                Performance to
+ Target Load   Actual Load      ssj_ops     Average Active Power (W)     Power Ratio
+====================================================================================================
+    100%          99.7%        1,204,106             222                5,424
+     90%          90.0%        1,086,857             205                5,302
+     80%          80.0%          966,206             189                5,112
+     70%          70.1%          845,216             170                4,972
+     60%          60.0%          723,942             153                4,732
+     50%          50.0%          603,488             140                4,311
+     40%          40.0%          482,770             128                3,772
+     30%          30.0%          361,790             118                3,066
+     20%          20.0%          241,561             109                2,216
+     10%          10.0%          120,712            98.0                1,232
+ Active Idle                             0            58.4                    0
+====================================================================================================
+                                                    ∑ssj_ops / ∑power =     2,927
+
+Aggregate Performance Data
+    100%          99.7%        1,204,106             221                5,448
+";
+
+fn sample(model: &dyn CpuPowerModel) -> CpuPowerMeasurements {
+    CpuPowerMeasurements::new(
+        (0..=20)
+            .map(|i| {
+                let utilization = i as f64 / 20.;
+                (utilization, model.get_power(utilization, None, None))
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn test_fit_exact_models() {
+    let models: Vec<(CpuPowerModelType, Box<dyn CpuPowerModel>, Option<f64>)> = vec![
+        (
+            CpuPowerModelType::Linear,
+            Box::new(LinearCpuPowerModel::new(60., 220.)),
+            None,
+        ),
+        (
+            CpuPowerModelType::Square,
+            Box::new(SquareCpuPowerModel::new(60., 220.)),
+            None,
+        ),
+        (
+            CpuPowerModelType::Cubic,
+            Box::new(CubicCpuPowerModel::new(60., 220.)),
+            None,
+        ),
+        (
+            CpuPowerModelType::Asymptotic,
+            Box::new(AsymptoticCpuPowerModel::new(60., 220., 0.5)),
+            Some(0.5),
+        ),
+        (
+            CpuPowerModelType::Mse,
+            Box::new(MseCpuPowerModel::new(60., 220., 1.4)),
+            Some(1.4),
+        ),
+    ];
+    for (model_type, model, param) in models {
+        let measurements = sample(model.as_ref());
+        let fitted = measurements.fit(model_type);
+        assert_eq!(fitted.model_type, model_type);
+        assert_abs_diff_eq!(fitted.min_power, 60., epsilon = 1e-4);
+        assert_abs_diff_eq!(fitted.max_power, 220., epsilon = 1e-4);
+        if let Some(param) = param {
+            assert_abs_diff_eq!(fitted.param.unwrap(), param, epsilon = 1e-4);
+        } else {
+            assert!(fitted.param.is_none());
+        }
+        assert_abs_diff_eq!(fitted.rmse, 0., epsilon = 1e-4);
+        assert_abs_diff_eq!(fitted.max_error, 0., epsilon = 1e-4);
+
+        let best = measurements.fit_best();
+        assert_eq!(best.model_type, model_type);
+    }
+}
+
+#[test]
+fn test_fit_errors() {
+    let measurements = CpuPowerMeasurements::new(vec![(0., 100.), (0.5, 165.), (1., 200.)]);
+    let fitted = measurements.fit(CpuPowerModelType::Linear);
+    // least squares line through these points is 105 + 100u with errors 5, -10 and 5
+    assert_abs_diff_eq!(fitted.min_power, 105., epsilon = 1e-9);
+    assert_abs_diff_eq!(fitted.max_power, 205., epsilon = 1e-9);
+    assert_abs_diff_eq!(fitted.rmse, 50f64.sqrt(), epsilon = 1e-9);
+    assert_abs_diff_eq!(fitted.max_error, 10., epsilon = 1e-9);
+    assert_abs_diff_eq!(fitted.model().get_power(0.2, None, None), 125., epsilon = 1e-9);
+
+    let all = measurements.fit_all();
+    assert_eq!(all.len(), 5);
+    for i in 1..all.len() {
+        assert!(all[i - 1].rmse <= all[i].rmse);
+    }
+    // concave measurements are fitted better by non-linear models
+    assert!(all[0].rmse < fitted.rmse);
+}
+
+#[test]
+fn test_parse_specpower() {
+    let measurements = CpuPowerMeasurements::parse_specpower(SPECPOWER_RESULT);
+    let points = measurements.points();
+    assert_eq!(points.len(), 11);
+    assert_eq!(points[0], (0., 58.4));
+    assert_abs_diff_eq!(points[1].0, 0.1);
+    assert_eq!(points[1].1, 98.);
+    assert_abs_diff_eq!(points[10].0, 0.997);
+    assert_eq!(points[10].1, 222.);
+
+    let best = measurements.fit_best();
+    let linear = measurements.fit(CpuPowerModelType::Linear);
+    assert!(best.rmse <= linear.rmse);
+    assert!(best.rmse < 10.);
+
+    let model = measurements.to_empirical_model();
+    assert_abs_diff_eq!(model.get_power(0., None, None), 58.4);
+    assert_abs_diff_eq!(model.get_power(0.5, None, None), 140.);
+    assert_abs_diff_eq!(model.get_power(1., None, None), 222.);
+}
+
+#[test]
+fn test_load_csv() {
+    let path = std::env::temp_dir().join("dslab_models_test_power_measurements.csv");
+    let mut file = std::fs::File::create(&path).unwrap();
+    writeln!(file, "utilization,power").unwrap();
+    writeln!(file, "0.75,180").unwrap();
+    writeln!(file, "0,80").unwrap();
+    writeln!(file, "0.25,105").unwrap();
+    writeln!(file, "1,230").unwrap();
+    drop(file);
+
+    let measurements = CpuPowerMeasurements::from_csv(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        measurements.points(),
+        &[(0., 80.), (0.25, 105.), (0.75, 180.), (1., 230.)]
+    );
+
+    let model = measurements.to_empirical_model();
+    assert_abs_diff_eq!(model.get_power(0.1, None, None), 90., epsilon = 1e-9);
+    assert_abs_diff_eq!(model.get_power(0.5, None, None), 142.5, epsilon = 1e-9);
+
+    let fitted = measurements.fit(CpuPowerModelType::Linear);
+    assert_abs_diff_eq!(fitted.min_power, 73.75, epsilon = 1e-9);
+    assert_abs_diff_eq!(fitted.max_power, 223.75, epsilon = 1e-9);
+}
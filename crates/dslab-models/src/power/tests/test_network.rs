@@ -0,0 +1,32 @@
+//! Tests for network device power models.
+
+use crate::power::network::{LinkPowerModel, SwitchPowerModel};
+use crate::power::network_models::constant::{ConstantLinkPowerModel, ConstantSwitchPowerModel};
+use crate::power::network_models::linear::{LinearLinkPowerModel, LinearSwitchPowerModel};
+
+#[test]
+fn test_constant_models() {
+    let switch = ConstantSwitchPowerModel::new(150.);
+    assert_eq!(switch.get_power(&[]), 150.);
+    assert_eq!(switch.get_power(&[0.5, 1.]), 150.);
+
+    let link = ConstantLinkPowerModel::new(2.);
+    assert_eq!(link.get_power(0.), 2.);
+    assert_eq!(link.get_power(1.), 2.);
+}
+
+#[test]
+fn test_linear_switch_model() {
+    let switch = LinearSwitchPowerModel::new(100., 1., 0.5);
+    assert_eq!(switch.get_power(&[]), 100.);
+    assert_eq!(switch.get_power(&[0., 0.]), 102.);
+    assert_eq!(switch.get_power(&[0., 1., 0.5]), 103.75);
+}
+
+#[test]
+fn test_linear_link_model() {
+    let link = LinearLinkPowerModel::new(1., 3.);
+    assert_eq!(link.get_power(0.), 1.);
+    assert_eq!(link.get_power(0.25), 1.5);
+    assert_eq!(link.get_power(1.), 3.);
+}
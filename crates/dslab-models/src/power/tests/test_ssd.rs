@@ -0,0 +1,84 @@
+//! Tests for SSD power models.
+
+use approx::assert_abs_diff_eq;
+
+use crate::power::host::{HostPowerModelBuilder, HostState};
+use crate::power::ssd::{SsdLowPowerState, SsdPowerModel, SsdState};
+use crate::power::ssd_models::constant::ConstantSsdPowerModel;
+use crate::power::ssd_models::throughput::ThroughputSsdPowerModel;
+
+#[test]
+fn test_constant_model() {
+    let model = HostPowerModelBuilder::new()
+        .ssd(Box::new(ConstantSsdPowerModel::new(3.)))
+        .build();
+
+    let mut state = HostState {
+        ssd_state: Some(SsdState::Idle),
+        ..Default::default()
+    };
+    assert_eq!(model.get_power(state), 3.);
+
+    state.ssd_state = Some(SsdState::Active {
+        read_throughput: 100.,
+        write_throughput: 0.,
+    });
+    assert_eq!(model.get_power(state), 3.);
+
+    state.ssd_state = Some(SsdState::LowPower(0));
+    assert_eq!(model.get_power(state), 3.);
+}
+
+#[test]
+fn test_throughput_model() {
+    let ssd_model = ThroughputSsdPowerModel::new(2., 6., 3000., 8., 2000.)
+        .with_low_power_state(0.05, 0.001, 0.002)
+        .with_low_power_state(0.005, 0.002, 0.01);
+    let model = HostPowerModelBuilder::new().ssd(Box::new(ssd_model.clone())).build();
+
+    let mut state = HostState {
+        ssd_state: Some(SsdState::Idle),
+        ..Default::default()
+    };
+    assert_eq!(model.get_power(state), 2.);
+
+    state.ssd_state = Some(SsdState::Active {
+        read_throughput: 0.,
+        write_throughput: 0.,
+    });
+    assert_eq!(model.get_power(state), 2.);
+
+    state.ssd_state = Some(SsdState::Active {
+        read_throughput: 1500.,
+        write_throughput: 0.,
+    });
+    assert_abs_diff_eq!(model.get_power(state), 4.);
+
+    state.ssd_state = Some(SsdState::Active {
+        read_throughput: 0.,
+        write_throughput: 5000.,
+    });
+    assert_abs_diff_eq!(model.get_power(state), 8.);
+
+    state.ssd_state = Some(SsdState::Active {
+        read_throughput: 3000.,
+        write_throughput: 500.,
+    });
+    assert_abs_diff_eq!(model.get_power(state), 7.5);
+
+    state.ssd_state = Some(SsdState::LowPower(0));
+    assert_eq!(model.get_power(state), 0.05);
+
+    state.ssd_state = Some(SsdState::LowPower(1));
+    assert_eq!(model.get_power(state), 0.005);
+
+    assert_eq!(
+        ssd_model.low_power_state(1),
+        Some(SsdLowPowerState {
+            power: 0.005,
+            entry_latency: 0.002,
+            exit_latency: 0.01,
+        })
+    );
+    assert_eq!(ssd_model.low_power_state(2), None);
+}
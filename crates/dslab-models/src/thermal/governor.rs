@@ -0,0 +1,123 @@
+//! Thermal governor.
+
+use crate::thermal::model::ThermalModel;
+
+/// Tolerance used when comparing the temperature with thresholds.
+const TEMPERATURE_EPSILON: f64 = 1e-6;
+
+/// Device performance level selected by the thermal governor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PerformanceLevel {
+    /// Device speed relative to its nominal speed.
+    pub speed: f64,
+    /// Relative CPU frequency passed to the CPU power model, see [`crate::power::cpu::CpuPowerModel`].
+    pub cpu_freq: Option<f64>,
+    /// CPU power management state passed to the CPU power model, see [`crate::power::cpu::CpuPowerModel`].
+    pub cpu_state: Option<usize>,
+}
+
+impl PerformanceLevel {
+    /// Creates a performance level with the given relative speed, CPU frequency and CPU state.
+    pub fn new(speed: f64, cpu_freq: Option<f64>, cpu_state: Option<usize>) -> Self {
+        Self {
+            speed,
+            cpu_freq,
+            cpu_state,
+        }
+    }
+}
+
+/// Step-wise thermal governor, which switches the device between performance levels based on its temperature.
+///
+/// The performance levels are ordered from the fastest to the slowest one, initially the fastest level is used.
+/// When the temperature is at or above the throttle temperature, the governor switches to the next slower level.
+/// When the temperature is at or below the release temperature, the governor switches to the next faster level.
+/// While the temperature stays beyond one of these thresholds, the level is changed once per the check interval.
+pub struct ThermalGovernor {
+    levels: Vec<PerformanceLevel>,
+    throttle_temperature: f64,
+    release_temperature: f64,
+    check_interval: f64,
+    current_level: usize,
+}
+
+impl ThermalGovernor {
+    /// Creates a governor with the given performance levels and temperature thresholds.
+    ///
+    /// The check interval is set to 1 second.
+    pub fn new(levels: Vec<PerformanceLevel>, throttle_temperature: f64, release_temperature: f64) -> Self {
+        assert!(!levels.is_empty(), "At least one performance level must be specified");
+        assert!(
+            release_temperature < throttle_temperature,
+            "Release temperature must be lower than throttle temperature"
+        );
+        Self {
+            levels,
+            throttle_temperature,
+            release_temperature,
+            check_interval: 1.,
+            current_level: 0,
+        }
+    }
+
+    /// Sets the interval between successive level changes while the temperature stays beyond the threshold.
+    pub fn with_check_interval(mut self, check_interval: f64) -> Self {
+        assert!(check_interval > 0., "Check interval must be positive");
+        self.check_interval = check_interval;
+        self
+    }
+
+    /// Returns the current performance level.
+    pub fn level(&self) -> &PerformanceLevel {
+        &self.levels[self.current_level]
+    }
+
+    /// Returns the index of the current performance level.
+    pub fn level_index(&self) -> usize {
+        self.current_level
+    }
+
+    fn can_throttle(&self) -> bool {
+        self.current_level + 1 < self.levels.len()
+    }
+
+    fn can_release(&self) -> bool {
+        self.current_level > 0
+    }
+
+    /// Switches the performance level according to the given temperature,
+    /// returns true if the level is changed.
+    pub fn update(&mut self, temperature: f64) -> bool {
+        if temperature >= self.throttle_temperature - TEMPERATURE_EPSILON && self.can_throttle() {
+            self.current_level += 1;
+            true
+        } else if temperature <= self.release_temperature + TEMPERATURE_EPSILON && self.can_release() {
+            self.current_level -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the time of the next check of the device temperature if the device keeps its current power,
+    /// or `None` if no level changes are expected.
+    pub fn next_check_time(&self, model: &dyn ThermalModel, time: f64) -> Option<f64> {
+        let temperature = model.temperature(time);
+        let mut next_time: Option<f64> = None;
+        if self.can_throttle() {
+            if temperature >= self.throttle_temperature - TEMPERATURE_EPSILON {
+                return Some(time + self.check_interval);
+            }
+            next_time = model.time_to_reach(self.throttle_temperature, time);
+        }
+        if self.can_release() {
+            if temperature <= self.release_temperature + TEMPERATURE_EPSILON {
+                return Some(time + self.check_interval);
+            }
+            if let Some(release_time) = model.time_to_reach(self.release_temperature, time) {
+                next_time = Some(next_time.map_or(release_time, |t| t.min(release_time)));
+            }
+        }
+        next_time
+    }
+}
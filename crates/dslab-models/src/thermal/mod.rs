@@ -0,0 +1,11 @@
+//! Thermal models and thermal management.
+//!
+//! A thermal model computes the temperature of a device (e.g. CPU or host) based on the dissipated power.
+//! A thermal governor limits the device performance when its temperature becomes too high.
+
+pub mod governor;
+pub mod model;
+pub mod rc;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,20 @@
+//! Thermal model trait.
+
+use dyn_clone::{clone_trait_object, DynClone};
+
+/// A model for computing the temperature of a device over time based on the power dissipated by the device.
+///
+/// The power is assumed to be constant between the successive calls of [`set_power`](Self::set_power).
+pub trait ThermalModel: DynClone {
+    /// Sets the power in Watts dissipated by the device starting from the given time.
+    fn set_power(&mut self, power: f64, time: f64);
+
+    /// Returns the device temperature in °C at the given time, which must not be earlier than the last power change.
+    fn temperature(&self, time: f64) -> f64;
+
+    /// Returns the earliest time not earlier than `time` when the device reaches the given temperature
+    /// if the current power is kept, or `None` if the temperature is never reached.
+    fn time_to_reach(&self, temperature: f64, time: f64) -> Option<f64>;
+}
+
+clone_trait_object!(ThermalModel);
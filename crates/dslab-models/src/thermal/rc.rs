@@ -0,0 +1,86 @@
+//! RC thermal model.
+
+use crate::thermal::model::ThermalModel;
+
+/// Lumped RC thermal model, which represents the device as a single thermal capacitance
+/// connected to the ambient via a thermal resistance.
+///
+/// The temperature `T` evolves according to the equation `C * dT/dt = P - (T - T_amb) / R`, where `P` is
+/// the dissipated power, `R` is the thermal resistance (°C/W), `C` is the thermal capacitance (J/°C)
+/// and `T_amb` is the ambient temperature. For constant power the solution is
+/// `T(t) = T_ss + (T(0) - T_ss) * exp(-t / (R * C))`, where `T_ss = T_amb + P * R` is the steady-state temperature.
+#[derive(Clone)]
+pub struct RcThermalModel {
+    resistance: f64,
+    capacitance: f64,
+    ambient_temperature: f64,
+    power: f64,
+    last_temperature: f64,
+    last_update: f64,
+}
+
+impl RcThermalModel {
+    /// Creates an RC thermal model with the given thermal resistance (°C/W), capacitance (J/°C)
+    /// and ambient temperature (°C).
+    ///
+    /// Initially the device temperature equals the ambient temperature and the power is zero.
+    pub fn new(resistance: f64, capacitance: f64, ambient_temperature: f64) -> Self {
+        assert!(
+            resistance > 0. && capacitance > 0.,
+            "Thermal resistance and capacitance must be positive"
+        );
+        Self {
+            resistance,
+            capacitance,
+            ambient_temperature,
+            power: 0.,
+            last_temperature: ambient_temperature,
+            last_update: 0.,
+        }
+    }
+
+    /// Sets the initial device temperature.
+    pub fn with_initial_temperature(mut self, temperature: f64) -> Self {
+        self.last_temperature = temperature;
+        self
+    }
+
+    /// Returns the thermal time constant `R * C`.
+    pub fn time_constant(&self) -> f64 {
+        self.resistance * self.capacitance
+    }
+
+    /// Returns the temperature which is reached asymptotically if the current power is kept.
+    pub fn steady_state_temperature(&self) -> f64 {
+        self.ambient_temperature + self.power * self.resistance
+    }
+}
+
+impl ThermalModel for RcThermalModel {
+    fn set_power(&mut self, power: f64, time: f64) {
+        self.last_temperature = self.temperature(time);
+        self.last_update = time;
+        self.power = power;
+    }
+
+    fn temperature(&self, time: f64) -> f64 {
+        let steady_state = self.steady_state_temperature();
+        let elapsed = (time - self.last_update).max(0.);
+        steady_state + (self.last_temperature - steady_state) * (-elapsed / self.time_constant()).exp()
+    }
+
+    fn time_to_reach(&self, temperature: f64, time: f64) -> Option<f64> {
+        let current = self.temperature(time);
+        if current == temperature {
+            return Some(time);
+        }
+        let steady_state = self.steady_state_temperature();
+        // the temperature changes monotonically towards the steady-state value without reaching it
+        let reachable = (current < temperature && temperature < steady_state)
+            || (steady_state < temperature && temperature < current);
+        if !reachable {
+            return None;
+        }
+        Some(time + self.time_constant() * ((current - steady_state) / (temperature - steady_state)).ln())
+    }
+}
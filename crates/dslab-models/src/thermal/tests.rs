@@ -0,0 +1,99 @@
+use approx::assert_abs_diff_eq;
+
+use crate::thermal::governor::{PerformanceLevel, ThermalGovernor};
+use crate::thermal::model::ThermalModel;
+use crate::thermal::rc::RcThermalModel;
+
+#[test]
+fn test_rc_model_heating_and_cooling() {
+    // time constant is 10 seconds, steady-state temperature at 100 W is 70 °C
+    let mut model = RcThermalModel::new(0.5, 20., 20.);
+    assert_eq!(model.temperature(0.), 20.);
+    model.set_power(100., 0.);
+    assert_abs_diff_eq!(model.temperature(10.), 70. - 50. * (-1f64).exp(), epsilon = 1e-9);
+    assert_abs_diff_eq!(model.temperature(1000.), 70., epsilon = 1e-9);
+
+    // the temperature is continuous when the power changes
+    let temperature = model.temperature(10.);
+    model.set_power(0., 10.);
+    assert_abs_diff_eq!(model.temperature(10.), temperature, epsilon = 1e-12);
+    assert_abs_diff_eq!(
+        model.temperature(20.),
+        20. + (temperature - 20.) * (-1f64).exp(),
+        epsilon = 1e-9
+    );
+}
+
+#[test]
+fn test_rc_model_time_to_reach() {
+    let mut model = RcThermalModel::new(0.5, 20., 20.).with_initial_temperature(30.);
+    model.set_power(100., 5.);
+    let time = model.time_to_reach(60., 5.).unwrap();
+    assert!(time > 5.);
+    assert_abs_diff_eq!(model.temperature(time), 60., epsilon = 1e-9);
+    // the steady-state temperature is never reached
+    assert_eq!(model.time_to_reach(70., 5.), None);
+    assert_eq!(model.time_to_reach(80., 5.), None);
+    assert_eq!(model.time_to_reach(25., 5.), None);
+
+    model.set_power(0., time);
+    let cooled = model.time_to_reach(40., time).unwrap();
+    assert_abs_diff_eq!(model.temperature(cooled), 40., epsilon = 1e-9);
+    assert_eq!(model.time_to_reach(60., cooled), None);
+}
+
+/// Simulates the device whose power depends on the performance level and returns the level changes.
+fn run_governor(governor: &mut ThermalGovernor, power: &[f64], until: f64) -> Vec<(f64, usize)> {
+    let mut model = RcThermalModel::new(0.5, 20., 20.);
+    model.set_power(power[0], 0.);
+    let mut changes = Vec::new();
+    let mut time = 0.;
+    while let Some(next_time) = governor.next_check_time(&model, time) {
+        if next_time > until {
+            break;
+        }
+        time = next_time;
+        if governor.update(model.temperature(time)) {
+            model.set_power(power[governor.level_index()], time);
+            changes.push((time, governor.level_index()));
+        }
+    }
+    changes
+}
+
+#[test]
+fn test_governor_throttling() {
+    let levels = vec![
+        PerformanceLevel::new(1., Some(1.), None),
+        PerformanceLevel::new(0.8, Some(0.5), None),
+        PerformanceLevel::new(0.5, Some(0.), None),
+    ];
+    let mut governor = ThermalGovernor::new(levels, 60., 50.).with_check_interval(2.);
+    // steady-state temperatures are 80, 65 and 45 °C
+    let changes = run_governor(&mut governor, &[120., 90., 50.], 1000.);
+    let mut reference = RcThermalModel::new(0.5, 20., 20.);
+    reference.set_power(120., 0.);
+    let throttle_time = reference.time_to_reach(60., 0.).unwrap();
+    // the first level is not enough to cool down the device, so the second one is used after the check interval
+    assert_abs_diff_eq!(changes[0].0, throttle_time, epsilon = 1e-9);
+    assert_eq!(changes[0].1, 1);
+    assert_abs_diff_eq!(changes[1].0, throttle_time + 2., epsilon = 1e-9);
+    assert_eq!(changes[1].1, 2);
+    // then the device oscillates between the levels
+    assert!(changes.len() > 4);
+    for change in changes.iter().skip(2) {
+        assert!(change.1 >= 1);
+    }
+}
+
+#[test]
+fn test_governor_without_throttling() {
+    let levels = vec![
+        PerformanceLevel::new(1., None, None),
+        PerformanceLevel::new(0.5, None, None),
+    ];
+    let mut governor = ThermalGovernor::new(levels, 60., 50.);
+    // steady-state temperature is 55 °C
+    assert!(run_governor(&mut governor, &[70., 40.], 1000.).is_empty());
+    assert_eq!(governor.level(), &PerformanceLevel::new(1., None, None));
+}
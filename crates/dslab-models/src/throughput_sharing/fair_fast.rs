@@ -1,36 +1,53 @@
 //! Fast implementation of fair throughput sharing model.
 
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap};
 
 use sugars::boxed;
 
 use dslab_core::SimulationContext;
 
 use super::functions::{make_constant_throughput_fn, ConstantFactorFn};
-use super::model::{ActivityFactorFn, ResourceThroughputFn, ThroughputSharingModel};
+use super::model::{
+    ActivityFactorFn, ActivityId, DynamicThroughputSharingModel, ResourceThroughputFn, ThroughputSharingModel,
+};
 
 const TOTAL_WORK_MAX_VALUE: f64 = 1e12;
 
+enum ActivityState {
+    Running { finish_work: f64 },
+    Suspended { remaining_work: f64 },
+}
+
 struct Activity<T> {
-    id: u64,
     item: T,
+    volume: f64,
+    factor: f64,
+    state: ActivityState,
+}
+
+/// Heap entry referring to a running activity.
+///
+/// Entries are not removed from the heap when the activity is removed or suspended,
+/// instead they are discarded when they reach the top of the heap.
+struct HeapEntry {
+    id: ActivityId,
     finish_work: f64,
 }
 
-impl<T> Activity<T> {
-    fn new(id: u64, item: T, finish_work: f64) -> Self {
-        Self { id, item, finish_work }
+impl HeapEntry {
+    fn new(id: ActivityId, finish_work: f64) -> Self {
+        Self { id, finish_work }
     }
 }
 
-impl<T> PartialOrd for Activity<T> {
+impl PartialOrd for HeapEntry {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl<T> Ord for Activity<T> {
+impl Ord for HeapEntry {
     fn cmp(&self, other: &Self) -> Ordering {
         other
             .finish_work
@@ -39,21 +56,24 @@ impl<T> Ord for Activity<T> {
     }
 }
 
-impl<T> PartialEq for Activity<T> {
+impl PartialEq for HeapEntry {
     fn eq(&self, other: &Self) -> bool {
         self.finish_work == other.finish_work && self.id == other.id
     }
 }
 
-impl<T> Eq for Activity<T> {}
+impl Eq for HeapEntry {}
 
 /// Fast implementation of fair throughput sharing model.
 pub struct FairThroughputSharingModel<T> {
-    activities: BinaryHeap<Activity<T>>,
+    heap: BinaryHeap<HeapEntry>,
+    activities: HashMap<ActivityId, Activity<T>>,
+    running_count: usize,
     throughput_function: ResourceThroughputFn,
     factor_function: Box<dyn ActivityFactorFn<T>>,
+    capacity: f64,
     throughput_per_activity: f64,
-    next_id: u64,
+    next_id: ActivityId,
     total_work: f64,
     last_update: f64,
 }
@@ -62,9 +82,12 @@ impl<T> FairThroughputSharingModel<T> {
     /// Creates model with given throughput and factor functions.
     pub fn new(throughput_function: ResourceThroughputFn, factor_function: Box<dyn ActivityFactorFn<T>>) -> Self {
         Self {
-            activities: BinaryHeap::new(),
+            heap: BinaryHeap::new(),
+            activities: HashMap::new(),
+            running_count: 0,
             throughput_function,
             factor_function,
+            capacity: 1.,
             throughput_per_activity: 0.,
             next_id: 0,
             total_work: 0.,
@@ -79,70 +102,204 @@ impl<T> FairThroughputSharingModel<T> {
 
     /// Creates model with dynamic throughput, represented by given closure.
     pub fn with_dynamic_throughput(throughput_function: ResourceThroughputFn) -> Self {
-        Self {
-            activities: BinaryHeap::new(),
-            throughput_function,
-            factor_function: boxed!(ConstantFactorFn::new(1.)),
-            throughput_per_activity: 0.,
-            next_id: 0,
-            total_work: 0.,
-            last_update: 0.,
-        }
+        Self::new(throughput_function, boxed!(ConstantFactorFn::new(1.)))
+    }
+
+    /// Returns the current total throughput of all running activities.
+    pub fn total_throughput(&self) -> f64 {
+        self.throughput_per_activity * self.running_count as f64
     }
 
     fn increment_total_work(&mut self, delta: f64) {
         self.total_work += delta;
         if self.total_work > TOTAL_WORK_MAX_VALUE {
-            let mut entries_vec = Vec::new();
-            while !self.activities.is_empty() {
-                let mut activity = self.activities.pop().unwrap();
-                activity.finish_work -= self.total_work;
-                entries_vec.push(activity);
+            // the heap is rebuilt from the running activities, which also drops the stale entries
+            let mut entries_vec = Vec::with_capacity(self.running_count);
+            for (id, activity) in self.activities.iter_mut() {
+                if let ActivityState::Running { finish_work } = &mut activity.state {
+                    *finish_work -= self.total_work;
+                    entries_vec.push(HeapEntry::new(*id, *finish_work));
+                }
             }
-            self.activities = entries_vec.into();
+            self.heap = entries_vec.into();
             self.total_work = 0.;
         }
     }
+
+    /// Updates the total work to the given time.
+    fn advance(&mut self, time: f64) {
+        if self.running_count > 0 {
+            self.increment_total_work((time - self.last_update) * self.throughput_per_activity);
+        }
+        self.last_update = time;
+    }
+
+    fn update_throughput(&mut self) {
+        let count = self.running_count;
+        if count > 0 {
+            self.throughput_per_activity = self.capacity * (self.throughput_function)(count) / count as f64;
+        } else {
+            self.throughput_per_activity = 0.;
+        }
+    }
+
+    fn is_valid(&self, entry: &HeapEntry) -> bool {
+        matches!(
+            self.activities.get(&entry.id),
+            Some(Activity { state: ActivityState::Running { finish_work }, .. }) if *finish_work == entry.finish_work
+        )
+    }
+
+    /// Removes the entries of removed or suspended activities from the top of the heap,
+    /// so that the top entry always refers to a running activity.
+    fn discard_stale_entries(&mut self) {
+        while let Some(entry) = self.heap.peek() {
+            if self.is_valid(entry) {
+                break;
+            }
+            self.heap.pop();
+        }
+    }
 }
 
 impl<T> ThroughputSharingModel<T> for FairThroughputSharingModel<T> {
-    fn insert(&mut self, item: T, volume: f64, ctx: &mut SimulationContext) {
-        if !self.activities.is_empty() {
-            self.increment_total_work((ctx.time() - self.last_update) * self.throughput_per_activity);
-        }
-        let volume = volume / self.factor_function.get_factor(&item, ctx);
+    fn insert(&mut self, item: T, volume: f64, ctx: &mut SimulationContext) -> ActivityId {
+        self.advance(ctx.time());
+        let factor = self.factor_function.get_factor(&item, ctx);
+        let volume = volume / factor;
         let finish_work = self.total_work + volume;
-        self.activities
-            .push(Activity::<T>::new(self.next_id, item, finish_work));
+        let id = self.next_id;
         self.next_id += 1;
-        let count = self.activities.len();
-        self.throughput_per_activity = (self.throughput_function)(count) / count as f64;
-        self.last_update = ctx.time();
+        self.heap.push(HeapEntry::new(id, finish_work));
+        self.activities.insert(
+            id,
+            Activity {
+                item,
+                volume,
+                factor,
+                state: ActivityState::Running { finish_work },
+            },
+        );
+        self.running_count += 1;
+        self.update_throughput();
+        id
     }
 
     fn pop(&mut self) -> Option<(f64, T)> {
-        if let Some(entry) = self.activities.pop() {
+        if self.capacity == 0. {
+            return None;
+        }
+        if let Some(entry) = self.heap.pop() {
+            let activity = self.activities.remove(&entry.id).unwrap();
             let remaining_work = entry.finish_work - self.total_work;
             let finish_time = self.last_update + remaining_work / self.throughput_per_activity;
             self.increment_total_work(remaining_work);
-            let count = self.activities.len();
-            if count > 0 {
-                self.throughput_per_activity = (self.throughput_function)(count) / count as f64;
-            } else {
-                self.throughput_per_activity = 0.;
-            }
+            self.running_count -= 1;
+            self.update_throughput();
             self.last_update = finish_time;
-            return Some((finish_time, entry.item));
+            self.discard_stale_entries();
+            return Some((finish_time, activity.item));
         }
         None
     }
 
     fn peek(&self) -> Option<(f64, &T)> {
-        self.activities.peek().map(|entry| {
+        if self.capacity == 0. {
+            return None;
+        }
+        self.heap.peek().map(|entry| {
             (
                 self.last_update + (entry.finish_work - self.total_work) / self.throughput_per_activity,
-                &entry.item,
+                &self.activities[&entry.id].item,
             )
         })
     }
 }
+
+impl<T> DynamicThroughputSharingModel<T> for FairThroughputSharingModel<T> {
+    fn remove(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> Option<(T, f64)> {
+        if !self.activities.contains_key(&id) {
+            return None;
+        }
+        self.advance(ctx.time());
+        let activity = self.activities.remove(&id).unwrap();
+        let remaining_work = match activity.state {
+            ActivityState::Running { finish_work } => {
+                self.running_count -= 1;
+                self.update_throughput();
+                self.discard_stale_entries();
+                finish_work - self.total_work
+            }
+            ActivityState::Suspended { remaining_work } => remaining_work,
+        };
+        Some((activity.item, remaining_work.max(0.) * activity.factor))
+    }
+
+    fn suspend(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> bool {
+        if !matches!(
+            self.activities.get(&id),
+            Some(Activity {
+                state: ActivityState::Running { .. },
+                ..
+            })
+        ) {
+            return false;
+        }
+        self.advance(ctx.time());
+        let activity = self.activities.get_mut(&id).unwrap();
+        if let ActivityState::Running { finish_work } = activity.state {
+            activity.state = ActivityState::Suspended {
+                remaining_work: (finish_work - self.total_work).max(0.),
+            };
+        }
+        self.running_count -= 1;
+        self.update_throughput();
+        self.discard_stale_entries();
+        true
+    }
+
+    fn resume(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> bool {
+        if !matches!(
+            self.activities.get(&id),
+            Some(Activity {
+                state: ActivityState::Suspended { .. },
+                ..
+            })
+        ) {
+            return false;
+        }
+        self.advance(ctx.time());
+        let activity = self.activities.get_mut(&id).unwrap();
+        if let ActivityState::Suspended { remaining_work } = activity.state {
+            let finish_work = self.total_work + remaining_work;
+            activity.state = ActivityState::Running { finish_work };
+            self.heap.push(HeapEntry::new(id, finish_work));
+        }
+        self.running_count += 1;
+        self.update_throughput();
+        true
+    }
+
+    fn progress(&self, id: ActivityId, ctx: &SimulationContext) -> Option<f64> {
+        self.activities.get(&id).map(|activity| {
+            let remaining_work = match activity.state {
+                ActivityState::Running { finish_work } => {
+                    finish_work - self.total_work - (ctx.time() - self.last_update) * self.throughput_per_activity
+                }
+                ActivityState::Suspended { remaining_work } => remaining_work,
+            };
+            if activity.volume > 0. {
+                (1. - remaining_work / activity.volume).clamp(0., 1.)
+            } else {
+                1.
+            }
+        })
+    }
+
+    fn set_capacity(&mut self, capacity: f64, ctx: &mut SimulationContext) {
+        assert!(capacity >= 0., "Capacity must be non-negative");
+        self.advance(ctx.time());
+        self.capacity = capacity;
+        self.update_throughput();
+    }
+}
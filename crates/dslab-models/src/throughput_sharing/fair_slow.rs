@@ -2,27 +2,41 @@
 //! at each activity creation and completion.
 
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap};
 
 use sugars::boxed;
 
 use dslab_core::SimulationContext;
 
 use super::functions::{make_constant_throughput_fn, ConstantFactorFn};
-use super::model::{ActivityFactorFn, ResourceThroughputFn, ThroughputSharingModel};
+use super::model::{
+    ActivityFactorFn, ActivityId, DynamicThroughputSharingModel, ResourceThroughputFn, ThroughputSharingModel,
+};
 
 struct Activity<T> {
     remaining_volume: f64,
-    id: u64,
+    id: ActivityId,
     item: T,
+    volume: f64,
+    factor: f64,
 }
 
 impl<T> Activity<T> {
-    fn new(remaining_volume: f64, id: u64, item: T) -> Self {
+    fn new(volume: f64, factor: f64, id: ActivityId, item: T) -> Self {
         Self {
-            remaining_volume,
+            remaining_volume: volume,
             id,
             item,
+            volume,
+            factor,
+        }
+    }
+
+    fn progress(&self, remaining_volume: f64) -> f64 {
+        if self.volume > 0. {
+            (1. - remaining_volume / self.volume).clamp(0., 1.)
+        } else {
+            1.
         }
     }
 }
@@ -56,7 +70,9 @@ pub struct SlowFairThroughputSharingModel<T> {
     throughput_function: ResourceThroughputFn,
     factor_function: Box<dyn ActivityFactorFn<T>>,
     entries: BinaryHeap<Activity<T>>,
-    next_id: u64,
+    suspended: HashMap<ActivityId, Activity<T>>,
+    next_id: ActivityId,
+    capacity: f64,
     last_throughput_per_item: f64,
     last_recalculation_time: f64,
 }
@@ -68,7 +84,9 @@ impl<T> SlowFairThroughputSharingModel<T> {
             throughput_function,
             factor_function,
             entries: BinaryHeap::new(),
+            suspended: HashMap::new(),
             next_id: 0,
+            capacity: 1.,
             last_throughput_per_item: 0.,
             last_recalculation_time: 0.,
         }
@@ -85,7 +103,9 @@ impl<T> SlowFairThroughputSharingModel<T> {
             throughput_function,
             factor_function: boxed!(ConstantFactorFn::new(1.)),
             entries: BinaryHeap::new(),
+            suspended: HashMap::new(),
             next_id: 0,
+            capacity: 1.,
             last_throughput_per_item: 0.,
             last_recalculation_time: 0.,
         }
@@ -94,34 +114,60 @@ impl<T> SlowFairThroughputSharingModel<T> {
     fn recalculate(&mut self, current_time: f64, throughput_per_item: f64) {
         let mut new_entries = BinaryHeap::<Activity<T>>::with_capacity(self.entries.len());
         let processed_volume = (current_time - self.last_recalculation_time) * self.last_throughput_per_item;
-        while let Some(entry) = self.entries.pop() {
-            let remaining_volume = entry.remaining_volume - processed_volume;
-            new_entries.push(Activity::<T>::new(remaining_volume, entry.id, entry.item));
+        while let Some(mut entry) = self.entries.pop() {
+            entry.remaining_volume -= processed_volume;
+            new_entries.push(entry);
         }
         self.entries = new_entries;
         self.last_throughput_per_item = throughput_per_item;
         self.last_recalculation_time = current_time;
     }
+
+    fn throughput_per_item(&self, count: usize) -> f64 {
+        if count > 0 {
+            self.capacity * (self.throughput_function)(count) / count as f64
+        } else {
+            0.
+        }
+    }
+
+    /// Recalculates the running activities at the current time and takes out the activity with given id.
+    fn take_running(&mut self, id: ActivityId, current_time: f64) -> Option<Activity<T>> {
+        if !self.entries.iter().any(|entry| entry.id == id) {
+            return None;
+        }
+        let new_count = self.entries.len() - 1;
+        self.recalculate(current_time, self.throughput_per_item(new_count));
+        let mut entries = std::mem::take(&mut self.entries).into_vec();
+        let pos = entries.iter().position(|entry| entry.id == id).unwrap();
+        let activity = entries.swap_remove(pos);
+        self.entries = entries.into();
+        Some(activity)
+    }
 }
 
 impl<T> ThroughputSharingModel<T> for SlowFairThroughputSharingModel<T> {
-    fn insert(&mut self, item: T, volume: f64, ctx: &mut SimulationContext) {
+    fn insert(&mut self, item: T, volume: f64, ctx: &mut SimulationContext) -> ActivityId {
         let new_count = self.entries.len() + 1;
-        self.recalculate(ctx.time(), (self.throughput_function)(new_count) / new_count as f64);
-        let volume = volume / self.factor_function.get_factor(&item, ctx);
-        self.entries.push(Activity::<T>::new(volume, self.next_id, item));
+        self.recalculate(ctx.time(), self.throughput_per_item(new_count));
+        let factor = self.factor_function.get_factor(&item, ctx);
+        let id = self.next_id;
+        self.entries.push(Activity::<T>::new(volume / factor, factor, id, item));
         self.next_id += 1;
+        id
     }
 
     fn pop(&mut self) -> Option<(f64, T)> {
+        if self.capacity == 0. {
+            return None;
+        }
         if let Some(entry) = self.entries.pop() {
             let complete_time = self.last_recalculation_time + entry.remaining_volume / self.last_throughput_per_item;
             if self.entries.is_empty() {
                 self.last_recalculation_time = complete_time;
                 self.last_throughput_per_item = 0.;
             } else {
-                let new_count = self.entries.len();
-                self.recalculate(complete_time, (self.throughput_function)(new_count) / new_count as f64);
+                self.recalculate(complete_time, self.throughput_per_item(self.entries.len()));
             }
             return Some((complete_time, entry.item));
         }
@@ -129,6 +175,9 @@ impl<T> ThroughputSharingModel<T> for SlowFairThroughputSharingModel<T> {
     }
 
     fn peek(&self) -> Option<(f64, &T)> {
+        if self.capacity == 0. {
+            return None;
+        }
         self.entries.peek().map(|entry| {
             (
                 self.last_recalculation_time + entry.remaining_volume / self.last_throughput_per_item,
@@ -137,3 +186,53 @@ impl<T> ThroughputSharingModel<T> for SlowFairThroughputSharingModel<T> {
         })
     }
 }
+
+impl<T> DynamicThroughputSharingModel<T> for SlowFairThroughputSharingModel<T> {
+    fn remove(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> Option<(T, f64)> {
+        let activity = self
+            .suspended
+            .remove(&id)
+            .or_else(|| self.take_running(id, ctx.time()))?;
+        Some((activity.item, activity.remaining_volume.max(0.) * activity.factor))
+    }
+
+    fn suspend(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> bool {
+        match self.take_running(id, ctx.time()) {
+            Some(mut activity) => {
+                activity.remaining_volume = activity.remaining_volume.max(0.);
+                self.suspended.insert(id, activity);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn resume(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> bool {
+        match self.suspended.remove(&id) {
+            Some(activity) => {
+                let new_count = self.entries.len() + 1;
+                self.recalculate(ctx.time(), self.throughput_per_item(new_count));
+                self.entries.push(activity);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn progress(&self, id: ActivityId, ctx: &SimulationContext) -> Option<f64> {
+        if let Some(activity) = self.suspended.get(&id) {
+            return Some(activity.progress(activity.remaining_volume));
+        }
+        let processed_volume = (ctx.time() - self.last_recalculation_time) * self.last_throughput_per_item;
+        self.entries
+            .iter()
+            .find(|entry| entry.id == id)
+            .map(|entry| entry.progress(entry.remaining_volume - processed_volume))
+    }
+
+    fn set_capacity(&mut self, capacity: f64, ctx: &mut SimulationContext) {
+        assert!(capacity >= 0., "Capacity must be non-negative");
+        self.capacity = capacity;
+        self.recalculate(ctx.time(), self.throughput_per_item(self.entries.len()));
+    }
+}
@@ -4,6 +4,8 @@ mod fair_fast;
 mod fair_slow;
 mod functions;
 mod model;
+mod weighted_fast;
+mod weighted_slow;
 
 #[cfg(test)]
 mod tests;
@@ -13,4 +15,8 @@ pub use fair_slow::SlowFairThroughputSharingModel;
 pub use functions::{
     make_constant_throughput_fn, make_uniform_factor_fn, ConstantFactorFn, EmpiricalFactorFn, RandomizedFactorFn,
 };
-pub use model::{ActivityFactorFn, ResourceThroughputFn, ThroughputSharingModel};
+pub use model::{
+    ActivityFactorFn, ActivityId, DynamicThroughputSharingModel, ResourceThroughputFn, ThroughputSharingModel,
+};
+pub use weighted_fast::WeightedThroughputSharingModel;
+pub use weighted_slow::SlowWeightedThroughputSharingModel;
@@ -2,12 +2,15 @@
 
 use dslab_core::context::SimulationContext;
 
+/// Identifier of activity in throughput sharing model, which is returned by [`ThroughputSharingModel::insert`].
+pub type ActivityId = u64;
+
 /// Trait for throughput sharing model.
 pub trait ThroughputSharingModel<T> {
-    /// Adds new activity into the model.
+    /// Adds new activity into the model and returns its identifier.
     ///
     /// Activity is represented by `item`, has amount of work `volume` and starts at `ctx.time()`.
-    fn insert(&mut self, item: T, volume: f64, ctx: &mut SimulationContext);
+    fn insert(&mut self, item: T, volume: f64, ctx: &mut SimulationContext) -> ActivityId;
     /// Returns the next activity completion time (if any) along with corresponding activity item.
     ///
     /// The returned activity is removed from the model.
@@ -18,6 +21,39 @@ pub trait ThroughputSharingModel<T> {
     fn peek(&self) -> Option<(f64, &T)>;
 }
 
+/// Throughput sharing model which also supports removing, suspending and resuming activities before their completion
+/// and changing the resource capacity.
+///
+/// In contrast to the basic operations of [`ThroughputSharingModel`], these operations require tracking
+/// the state of each activity, so they are provided by a separate trait.
+pub trait DynamicThroughputSharingModel<T>: ThroughputSharingModel<T> {
+    /// Removes the activity from the model at `ctx.time()` before its completion.
+    ///
+    /// Returns the activity item along with the remaining amount of work,
+    /// or `None` if there is no such activity (e.g. it is already completed).
+    fn remove(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> Option<(T, f64)>;
+    /// Suspends the running activity at `ctx.time()`.
+    ///
+    /// Suspended activity keeps its remaining amount of work and does not consume the resource throughput
+    /// until it is resumed. Returns false if there is no such running activity.
+    fn suspend(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> bool;
+    /// Resumes the suspended activity at `ctx.time()`.
+    ///
+    /// Returns false if there is no such suspended activity.
+    fn resume(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> bool;
+    /// Returns the fraction of work completed by the activity at `ctx.time()` (a value between 0 and 1),
+    /// or `None` if there is no such activity.
+    fn progress(&self, id: ActivityId, ctx: &SimulationContext) -> Option<f64>;
+    /// Changes the resource capacity starting from `ctx.time()`.
+    ///
+    /// The capacity is relative to the nominal resource throughput, i.e. the total resource throughput is computed
+    /// as `capacity * throughput_function(num of activities)`. The initial capacity is 1.
+    /// The work done by activities before the change is preserved, while the completion times of running activities
+    /// are recomputed, so the previously obtained completion times must be discarded.
+    /// While the capacity is zero, the activities make no progress and `peek` and `pop` return `None`.
+    fn set_capacity(&mut self, capacity: f64, ctx: &mut SimulationContext);
+}
+
 /// Function that computes the total resource throughput based on the number of concurrent activities.
 ///
 /// It can be used to model the performance degradation caused by interference, resource contention, etc.
@@ -5,7 +5,9 @@ use dslab_core::{Simulation, SimulationContext};
 use super::fair_fast::FairThroughputSharingModel;
 use super::fair_slow::SlowFairThroughputSharingModel;
 use super::functions::make_constant_throughput_fn;
-use super::model::{ActivityFactorFn, ThroughputSharingModel};
+use super::model::{ActivityFactorFn, ActivityId, DynamicThroughputSharingModel, ThroughputSharingModel};
+use super::weighted_fast::WeightedThroughputSharingModel;
+use super::weighted_slow::SlowWeightedThroughputSharingModel;
 
 fn assert_float_eq(x: f64, y: f64, eps: f64) {
     assert!(
@@ -16,13 +18,17 @@ fn assert_float_eq(x: f64, y: f64, eps: f64) {
     );
 }
 
-struct ModelsTester {
-    fast_model: FairThroughputSharingModel<u32>,
-    slow_model: SlowFairThroughputSharingModel<u32>,
+struct ModelsTester<F = FairThroughputSharingModel<u32>, S = SlowFairThroughputSharingModel<u32>> {
+    fast_model: F,
+    slow_model: S,
     sim: Simulation,
     ctx: SimulationContext,
+    /// Tolerance used when comparing the results of fast and slow models.
+    eps: f64,
 }
 
+type WeightedModelsTester = ModelsTester<WeightedThroughputSharingModel<u32>, SlowWeightedThroughputSharingModel<u32>>;
+
 impl ModelsTester {
     fn with_fixed_throughput(bandwidth: f64) -> Self {
         let mut sim = Simulation::new(123);
@@ -32,6 +38,7 @@ impl ModelsTester {
             slow_model: SlowFairThroughputSharingModel::with_fixed_throughput(bandwidth),
             sim,
             ctx,
+            eps: 1e-12,
         }
     }
 
@@ -43,20 +50,127 @@ impl ModelsTester {
             slow_model: SlowFairThroughputSharingModel::with_dynamic_throughput(boxed!(throughput_function)),
             sim,
             ctx,
+            eps: 1e-12,
         }
     }
+}
 
+impl WeightedModelsTester {
+    fn weighted_with_fixed_throughput(bandwidth: f64) -> Self {
+        Self::weighted_with_dynamic_throughput(move |_| bandwidth)
+    }
+
+    fn weighted_with_dynamic_throughput(throughput_function: impl Fn(usize) -> f64 + Clone + 'static) -> Self {
+        let mut sim = Simulation::new(123);
+        let ctx = sim.create_context("test");
+        Self {
+            fast_model: WeightedThroughputSharingModel::with_dynamic_throughput(boxed!(throughput_function.clone())),
+            slow_model: SlowWeightedThroughputSharingModel::with_dynamic_throughput(boxed!(throughput_function)),
+            sim,
+            ctx,
+            eps: 1e-12,
+        }
+    }
+
+    fn insert_weighted_and_compare(
+        &mut self,
+        item: u32,
+        volume: f64,
+        weight: f64,
+        max_throughput: Option<f64>,
+    ) -> ActivityId {
+        let id = self
+            .fast_model
+            .insert_weighted(item, volume, weight, max_throughput, &mut self.ctx);
+        assert_eq!(
+            self.slow_model
+                .insert_weighted(item, volume, weight, max_throughput, &mut self.ctx),
+            id
+        );
+        self.compare_next();
+        id
+    }
+}
+
+impl<F: DynamicThroughputSharingModel<u32>, S: DynamicThroughputSharingModel<u32>> ModelsTester<F, S> {
     fn advance_time(&mut self, duration: f64) {
         self.sim.step_for_duration(duration);
     }
 
-    fn insert_and_compare(&mut self, item: u32, volume: f64) {
-        self.fast_model.insert(item, volume, &mut self.ctx);
-        self.slow_model.insert(item, volume, &mut self.ctx);
-        let fast_item = self.fast_model.peek().unwrap();
-        let slow_item = self.slow_model.peek().unwrap();
-        assert_float_eq(fast_item.0, slow_item.0, 1e-12);
-        assert_eq!(fast_item.1, slow_item.1);
+    fn insert_and_compare(&mut self, item: u32, volume: f64) -> ActivityId {
+        let id = self.fast_model.insert(item, volume, &mut self.ctx);
+        assert_eq!(self.slow_model.insert(item, volume, &mut self.ctx), id);
+        self.compare_next();
+        id
+    }
+
+    fn compare_next(&self) {
+        match (self.fast_model.peek(), self.slow_model.peek()) {
+            (Some(fast_item), Some(slow_item)) => {
+                assert_float_eq(fast_item.0, slow_item.0, self.eps);
+                assert_eq!(fast_item.1, slow_item.1);
+            }
+            (fast_item, slow_item) => assert_eq!(fast_item.is_none(), slow_item.is_none()),
+        }
+    }
+
+    fn remove_and_compare(&mut self, id: ActivityId) -> Option<(u32, f64)> {
+        let fast_result = self.fast_model.remove(id, &mut self.ctx);
+        let slow_result = self.slow_model.remove(id, &mut self.ctx);
+        assert_eq!(fast_result.is_some(), slow_result.is_some());
+        if let (Some(fast_result), Some(slow_result)) = (fast_result, slow_result) {
+            assert_eq!(fast_result.0, slow_result.0);
+            assert_float_eq(fast_result.1, slow_result.1, self.eps);
+        }
+        self.compare_next();
+        fast_result
+    }
+
+    fn suspend_and_compare(&mut self, id: ActivityId) -> bool {
+        let result = self.fast_model.suspend(id, &mut self.ctx);
+        assert_eq!(self.slow_model.suspend(id, &mut self.ctx), result);
+        self.compare_next();
+        result
+    }
+
+    fn resume_and_compare(&mut self, id: ActivityId) -> bool {
+        let result = self.fast_model.resume(id, &mut self.ctx);
+        assert_eq!(self.slow_model.resume(id, &mut self.ctx), result);
+        self.compare_next();
+        result
+    }
+
+    /// Pops the activities completed until the given time, returns the number of completed activities.
+    fn pop_until_and_compare(&mut self, time: f64) -> usize {
+        let mut completed = 0;
+        while let Some((finish_time, _)) = self.fast_model.peek() {
+            if finish_time > time {
+                break;
+            }
+            let (fast_time, fast_item) = self.fast_model.pop().unwrap();
+            let (slow_time, slow_item) = self.slow_model.pop().unwrap();
+            assert_float_eq(fast_time, slow_time, self.eps);
+            assert_eq!(fast_item, slow_item);
+            self.compare_next();
+            completed += 1;
+        }
+        completed
+    }
+
+    fn set_capacity_and_compare(&mut self, capacity: f64) {
+        self.fast_model.set_capacity(capacity, &mut self.ctx);
+        self.slow_model.set_capacity(capacity, &mut self.ctx);
+        self.compare_next();
+    }
+
+    fn progress_and_compare(&self, id: ActivityId) -> Option<f64> {
+        let result = self.fast_model.progress(id, &self.ctx);
+        let slow_result = self.slow_model.progress(id, &self.ctx);
+        assert_eq!(result.is_some(), slow_result.is_some());
+        if let (Some(fast_progress), Some(slow_progress)) = (result, slow_result) {
+            assert_float_eq(fast_progress, slow_progress, self.eps);
+        }
+        result
     }
 
     fn pop_all_and_compare(&mut self) -> Vec<(f64, u32)> {
@@ -70,7 +184,7 @@ impl ModelsTester {
         }
         println!();
         for i in 0..fast_model_result.len() {
-            assert_float_eq(fast_model_result[i].0, slow_model_result[i].0, 1e-12);
+            assert_float_eq(fast_model_result[i].0, slow_model_result[i].0, self.eps);
             println!("{} {}", fast_model_result[i].0, slow_model_result[i].0);
             assert_eq!(fast_model_result[i].1, slow_model_result[i].1);
         }
@@ -241,3 +355,297 @@ fn throughput_factor_and_degradation() {
     assert_eq!(model.pop(), Some((4.125, 0)));
     assert_eq!(model.pop(), Some((5.125, 1)));
 }
+
+#[test]
+fn remove_activity() {
+    let mut tester = ModelsTester::with_fixed_throughput(100.);
+    let id = tester.insert_and_compare(0, 200.);
+    tester.insert_and_compare(1, 400.);
+    tester.advance_time(1.);
+    assert_eq!(tester.remove_and_compare(id), Some((0, 150.)));
+    assert_eq!(tester.remove_and_compare(id), None);
+    assert_eq!(tester.pop_all_and_compare(), vec![(4.5, 1)]);
+}
+
+#[test]
+fn remove_last_activity() {
+    let mut tester = ModelsTester::with_fixed_throughput(100.);
+    let id = tester.insert_and_compare(0, 200.);
+    tester.advance_time(1.);
+    assert_eq!(tester.remove_and_compare(id), Some((0, 100.)));
+    assert!(tester.fast_model.peek().is_none());
+    tester.advance_time(1.);
+    tester.insert_and_compare(1, 100.);
+    assert_eq!(tester.pop_all_and_compare(), vec![(3., 1)]);
+}
+
+#[test]
+fn suspend_and_resume() {
+    let mut tester = ModelsTester::with_fixed_throughput(100.);
+    let id = tester.insert_and_compare(0, 200.);
+    tester.insert_and_compare(1, 200.);
+    tester.advance_time(1.);
+    assert!(tester.suspend_and_compare(id));
+    assert!(!tester.suspend_and_compare(id));
+    assert_float_eq(tester.progress_and_compare(id).unwrap(), 0.25, 1e-12);
+    tester.advance_time(1.);
+    // suspended activity does not make progress
+    assert_float_eq(tester.progress_and_compare(id).unwrap(), 0.25, 1e-12);
+    assert!(tester.resume_and_compare(id));
+    assert!(!tester.resume_and_compare(id));
+    assert_eq!(tester.pop_all_and_compare(), vec![(3., 1), (4., 0)]);
+}
+
+#[test]
+fn suspend_all_activities() {
+    let mut tester = ModelsTester::with_fixed_throughput(100.);
+    let id0 = tester.insert_and_compare(0, 100.);
+    let id1 = tester.insert_and_compare(1, 300.);
+    tester.advance_time(1.);
+    tester.suspend_and_compare(id0);
+    tester.suspend_and_compare(id1);
+    assert!(tester.fast_model.peek().is_none());
+    tester.advance_time(5.);
+    tester.resume_and_compare(id1);
+    tester.resume_and_compare(id0);
+    assert_eq!(tester.pop_all_and_compare(), vec![(7., 0), (9., 1)]);
+    // removal of suspended activity returns its remaining volume
+    let id2 = tester.insert_and_compare(2, 100.);
+    tester.suspend_and_compare(id2);
+    assert_eq!(tester.remove_and_compare(id2), Some((2, 100.)));
+}
+
+#[test]
+fn activity_progress() {
+    let mut tester = ModelsTester::with_fixed_throughput(100.);
+    let id0 = tester.insert_and_compare(0, 100.);
+    let id1 = tester.insert_and_compare(1, 400.);
+    assert_eq!(tester.progress_and_compare(id0), Some(0.));
+    tester.advance_time(1.);
+    assert_float_eq(tester.progress_and_compare(id0).unwrap(), 0.5, 1e-12);
+    assert_float_eq(tester.progress_and_compare(id1).unwrap(), 0.125, 1e-12);
+    assert_eq!(tester.fast_model.pop(), Some((2., 0)));
+    assert_eq!(tester.slow_model.pop(), Some((2., 0)));
+    assert_eq!(tester.progress_and_compare(id0), None);
+    tester.advance_time(2.);
+    assert_float_eq(tester.progress_and_compare(id1).unwrap(), 0.5, 1e-12);
+}
+
+#[test]
+fn remove_with_throughput_factor() {
+    let mut sim = Simulation::new(123);
+    let mut ctx = sim.create_context("test");
+    let tf = make_constant_throughput_fn(100.);
+    let mut model: FairThroughputSharingModel<u32> =
+        FairThroughputSharingModel::new(tf, boxed!(TestThroughputFactorFunction {}));
+    let id = model.insert(1, 100., &mut ctx);
+    sim.step_until_time(1.);
+    // the effective throughput is 50, so the half of work is done
+    assert_float_eq(model.progress(id, &ctx).unwrap(), 0.5, 1e-12);
+    assert_eq!(model.remove(id, &mut ctx), Some((1, 50.)));
+}
+
+#[test]
+fn random_operations() {
+    let mut tester = ModelsTester::with_dynamic_throughput(|n| 100. / (1. + 0.1 * n as f64));
+    // the fast model accumulates rounding errors in its shifted remaining volumes over a long sequence
+    // of suspensions, resumptions and capacity changes, so the results match with lower precision
+    tester.eps = 1e-9;
+    let mut ids = Vec::new();
+    let mut completed = 0;
+    for i in 0..1000 {
+        let time = tester.sim.time() + tester.ctx.gen_range(0.0..1.0);
+        completed += tester.pop_until_and_compare(time);
+        tester.advance_time(time - tester.sim.time());
+        let op = tester.ctx.gen_range(0..11);
+        if op == 10 {
+            let capacity = tester.ctx.gen_range(0.2..2.0);
+            tester.set_capacity_and_compare(capacity);
+            continue;
+        }
+        if op < 5 || ids.is_empty() {
+            let volume = tester.ctx.gen_range(1.0..100.0);
+            ids.push(tester.insert_and_compare(i, volume));
+            continue;
+        }
+        let id = ids[tester.ctx.gen_range(0..ids.len())];
+        match op {
+            5 | 6 => tester.suspend_and_compare(id),
+            7 | 8 => tester.resume_and_compare(id),
+            _ => tester.remove_and_compare(id).is_some(),
+        };
+        tester.progress_and_compare(id);
+    }
+    assert!(completed > 0);
+    tester.pop_until_and_compare(f64::INFINITY);
+}
+
+#[test]
+fn weighted_sharing() {
+    let mut tester = WeightedModelsTester::weighted_with_fixed_throughput(100.);
+    tester.insert_weighted_and_compare(0, 100., 1., None);
+    tester.insert_weighted_and_compare(1, 300., 3., None);
+    assert_eq!(tester.pop_all_and_compare(), vec![(4., 0), (4., 1)]);
+}
+
+#[test]
+fn weighted_overlapping_activities() {
+    let mut tester = WeightedModelsTester::weighted_with_fixed_throughput(100.);
+    tester.insert_weighted_and_compare(0, 400., 3., None);
+    tester.advance_time(1.);
+    tester.insert_weighted_and_compare(1, 100., 1., None);
+    // activity 0 gets 75 after the second one arrives: 100 + 75 * 4 = 400
+    assert_eq!(tester.pop_all_and_compare(), vec![(5., 0), (5., 1)]);
+}
+
+#[test]
+fn capped_activity() {
+    let mut tester = WeightedModelsTester::weighted_with_fixed_throughput(100.);
+    tester.insert_weighted_and_compare(0, 100., 1., Some(20.));
+    tester.insert_weighted_and_compare(1, 480., 1., None);
+    // the throughput unused by the capped activity goes to the other one
+    assert_eq!(tester.pop_all_and_compare(), vec![(5., 0), (5.8, 1)]);
+}
+
+#[test]
+fn cap_above_fair_share() {
+    let mut tester = WeightedModelsTester::weighted_with_fixed_throughput(100.);
+    tester.insert_weighted_and_compare(0, 90., 1., Some(60.));
+    tester.insert_weighted_and_compare(1, 90., 1., None);
+    tester.insert_weighted_and_compare(2, 50., 2., Some(10.));
+    // the activity 2 is capped at 10, the remaining 90 is shared equally by the rest
+    assert_eq!(tester.pop_all_and_compare(), vec![(2., 0), (2., 1), (5., 2)]);
+}
+
+#[test]
+fn all_activities_capped() {
+    let mut tester = WeightedModelsTester::weighted_with_fixed_throughput(100.);
+    tester.insert_weighted_and_compare(0, 100., 1., Some(10.));
+    tester.insert_weighted_and_compare(1, 100., 5., Some(20.));
+    assert_eq!(tester.pop_all_and_compare(), vec![(5., 1), (10., 0)]);
+}
+
+#[test]
+fn weighted_model_without_weights_is_fair() {
+    let mut fair = ModelsTester::with_fixed_throughput(100.);
+    let mut weighted = WeightedModelsTester::weighted_with_fixed_throughput(100.);
+    for i in 0..20 {
+        fair.insert_and_compare(i, (i * 37 % 11 + 1) as f64 * 10.);
+        weighted.insert_and_compare(i, (i * 37 % 11 + 1) as f64 * 10.);
+        fair.advance_time(0.5);
+        weighted.advance_time(0.5);
+    }
+    let fair_result = fair.pop_all_and_compare();
+    let weighted_result = weighted.pop_all_and_compare();
+    for (a, b) in fair_result.iter().zip(weighted_result.iter()) {
+        assert_float_eq(a.0, b.0, 1e-12);
+        assert_eq!(a.1, b.1);
+    }
+}
+
+#[test]
+fn weighted_suspend_and_remove() {
+    let mut tester = WeightedModelsTester::weighted_with_fixed_throughput(100.);
+    let id0 = tester.insert_weighted_and_compare(0, 300., 3., None);
+    let id1 = tester.insert_weighted_and_compare(1, 100., 1., Some(10.));
+    tester.insert_weighted_and_compare(2, 600., 1., None);
+    tester.advance_time(1.);
+    assert!(tester.suspend_and_compare(id0));
+    assert_float_eq(tester.progress_and_compare(id1).unwrap(), 0.1, 1e-12);
+    tester.advance_time(1.);
+    assert!(tester.resume_and_compare(id0));
+    tester.advance_time(1.);
+    assert!(tester.remove_and_compare(id1).is_some());
+    tester.pop_all_and_compare();
+}
+
+#[test]
+fn weighted_random_operations() {
+    let mut tester = WeightedModelsTester::weighted_with_dynamic_throughput(|n| 100. / (1. + 0.05 * n as f64));
+    let mut ids = Vec::new();
+    let mut completed = 0;
+    for i in 0..1000 {
+        let time = tester.sim.time() + tester.ctx.gen_range(0.0..1.0);
+        completed += tester.pop_until_and_compare(time);
+        tester.advance_time(time - tester.sim.time());
+        let op = tester.ctx.gen_range(0..11);
+        if op == 10 {
+            let capacity = tester.ctx.gen_range(0.2..2.0);
+            tester.set_capacity_and_compare(capacity);
+            continue;
+        }
+        if op < 5 || ids.is_empty() {
+            let volume = tester.ctx.gen_range(1.0..100.0);
+            let weight = tester.ctx.gen_range(1..5) as f64;
+            let max_throughput = if tester.ctx.gen_range(0..2) == 0 {
+                Some(tester.ctx.gen_range(1.0..30.0))
+            } else {
+                None
+            };
+            ids.push(tester.insert_weighted_and_compare(i, volume, weight, max_throughput));
+            continue;
+        }
+        let id = ids[tester.ctx.gen_range(0..ids.len())];
+        match op {
+            5 | 6 => tester.suspend_and_compare(id),
+            7 | 8 => tester.resume_and_compare(id),
+            _ => tester.remove_and_compare(id).is_some(),
+        };
+        tester.progress_and_compare(id);
+    }
+    assert!(completed > 0);
+    tester.pop_until_and_compare(f64::INFINITY);
+}
+
+#[test]
+fn capacity_change() {
+    let mut tester = ModelsTester::with_fixed_throughput(100.);
+    tester.insert_and_compare(0, 200.);
+    tester.advance_time(1.);
+    tester.set_capacity_and_compare(0.5);
+    tester.insert_and_compare(1, 50.);
+    tester.advance_time(1.);
+    tester.set_capacity_and_compare(2.);
+    // at time 2 the activities have 75 and 25 units of work left
+    assert_eq!(tester.pop_all_and_compare(), vec![(2.25, 1), (2.5, 0)]);
+}
+
+#[test]
+fn zero_capacity() {
+    let mut tester = ModelsTester::with_fixed_throughput(100.);
+    let id = tester.insert_and_compare(0, 200.);
+    tester.advance_time(1.);
+    tester.set_capacity_and_compare(0.);
+    // no activity can complete while the capacity is zero
+    assert!(tester.fast_model.peek().is_none());
+    assert!(tester.fast_model.pop().is_none());
+    tester.advance_time(10.);
+    assert_float_eq(tester.progress_and_compare(id).unwrap(), 0.5, 1e-12);
+    tester.set_capacity_and_compare(1.);
+    assert_eq!(tester.pop_all_and_compare(), vec![(12., 0)]);
+}
+
+#[test]
+fn weighted_capacity_change() {
+    let mut tester = WeightedModelsTester::weighted_with_fixed_throughput(100.);
+    tester.insert_weighted_and_compare(0, 100., 1., Some(40.));
+    tester.insert_weighted_and_compare(1, 300., 1., None);
+    tester.advance_time(1.);
+    // the capped activity becomes uncapped when the capacity is reduced
+    tester.set_capacity_and_compare(0.5);
+    assert_eq!(tester.pop_all_and_compare(), vec![(3.4, 0), (7., 1)]);
+}
+
+#[test]
+fn weighted_zero_capacity() {
+    let mut tester = WeightedModelsTester::weighted_with_fixed_throughput(100.);
+    tester.insert_weighted_and_compare(0, 100., 1., Some(40.));
+    tester.insert_weighted_and_compare(1, 300., 1., None);
+    tester.advance_time(1.);
+    tester.set_capacity_and_compare(0.);
+    assert!(tester.fast_model.peek().is_none());
+    assert!(tester.fast_model.pop().is_none());
+    tester.advance_time(2.);
+    tester.set_capacity_and_compare(1.);
+    assert_eq!(tester.pop_all_and_compare(), vec![(4.5, 0), (6., 1)]);
+}
@@ -0,0 +1,488 @@
+//! Fast implementation of weighted max-min throughput sharing model.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, BinaryHeap, HashMap};
+
+use sugars::boxed;
+
+use dslab_core::SimulationContext;
+
+use super::functions::{make_constant_throughput_fn, ConstantFactorFn};
+use super::model::{
+    ActivityFactorFn, ActivityId, DynamicThroughputSharingModel, ResourceThroughputFn, ThroughputSharingModel,
+};
+
+const TOTAL_WORK_MAX_VALUE: f64 = 1e12;
+
+enum ActivityState {
+    /// Gets the throughput proportional to the weight, finishes when the total work reaches `finish_work`.
+    Uncapped {
+        finish_work: f64,
+    },
+    /// Gets the maximum throughput, finishes at `finish_time`.
+    Capped {
+        finish_time: f64,
+    },
+    Suspended {
+        remaining_volume: f64,
+    },
+}
+
+struct Activity<T> {
+    item: T,
+    volume: f64,
+    factor: f64,
+    weight: f64,
+    max_throughput: f64,
+    state: ActivityState,
+}
+
+impl<T> Activity<T> {
+    /// Returns the throughput per unit of weight above which the activity is capped.
+    fn threshold(&self, id: ActivityId) -> Threshold {
+        Threshold {
+            value: self.max_throughput / self.weight,
+            id,
+        }
+    }
+}
+
+/// Heap entry referring to a running activity, ordered by the finish work or finish time.
+///
+/// Entries are not removed from the heap when the activity changes its state,
+/// instead they are discarded when they reach the top of the heap.
+struct HeapEntry {
+    id: ActivityId,
+    key: f64,
+}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key.total_cmp(&self.key).then(other.id.cmp(&self.id))
+    }
+}
+
+impl PartialEq for HeapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.id == other.id
+    }
+}
+
+impl Eq for HeapEntry {}
+
+#[derive(Clone, Copy)]
+struct Threshold {
+    value: f64,
+    id: ActivityId,
+}
+
+impl PartialOrd for Threshold {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Threshold {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.total_cmp(&other.value).then(self.id.cmp(&other.id))
+    }
+}
+
+impl PartialEq for Threshold {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Threshold {}
+
+/// Fast implementation of weighted max-min throughput sharing model.
+///
+/// Each activity has a weight and an optional maximum throughput. The resource throughput is shared between
+/// the activities proportionally to their weights, while the throughput of each activity does not exceed its maximum.
+/// The throughput not used by the capped activities is shared between the rest of activities.
+///
+/// Activities inserted via [`ThroughputSharingModel::insert`] have weight 1 and unlimited throughput,
+/// so without using [`Self::insert_weighted`] the model behaves like
+/// [`FairThroughputSharingModel`](super::FairThroughputSharingModel).
+pub struct WeightedThroughputSharingModel<T> {
+    uncapped_heap: BinaryHeap<HeapEntry>,
+    capped_heap: BinaryHeap<HeapEntry>,
+    activities: HashMap<ActivityId, Activity<T>>,
+    /// Thresholds of capped activities.
+    capped: BTreeSet<Threshold>,
+    /// Thresholds of uncapped activities with limited maximum throughput.
+    uncapped_limited: BTreeSet<Threshold>,
+    running_count: usize,
+    uncapped_weight: f64,
+    capped_throughput: f64,
+    throughput_function: ResourceThroughputFn,
+    factor_function: Box<dyn ActivityFactorFn<T>>,
+    capacity: f64,
+    throughput_per_weight: f64,
+    next_id: ActivityId,
+    total_work: f64,
+    last_update: f64,
+}
+
+impl<T> WeightedThroughputSharingModel<T> {
+    /// Creates model with given throughput and factor functions.
+    pub fn new(throughput_function: ResourceThroughputFn, factor_function: Box<dyn ActivityFactorFn<T>>) -> Self {
+        Self {
+            uncapped_heap: BinaryHeap::new(),
+            capped_heap: BinaryHeap::new(),
+            activities: HashMap::new(),
+            capped: BTreeSet::new(),
+            uncapped_limited: BTreeSet::new(),
+            running_count: 0,
+            uncapped_weight: 0.,
+            capped_throughput: 0.,
+            throughput_function,
+            factor_function,
+            capacity: 1.,
+            throughput_per_weight: 0.,
+            next_id: 0,
+            total_work: 0.,
+            last_update: 0.,
+        }
+    }
+
+    /// Creates model with fixed throughput.
+    pub fn with_fixed_throughput(throughput: f64) -> Self {
+        Self::with_dynamic_throughput(make_constant_throughput_fn(throughput))
+    }
+
+    /// Creates model with dynamic throughput, represented by given closure.
+    pub fn with_dynamic_throughput(throughput_function: ResourceThroughputFn) -> Self {
+        Self::new(throughput_function, boxed!(ConstantFactorFn::new(1.)))
+    }
+
+    /// Adds new activity with the given weight and optional maximum throughput into the model
+    /// and returns its identifier.
+    pub fn insert_weighted(
+        &mut self,
+        item: T,
+        volume: f64,
+        weight: f64,
+        max_throughput: Option<f64>,
+        ctx: &mut SimulationContext,
+    ) -> ActivityId {
+        assert!(weight > 0., "Activity weight must be positive");
+        self.advance(ctx.time());
+        let factor = self.factor_function.get_factor(&item, ctx);
+        let volume = volume / factor;
+        let id = self.next_id;
+        self.next_id += 1;
+        self.activities.insert(
+            id,
+            Activity {
+                item,
+                volume,
+                factor,
+                weight,
+                max_throughput: max_throughput.unwrap_or(f64::INFINITY),
+                state: ActivityState::Suspended {
+                    remaining_volume: volume,
+                },
+            },
+        );
+        self.start(id);
+        self.rebalance();
+        id
+    }
+
+    fn uncapped_count(&self) -> usize {
+        self.running_count - self.capped.len()
+    }
+
+    fn increment_total_work(&mut self, delta: f64) {
+        self.total_work += delta;
+        if self.total_work > TOTAL_WORK_MAX_VALUE {
+            let mut entries_vec = Vec::new();
+            for (id, activity) in self.activities.iter_mut() {
+                if let ActivityState::Uncapped { finish_work } = &mut activity.state {
+                    *finish_work -= self.total_work;
+                    entries_vec.push(HeapEntry {
+                        id: *id,
+                        key: *finish_work,
+                    });
+                }
+            }
+            self.uncapped_heap = entries_vec.into();
+            self.total_work = 0.;
+        }
+    }
+
+    /// Updates the total work to the given time.
+    fn advance(&mut self, time: f64) {
+        if self.uncapped_count() > 0 {
+            self.increment_total_work((time - self.last_update) * self.throughput_per_weight);
+        }
+        self.last_update = time;
+    }
+
+    /// Returns the remaining volume of the running activity at the last update time.
+    fn remaining_volume(&self, activity: &Activity<T>) -> f64 {
+        match activity.state {
+            ActivityState::Uncapped { finish_work } => (finish_work - self.total_work) * activity.weight,
+            ActivityState::Capped { finish_time } => (finish_time - self.last_update) * activity.max_throughput,
+            ActivityState::Suspended { remaining_volume } => remaining_volume,
+        }
+        .max(0.)
+    }
+
+    /// Starts the suspended activity as uncapped, [`Self::rebalance`] must be called afterwards.
+    fn start(&mut self, id: ActivityId) {
+        let activity = self.activities.get_mut(&id).unwrap();
+        if let ActivityState::Suspended { remaining_volume } = activity.state {
+            let finish_work = self.total_work + remaining_volume / activity.weight;
+            activity.state = ActivityState::Uncapped { finish_work };
+            self.uncapped_heap.push(HeapEntry { id, key: finish_work });
+            self.uncapped_weight += activity.weight;
+            if activity.max_throughput.is_finite() {
+                self.uncapped_limited.insert(activity.threshold(id));
+            }
+            self.running_count += 1;
+        }
+    }
+
+    /// Stops the running activity and returns its remaining volume, [`Self::rebalance`] must be called afterwards.
+    fn stop(&mut self, id: ActivityId) -> f64 {
+        let remaining_volume = self.remaining_volume(&self.activities[&id]);
+        let activity = self.activities.get_mut(&id).unwrap();
+        match activity.state {
+            ActivityState::Uncapped { .. } => {
+                self.uncapped_weight -= activity.weight;
+                self.uncapped_limited.remove(&activity.threshold(id));
+            }
+            ActivityState::Capped { .. } => {
+                self.capped_throughput -= activity.max_throughput;
+                self.capped.remove(&activity.threshold(id));
+            }
+            ActivityState::Suspended { .. } => return remaining_volume,
+        }
+        activity.state = ActivityState::Suspended { remaining_volume };
+        self.running_count -= 1;
+        remaining_volume
+    }
+
+    fn set_capped(&mut self, id: ActivityId, capped: bool) {
+        let remaining_volume = self.remaining_volume(&self.activities[&id]);
+        let activity = self.activities.get_mut(&id).unwrap();
+        let threshold = activity.threshold(id);
+        if capped {
+            let finish_time = self.last_update + remaining_volume / activity.max_throughput;
+            activity.state = ActivityState::Capped { finish_time };
+            self.capped_heap.push(HeapEntry { id, key: finish_time });
+            self.uncapped_limited.remove(&threshold);
+            self.capped.insert(threshold);
+            self.uncapped_weight -= activity.weight;
+            self.capped_throughput += activity.max_throughput;
+        } else {
+            let finish_work = self.total_work + remaining_volume / activity.weight;
+            activity.state = ActivityState::Uncapped { finish_work };
+            self.uncapped_heap.push(HeapEntry { id, key: finish_work });
+            self.capped.remove(&threshold);
+            self.uncapped_limited.insert(threshold);
+            self.capped_throughput -= activity.max_throughput;
+            self.uncapped_weight += activity.weight;
+        }
+    }
+
+    fn compute_throughput_per_weight(&mut self) -> f64 {
+        // reset the accumulated sums to avoid the rounding errors
+        if self.capped.is_empty() {
+            self.capped_throughput = 0.;
+        }
+        if self.running_count == 0 {
+            return f64::INFINITY;
+        }
+        let throughput = self.capacity * (self.throughput_function)(self.running_count);
+        if self.uncapped_count() == 0 {
+            self.uncapped_weight = 0.;
+            // if the resource throughput is not enough for all capped activities,
+            // zero value makes the activity with the largest threshold uncapped
+            return if throughput >= self.capped_throughput {
+                f64::INFINITY
+            } else {
+                0.
+            };
+        }
+        (throughput - self.capped_throughput).max(0.) / self.uncapped_weight
+    }
+
+    /// Recomputes the throughput allocation after the set of running activities is changed.
+    ///
+    /// The capped activities always form a prefix of running activities sorted by the threshold,
+    /// so only the activities near the boundary of this prefix are moved between capped and uncapped.
+    fn rebalance(&mut self) {
+        let mut throughput_per_weight = self.compute_throughput_per_weight();
+        while let Some(&threshold) = self.capped.last() {
+            if threshold.value < throughput_per_weight {
+                break;
+            }
+            self.set_capped(threshold.id, false);
+            throughput_per_weight = self.compute_throughput_per_weight();
+        }
+        while let Some(&threshold) = self.uncapped_limited.first() {
+            if threshold.value >= throughput_per_weight {
+                break;
+            }
+            self.set_capped(threshold.id, true);
+            throughput_per_weight = self.compute_throughput_per_weight();
+        }
+        self.throughput_per_weight = throughput_per_weight;
+        self.discard_stale_entries();
+    }
+
+    /// Removes the stale entries from the top of the heaps,
+    /// so that the top entries always refer to the activities in the corresponding state.
+    fn discard_stale_entries(&mut self) {
+        while let Some(entry) = self.uncapped_heap.peek() {
+            match self.activities.get(&entry.id) {
+                Some(Activity {
+                    state: ActivityState::Uncapped { finish_work },
+                    ..
+                }) if *finish_work == entry.key => break,
+                _ => self.uncapped_heap.pop(),
+            };
+        }
+        while let Some(entry) = self.capped_heap.peek() {
+            match self.activities.get(&entry.id) {
+                Some(Activity {
+                    state: ActivityState::Capped { finish_time },
+                    ..
+                }) if *finish_time == entry.key => break,
+                _ => self.capped_heap.pop(),
+            };
+        }
+    }
+
+    /// Returns the next completion time along with the activity id and the flag indicating whether it is capped.
+    fn next_completion(&self) -> Option<(f64, ActivityId, bool)> {
+        let uncapped = self.uncapped_heap.peek().map(|entry| {
+            (
+                self.last_update + (entry.key - self.total_work) / self.throughput_per_weight,
+                entry.id,
+                false,
+            )
+        });
+        let capped = self.capped_heap.peek().map(|entry| (entry.key, entry.id, true));
+        match (uncapped, capped) {
+            (Some(u), Some(c)) => {
+                if c.0.total_cmp(&u.0).then(c.1.cmp(&u.1)).is_lt() {
+                    Some(c)
+                } else {
+                    Some(u)
+                }
+            }
+            (u, c) => u.or(c),
+        }
+    }
+}
+
+impl<T> ThroughputSharingModel<T> for WeightedThroughputSharingModel<T> {
+    fn insert(&mut self, item: T, volume: f64, ctx: &mut SimulationContext) -> ActivityId {
+        self.insert_weighted(item, volume, 1., None, ctx)
+    }
+
+    fn pop(&mut self) -> Option<(f64, T)> {
+        if self.capacity == 0. {
+            return None;
+        }
+        let (finish_time, id, capped) = self.next_completion()?;
+        if capped {
+            self.advance(finish_time);
+        } else {
+            let entry = self.uncapped_heap.peek().unwrap();
+            self.increment_total_work(entry.key - self.total_work);
+            self.last_update = finish_time;
+        }
+        self.stop(id);
+        let activity = self.activities.remove(&id).unwrap();
+        self.rebalance();
+        Some((finish_time, activity.item))
+    }
+
+    fn peek(&self) -> Option<(f64, &T)> {
+        if self.capacity == 0. {
+            return None;
+        }
+        self.next_completion()
+            .map(|(time, id, _)| (time, &self.activities[&id].item))
+    }
+}
+
+impl<T> DynamicThroughputSharingModel<T> for WeightedThroughputSharingModel<T> {
+    fn remove(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> Option<(T, f64)> {
+        if !self.activities.contains_key(&id) {
+            return None;
+        }
+        self.advance(ctx.time());
+        let remaining_volume = self.stop(id);
+        let activity = self.activities.remove(&id).unwrap();
+        self.rebalance();
+        Some((activity.item, remaining_volume * activity.factor))
+    }
+
+    fn suspend(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> bool {
+        match self.activities.get(&id) {
+            Some(Activity {
+                state: ActivityState::Suspended { .. },
+                ..
+            })
+            | None => false,
+            _ => {
+                self.advance(ctx.time());
+                self.stop(id);
+                self.rebalance();
+                true
+            }
+        }
+    }
+
+    fn resume(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> bool {
+        match self.activities.get(&id) {
+            Some(Activity {
+                state: ActivityState::Suspended { .. },
+                ..
+            }) => {
+                self.advance(ctx.time());
+                self.start(id);
+                self.rebalance();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn progress(&self, id: ActivityId, ctx: &SimulationContext) -> Option<f64> {
+        self.activities.get(&id).map(|activity| {
+            let elapsed = ctx.time() - self.last_update;
+            let processed_volume = match activity.state {
+                ActivityState::Uncapped { .. } => elapsed * self.throughput_per_weight * activity.weight,
+                ActivityState::Capped { .. } => elapsed * activity.max_throughput,
+                ActivityState::Suspended { .. } => 0.,
+            };
+            let remaining_volume = self.remaining_volume(activity) - processed_volume;
+            if activity.volume > 0. {
+                (1. - remaining_volume / activity.volume).clamp(0., 1.)
+            } else {
+                1.
+            }
+        })
+    }
+
+    fn set_capacity(&mut self, capacity: f64, ctx: &mut SimulationContext) {
+        assert!(capacity >= 0., "Capacity must be non-negative");
+        self.advance(ctx.time());
+        self.capacity = capacity;
+        self.rebalance();
+    }
+}
@@ -0,0 +1,241 @@
+//! Slow implementation of weighted max-min throughput sharing model, which recalculates the throughput of all
+//! activities at each activity creation and completion.
+
+use std::collections::HashMap;
+
+use sugars::boxed;
+
+use dslab_core::SimulationContext;
+
+use super::functions::{make_constant_throughput_fn, ConstantFactorFn};
+use super::model::{
+    ActivityFactorFn, ActivityId, DynamicThroughputSharingModel, ResourceThroughputFn, ThroughputSharingModel,
+};
+
+struct Activity<T> {
+    id: ActivityId,
+    item: T,
+    remaining_volume: f64,
+    volume: f64,
+    factor: f64,
+    weight: f64,
+    max_throughput: f64,
+    throughput: f64,
+}
+
+impl<T> Activity<T> {
+    fn completion_time(&self, current_time: f64) -> f64 {
+        current_time + self.remaining_volume / self.throughput
+    }
+}
+
+/// Slow implementation of weighted max-min throughput sharing model, which recalculates the throughput of all
+/// activities at each activity creation and completion.
+pub struct SlowWeightedThroughputSharingModel<T> {
+    throughput_function: ResourceThroughputFn,
+    factor_function: Box<dyn ActivityFactorFn<T>>,
+    entries: Vec<Activity<T>>,
+    suspended: HashMap<ActivityId, Activity<T>>,
+    next_id: ActivityId,
+    capacity: f64,
+    last_recalculation_time: f64,
+}
+
+impl<T> SlowWeightedThroughputSharingModel<T> {
+    /// Creates model with given throughput and factor functions.
+    pub fn new(throughput_function: ResourceThroughputFn, factor_function: Box<dyn ActivityFactorFn<T>>) -> Self {
+        Self {
+            throughput_function,
+            factor_function,
+            entries: Vec::new(),
+            suspended: HashMap::new(),
+            next_id: 0,
+            capacity: 1.,
+            last_recalculation_time: 0.,
+        }
+    }
+
+    /// Creates model with fixed throughput.
+    pub fn with_fixed_throughput(throughput: f64) -> Self {
+        Self::with_dynamic_throughput(make_constant_throughput_fn(throughput))
+    }
+
+    /// Creates model with dynamic throughput, represented by given closure.
+    pub fn with_dynamic_throughput(throughput_function: ResourceThroughputFn) -> Self {
+        Self::new(throughput_function, boxed!(ConstantFactorFn::new(1.)))
+    }
+
+    /// Adds new activity with the given weight and optional maximum throughput into the model
+    /// and returns its identifier.
+    pub fn insert_weighted(
+        &mut self,
+        item: T,
+        volume: f64,
+        weight: f64,
+        max_throughput: Option<f64>,
+        ctx: &mut SimulationContext,
+    ) -> ActivityId {
+        assert!(weight > 0., "Activity weight must be positive");
+        self.update(ctx.time());
+        let factor = self.factor_function.get_factor(&item, ctx);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.push(Activity {
+            id,
+            item,
+            remaining_volume: volume / factor,
+            volume: volume / factor,
+            factor,
+            weight,
+            max_throughput: max_throughput.unwrap_or(f64::INFINITY),
+            throughput: 0.,
+        });
+        self.allocate();
+        id
+    }
+
+    /// Updates the remaining volumes of all activities to the given time.
+    fn update(&mut self, current_time: f64) {
+        let elapsed = current_time - self.last_recalculation_time;
+        for entry in self.entries.iter_mut() {
+            entry.remaining_volume -= entry.throughput * elapsed;
+        }
+        self.last_recalculation_time = current_time;
+    }
+
+    /// Computes the throughput of all activities by progressive filling.
+    fn allocate(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let mut remaining_throughput = self.capacity * (self.throughput_function)(self.entries.len());
+        let mut remaining_weight: f64 = self.entries.iter().map(|entry| entry.weight).sum();
+        let mut order: Vec<usize> = (0..self.entries.len()).collect();
+        order.sort_by(|&a, &b| {
+            let a = &self.entries[a];
+            let b = &self.entries[b];
+            (a.max_throughput / a.weight).total_cmp(&(b.max_throughput / b.weight))
+        });
+        let mut uncapped_start = 0;
+        for &idx in order.iter() {
+            let entry = &mut self.entries[idx];
+            let throughput_per_weight = remaining_throughput.max(0.) / remaining_weight;
+            if entry.max_throughput / entry.weight >= throughput_per_weight {
+                break;
+            }
+            entry.throughput = entry.max_throughput;
+            remaining_throughput -= entry.max_throughput;
+            remaining_weight -= entry.weight;
+            uncapped_start += 1;
+        }
+        if uncapped_start < order.len() {
+            let throughput_per_weight = remaining_throughput.max(0.) / remaining_weight;
+            for &idx in order[uncapped_start..].iter() {
+                let entry = &mut self.entries[idx];
+                entry.throughput = throughput_per_weight * entry.weight;
+            }
+        }
+    }
+
+    fn next_completion(&self) -> Option<usize> {
+        (0..self.entries.len()).min_by(|&a, &b| {
+            let a = &self.entries[a];
+            let b = &self.entries[b];
+            a.completion_time(self.last_recalculation_time)
+                .total_cmp(&b.completion_time(self.last_recalculation_time))
+                .then(a.id.cmp(&b.id))
+        })
+    }
+
+    /// Updates the activities at the current time and takes out the running activity with given id.
+    fn take_running(&mut self, id: ActivityId, current_time: f64) -> Option<Activity<T>> {
+        let pos = self.entries.iter().position(|entry| entry.id == id)?;
+        self.update(current_time);
+        let mut activity = self.entries.remove(pos);
+        activity.remaining_volume = activity.remaining_volume.max(0.);
+        self.allocate();
+        Some(activity)
+    }
+}
+
+impl<T> ThroughputSharingModel<T> for SlowWeightedThroughputSharingModel<T> {
+    fn insert(&mut self, item: T, volume: f64, ctx: &mut SimulationContext) -> ActivityId {
+        self.insert_weighted(item, volume, 1., None, ctx)
+    }
+
+    fn pop(&mut self) -> Option<(f64, T)> {
+        if self.capacity == 0. {
+            return None;
+        }
+        let pos = self.next_completion()?;
+        let complete_time = self.entries[pos].completion_time(self.last_recalculation_time);
+        let id = self.entries[pos].id;
+        let activity = self.take_running(id, complete_time).unwrap();
+        Some((complete_time, activity.item))
+    }
+
+    fn peek(&self) -> Option<(f64, &T)> {
+        if self.capacity == 0. {
+            return None;
+        }
+        self.next_completion().map(|pos| {
+            let entry = &self.entries[pos];
+            (entry.completion_time(self.last_recalculation_time), &entry.item)
+        })
+    }
+}
+
+impl<T> DynamicThroughputSharingModel<T> for SlowWeightedThroughputSharingModel<T> {
+    fn remove(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> Option<(T, f64)> {
+        let activity = self
+            .suspended
+            .remove(&id)
+            .or_else(|| self.take_running(id, ctx.time()))?;
+        Some((activity.item, activity.remaining_volume * activity.factor))
+    }
+
+    fn suspend(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> bool {
+        match self.take_running(id, ctx.time()) {
+            Some(activity) => {
+                self.suspended.insert(id, activity);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn resume(&mut self, id: ActivityId, ctx: &mut SimulationContext) -> bool {
+        match self.suspended.remove(&id) {
+            Some(activity) => {
+                self.update(ctx.time());
+                self.entries.push(activity);
+                self.allocate();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn progress(&self, id: ActivityId, ctx: &SimulationContext) -> Option<f64> {
+        let (activity, remaining_volume) = match self.suspended.get(&id) {
+            Some(activity) => (activity, activity.remaining_volume),
+            None => {
+                let activity = self.entries.iter().find(|entry| entry.id == id)?;
+                let elapsed = ctx.time() - self.last_recalculation_time;
+                (activity, activity.remaining_volume - activity.throughput * elapsed)
+            }
+        };
+        if activity.volume > 0. {
+            Some((1. - remaining_volume / activity.volume).clamp(0., 1.))
+        } else {
+            Some(1.)
+        }
+    }
+
+    fn set_capacity(&mut self, capacity: f64, ctx: &mut SimulationContext) {
+        assert!(capacity >= 0., "Capacity must be non-negative");
+        self.update(ctx.time());
+        self.capacity = capacity;
+        self.allocate();
+    }
+}
@@ -0,0 +1,100 @@
+//! Energy consumption accounting for network devices.
+
+use std::collections::BTreeMap;
+
+use crate::{LinkId, NodeId, Topology};
+
+/// Accumulates the energy consumed by network nodes and links with attached power models.
+///
+/// The power consumption is assumed to be constant between the updates, which are performed upon each change
+/// of link usage.
+pub(crate) struct EnergyMeter {
+    last_update_time: f64,
+    node_power: BTreeMap<NodeId, f64>,
+    node_energy: BTreeMap<NodeId, f64>,
+    link_power: BTreeMap<LinkId, f64>,
+    link_energy: BTreeMap<LinkId, f64>,
+}
+
+impl EnergyMeter {
+    pub fn new(time: f64) -> Self {
+        Self {
+            last_update_time: time,
+            node_power: BTreeMap::new(),
+            node_energy: BTreeMap::new(),
+            link_power: BTreeMap::new(),
+            link_energy: BTreeMap::new(),
+        }
+    }
+
+    /// Accounts the energy consumed since the last update and recomputes the power consumption
+    /// using the current link utilizations.
+    pub fn update(&mut self, time: f64, topology: &Topology, link_utilization: &[f64]) {
+        let elapsed = time - self.last_update_time;
+        for (&node_id, &power) in self.node_power.iter() {
+            *self.node_energy.entry(node_id).or_default() += power * elapsed;
+        }
+        for (&link_id, &power) in self.link_power.iter() {
+            *self.link_energy.entry(link_id).or_default() += power * elapsed;
+        }
+        self.last_update_time = time;
+
+        self.node_power = topology
+            .node_power_models()
+            .map(|(node_id, model)| {
+                let ports = port_utilizations(node_id, topology, link_utilization);
+                (node_id, model.get_power(&ports))
+            })
+            .collect();
+        // removed links are turned off and do not consume power
+        self.link_power = topology
+            .link_power_models()
+            .filter(|(link_id, _)| !topology.is_link_removed(*link_id))
+            .map(|(link_id, model)| (link_id, model.get_power(link_utilization[link_id])))
+            .collect();
+    }
+
+    pub fn node_power(&self, node_id: NodeId) -> f64 {
+        self.node_power.get(&node_id).copied().unwrap_or(0.)
+    }
+
+    pub fn link_power(&self, link_id: LinkId) -> f64 {
+        self.link_power.get(&link_id).copied().unwrap_or(0.)
+    }
+
+    pub fn total_power(&self) -> f64 {
+        self.node_power.values().sum::<f64>() + self.link_power.values().sum::<f64>()
+    }
+
+    pub fn node_energy(&self, node_id: NodeId, time: f64) -> f64 {
+        self.node_energy.get(&node_id).copied().unwrap_or(0.)
+            + self.node_power(node_id) * (time - self.last_update_time)
+    }
+
+    pub fn link_energy(&self, link_id: LinkId, time: f64) -> f64 {
+        self.link_energy.get(&link_id).copied().unwrap_or(0.)
+            + self.link_power(link_id) * (time - self.last_update_time)
+    }
+
+    pub fn total_energy(&self, time: f64) -> f64 {
+        self.node_energy.values().sum::<f64>()
+            + self.link_energy.values().sum::<f64>()
+            + self.total_power() * (time - self.last_update_time)
+    }
+}
+
+/// Returns the utilizations of node ports.
+///
+/// Each port corresponds to a neighbor node connected by at least one link, its utilization is equal to
+/// the maximum utilization of these links.
+fn port_utilizations(node_id: NodeId, topology: &Topology, link_utilization: &[f64]) -> Vec<f64> {
+    let mut ports: BTreeMap<NodeId, f64> = BTreeMap::new();
+    let links = topology.node_links_map()[&node_id]
+        .iter()
+        .chain(topology.inv_node_links_map()[&node_id].iter());
+    for (&neighbor, &link_id) in links {
+        let port = ports.entry(neighbor).or_default();
+        *port = port.max(link_utilization[link_id]);
+    }
+    ports.into_values().collect()
+}
@@ -13,6 +13,20 @@
 //! information about the network [`Topology`] (links connecting the nodes) and relies on
 //! [`RoutingAlgorithm`](crate::routing::RoutingAlgorithm) to compute paths between the nodes. The link's bandwidth is
 //! shared fairly among the transfers using the link.
+//! - [`TcpAwareNetworkModel`](crate::models::TcpAwareNetworkModel): Topology-aware model similar to SimGrid's LV08
+//! model, where the link's bandwidth is shared among the transfers with weights depending on their round-trip times
+//! and the transfer throughput is bounded by the TCP window size.
+//! - [`PacketLevelNetworkModel`](crate::models::PacketLevelNetworkModel): Topology-aware model which simulates the
+//! transmission of individual packets through the link queues with a simple window-based congestion control.
+//!
+//! ## Messages
+//!
+//! Besides data transfers, components can exchange messages and events via [`Network`]. The messages sent with
+//! [`Network::send_msg`] and [`Network::send_event`] are delivered after the path latency, while the sized variants
+//! [`Network::send_sized_msg`] and [`Network::send_sized_event`] also account for the message size according to
+//! the [`MessageDeliveryMode`]. The sized messages are used only by the components which send their messages
+//! via [`Network`] explicitly. In particular, the DAG runner in `dslab-dag` submits computations to resources directly
+//! and `dslab-iaas` components use the fixed message delay from the simulation config.
 //!
 //! ## Examples
 //!
@@ -26,16 +40,20 @@
 
 #![warn(missing_docs)]
 
+mod energy;
 pub mod link;
 pub mod model;
 pub mod models;
+pub mod monitoring;
 pub mod network;
 pub mod node;
+pub mod platform;
 pub mod routing;
 pub mod topology;
+pub mod traffic;
 
 pub use link::{BandwidthSharingPolicy, Link, LinkId};
-pub use model::{DataTransfer, DataTransferCompleted, NetworkModel};
-pub use network::{Message, MessageDelivered, Network};
+pub use model::{DataTransfer, DataTransferCompleted, ModelWakeup, NetworkModel};
+pub use network::{Message, MessageDelivered, MessageDeliveryMode, MulticastCompleted, Network};
 pub use node::{Node, NodeId};
 pub use topology::Topology;
@@ -5,7 +5,8 @@ use serde::Serialize;
 use dslab_core::component::Id;
 use dslab_core::context::SimulationContext;
 
-use crate::{NodeId, Topology};
+use crate::monitoring::LinkStats;
+use crate::{LinkId, NodeId, Topology};
 
 /// Represents a data transfer between two simulation components located on a network.
 #[derive(Clone, Debug, Serialize)]
@@ -33,6 +34,13 @@ pub struct DataTransferCompleted {
     pub dt: DataTransfer,
 }
 
+/// Event used by the network model to schedule its own processing at some future time.
+///
+/// The event must be emitted via [`SimulationContext::emit_self`], upon its receipt the network calls
+/// [`NetworkModel::on_wakeup`] of the main network model (local models of nodes do not receive this callback).
+#[derive(Clone, Serialize)]
+pub struct ModelWakeup {}
+
 /// Network model interface.
 ///
 /// The main functions of the network model:
@@ -51,6 +59,14 @@ pub trait NetworkModel {
     /// Returns the network latency from node `src` to node `dst`.
     fn latency(&self, src: NodeId, dst: NodeId) -> f64;
 
+    /// Returns true if the model simulates the propagation of data through the network by itself.
+    ///
+    /// Otherwise the network delays the start of each data transfer by the latency between its source and
+    /// destination, so that the model needs to calculate only the remaining part of transfer time.
+    fn simulates_latency(&self) -> bool {
+        false
+    }
+
     /// Starts data transfer.
     ///
     /// Must calculate the transfer completion time and emit the [`DataTransferCompleted`] event at this time.
@@ -62,6 +78,25 @@ pub trait NetworkModel {
     /// This is necessary since the model itself does not receive the [`DataTransferCompleted`] event.
     fn on_transfer_completion(&mut self, dt: DataTransfer, ctx: &mut SimulationContext);
 
+    /// Returns true if the model supports multicast transfers via [`Self::start_multicast_transfer`].
+    fn supports_multicast(&self) -> bool {
+        false
+    }
+
+    /// Starts multicast data transfer over the distribution tree consisting of the given links.
+    ///
+    /// The data crosses each link of the tree once, so the transfer must be accounted as a single transfer on each
+    /// of these links. The transfer is completed in the same way as in [`Self::start_transfer`], the propagation of
+    /// data from the source to each of the destinations (latency) is accounted by the network.
+    fn start_multicast_transfer(&mut self, _dt: DataTransfer, _tree: Vec<LinkId>, _ctx: &mut SimulationContext) {
+        panic!("Multicast transfers are not supported by this model");
+    }
+
+    /// Callback for processing the [`ModelWakeup`] event previously emitted by the model.
+    ///
+    /// Can be used by models which simulate intermediate steps of data transfers, e.g. packet transmissions.
+    fn on_wakeup(&mut self, _ctx: &mut SimulationContext) {}
+
     /// Returns a reference to inner network topology.
     ///
     /// Must be implemented for topology-aware model.
@@ -84,6 +119,28 @@ pub trait NetworkModel {
         None
     }
 
+    /// Returns the links on the path from node `src` to node `dst`.
+    ///
+    /// Must be implemented for topology-aware model.
+    fn path(&self, _src: NodeId, _dst: NodeId) -> Vec<LinkId> {
+        assert!(
+            !self.is_topology_aware(),
+            "This method must be implemented for topology-aware model"
+        );
+        Vec::new()
+    }
+
+    /// Returns the current statistics of the link at the given time.
+    ///
+    /// Must be implemented for topology-aware model.
+    fn link_stats(&self, _link_id: LinkId, _time: f64) -> Option<LinkStats> {
+        assert!(
+            !self.is_topology_aware(),
+            "This method must be implemented for topology-aware model"
+        );
+        None
+    }
+
     /// Callback for notifying topology-aware model about the topology change.
     ///
     /// Must be implemented for topology-aware model.
@@ -93,4 +150,27 @@ pub trait NetworkModel {
             "This method must be implemented for topology-aware model"
         );
     }
+
+    /// Callback for notifying topology-aware model about the node added after the topology initialization.
+    ///
+    /// By default calls [`Self::on_topology_change`].
+    fn on_node_added(&mut self, _node_id: NodeId, ctx: &mut SimulationContext) {
+        self.on_topology_change(ctx);
+    }
+
+    /// Callback for notifying topology-aware model about the link added after the topology initialization.
+    ///
+    /// Called only if the link connects the nodes which were not directly connected before,
+    /// otherwise [`Self::on_topology_change`] is called.
+    /// By default calls [`Self::on_topology_change`].
+    fn on_link_added(&mut self, _link_id: LinkId, ctx: &mut SimulationContext) {
+        self.on_topology_change(ctx);
+    }
+
+    /// Callback for notifying topology-aware model about the link removed after the topology initialization.
+    ///
+    /// By default calls [`Self::on_topology_change`].
+    fn on_link_removed(&mut self, _link_id: LinkId, ctx: &mut SimulationContext) {
+        self.on_topology_change(ctx);
+    }
 }
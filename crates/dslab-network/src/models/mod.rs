@@ -1,9 +1,13 @@
 //! Network model implementations.
 
 pub mod constant;
+pub mod packet_level;
 pub mod shared;
+pub mod tcp_aware;
 pub mod topology_aware;
 
 pub use constant::ConstantBandwidthNetworkModel;
+pub use packet_level::PacketLevelNetworkModel;
 pub use shared::SharedBandwidthNetworkModel;
+pub use tcp_aware::TcpAwareNetworkModel;
 pub use topology_aware::TopologyAwareNetworkModel;
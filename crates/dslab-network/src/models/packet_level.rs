@@ -0,0 +1,579 @@
+//! Packet-level network model.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap, VecDeque};
+
+use dslab_core::context::SimulationContext;
+use dslab_core::event::EventId;
+use dslab_core::log_debug;
+
+use crate::model::ModelWakeup;
+use crate::monitoring::{LinkStats, LinkStatsTracker};
+use crate::routing::{RoutingAlgorithm, ShortestPathFloydWarshall};
+use crate::{BandwidthSharingPolicy, DataTransfer, DataTransferCompleted, LinkId, NetworkModel, NodeId, Topology};
+
+// Packets and events --------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug)]
+struct Packet {
+    transfer_id: usize,
+    seq: usize,
+    size: f64,
+    hop: usize,
+    attempt: u32,
+    send_time: f64,
+}
+
+#[derive(Debug)]
+enum PacketEvent {
+    /// Packet arrives to the node preceding the hop `packet.hop` of the path (or to the destination).
+    Arrival(Packet),
+    /// Link finished the transmission of the packet.
+    Transmitted(LinkId, Packet),
+    /// Sender receives the acknowledgement of the packet.
+    Ack(Packet),
+    /// Retransmission timeout of the packet expires.
+    Timeout(Packet),
+}
+
+struct ScheduledEvent {
+    time: f64,
+    order: u64,
+    event: PacketEvent,
+}
+
+impl Ord for ScheduledEvent {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed order to extract the earliest event from BinaryHeap
+        other
+            .time
+            .total_cmp(&self.time)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl PartialOrd for ScheduledEvent {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for ScheduledEvent {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ScheduledEvent {}
+
+#[derive(Default)]
+struct EventQueue {
+    heap: BinaryHeap<ScheduledEvent>,
+    next_order: u64,
+}
+
+impl EventQueue {
+    fn push(&mut self, time: f64, event: PacketEvent) {
+        self.heap.push(ScheduledEvent {
+            time,
+            order: self.next_order,
+            event,
+        });
+        self.next_order += 1;
+    }
+
+    fn next_time(&self) -> Option<f64> {
+        self.heap.peek().map(|e| e.time)
+    }
+
+    fn pop_until(&mut self, time: f64) -> Option<ScheduledEvent> {
+        if self.next_time()? <= time {
+            self.heap.pop()
+        } else {
+            None
+        }
+    }
+}
+
+// Transfer state ------------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum PacketStatus {
+    NotSent,
+    InFlight(u32),
+    Lost,
+    Acked,
+}
+
+struct PacketTransfer {
+    dt: DataTransfer,
+    path: Vec<LinkId>,
+    ack_delay: f64,
+    packet_count: usize,
+    packet_size: f64,
+    // sender state
+    status: Vec<PacketStatus>,
+    attempts: Vec<u32>,
+    next_seq: usize,
+    retransmissions: VecDeque<usize>,
+    in_flight: usize,
+    window: f64,
+    slow_start_threshold: f64,
+    last_window_reduction: f64,
+    smoothed_rtt: Option<f64>,
+    rtt_variation: f64,
+    timeout: f64,
+    // receiver state
+    received: Vec<bool>,
+    received_count: usize,
+}
+
+impl PacketTransfer {
+    fn packet_size(&self, seq: usize) -> f64 {
+        if seq + 1 == self.packet_count {
+            self.dt.size - self.packet_size * seq as f64
+        } else {
+            self.packet_size
+        }
+    }
+
+    fn on_rtt_sample(&mut self, rtt: f64, min_timeout: f64) {
+        match self.smoothed_rtt {
+            Some(srtt) => {
+                self.rtt_variation = 0.75 * self.rtt_variation + 0.25 * (srtt - rtt).abs();
+                self.smoothed_rtt = Some(0.875 * srtt + 0.125 * rtt);
+            }
+            None => {
+                self.rtt_variation = rtt / 2.;
+                self.smoothed_rtt = Some(rtt);
+            }
+        }
+        self.timeout = (self.smoothed_rtt.unwrap() + 4. * self.rtt_variation).max(min_timeout);
+    }
+}
+
+// Link state ----------------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Default)]
+struct LinkState {
+    queue: VecDeque<Packet>,
+    transmitting: usize,
+    transfers: usize,
+    stats: LinkStatsTracker,
+}
+
+// Model ---------------------------------------------------------------------------------------------------------------
+
+/// Topology-aware model which simulates the transmission of individual packets.
+///
+/// Each data transfer is split into packets of MTU size which are sent along the path computed by
+/// [`RoutingAlgorithm`](crate::routing::RoutingAlgorithm). A link transmits one packet at a time, while the arriving
+/// packets wait in the link's output queue of finite size. The packets arriving to the full queue are dropped
+/// (drop-tail policy). Links with [`BandwidthSharingPolicy::NonShared`] policy transmit all packets immediately
+/// without queueing.
+///
+/// The sending rate of each transfer is controlled by a simple window-based congestion control similar to TCP Reno.
+/// Window grows by one packet per acknowledgement during slow start and by one packet per window afterwards.
+/// Lost packets are detected via retransmission timeout, which is computed from the measured round-trip times,
+/// and cause the window to be halved. Acknowledgements are delivered back to the sender after the path latency
+/// without consuming the link bandwidth.
+///
+/// The transfer is completed when all its packets are received by the destination.
+pub struct PacketLevelNetworkModel {
+    topology: Topology,
+    routing: Box<dyn RoutingAlgorithm>,
+    mtu: f64,
+    queue_size: usize,
+    initial_window: f64,
+    max_window: f64,
+    min_timeout: f64,
+    transfers: BTreeMap<usize, PacketTransfer>,
+    links: Vec<LinkState>,
+    events: EventQueue,
+    next_wakeup: Option<(EventId, f64)>,
+}
+
+impl Default for PacketLevelNetworkModel {
+    fn default() -> Self {
+        PacketLevelNetworkModel {
+            topology: Topology::default(),
+            routing: Box::<ShortestPathFloydWarshall>::default(),
+            mtu: 1500.,
+            queue_size: 100,
+            initial_window: 10.,
+            max_window: f64::INFINITY,
+            min_timeout: 0.,
+            transfers: BTreeMap::new(),
+            links: Vec::new(),
+            events: EventQueue::default(),
+            next_wakeup: None,
+        }
+    }
+}
+
+impl PacketLevelNetworkModel {
+    /// Creates a new network model with empty topology.
+    ///
+    /// Uses [`ShortestPathFloydWarshall`] as default routing algorithm, MTU of 1500 bytes, queues of 100 packets
+    /// and initial congestion window of 10 packets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the used routing algorithm.
+    pub fn with_routing(mut self, routing: Box<dyn RoutingAlgorithm>) -> Self {
+        self.routing = routing;
+        self
+    }
+
+    /// Sets the maximum packet size.
+    pub fn with_mtu(mut self, mtu: f64) -> Self {
+        assert!(mtu > 0., "MTU must be > 0");
+        self.mtu = mtu;
+        self
+    }
+
+    /// Sets the maximum number of packets waiting in the output queue of each link.
+    pub fn with_queue_size(mut self, queue_size: usize) -> Self {
+        self.queue_size = queue_size;
+        self
+    }
+
+    /// Sets the initial size of congestion window (in packets).
+    pub fn with_initial_window(mut self, initial_window: f64) -> Self {
+        assert!(initial_window >= 1., "Initial window must be >= 1");
+        self.initial_window = initial_window;
+        self
+    }
+
+    /// Sets the maximum size of congestion window (in packets), which models the receiver window.
+    pub fn with_max_window(mut self, max_window: f64) -> Self {
+        assert!(max_window >= 1., "Max window must be >= 1");
+        self.max_window = max_window;
+        self
+    }
+
+    /// Sets the lower bound of retransmission timeout.
+    pub fn with_min_retransmission_timeout(mut self, min_timeout: f64) -> Self {
+        self.min_timeout = min_timeout;
+        self
+    }
+
+    fn get_path(&self, src: NodeId, dst: NodeId) -> Vec<LinkId> {
+        self.routing
+            .get_path_iter(src, dst, &self.topology)
+            .unwrap_or_else(|| panic!("No path from {} to {}", src, dst))
+            .collect()
+    }
+
+    /// Sends the packets of transfer allowed by its congestion window.
+    fn send_packets(&mut self, transfer_id: usize, time: f64) {
+        let transfer = self.transfers.get_mut(&transfer_id).unwrap();
+        while (transfer.in_flight as f64) < transfer.window.floor().max(1.) {
+            let seq = loop {
+                match transfer.retransmissions.pop_front() {
+                    Some(seq) if transfer.status[seq] == PacketStatus::Lost => break Some(seq),
+                    Some(_) => continue,
+                    None if transfer.next_seq < transfer.packet_count => {
+                        transfer.next_seq += 1;
+                        break Some(transfer.next_seq - 1);
+                    }
+                    None => break None,
+                }
+            };
+            let Some(seq) = seq else { break };
+            transfer.attempts[seq] += 1;
+            transfer.status[seq] = PacketStatus::InFlight(transfer.attempts[seq]);
+            transfer.in_flight += 1;
+            let packet = Packet {
+                transfer_id,
+                seq,
+                size: transfer.packet_size(seq),
+                hop: 0,
+                attempt: transfer.attempts[seq],
+                send_time: time,
+            };
+            self.events
+                .push(time + transfer.timeout, PacketEvent::Timeout(packet.clone()));
+            self.events.push(time, PacketEvent::Arrival(packet));
+        }
+    }
+
+    fn start_transmission(&mut self, link_id: LinkId, packet: Packet, time: f64) {
+        let bandwidth = self.topology.link(link_id).bandwidth;
+        let link = &mut self.links[link_id];
+        link.stats.advance(time, bandwidth);
+        link.transmitting += 1;
+        link.stats.throughput = link.transmitting as f64 * bandwidth;
+        self.events.push(
+            time + packet.size / bandwidth,
+            PacketEvent::Transmitted(link_id, packet),
+        );
+    }
+
+    fn on_packet_arrival(&mut self, packet: Packet, time: f64, ctx: &mut SimulationContext) {
+        let Some(transfer) = self.transfers.get_mut(&packet.transfer_id) else {
+            // transfer is already completed
+            return;
+        };
+        if packet.hop < transfer.path.len() {
+            let link_id = transfer.path[packet.hop];
+            let link = &mut self.links[link_id];
+            if matches!(
+                self.topology.link(link_id).sharing_policy,
+                BandwidthSharingPolicy::NonShared
+            ) || link.transmitting == 0
+            {
+                self.start_transmission(link_id, packet, time);
+            } else if link.queue.len() < self.queue_size {
+                link.queue.push_back(packet);
+            } else {
+                log_debug!(
+                    ctx,
+                    "dropped packet {} of data transfer {} at link {}",
+                    packet.seq,
+                    packet.transfer_id,
+                    link_id
+                );
+            }
+            return;
+        }
+
+        if !transfer.received[packet.seq] {
+            transfer.received[packet.seq] = true;
+            transfer.received_count += 1;
+        }
+        if transfer.received_count == transfer.packet_count {
+            let transfer = self.transfers.remove(&packet.transfer_id).unwrap();
+            for &link_id in transfer.path.iter() {
+                self.links[link_id].transfers -= 1;
+            }
+            ctx.emit_self(DataTransferCompleted { dt: transfer.dt }, (time - ctx.time()).max(0.));
+        } else {
+            self.events.push(time + transfer.ack_delay, PacketEvent::Ack(packet));
+        }
+    }
+
+    fn on_packet_transmitted(&mut self, link_id: LinkId, mut packet: Packet, time: f64) {
+        let link_params = self.topology.link(link_id);
+        let (bandwidth, latency) = (link_params.bandwidth, link_params.latency);
+        let link = &mut self.links[link_id];
+        link.stats.advance(time, bandwidth);
+        link.transmitting -= 1;
+        link.stats.throughput = link.transmitting as f64 * bandwidth;
+        let next_packet = link.queue.pop_front();
+        packet.hop += 1;
+        self.events.push(time + latency, PacketEvent::Arrival(packet));
+        if let Some(next_packet) = next_packet {
+            self.start_transmission(link_id, next_packet, time);
+        }
+    }
+
+    fn on_packet_ack(&mut self, packet: Packet, time: f64) {
+        let min_timeout = self.min_timeout;
+        let max_window = self.max_window;
+        let Some(transfer) = self.transfers.get_mut(&packet.transfer_id) else {
+            return;
+        };
+        match transfer.status[packet.seq] {
+            PacketStatus::InFlight(attempt) => {
+                transfer.in_flight -= 1;
+                // RTT is measured only for packets which were not retransmitted (Karn's algorithm)
+                if attempt == 1 && packet.attempt == 1 {
+                    transfer.on_rtt_sample(time - packet.send_time, min_timeout);
+                }
+                if transfer.window < transfer.slow_start_threshold {
+                    transfer.window += 1.;
+                } else {
+                    transfer.window += 1. / transfer.window;
+                }
+                transfer.window = transfer.window.min(max_window);
+            }
+            PacketStatus::Lost => {}
+            _ => return,
+        }
+        transfer.status[packet.seq] = PacketStatus::Acked;
+        self.send_packets(packet.transfer_id, time);
+    }
+
+    fn on_packet_timeout(&mut self, packet: Packet, time: f64) {
+        let Some(transfer) = self.transfers.get_mut(&packet.transfer_id) else {
+            return;
+        };
+        if transfer.status[packet.seq] != PacketStatus::InFlight(packet.attempt) {
+            return;
+        }
+        transfer.in_flight -= 1;
+        transfer.status[packet.seq] = PacketStatus::Lost;
+        transfer.retransmissions.push_back(packet.seq);
+        // reduce the window once per loss event, i.e. ignore losses of packets sent before the last reduction
+        if packet.send_time >= transfer.last_window_reduction {
+            transfer.slow_start_threshold = (transfer.window / 2.).max(1.);
+            transfer.window = transfer.slow_start_threshold;
+            transfer.last_window_reduction = time;
+            transfer.timeout *= 2.;
+        }
+        self.send_packets(packet.transfer_id, time);
+    }
+
+    /// Schedules the wakeup of the model at the time of the next internal event.
+    fn update_wakeup(&mut self, ctx: &mut SimulationContext) {
+        let next_time = self.events.next_time();
+        if let Some((event_id, time)) = self.next_wakeup {
+            if Some(time) == next_time {
+                return;
+            }
+            ctx.cancel_event(event_id);
+            self.next_wakeup = None;
+        }
+        if let Some(time) = next_time {
+            let event_id = ctx.emit_self(ModelWakeup {}, (time - ctx.time()).max(0.));
+            self.next_wakeup = Some((event_id, time));
+        }
+    }
+
+    fn validate_array_lengths(&mut self) {
+        self.links.resize(self.topology.link_count(), LinkState::default());
+    }
+}
+
+impl NetworkModel for PacketLevelNetworkModel {
+    fn is_topology_aware(&self) -> bool {
+        true
+    }
+
+    fn simulates_latency(&self) -> bool {
+        true
+    }
+
+    fn bandwidth(&self, src: NodeId, dst: NodeId) -> f64 {
+        let path = self
+            .routing
+            .get_path_iter(src, dst, &self.topology)
+            .unwrap_or_else(|| panic!("No path from {} to {}", src, dst));
+        self.topology.get_path_bandwidth(path)
+    }
+
+    fn latency(&self, src: NodeId, dst: NodeId) -> f64 {
+        let path = self
+            .routing
+            .get_path_iter(src, dst, &self.topology)
+            .unwrap_or_else(|| panic!("No path from {} to {}", src, dst));
+        self.topology.get_path_latency(path)
+    }
+
+    fn start_transfer(&mut self, dt: DataTransfer, ctx: &mut SimulationContext) {
+        self.validate_array_lengths();
+        let path = self.get_path(dt.src_node_id, dt.dst_node_id);
+        let ack_delay = path
+            .iter()
+            .map(|&link_id| self.topology.link(link_id).latency)
+            .sum::<f64>();
+        let base_rtt = path
+            .iter()
+            .map(|&link_id| self.mtu / self.topology.link(link_id).bandwidth)
+            .sum::<f64>()
+            + 2. * ack_delay;
+        for &link_id in path.iter() {
+            self.links[link_id].transfers += 1;
+        }
+        let packet_count = ((dt.size / self.mtu).ceil() as usize).max(1);
+        let id = dt.id;
+        assert!(!self.transfers.contains_key(&id));
+        self.transfers.insert(
+            id,
+            PacketTransfer {
+                dt,
+                path,
+                ack_delay,
+                packet_count,
+                packet_size: self.mtu,
+                status: vec![PacketStatus::NotSent; packet_count],
+                attempts: vec![0; packet_count],
+                next_seq: 0,
+                retransmissions: VecDeque::new(),
+                in_flight: 0,
+                window: self.initial_window.min(self.max_window),
+                slow_start_threshold: self.max_window,
+                last_window_reduction: ctx.time(),
+                smoothed_rtt: None,
+                rtt_variation: 0.,
+                timeout: (3. * base_rtt).max(self.min_timeout),
+                received: vec![false; packet_count],
+                received_count: 0,
+            },
+        );
+        self.send_packets(id, ctx.time());
+        self.update_wakeup(ctx);
+    }
+
+    fn on_transfer_completion(&mut self, _dt: DataTransfer, _ctx: &mut SimulationContext) {
+        // the transfer state is already removed upon receiving the last packet
+    }
+
+    fn on_wakeup(&mut self, ctx: &mut SimulationContext) {
+        let Some((_, time)) = self.next_wakeup.take() else {
+            return;
+        };
+        while let Some(scheduled) = self.events.pop_until(time) {
+            match scheduled.event {
+                PacketEvent::Arrival(packet) => self.on_packet_arrival(packet, time, ctx),
+                PacketEvent::Transmitted(link_id, packet) => self.on_packet_transmitted(link_id, packet, time),
+                PacketEvent::Ack(packet) => self.on_packet_ack(packet, time),
+                PacketEvent::Timeout(packet) => self.on_packet_timeout(packet, time),
+            }
+        }
+        self.update_wakeup(ctx);
+    }
+
+    fn path(&self, src: NodeId, dst: NodeId) -> Vec<LinkId> {
+        self.get_path(src, dst)
+    }
+
+    fn link_stats(&self, link_id: LinkId, time: f64) -> Option<LinkStats> {
+        let bandwidth = self.topology.link(link_id).bandwidth;
+        let active_transfers = self.links.get(link_id).map_or(0, |link| link.transfers);
+        let mut stats = self
+            .links
+            .get(link_id)
+            .map(|link| link.stats.clone())
+            .unwrap_or_default();
+        stats.advance(time.max(stats.last_update_time), bandwidth);
+        Some(LinkStats {
+            link_id,
+            throughput: stats.throughput,
+            utilization: stats.throughput / bandwidth,
+            active_transfers,
+            data_transferred: stats.data_transferred,
+            utilization_integral: stats.utilization_integral,
+        })
+    }
+
+    fn topology(&self) -> Option<&Topology> {
+        Some(&self.topology)
+    }
+
+    fn topology_mut(&mut self) -> Option<&mut Topology> {
+        Some(&mut self.topology)
+    }
+
+    fn on_topology_change(&mut self, _ctx: &mut SimulationContext) {
+        self.routing.init(&self.topology);
+        self.validate_array_lengths();
+    }
+
+    fn on_node_added(&mut self, node_id: NodeId, _ctx: &mut SimulationContext) {
+        self.routing.on_node_added(node_id, &self.topology);
+        self.validate_array_lengths();
+    }
+
+    fn on_link_added(&mut self, link_id: LinkId, _ctx: &mut SimulationContext) {
+        self.routing.on_link_added(link_id, &self.topology);
+        self.validate_array_lengths();
+    }
+
+    fn on_link_removed(&mut self, link_id: LinkId, _ctx: &mut SimulationContext) {
+        self.routing.on_link_removed(link_id, &self.topology);
+        self.validate_array_lengths();
+    }
+}
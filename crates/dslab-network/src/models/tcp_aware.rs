@@ -0,0 +1,358 @@
+//! TCP-aware flow-level network model.
+
+use std::collections::BTreeMap;
+
+use dslab_core::context::SimulationContext;
+use dslab_core::event::EventId;
+
+use crate::monitoring::{LinkStats, LinkStatsTracker};
+use crate::routing::{RoutingAlgorithm, ShortestPathFloydWarshall};
+use crate::{BandwidthSharingPolicy, DataTransfer, DataTransferCompleted, LinkId, NetworkModel, NodeId, Topology};
+
+/// Latency factor of LV08 model.
+pub const LV08_LATENCY_FACTOR: f64 = 13.01;
+/// Bandwidth factor of LV08 model.
+pub const LV08_BANDWIDTH_FACTOR: f64 = 0.97;
+/// Weight of bandwidth term in flow sharing penalty of LV08 model (in bytes).
+pub const LV08_WEIGHT_S: f64 = 20537.;
+/// TCP window size of LV08 model (in bytes).
+pub const LV08_TCP_WINDOW: f64 = 4194304.;
+
+struct TransferInfo {
+    dt: DataTransfer,
+    path: Vec<LinkId>,
+    weight: f64,
+    rate_bound: f64,
+    size_left: f64,
+    throughput: f64,
+    last_update_time: f64,
+}
+
+impl TransferInfo {
+    fn expected_finish(&self) -> f64 {
+        self.last_update_time + self.size_left / self.throughput
+    }
+}
+
+/// Topology-aware flow-level model which accounts for the effects of TCP congestion control,
+/// similar to the LV08 model from [SimGrid](https://simgrid.org/doc/latest/Configuring_SimGrid.html#options-model-network).
+///
+/// The link bandwidth is shared among the transfers using weighted max-min fairness, where the weight of each transfer
+/// is inversely proportional to its sharing penalty computed as the sum of `latency + weight_s / bandwidth` over
+/// the path links. Thus the transfers with larger round-trip time get smaller share of the bottleneck link.
+/// The transfer throughput is also bounded by `tcp_window / (2 * latency)`, i.e. by the TCP window size divided
+/// by the round-trip time.
+///
+/// The link latencies and bandwidths are multiplied by the latency and bandwidth factors respectively,
+/// which allows to calibrate the model against the real network. By default the LV08 parameters are used.
+/// Note that the bandwidth shares remain weighted by the path latency for any parameter values,
+/// so unlike [`TopologyAwareNetworkModel`](crate::models::TopologyAwareNetworkModel) the transfers sharing
+/// a bottleneck link get equal shares only if their paths have equal latencies.
+pub struct TcpAwareNetworkModel {
+    topology: Topology,
+    routing: Box<dyn RoutingAlgorithm>,
+    latency_factor: f64,
+    bandwidth_factor: f64,
+    weight_s: f64,
+    tcp_window: f64,
+    transfers: BTreeMap<usize, TransferInfo>,
+    link_stats: Vec<LinkStatsTracker>,
+    link_transfers: Vec<usize>,
+    next_event: Option<EventId>,
+}
+
+impl Default for TcpAwareNetworkModel {
+    fn default() -> Self {
+        TcpAwareNetworkModel {
+            topology: Topology::default(),
+            routing: Box::<ShortestPathFloydWarshall>::default(),
+            latency_factor: LV08_LATENCY_FACTOR,
+            bandwidth_factor: LV08_BANDWIDTH_FACTOR,
+            weight_s: LV08_WEIGHT_S,
+            tcp_window: LV08_TCP_WINDOW,
+            transfers: BTreeMap::new(),
+            link_stats: Vec::new(),
+            link_transfers: Vec::new(),
+            next_event: None,
+        }
+    }
+}
+
+impl TcpAwareNetworkModel {
+    /// Creates a new network model with empty topology and parameters of LV08 model.
+    ///
+    /// Uses [`ShortestPathFloydWarshall`] as default routing algorithm.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the used routing algorithm.
+    pub fn with_routing(mut self, routing: Box<dyn RoutingAlgorithm>) -> Self {
+        self.routing = routing;
+        self
+    }
+
+    /// Sets the factor applied to link latencies.
+    pub fn with_latency_factor(mut self, latency_factor: f64) -> Self {
+        self.latency_factor = latency_factor;
+        self
+    }
+
+    /// Sets the factor applied to link bandwidths.
+    pub fn with_bandwidth_factor(mut self, bandwidth_factor: f64) -> Self {
+        assert!(bandwidth_factor > 0., "Bandwidth factor must be > 0");
+        self.bandwidth_factor = bandwidth_factor;
+        self
+    }
+
+    /// Sets the weight of bandwidth term in the sharing penalty of transfers (in data units).
+    pub fn with_weight_s(mut self, weight_s: f64) -> Self {
+        self.weight_s = weight_s;
+        self
+    }
+
+    /// Sets the TCP window size (in data units), which bounds the transfer throughput.
+    pub fn with_tcp_window(mut self, tcp_window: f64) -> Self {
+        assert!(tcp_window > 0., "TCP window must be > 0");
+        self.tcp_window = tcp_window;
+        self
+    }
+
+    fn get_path(&self, src: NodeId, dst: NodeId) -> Vec<LinkId> {
+        self.routing
+            .get_path_iter(src, dst, &self.topology)
+            .unwrap_or_else(|| panic!("No path from {} to {}", src, dst))
+            .collect()
+    }
+
+    fn path_latency(&self, path: &[LinkId]) -> f64 {
+        path.iter()
+            .map(|&link_id| self.topology.link(link_id).latency * self.latency_factor)
+            .sum()
+    }
+
+    /// Updates the transferred data sizes, recalculates the throughputs of all transfers
+    /// and schedules the next transfer completion.
+    fn update(&mut self, ctx: &mut SimulationContext) {
+        let time = ctx.time();
+        for transfer in self.transfers.values_mut() {
+            transfer.size_left -= transfer.throughput * (time - transfer.last_update_time);
+            transfer.size_left = transfer.size_left.max(0.);
+            transfer.last_update_time = time;
+        }
+        self.calc_throughputs();
+        self.update_link_stats(time);
+
+        if let Some(event_id) = self.next_event.take() {
+            ctx.cancel_event(event_id);
+        }
+        if let Some(transfer) = self
+            .transfers
+            .values()
+            .min_by(|x, y| x.expected_finish().total_cmp(&y.expected_finish()))
+        {
+            self.next_event = Some(ctx.emit_self(
+                DataTransferCompleted {
+                    dt: transfer.dt.clone(),
+                },
+                transfer.expected_finish() - time,
+            ));
+        }
+    }
+
+    /// Computes weighted max-min fair throughputs with per-transfer bounds via progressive filling.
+    fn calc_throughputs(&mut self) {
+        let link_count = self.topology.link_count();
+        let mut left_bandwidth = (0..link_count)
+            .map(|link_id| self.topology.link(link_id).bandwidth * self.bandwidth_factor)
+            .collect::<Vec<_>>();
+        let mut weight_sum = vec![0.; link_count];
+        let mut unassigned = Vec::new();
+        for (&id, transfer) in self.transfers.iter() {
+            for &link_id in transfer.path.iter() {
+                weight_sum[link_id] += transfer.weight;
+            }
+            unassigned.push(id);
+        }
+
+        while !unassigned.is_empty() {
+            // the level of throughput per unit of weight at which each transfer becomes bottlenecked
+            let levels = unassigned
+                .iter()
+                .map(|id| {
+                    let transfer = &self.transfers[id];
+                    transfer
+                        .path
+                        .iter()
+                        .filter(|&&link_id| {
+                            matches!(
+                                self.topology.link(link_id).sharing_policy,
+                                BandwidthSharingPolicy::Shared
+                            )
+                        })
+                        .map(|&link_id| left_bandwidth[link_id].max(0.) / weight_sum[link_id])
+                        .fold(transfer.rate_bound / transfer.weight, f64::min)
+                })
+                .collect::<Vec<_>>();
+            let min_level = levels.iter().cloned().fold(f64::INFINITY, f64::min);
+            let mut still_unassigned = Vec::new();
+            for (id, level) in unassigned.into_iter().zip(levels) {
+                if level > min_level * (1. + 1e-12) {
+                    still_unassigned.push(id);
+                    continue;
+                }
+                let transfer = self.transfers.get_mut(&id).unwrap();
+                transfer.throughput = min_level * transfer.weight;
+                for &link_id in transfer.path.iter() {
+                    left_bandwidth[link_id] -= transfer.throughput;
+                    weight_sum[link_id] -= transfer.weight;
+                }
+            }
+            unassigned = still_unassigned;
+        }
+    }
+
+    fn update_link_stats(&mut self, time: f64) {
+        let mut throughputs = vec![0.; self.topology.link_count()];
+        for transfer in self.transfers.values() {
+            for &link_id in transfer.path.iter() {
+                throughputs[link_id] += transfer.throughput;
+            }
+        }
+        for (link_id, throughput) in throughputs.into_iter().enumerate() {
+            let stats = &mut self.link_stats[link_id];
+            stats.advance(time, self.topology.link(link_id).bandwidth);
+            stats.throughput = throughput;
+        }
+    }
+
+    fn validate_array_lengths(&mut self) {
+        let link_count = self.topology.link_count();
+        self.link_stats.resize(link_count, LinkStatsTracker::default());
+        self.link_transfers.resize(link_count, 0);
+    }
+
+    fn update_after_topology_change(&mut self, ctx: &mut SimulationContext) {
+        self.validate_array_lengths();
+        self.update(ctx);
+    }
+}
+
+impl NetworkModel for TcpAwareNetworkModel {
+    fn is_topology_aware(&self) -> bool {
+        true
+    }
+
+    fn bandwidth(&self, src: NodeId, dst: NodeId) -> f64 {
+        let path = self
+            .routing
+            .get_path_iter(src, dst, &self.topology)
+            .unwrap_or_else(|| panic!("No path from {} to {}", src, dst));
+        self.topology.get_path_bandwidth(path) * self.bandwidth_factor
+    }
+
+    fn latency(&self, src: NodeId, dst: NodeId) -> f64 {
+        let path = self
+            .routing
+            .get_path_iter(src, dst, &self.topology)
+            .unwrap_or_else(|| panic!("No path from {} to {}", src, dst));
+        self.topology.get_path_latency(path) * self.latency_factor
+    }
+
+    fn start_transfer(&mut self, dt: DataTransfer, ctx: &mut SimulationContext) {
+        self.validate_array_lengths();
+        let path = self.get_path(dt.src_node_id, dt.dst_node_id);
+        let latency = self.path_latency(&path);
+        let penalty = path
+            .iter()
+            .map(|&link_id| {
+                let link = self.topology.link(link_id);
+                link.latency * self.latency_factor + self.weight_s / (link.bandwidth * self.bandwidth_factor)
+            })
+            .sum::<f64>();
+        let mut rate_bound = if latency > 0. {
+            self.tcp_window / (2. * latency)
+        } else {
+            f64::INFINITY
+        };
+        for &link_id in path.iter() {
+            let link = self.topology.link(link_id);
+            if let BandwidthSharingPolicy::NonShared = link.sharing_policy {
+                rate_bound = rate_bound.min(link.bandwidth * self.bandwidth_factor);
+            }
+            self.link_transfers[link_id] += 1;
+        }
+        let id = dt.id;
+        let size = dt.size;
+        assert!(!self.transfers.contains_key(&id));
+        self.transfers.insert(
+            id,
+            TransferInfo {
+                dt,
+                path,
+                weight: if penalty > 0. { 1. / penalty } else { 1. },
+                rate_bound,
+                size_left: size,
+                throughput: 0.,
+                last_update_time: ctx.time(),
+            },
+        );
+        self.update(ctx);
+    }
+
+    fn on_transfer_completion(&mut self, dt: DataTransfer, ctx: &mut SimulationContext) {
+        self.validate_array_lengths();
+        self.next_event = None;
+        let transfer = self.transfers.remove(&dt.id).unwrap();
+        for &link_id in transfer.path.iter() {
+            self.link_transfers[link_id] -= 1;
+        }
+        self.update(ctx);
+    }
+
+    fn path(&self, src: NodeId, dst: NodeId) -> Vec<LinkId> {
+        self.get_path(src, dst)
+    }
+
+    fn link_stats(&self, link_id: LinkId, time: f64) -> Option<LinkStats> {
+        let bandwidth = self.topology.link(link_id).bandwidth;
+        let mut stats = self.link_stats.get(link_id).cloned().unwrap_or_default();
+        stats.advance(time.max(stats.last_update_time), bandwidth);
+        Some(LinkStats {
+            link_id,
+            throughput: stats.throughput,
+            utilization: stats.throughput / bandwidth,
+            active_transfers: self.link_transfers.get(link_id).copied().unwrap_or(0),
+            data_transferred: stats.data_transferred,
+            utilization_integral: stats.utilization_integral,
+        })
+    }
+
+    fn topology(&self) -> Option<&Topology> {
+        Some(&self.topology)
+    }
+
+    fn topology_mut(&mut self) -> Option<&mut Topology> {
+        Some(&mut self.topology)
+    }
+
+    fn on_topology_change(&mut self, ctx: &mut SimulationContext) {
+        self.routing.init(&self.topology);
+        self.update_after_topology_change(ctx);
+    }
+
+    fn on_node_added(&mut self, node_id: NodeId, ctx: &mut SimulationContext) {
+        self.routing.on_node_added(node_id, &self.topology);
+        self.update_after_topology_change(ctx);
+    }
+
+    fn on_link_added(&mut self, link_id: LinkId, ctx: &mut SimulationContext) {
+        self.routing.on_link_added(link_id, &self.topology);
+        self.update_after_topology_change(ctx);
+    }
+
+    fn on_link_removed(&mut self, link_id: LinkId, ctx: &mut SimulationContext) {
+        self.routing.on_link_removed(link_id, &self.topology);
+        self.update_after_topology_change(ctx);
+    }
+}
@@ -5,6 +5,7 @@ use std::collections::{BTreeMap, BinaryHeap, HashSet, VecDeque};
 
 use dslab_core::context::SimulationContext;
 
+use crate::monitoring::{LinkStats, LinkStatsTracker};
 use crate::routing::{RoutingAlgorithm, ShortestPathFloydWarshall};
 use crate::{BandwidthSharingPolicy, DataTransfer, DataTransferCompleted, LinkId, NetworkModel, NodeId, Topology};
 
@@ -94,6 +95,8 @@ impl TransferInfo {
 /// Topology-aware model which uses information about the network [`Topology`] (links connecting the nodes)
 /// and relies on [`RoutingAlgorithm`](crate::routing::RoutingAlgorithm) to compute paths between the nodes.
 /// The link's bandwidth is shared fairly among the transfers using the link.  
+///
+/// Supports multicast transfers, which are accounted as a single transfer on each link of the distribution tree.
 pub struct TopologyAwareNetworkModel {
     topology: Topology,
     routing: Box<dyn RoutingAlgorithm>,
@@ -103,6 +106,7 @@ pub struct TopologyAwareNetworkModel {
     next_event: Option<u64>,
     next_event_index: Option<usize>,
     link_data: Vec<Option<LinkUsage>>,
+    link_stats: Vec<LinkStatsTracker>,
     full_mesh_optimization: bool,
 }
 
@@ -118,6 +122,7 @@ impl Default for TopologyAwareNetworkModel {
             next_event: None,
             next_event_index: None,
             link_data: Vec::new(),
+            link_stats: Vec::new(),
             full_mesh_optimization: false,
         }
     }
@@ -215,10 +220,7 @@ impl TopologyAwareNetworkModel {
             ctx.cancel_event(event_id)
         };
 
-        let affected_links = affected_transfers
-            .iter()
-            .flat_map(|transfer| self.current_transfers[transfer].path.iter().cloned())
-            .collect::<HashSet<LinkId>>();
+        let affected_links = self.get_affected_links(&affected_transfers);
 
         let transfers_through_link = &mut self.tmp_transfers_through_link;
 
@@ -385,13 +387,80 @@ impl TopologyAwareNetworkModel {
         }
     }
 
+    /// Updates the statistics of given links after the change of transfer throughputs.
+    fn update_link_stats<I: IntoIterator<Item = LinkId>>(&mut self, links: I, time: f64) {
+        for link_id in links {
+            let transfers = &self.transfers_through_link[link_id];
+            let throughput = if transfers.is_empty() {
+                0.
+            } else {
+                transfers
+                    .iter()
+                    .map(|transfer_id| self.current_transfers[transfer_id].throughput)
+                    .sum()
+            };
+            let stats = &mut self.link_stats[link_id];
+            stats.advance(time, self.topology.link(link_id).bandwidth);
+            stats.throughput = throughput;
+        }
+    }
+
+    fn get_affected_links(&self, affected_transfers: &HashSet<usize>) -> HashSet<LinkId> {
+        affected_transfers
+            .iter()
+            .flat_map(|transfer| self.current_transfers[transfer].path.iter().cloned())
+            .collect()
+    }
+
+    /// Returns the links currently used by at least one transfer.
+    fn busy_links(&self) -> Vec<LinkId> {
+        self.transfers_through_link
+            .iter()
+            .enumerate()
+            .filter(|(_, transfers)| !transfers.is_empty())
+            .map(|(link_id, _)| link_id)
+            .collect()
+    }
+
+    /// Starts the transfer of data over the given links.
+    fn add_transfer(&mut self, dt: DataTransfer, path: Vec<LinkId>, ctx: &mut SimulationContext) {
+        self.validate_array_lengths();
+        let id = dt.id;
+        assert!(!self.current_transfers.contains_key(&dt.id));
+        for &link in path.iter() {
+            self.transfers_through_link[link].push(id);
+        }
+        self.current_transfers
+            .insert(id, TransferInfo::new(dt, path, ctx.time()));
+
+        if self.full_mesh_optimization {
+            let affected_transfers = self.get_affected_transfers(id);
+            let affected_links = self.get_affected_links(&affected_transfers);
+            self.calc(ctx, affected_transfers);
+            self.update_link_stats(affected_links, ctx.time());
+        } else {
+            self.calc_all(ctx);
+            self.update_link_stats(self.busy_links(), ctx.time());
+        }
+        self.update_next_event(ctx);
+    }
+
     fn validate_array_lengths(&mut self) {
         let topology = &self.topology;
         self.link_data.resize(topology.link_count(), None);
+        self.link_stats
+            .resize(topology.link_count(), LinkStatsTracker::default());
         self.transfers_through_link.resize(topology.link_count(), Vec::new());
         self.tmp_transfers_through_link
             .resize(topology.link_count(), Vec::new());
     }
+
+    fn update_after_topology_change(&mut self, ctx: &mut SimulationContext) {
+        self.validate_array_lengths();
+        self.calc_all(ctx);
+        self.update_link_stats(self.busy_links(), ctx.time());
+        self.update_next_event(ctx);
+    }
 }
 
 impl NetworkModel for TopologyAwareNetworkModel {
@@ -416,27 +485,20 @@ impl NetworkModel for TopologyAwareNetworkModel {
     }
 
     fn start_transfer(&mut self, dt: DataTransfer, ctx: &mut SimulationContext) {
-        self.validate_array_lengths();
         let path = self
             .routing
             .get_path_iter(dt.src_node_id, dt.dst_node_id, &self.topology)
             .unwrap_or_else(|| panic!("No path from {} to {}", dt.src_node_id, dt.dst_node_id))
             .collect::<Vec<_>>();
-        let id = dt.id;
-        assert!(!self.current_transfers.contains_key(&dt.id));
-        for &link in path.iter() {
-            self.transfers_through_link[link].push(id);
-        }
-        self.current_transfers
-            .insert(id, TransferInfo::new(dt, path, ctx.time()));
+        self.add_transfer(dt, path, ctx);
+    }
 
-        if self.full_mesh_optimization {
-            let affected_transfers = self.get_affected_transfers(id);
-            self.calc(ctx, affected_transfers);
-        } else {
-            self.calc_all(ctx);
-        }
-        self.update_next_event(ctx);
+    fn supports_multicast(&self) -> bool {
+        true
+    }
+
+    fn start_multicast_transfer(&mut self, dt: DataTransfer, tree: Vec<LinkId>, ctx: &mut SimulationContext) {
+        self.add_transfer(dt, tree, ctx);
     }
 
     fn on_transfer_completion(&mut self, _dt: DataTransfer, ctx: &mut SimulationContext) {
@@ -457,13 +519,41 @@ impl NetworkModel for TopologyAwareNetworkModel {
         self.next_event = None;
         self.next_event_index = None;
         if self.full_mesh_optimization {
+            let mut affected_links = self.get_affected_links(&affected_transfers);
+            affected_links.extend(transfer.path.iter());
             self.calc(ctx, affected_transfers);
+            self.update_link_stats(affected_links, ctx.time());
         } else {
             self.calc_all(ctx);
+            let mut affected_links = self.busy_links();
+            affected_links.extend(transfer.path.iter());
+            self.update_link_stats(affected_links, ctx.time());
         }
         self.update_next_event(ctx);
     }
 
+    fn path(&self, src: NodeId, dst: NodeId) -> Vec<LinkId> {
+        self.routing
+            .get_path_iter(src, dst, &self.topology)
+            .unwrap_or_else(|| panic!("No path from {} to {}", src, dst))
+            .collect()
+    }
+
+    fn link_stats(&self, link_id: LinkId, time: f64) -> Option<LinkStats> {
+        let bandwidth = self.topology.link(link_id).bandwidth;
+        let active_transfers = self.transfers_through_link.get(link_id).map_or(0, |t| t.len());
+        let mut stats = self.link_stats.get(link_id).cloned().unwrap_or_default();
+        stats.advance(time.max(stats.last_update_time), bandwidth);
+        Some(LinkStats {
+            link_id,
+            throughput: stats.throughput,
+            utilization: stats.throughput / bandwidth,
+            active_transfers,
+            data_transferred: stats.data_transferred,
+            utilization_integral: stats.utilization_integral,
+        })
+    }
+
     fn topology(&self) -> Option<&Topology> {
         Some(&self.topology)
     }
@@ -474,8 +564,21 @@ impl NetworkModel for TopologyAwareNetworkModel {
 
     fn on_topology_change(&mut self, ctx: &mut SimulationContext) {
         self.routing.init(&self.topology);
-        self.validate_array_lengths();
-        self.calc_all(ctx);
-        self.update_next_event(ctx);
+        self.update_after_topology_change(ctx);
+    }
+
+    fn on_node_added(&mut self, node_id: NodeId, ctx: &mut SimulationContext) {
+        self.routing.on_node_added(node_id, &self.topology);
+        self.update_after_topology_change(ctx);
+    }
+
+    fn on_link_added(&mut self, link_id: LinkId, ctx: &mut SimulationContext) {
+        self.routing.on_link_added(link_id, &self.topology);
+        self.update_after_topology_change(ctx);
+    }
+
+    fn on_link_removed(&mut self, link_id: LinkId, ctx: &mut SimulationContext) {
+        self.routing.on_link_removed(link_id, &self.topology);
+        self.update_after_topology_change(ctx);
     }
 }
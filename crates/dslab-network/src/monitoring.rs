@@ -0,0 +1,147 @@
+//! Monitoring of network link utilization.
+
+use std::collections::HashMap;
+use std::fs::File;
+
+use serde::Serialize;
+
+use crate::LinkId;
+
+/// Current and accumulated statistics of a network link.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct LinkStats {
+    /// Link id.
+    pub link_id: LinkId,
+    /// Total throughput of data transfers currently using the link.
+    pub throughput: f64,
+    /// Current link utilization, i.e. the ratio of used throughput to the link bandwidth.
+    ///
+    /// Can exceed 1 for links with [`BandwidthSharingPolicy::NonShared`](crate::BandwidthSharingPolicy::NonShared)
+    /// policy, since each transfer gets the full link bandwidth.
+    pub utilization: f64,
+    /// Number of data transfers currently using the link.
+    pub active_transfers: usize,
+    /// Total amount of data carried by the link so far.
+    pub data_transferred: f64,
+    /// Link utilization integrated over time.
+    pub utilization_integral: f64,
+}
+
+impl LinkStats {
+    /// Returns the average link utilization over the time interval from 0 to `time`.
+    pub fn average_utilization(&self, time: f64) -> f64 {
+        if time > 0. {
+            self.utilization_integral / time
+        } else {
+            0.
+        }
+    }
+}
+
+/// Accumulates the statistics of a link between the changes of its throughput.
+#[derive(Clone, Default)]
+pub(crate) struct LinkStatsTracker {
+    pub throughput: f64,
+    pub data_transferred: f64,
+    pub utilization_integral: f64,
+    pub last_update_time: f64,
+}
+
+impl LinkStatsTracker {
+    pub fn advance(&mut self, time: f64, bandwidth: f64) {
+        let duration = time - self.last_update_time;
+        self.data_transferred += self.throughput * duration;
+        self.utilization_integral += self.throughput / bandwidth * duration;
+        self.last_update_time = time;
+    }
+}
+
+/// Defines when the link utilization samples are recorded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LinkSamplingMode {
+    /// Samples are recorded with the specified time interval while there are active transfers in the network.
+    Interval(f64),
+    /// Samples are recorded on every change of link utilization.
+    OnChange,
+}
+
+/// Link utilization sample.
+#[derive(Clone, Debug, Serialize)]
+pub struct LinkUtilizationSample {
+    /// Sample time.
+    pub time: f64,
+    /// Link id.
+    pub link_id: LinkId,
+    /// Link utilization at the sample time.
+    pub utilization: f64,
+    /// Number of data transfers using the link at the sample time.
+    pub active_transfers: usize,
+}
+
+/// Stores the time series of link utilization samples.
+pub struct LinkUtilizationLog {
+    mode: LinkSamplingMode,
+    samples: Vec<LinkUtilizationSample>,
+    last_samples: HashMap<LinkId, (f64, usize)>,
+}
+
+impl LinkUtilizationLog {
+    /// Creates a new empty log with specified sampling mode.
+    pub fn new(mode: LinkSamplingMode) -> Self {
+        if let LinkSamplingMode::Interval(interval) = mode {
+            assert!(interval > 0., "Sampling interval must be > 0");
+        }
+        Self {
+            mode,
+            samples: Vec::new(),
+            last_samples: HashMap::new(),
+        }
+    }
+
+    /// Returns the used sampling mode.
+    pub fn mode(&self) -> LinkSamplingMode {
+        self.mode
+    }
+
+    /// Records the samples for the passed link statistics.
+    ///
+    /// In [`LinkSamplingMode::OnChange`] mode, the sample for a link is recorded only if its state has changed.
+    pub fn record(&mut self, time: f64, stats: &[LinkStats]) {
+        for link_stats in stats {
+            let state = (link_stats.utilization, link_stats.active_transfers);
+            if self.mode == LinkSamplingMode::OnChange && self.last_samples.get(&link_stats.link_id) == Some(&state) {
+                continue;
+            }
+            self.last_samples.insert(link_stats.link_id, state);
+            self.samples.push(LinkUtilizationSample {
+                time,
+                link_id: link_stats.link_id,
+                utilization: link_stats.utilization,
+                active_transfers: link_stats.active_transfers,
+            });
+        }
+    }
+
+    /// Returns the recorded samples.
+    pub fn samples(&self) -> &[LinkUtilizationSample] {
+        &self.samples
+    }
+
+    /// Saves the recorded samples to CSV file.
+    pub fn save_csv(&self, path: &str) -> Result<(), std::io::Error> {
+        let file = File::create(path)?;
+        let mut wtr = csv::Writer::from_writer(file);
+        for sample in &self.samples {
+            wtr.serialize(sample)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// Saves the recorded samples to JSON file.
+    pub fn save_json(&self, path: &str) -> Result<(), std::io::Error> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(file, &self.samples)?;
+        Ok(())
+    }
+}
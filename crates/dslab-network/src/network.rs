@@ -1,7 +1,7 @@
 //! Simulation component representing a network.
 
 use indexmap::IndexMap;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use serde::Serialize;
@@ -11,7 +11,11 @@ use dslab_core::context::SimulationContext;
 use dslab_core::event::{Event, EventData, EventId};
 use dslab_core::handler::EventHandler;
 use dslab_core::{cast, log_debug};
+use dslab_models::power::network::{LinkPowerModel, SwitchPowerModel};
 
+use crate::energy::EnergyMeter;
+use crate::model::ModelWakeup;
+use crate::monitoring::{LinkSamplingMode, LinkStats, LinkUtilizationLog};
 use crate::{DataTransfer, DataTransferCompleted, Link, LinkId, NetworkModel, Node, NodeId};
 
 /// Represents a message sent between two simulation components over the network.
@@ -34,11 +38,69 @@ pub struct MessageDelivered {
     pub msg: Message,
 }
 
+/// Event signalling the completion of multicast data transfer to all destinations.
+#[derive(Clone, Serialize)]
+pub struct MulticastCompleted {
+    /// Unique multicast transfer id.
+    pub id: usize,
+    /// Simulation component which is sending the data.
+    pub src: Id,
+    /// Simulation components receiving the data.
+    pub dsts: Vec<Id>,
+    /// Data size.
+    pub size: f64,
+}
+
+/// Defines how the delivery time of sized messages and events is calculated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageDeliveryMode {
+    /// The delivery time is equal to the path latency plus the serialization delay of the message
+    /// at the bottleneck (minimum) bandwidth of the path.
+    CutThrough,
+    /// Each intermediate node receives the whole message before forwarding it,
+    /// so the serialization delay is added on every link of the path.
+    ///
+    /// Requires topology-aware model, otherwise behaves like [`MessageDeliveryMode::CutThrough`].
+    StoreAndForward,
+    /// The message is delivered as a data transfer via the network model,
+    /// i.e. it shares the bandwidth with other ongoing transfers.
+    Contended,
+}
+
 #[derive(Clone, Serialize)]
 struct StartDataTransfer {
     dt: DataTransfer,
 }
 
+#[derive(Clone, Serialize)]
+struct StartMulticastTransfer {
+    dt: DataTransfer,
+    tree: Vec<LinkId>,
+}
+
+#[derive(Clone, Serialize)]
+struct SampleLinkUtilization {}
+
+struct MulticastState {
+    src: Id,
+    src_node_id: NodeId,
+    dsts: Vec<Id>,
+    size: f64,
+    notification_dst: Id,
+    parts_left: usize,
+    completion_time: f64,
+}
+
+/// Part of multicast transfer, which is performed either as a single transfer over the distribution tree
+/// or as a regular transfer to one of destinations.
+struct MulticastPart {
+    multicast_id: usize,
+    dsts: Vec<(Id, NodeId)>,
+    over_tree: bool,
+}
+
+type PendingDelivery = Box<dyn FnOnce(&SimulationContext)>;
+
 /// Simulation component representing a network.
 ///
 /// This is the main entry point for all network operations, which relies internally on the supplied network model.
@@ -50,6 +112,13 @@ pub struct Network {
     next_dt_id: AtomicUsize,
     next_msg_id: AtomicUsize,
     topology_initialized: bool,
+    message_delivery_mode: MessageDeliveryMode,
+    pending_deliveries: HashMap<usize, PendingDelivery>,
+    multicasts: HashMap<usize, MulticastState>,
+    multicast_parts: HashMap<usize, MulticastPart>,
+    link_utilization_log: Option<LinkUtilizationLog>,
+    link_sampling_event: Option<EventId>,
+    energy_meter: Option<EnergyMeter>,
     ctx: SimulationContext,
 }
 
@@ -64,6 +133,13 @@ impl Network {
             next_dt_id: AtomicUsize::new(0),
             next_msg_id: AtomicUsize::new(0),
             topology_initialized: false,
+            message_delivery_mode: MessageDeliveryMode::CutThrough,
+            pending_deliveries: HashMap::new(),
+            multicasts: HashMap::new(),
+            multicast_parts: HashMap::new(),
+            link_utilization_log: None,
+            link_sampling_event: None,
+            energy_meter: None,
             ctx,
         }
     }
@@ -88,6 +164,9 @@ impl Network {
         };
         self.nodes_name_map.insert(name, node_id);
         self.local_models.insert(node_id, local_model);
+        if self.topology_initialized {
+            self.network_model.on_node_added(node_id, &mut self.ctx);
+        }
         node_id
     }
 
@@ -112,10 +191,9 @@ impl Network {
         );
         let node1 = self.get_node_id(node1);
         let node2 = self.get_node_id(node2);
+        let replaces_link = self.has_link_between(node1, node2) || self.has_link_between(node2, node1);
         let link_id = self.network_model.topology_mut().unwrap().add_link(node1, node2, link);
-        if self.topology_initialized {
-            self.network_model.on_topology_change(&mut self.ctx);
-        }
+        self.notify_links_added(&[link_id], replaces_link);
         link_id
     }
 
@@ -127,14 +205,13 @@ impl Network {
         );
         let node_from = self.get_node_id(node_from);
         let node_to = self.get_node_id(node_to);
+        let replaces_link = self.has_link_between(node_from, node_to);
         let link_id = self
             .network_model
             .topology_mut()
             .unwrap()
             .add_unidirectional_link(node_from, node_to, link);
-        if self.topology_initialized {
-            self.network_model.on_topology_change(&mut self.ctx);
-        }
+        self.notify_links_added(&[link_id], replaces_link);
         link_id
     }
 
@@ -148,20 +225,58 @@ impl Network {
         );
         let node1 = self.get_node_id(node1);
         let node2 = self.get_node_id(node2);
+        let replaces_link = self.has_link_between(node1, node2) || self.has_link_between(node2, node1);
         let (uplink_id, downlink_id) = self
             .network_model
             .topology_mut()
             .unwrap()
             .add_full_duplex_link(node1, node2, link);
+        self.notify_links_added(&[uplink_id, downlink_id], replaces_link);
+        (uplink_id, downlink_id)
+    }
+
+    /// Removes the link from the network topology.
+    ///
+    /// The paths between the nodes are updated accordingly, while the transfers already using the link
+    /// are not affected. The link id is not reused by the links added later.
+    pub fn remove_link(&mut self, link_id: LinkId) {
+        assert!(
+            self.network_model.is_topology_aware(),
+            "This method requires topology-aware model"
+        );
+        self.network_model.topology_mut().unwrap().remove_link(link_id);
         if self.topology_initialized {
+            self.network_model.on_link_removed(link_id, &mut self.ctx);
+        }
+        self.update_energy();
+    }
+
+    fn has_link_between(&self, node_from: NodeId, node_to: NodeId) -> bool {
+        self.network_model
+            .topology()
+            .unwrap()
+            .has_link_between(node_from, node_to)
+    }
+
+    fn notify_links_added(&mut self, link_ids: &[LinkId], replaces_link: bool) {
+        if !self.topology_initialized {
+            return;
+        }
+        // replacing the existing link can make the paths longer, which requires the full recomputation
+        if replaces_link {
             self.network_model.on_topology_change(&mut self.ctx);
+        } else {
+            for &link_id in link_ids {
+                self.network_model.on_link_added(link_id, &mut self.ctx);
+            }
         }
-        (uplink_id, downlink_id)
+        self.update_energy();
     }
 
     /// Performs initialization of network topology, such as computing the paths between the nodes.
     ///
     /// Must be called after all links are added and before submitting any operations.
+    /// The nodes and links added or removed afterwards are handled by incrementally updating the paths.
     pub fn init_topology(&mut self) {
         assert!(
             self.network_model.is_topology_aware(),
@@ -215,6 +330,204 @@ impl Network {
         }
     }
 
+    /// Returns the delivery time of a message of given size between two simulation components
+    /// without taking into account the contention with other transfers.
+    ///
+    /// The delivery time is calculated according to the current [`MessageDeliveryMode`].
+    /// For [`MessageDeliveryMode::Contended`] mode, the delivery time in the absence of other transfers is returned.
+    pub fn message_delay(&self, src: Id, dst: Id, size: f64) -> f64 {
+        let src_node_id = self.get_location(src);
+        let dst_node_id = self.get_location(dst);
+        if self.message_delivery_mode == MessageDeliveryMode::StoreAndForward
+            && src_node_id != dst_node_id
+            && self.network_model.is_topology_aware()
+        {
+            let topology = self.network_model.topology().unwrap();
+            self.network_model
+                .path(src_node_id, dst_node_id)
+                .into_iter()
+                .map(|link_id| {
+                    let link = topology.link(link_id);
+                    link.latency + size / link.bandwidth
+                })
+                .sum()
+        } else {
+            self.latency(src, dst) + size / self.bandwidth(src, dst)
+        }
+    }
+
+    // Link monitoring -----------------------------------------------------------------------------------------------
+
+    /// Returns the current statistics of the link.
+    pub fn link_stats(&self, link_id: LinkId) -> LinkStats {
+        assert!(
+            self.network_model.is_topology_aware(),
+            "This method requires topology-aware model"
+        );
+        self.network_model.link_stats(link_id, self.ctx.time()).unwrap()
+    }
+
+    /// Returns the current statistics of all links.
+    pub fn all_link_stats(&self) -> Vec<LinkStats> {
+        assert!(
+            self.network_model.is_topology_aware(),
+            "This method requires topology-aware model"
+        );
+        let link_count = self.network_model.topology().unwrap().link_count();
+        (0..link_count).map(|link_id| self.link_stats(link_id)).collect()
+    }
+
+    /// Enables recording of link utilization time series with specified sampling mode.
+    ///
+    /// The recorded samples can be accessed via [`Self::link_utilization_log`].
+    pub fn enable_link_monitoring(&mut self, mode: LinkSamplingMode) {
+        assert!(
+            self.network_model.is_topology_aware(),
+            "This method requires topology-aware model"
+        );
+        if let Some(event_id) = self.link_sampling_event.take() {
+            self.ctx.cancel_event(event_id);
+        }
+        self.link_utilization_log = Some(LinkUtilizationLog::new(mode));
+        self.on_link_usage_change();
+    }
+
+    /// Returns the recorded link utilization time series if the link monitoring is enabled.
+    pub fn link_utilization_log(&self) -> Option<&LinkUtilizationLog> {
+        self.link_utilization_log.as_ref()
+    }
+
+    fn record_link_utilization(&mut self) -> bool {
+        let stats = self.all_link_stats();
+        let has_active_transfers = stats.iter().any(|s| s.active_transfers > 0);
+        self.link_utilization_log
+            .as_mut()
+            .unwrap()
+            .record(self.ctx.time(), &stats);
+        has_active_transfers
+    }
+
+    fn schedule_link_sampling(&mut self, interval: f64) {
+        let time = self.ctx.time();
+        let next_time = ((time / interval).floor() + 1.) * interval;
+        self.link_sampling_event = Some(self.ctx.emit_self(SampleLinkUtilization {}, next_time - time));
+    }
+
+    fn on_link_usage_change(&mut self) {
+        self.update_energy();
+        let mode = match &self.link_utilization_log {
+            Some(log) => log.mode(),
+            None => return,
+        };
+        match mode {
+            LinkSamplingMode::OnChange => {
+                self.record_link_utilization();
+            }
+            LinkSamplingMode::Interval(interval) => {
+                if self.link_sampling_event.is_none() && self.record_link_utilization() {
+                    self.schedule_link_sampling(interval);
+                }
+            }
+        }
+    }
+
+    // Energy consumption ----------------------------------------------------------------------------------------------
+
+    /// Sets the power model of network device (e.g. switch) represented by the node.
+    ///
+    /// The device power is computed from the utilization of its ports, where each port corresponds to a neighbor
+    /// node connected by the links. The energy consumption is accounted from the moment of this call.
+    pub fn set_node_power_model(&mut self, node: &str, power_model: Box<dyn SwitchPowerModel>) {
+        assert!(
+            self.network_model.is_topology_aware(),
+            "This method requires topology-aware model"
+        );
+        let node_id = self.get_node_id(node);
+        self.network_model
+            .topology_mut()
+            .unwrap()
+            .set_node_power_model(node_id, power_model);
+        self.update_energy_meter();
+    }
+
+    /// Sets the power model of the link.
+    ///
+    /// The energy consumption is accounted from the moment of this call.
+    /// The removed links are considered as turned off and do not consume power.
+    pub fn set_link_power_model(&mut self, link_id: LinkId, power_model: Box<dyn LinkPowerModel>) {
+        assert!(
+            self.network_model.is_topology_aware(),
+            "This method requires topology-aware model"
+        );
+        self.network_model
+            .topology_mut()
+            .unwrap()
+            .set_link_power_model(link_id, power_model);
+        self.update_energy_meter();
+    }
+
+    /// Returns the current power consumption of the node in Watts.
+    pub fn node_power(&self, node: &str) -> f64 {
+        let node_id = self.get_node_id(node);
+        self.energy_meter.as_ref().map_or(0., |meter| meter.node_power(node_id))
+    }
+
+    /// Returns the current power consumption of the link in Watts.
+    pub fn link_power(&self, link_id: LinkId) -> f64 {
+        self.energy_meter.as_ref().map_or(0., |meter| meter.link_power(link_id))
+    }
+
+    /// Returns the current power consumption of all network nodes and links in Watts.
+    pub fn total_power(&self) -> f64 {
+        self.energy_meter.as_ref().map_or(0., |meter| meter.total_power())
+    }
+
+    /// Returns the energy consumed by the node so far in Joules.
+    pub fn node_energy(&self, node: &str) -> f64 {
+        let node_id = self.get_node_id(node);
+        self.energy_meter
+            .as_ref()
+            .map_or(0., |meter| meter.node_energy(node_id, self.ctx.time()))
+    }
+
+    /// Returns the energy consumed by the link so far in Joules.
+    pub fn link_energy(&self, link_id: LinkId) -> f64 {
+        self.energy_meter
+            .as_ref()
+            .map_or(0., |meter| meter.link_energy(link_id, self.ctx.time()))
+    }
+
+    /// Returns the energy consumed by all network nodes and links so far in Joules.
+    pub fn total_energy(&self) -> f64 {
+        self.energy_meter
+            .as_ref()
+            .map_or(0., |meter| meter.total_energy(self.ctx.time()))
+    }
+
+    fn update_energy_meter(&mut self) {
+        if self.energy_meter.is_none() {
+            self.energy_meter = Some(EnergyMeter::new(self.ctx.time()));
+        }
+        self.update_energy();
+    }
+
+    fn update_energy(&mut self) {
+        if self.energy_meter.is_none() {
+            return;
+        }
+        let link_utilization = self
+            .all_link_stats()
+            .iter()
+            .map(|stats| stats.utilization.min(1.))
+            .collect::<Vec<_>>();
+        let time = self.ctx.time();
+        let topology = self.network_model.topology().unwrap();
+        self.energy_meter
+            .as_mut()
+            .unwrap()
+            .update(time, topology, &link_utilization);
+    }
+
     // Operations ------------------------------------------------------------------------------------------------------
 
     /// Starts a data transfer between two simulation components, returns unique transfer id.
@@ -225,6 +538,42 @@ impl Network {
     pub fn transfer_data(&mut self, src: Id, dst: Id, size: f64, notification_dst: Id) -> usize {
         let src_node_id = self.get_location(src);
         let dst_node_id = self.get_location(dst);
+        self.start_transfer(src, src_node_id, dst, dst_node_id, size, notification_dst)
+    }
+
+    /// Starts a data transfer between two network nodes, returns unique transfer id.
+    ///
+    /// Can be used to generate the network traffic which is not related to particular simulation components.
+    /// The `src` and `dst` fields of the transfer are set to `notification_dst`.
+    /// The [`DataTransferCompleted`] event is sent to `notification_dst` on the transfer completion.
+    pub fn transfer_data_between_nodes(
+        &mut self,
+        src_node: &str,
+        dst_node: &str,
+        size: f64,
+        notification_dst: Id,
+    ) -> usize {
+        let src_node_id = self.get_node_id(src_node);
+        let dst_node_id = self.get_node_id(dst_node);
+        self.start_transfer(
+            notification_dst,
+            src_node_id,
+            notification_dst,
+            dst_node_id,
+            size,
+            notification_dst,
+        )
+    }
+
+    fn start_transfer(
+        &mut self,
+        src: Id,
+        src_node_id: NodeId,
+        dst: Id,
+        dst_node_id: NodeId,
+        size: f64,
+        notification_dst: Id,
+    ) -> usize {
         let transfer_id = self.next_dt_id.fetch_add(1, Ordering::Relaxed);
         let dt = DataTransfer {
             id: transfer_id,
@@ -243,13 +592,176 @@ impl Network {
             dt.dst,
             dt.size
         );
-        // The fixed part of data transfer time (latency) is modeled by the delayed StartDataTransfer event.
-        // The remaining part is calculated by the underlying network model (see handling of StartDataTransfer event).
-        let delay = self.latency(src, dst);
+        // The fixed part of data transfer time (latency) is modeled by the delayed StartDataTransfer event,
+        // unless the model simulates it by itself. The remaining part is calculated by the underlying network model
+        // (see handling of StartDataTransfer event).
+        let model = if src_node_id == dst_node_id {
+            &self.local_models[&src_node_id]
+        } else {
+            &self.network_model
+        };
+        let delay = if model.simulates_latency() {
+            0.
+        } else {
+            model.latency(src_node_id, dst_node_id)
+        };
         self.ctx.emit_self(StartDataTransfer { dt }, delay);
         transfer_id
     }
 
+    /// Starts a multicast data transfer from one simulation component to several others, returns unique transfer id.
+    ///
+    /// If the network model supports multicast (see [`NetworkModel::supports_multicast`]), the data is sent over
+    /// the distribution tree formed by the paths from the source to destinations and crosses each link of the tree
+    /// once. Otherwise the data is sent to each destination by a separate transfer, the same is done for the
+    /// destinations located on the same node as the source.
+    ///
+    /// The [`DataTransferCompleted`] event with the id of multicast transfer is sent to `notification_dst`
+    /// on the completion of transfer to each destination. The [`MulticastCompleted`] event is sent to
+    /// `notification_dst` when the data is transferred to all destinations.
+    pub fn multicast_data(&mut self, src: Id, dsts: &[Id], size: f64, notification_dst: Id) -> usize {
+        assert!(
+            !dsts.is_empty(),
+            "Multicast transfer must have at least one destination"
+        );
+        let multicast_id = self.next_dt_id.fetch_add(1, Ordering::Relaxed);
+        log_debug!(
+            self.ctx,
+            "new multicast transfer {} from {} to {:?} of size {}",
+            multicast_id,
+            src,
+            dsts,
+            size
+        );
+        let src_node_id = self.get_location(src);
+        let use_tree = self.network_model.supports_multicast();
+        let mut tree_dsts = Vec::new();
+        let mut parts = Vec::new();
+        for &dst in dsts {
+            let dst_node_id = self.get_location(dst);
+            if use_tree && dst_node_id != src_node_id {
+                tree_dsts.push((dst, dst_node_id));
+            } else {
+                let part_id = self.start_transfer(src, src_node_id, dst, dst_node_id, size, notification_dst);
+                parts.push((part_id, vec![(dst, dst_node_id)], false));
+            }
+        }
+        if !tree_dsts.is_empty() {
+            let mut tree = Vec::new();
+            let mut tree_links = HashSet::new();
+            for &(_, dst_node_id) in tree_dsts.iter() {
+                for link_id in self.network_model.path(src_node_id, dst_node_id) {
+                    if tree_links.insert(link_id) {
+                        tree.push(link_id);
+                    }
+                }
+            }
+            // the tree transfer is represented by the transfer to the first destination
+            let part_id = self.next_dt_id.fetch_add(1, Ordering::Relaxed);
+            let dt = DataTransfer {
+                id: part_id,
+                src,
+                src_node_id,
+                dst: tree_dsts[0].0,
+                dst_node_id: tree_dsts[0].1,
+                size,
+                notification_dst,
+            };
+            self.ctx.emit_self_now(StartMulticastTransfer { dt, tree });
+            parts.push((part_id, tree_dsts, true));
+        }
+        self.multicasts.insert(
+            multicast_id,
+            MulticastState {
+                src,
+                src_node_id,
+                dsts: dsts.to_vec(),
+                size,
+                notification_dst,
+                parts_left: parts.len(),
+                completion_time: self.ctx.time(),
+            },
+        );
+        for (part_id, dsts, over_tree) in parts {
+            self.multicast_parts.insert(
+                part_id,
+                MulticastPart {
+                    multicast_id,
+                    dsts,
+                    over_tree,
+                },
+            );
+        }
+        multicast_id
+    }
+
+    /// Starts a multicast data transfer from the simulation component to all other components
+    /// with registered network locations, returns unique transfer id.
+    ///
+    /// See [`Self::multicast_data`] for details.
+    pub fn broadcast_data(&mut self, src: Id, size: f64, notification_dst: Id) -> usize {
+        let mut dsts = self
+            .locations
+            .keys()
+            .copied()
+            .filter(|&id| id != src)
+            .collect::<Vec<_>>();
+        dsts.sort();
+        self.multicast_data(src, &dsts, size, notification_dst)
+    }
+
+    fn on_multicast_part_completion(&mut self, part: MulticastPart) {
+        let state = self.multicasts.get_mut(&part.multicast_id).unwrap();
+        for (dst, dst_node_id) in part.dsts {
+            // the data propagation over the tree is not included in the tree transfer time
+            let delay = if part.over_tree {
+                self.network_model.latency(state.src_node_id, dst_node_id)
+            } else {
+                0.
+            };
+            let dt = DataTransfer {
+                id: part.multicast_id,
+                src: state.src,
+                src_node_id: state.src_node_id,
+                dst,
+                dst_node_id,
+                size: state.size,
+                notification_dst: state.notification_dst,
+            };
+            self.ctx
+                .emit(DataTransferCompleted { dt }, state.notification_dst, delay);
+            state.completion_time = state.completion_time.max(self.ctx.time() + delay);
+        }
+        state.parts_left -= 1;
+        if state.parts_left == 0 {
+            let state = self.multicasts.remove(&part.multicast_id).unwrap();
+            log_debug!(self.ctx, "completed multicast transfer {}", part.multicast_id);
+            self.ctx.emit(
+                MulticastCompleted {
+                    id: part.multicast_id,
+                    src: state.src,
+                    dsts: state.dsts,
+                    size: state.size,
+                },
+                state.notification_dst,
+                state.completion_time - self.ctx.time(),
+            );
+        }
+    }
+
+    /// Sets the mode used to calculate the delivery time of sized messages and events.
+    ///
+    /// See [`Self::send_sized_msg`] and [`Self::send_sized_event`]. The default mode is
+    /// [`MessageDeliveryMode::CutThrough`].
+    pub fn set_message_delivery_mode(&mut self, mode: MessageDeliveryMode) {
+        self.message_delivery_mode = mode;
+    }
+
+    /// Returns the mode used to calculate the delivery time of sized messages and events.
+    pub fn message_delivery_mode(&self) -> MessageDeliveryMode {
+        self.message_delivery_mode
+    }
+
     /// Sends a message between two simulation components, returns unique message id.
     ///
     /// The network locations of these components must be previously registered via [`Self::set_location`].
@@ -278,6 +790,65 @@ impl Network {
         let delay = self.latency(src, dst);
         self.ctx.emit_as(data, src, dst, delay)
     }
+
+    /// Sends a message of given size between two simulation components, returns unique message id.
+    ///
+    /// Unlike [`Self::send_msg`], the message delivery time takes into account the message size
+    /// according to the current [`MessageDeliveryMode`] (see [`Self::set_message_delivery_mode`]).
+    /// The [`MessageDelivered`] event is sent to `dst` on the message delivery.
+    pub fn send_sized_msg(&mut self, message: String, src: Id, dst: Id, size: f64) -> usize {
+        log_debug!(
+            self.ctx,
+            "{} sent message '{}' of size {} to {}",
+            src,
+            message,
+            size,
+            dst
+        );
+        let msg_id = self.next_msg_id.fetch_add(1, Ordering::Relaxed);
+        let msg = Message {
+            id: msg_id,
+            src,
+            dst,
+            data: message,
+        };
+        self.deliver_sized(MessageDelivered { msg }, src, dst, size, msg_id);
+        msg_id
+    }
+
+    /// Sends an event of given size between two simulation components, returns unique message id.
+    ///
+    /// Unlike [`Self::send_event`], the event delivery time takes into account the event size
+    /// according to the current [`MessageDeliveryMode`] (see [`Self::set_message_delivery_mode`]).
+    /// The returned id is not an event id, since in [`MessageDeliveryMode::Contended`] mode
+    /// the event is created only when the corresponding data transfer is completed.
+    pub fn send_sized_event<T: EventData>(&mut self, data: T, src: Id, dst: Id, size: f64) -> usize {
+        log_debug!(self.ctx, "{} sent event of size {} to {}", src, size, dst);
+        let msg_id = self.next_msg_id.fetch_add(1, Ordering::Relaxed);
+        self.deliver_sized(data, src, dst, size, msg_id);
+        msg_id
+    }
+
+    fn deliver_sized<T: EventData>(&mut self, data: T, src: Id, dst: Id, size: f64, msg_id: usize) {
+        if self.message_delivery_mode == MessageDeliveryMode::Contended {
+            let transfer_id = self.transfer_data(src, dst, size, self.ctx.id());
+            log_debug!(
+                self.ctx,
+                "message {} is delivered via data transfer {}",
+                msg_id,
+                transfer_id
+            );
+            self.pending_deliveries.insert(
+                transfer_id,
+                Box::new(move |ctx: &SimulationContext| {
+                    ctx.emit_as(data, src, dst, 0.);
+                }),
+            );
+        } else {
+            let delay = self.message_delay(src, dst, size);
+            self.ctx.emit_as(data, src, dst, delay);
+        }
+    }
 }
 
 impl EventHandler for Network {
@@ -290,6 +861,7 @@ impl EventHandler for Network {
                     &mut self.network_model
                 };
                 model.start_transfer(dt, &mut self.ctx);
+                self.on_link_usage_change();
             }
             DataTransferCompleted { dt } => {
                 log_debug!(
@@ -306,8 +878,33 @@ impl EventHandler for Network {
                     &mut self.network_model
                 };
                 model.on_transfer_completion(dt.clone(), &mut self.ctx);
-                let notification_dst = dt.notification_dst;
-                self.ctx.emit_now(DataTransferCompleted { dt }, notification_dst);
+                self.on_link_usage_change();
+                if let Some(deliver) = self.pending_deliveries.remove(&dt.id) {
+                    deliver(&self.ctx);
+                } else if let Some(part) = self.multicast_parts.remove(&dt.id) {
+                    self.on_multicast_part_completion(part);
+                } else {
+                    let notification_dst = dt.notification_dst;
+                    self.ctx.emit_now(DataTransferCompleted { dt }, notification_dst);
+                }
+            }
+            StartMulticastTransfer { dt, tree } => {
+                self.network_model.start_multicast_transfer(dt, tree, &mut self.ctx);
+                self.on_link_usage_change();
+            }
+            ModelWakeup {} => {
+                self.network_model.on_wakeup(&mut self.ctx);
+                self.on_link_usage_change();
+            }
+            SampleLinkUtilization {} => {
+                self.link_sampling_event = None;
+                if self.record_link_utilization() {
+                    if let Some(LinkSamplingMode::Interval(interval)) =
+                        self.link_utilization_log.as_ref().map(|log| log.mode())
+                    {
+                        self.schedule_link_sampling(interval);
+                    }
+                }
             }
         })
     }
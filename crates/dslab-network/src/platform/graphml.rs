@@ -0,0 +1,106 @@
+//! Loader of GraphML topology files.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::platform::xml::{parse_document, Element};
+use crate::platform::{Platform, PlatformBuilder, PlatformHost};
+use crate::Link;
+
+/// Returns the value of node or edge attribute, which is defined by `<data>` element referring to `<key>`.
+fn data_value<'a>(element: &'a Element, keys: &HashMap<&str, &str>, names: &[&str]) -> Option<&'a str> {
+    element.children_named("data").find_map(|data| {
+        let key = data.required_attr("key");
+        let name = keys.get(key).copied().unwrap_or(key);
+        if names.contains(&name) {
+            Some(data.text.trim())
+        } else {
+            None
+        }
+    })
+}
+
+fn parse_number(value: &str) -> f64 {
+    value
+        .parse()
+        .unwrap_or_else(|_| panic!("Can't parse number '{}'", value))
+}
+
+impl Platform {
+    /// Reads platform from a file in [GraphML](http://graphml.graphdrawing.org/) format.
+    ///
+    /// Each graph node becomes a host, unless its `type` attribute is `router` or `switch`.
+    /// Host speed and cores are read from `speed` (flop/s) and `cores` attributes, if present.
+    /// Each edge becomes a link with bandwidth read from `bandwidth` (bytes/s) or `LinkSpeedRaw` (bits/s,
+    /// as in Internet Topology Zoo) attribute and latency read from `latency` (seconds) attribute.
+    /// The missing parameters are taken from `default_link`. The links are bidirectional
+    /// unless the graph or edge is directed.
+    pub fn from_graphml<P: AsRef<Path>>(file: P, default_link: Link) -> Self {
+        Self::from_graphml_str(
+            &std::fs::read_to_string(&file).unwrap_or_else(|_| panic!("Can't read file {}", file.as_ref().display())),
+            default_link,
+        )
+    }
+
+    /// Reads platform from a string in [GraphML](http://graphml.graphdrawing.org/) format.
+    ///
+    /// See [`Self::from_graphml`].
+    pub fn from_graphml_str(content: &str, default_link: Link) -> Self {
+        let root = parse_document(content).unwrap_or_else(|e| panic!("Can't parse GraphML: {}", e));
+        assert!(root.name == "graphml", "Root element must be <graphml>");
+        let keys = root
+            .children_named("key")
+            .map(|key| {
+                (
+                    key.required_attr("id"),
+                    key.attr("attr.name").unwrap_or(key.required_attr("id")),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        let graph = root
+            .children_named("graph")
+            .next()
+            .unwrap_or_else(|| panic!("GraphML file contains no graph"));
+        let directed_graph = graph.attr("edgedefault") == Some("directed");
+
+        let mut builder = PlatformBuilder::default();
+        for node in graph.children_named("node") {
+            let name = node.required_attr("id");
+            match data_value(node, &keys, &["type"]) {
+                Some("router") | Some("switch") => builder.add_router(name),
+                _ => builder.add_host(PlatformHost {
+                    name: name.to_string(),
+                    speed: data_value(node, &keys, &["speed"]).map_or(1., parse_number),
+                    cores: data_value(node, &keys, &["cores"]).map_or(1, |c| parse_number(c) as u32),
+                    properties: BTreeMap::new(),
+                }),
+            }
+        }
+        for (i, edge) in graph.children_named("edge").enumerate() {
+            let name = edge
+                .attr("id")
+                .map(|id| id.to_string())
+                .unwrap_or_else(|| format!("edge_{}", i));
+            let mut link = default_link;
+            if let Some(bandwidth) = data_value(edge, &keys, &["bandwidth"]) {
+                link.bandwidth = parse_number(bandwidth);
+            } else if let Some(bandwidth) = data_value(edge, &keys, &["LinkSpeedRaw"]) {
+                link.bandwidth = parse_number(bandwidth) / 8.;
+            }
+            if let Some(latency) = data_value(edge, &keys, &["latency"]) {
+                link.latency = parse_number(latency);
+            }
+            let (src, dst) = (edge.required_attr("source"), edge.required_attr("target"));
+            builder.add_link(&name, link);
+            builder.add_route(src, dst, vec![name.clone()]);
+            let directed = edge.attr("directed").map_or(directed_graph, |d| d == "true");
+            if !directed {
+                builder.add_route(dst, src, vec![name]);
+            }
+        }
+        let mut platform = builder.build();
+        // edges do not define routes, so the paths are computed by the fallback routing
+        platform.routes.clear();
+        platform
+    }
+}
@@ -0,0 +1,428 @@
+//! Import of network platforms from external description formats.
+//!
+//! Supported formats:
+//!
+//! - [SimGrid platform XML](https://simgrid.org/doc/latest/Platform.html) (see [`Platform::from_simgrid_xml`]),
+//! - [GraphML](http://graphml.graphdrawing.org/) (see [`Platform::from_graphml`]).
+//!
+//! The loaded [`Platform`] contains hosts, routers, links and explicit routes between the nodes.
+//! It can be added to [`Network`] via [`Platform::add_to_network`], while the explicit routes are
+//! followed by the routing algorithm returned by [`Platform::routing`].
+
+mod graphml;
+mod simgrid;
+mod xml;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::models::ConstantBandwidthNetworkModel;
+use crate::routing::{RoutingAlgorithm, StaticRouting};
+use crate::{Link, Network, NetworkModel};
+
+/// Host (computing node) of the platform.
+#[derive(Clone, Debug)]
+pub struct PlatformHost {
+    /// Host name.
+    pub name: String,
+    /// Host speed in flop/s.
+    pub speed: f64,
+    /// Number of host cores.
+    pub cores: u32,
+    /// Additional host properties.
+    pub properties: BTreeMap<String, String>,
+}
+
+/// Link between two nodes of the platform.
+#[derive(Clone, Debug)]
+pub struct PlatformLink {
+    /// Link name.
+    pub name: String,
+    /// Name of the first (source) node.
+    pub src: String,
+    /// Name of the second (destination) node.
+    pub dst: String,
+    /// Link parameters, bandwidth is in bytes/s and latency is in seconds.
+    pub link: Link,
+    /// Whether the link can be used in both directions.
+    pub bidirectional: bool,
+}
+
+/// Explicit route between two nodes of the platform.
+#[derive(Clone, Debug)]
+pub struct PlatformRoute {
+    /// Name of the source node.
+    pub src: String,
+    /// Name of the destination node.
+    pub dst: String,
+    /// Names of the nodes on the route including the source and destination nodes.
+    pub nodes: Vec<String>,
+}
+
+/// Network platform loaded from external description.
+#[derive(Clone, Debug, Default)]
+pub struct Platform {
+    /// Platform hosts.
+    pub hosts: Vec<PlatformHost>,
+    /// Platform routers, including the auxiliary nodes connecting the links.
+    pub routers: Vec<String>,
+    /// Platform links.
+    pub links: Vec<PlatformLink>,
+    /// Explicit routes between the nodes.
+    pub routes: Vec<PlatformRoute>,
+    /// Parameters of the loopback links used for communications inside the hosts.
+    pub loopbacks: BTreeMap<String, Link>,
+}
+
+impl Platform {
+    /// Returns the host by its name.
+    pub fn host(&self, name: &str) -> Option<&PlatformHost> {
+        self.hosts.iter().find(|host| host.name == name)
+    }
+
+    /// Multiplies the bandwidth of all links (including loopbacks) by the given factor.
+    ///
+    /// Can be used to convert the bandwidth to the units used in simulation, e.g. MB/s.
+    pub fn scale_bandwidth(&mut self, factor: f64) {
+        for link in self.links.iter_mut() {
+            link.link.bandwidth *= factor;
+        }
+        for link in self.loopbacks.values_mut() {
+            link.bandwidth *= factor;
+        }
+    }
+
+    /// Adds the platform nodes and links to the network.
+    ///
+    /// The intra-node communications are modeled with [`ConstantBandwidthNetworkModel`] using the host loopback
+    /// parameters if they are specified, otherwise the model is created with `local_model` function.
+    /// The network must use a topology-aware model, and [`Network::init_topology`] must be called afterwards.
+    pub fn add_to_network<F>(&self, network: &mut Network, local_model: F)
+    where
+        F: Fn(&str) -> Box<dyn NetworkModel>,
+    {
+        for host in self.hosts.iter() {
+            let model: Box<dyn NetworkModel> = match self.loopbacks.get(&host.name) {
+                Some(loopback) => Box::new(ConstantBandwidthNetworkModel::new(loopback.bandwidth, loopback.latency)),
+                None => local_model(&host.name),
+            };
+            network.add_node(&host.name, model);
+        }
+        for router in self.routers.iter() {
+            network.add_node(router, local_model(router));
+        }
+        for link in self.links.iter() {
+            if link.bidirectional {
+                network.add_link(&link.src, &link.dst, link.link);
+            } else {
+                network.add_unidirectional_link(&link.src, &link.dst, link.link);
+            }
+        }
+    }
+
+    /// Returns the routing algorithm which follows the platform routes.
+    ///
+    /// The paths between the nodes without explicit route are computed using
+    /// [`ShortestPathFloydWarshall`](crate::routing::ShortestPathFloydWarshall).
+    pub fn routing(&self) -> Box<dyn RoutingAlgorithm> {
+        // routes without links (from a node to itself) are handled by the fallback
+        let routes = self.routes.iter().filter(|route| route.nodes.len() >= 2);
+        let routing = routes.fold(StaticRouting::new(), |routing, route| {
+            let nodes = route.nodes.iter().map(|node| node.as_str()).collect::<Vec<_>>();
+            routing.with_route(&nodes)
+        });
+        Box::new(routing)
+    }
+}
+
+// Builder -------------------------------------------------------------------------------------------------------------
+
+/// Route given as a sequence of links, which is used to infer the link endpoints.
+struct LinkRoute {
+    src: String,
+    dst: String,
+    links: Vec<String>,
+}
+
+/// Builds the platform graph from the nodes, links and routes given as sequences of links (as in SimGrid).
+///
+/// Each link has two ends, which are matched with the nodes and other link ends using the routes.
+/// The ends which are not matched with any host or router become auxiliary router nodes.
+#[derive(Default)]
+struct PlatformBuilder {
+    hosts: Vec<PlatformHost>,
+    routers: Vec<String>,
+    links: Vec<(String, Link)>,
+    link_ids: HashMap<String, usize>,
+    routes: Vec<LinkRoute>,
+    loopbacks: BTreeMap<String, Link>,
+}
+
+impl PlatformBuilder {
+    fn add_host(&mut self, host: PlatformHost) {
+        self.hosts.push(host);
+    }
+
+    fn add_router(&mut self, name: &str) {
+        self.routers.push(name.to_string());
+    }
+
+    fn add_link(&mut self, name: &str, link: Link) {
+        assert!(
+            !self.link_ids.contains_key(name),
+            "Link {} is defined more than once",
+            name
+        );
+        self.link_ids.insert(name.to_string(), self.links.len());
+        self.links.push((name.to_string(), link));
+    }
+
+    fn has_link(&self, name: &str) -> bool {
+        self.link_ids.contains_key(name)
+    }
+
+    fn add_route(&mut self, src: &str, dst: &str, links: Vec<String>) {
+        self.routes.push(LinkRoute {
+            src: src.to_string(),
+            dst: dst.to_string(),
+            links,
+        });
+    }
+
+    fn add_loopback(&mut self, host: &str, link: Link) {
+        self.loopbacks.insert(host.to_string(), link);
+    }
+
+    fn build(mut self) -> Platform {
+        let node_names = self
+            .hosts
+            .iter()
+            .map(|host| host.name.clone())
+            .chain(self.routers.iter().cloned())
+            .collect::<Vec<_>>();
+        let named_count = node_names.len();
+        let mut node_elems = HashMap::new();
+        for (elem, name) in node_names.iter().enumerate() {
+            assert!(
+                node_elems.insert(name.as_str(), elem).is_none(),
+                "Node {} is defined more than once",
+                name
+            );
+        }
+        let get_node_elem = |name: &str| {
+            *node_elems
+                .get(name)
+                .unwrap_or_else(|| panic!("Node {} used in route is not found", name))
+        };
+        let link_end = |link: usize, end: usize| named_count + 2 * link + end;
+
+        // Elements are the named nodes followed by two ends of each link.
+        let mut classes = EndClasses::new(named_count + 2 * self.links.len(), named_count, &node_names);
+        let mut used_forward = vec![false; self.links.len()];
+        let mut used_backward = vec![false; self.links.len()];
+        let mut traversals = Vec::new();
+
+        for route in self.routes.iter() {
+            let link_ids = route
+                .links
+                .iter()
+                .map(|name| {
+                    *self
+                        .link_ids
+                        .get(name)
+                        .unwrap_or_else(|| panic!("Link {} used in route is not found", name))
+                })
+                .collect::<Vec<_>>();
+            if route.src == route.dst {
+                if !link_ids.is_empty() && !self.loopbacks.contains_key(&route.src) {
+                    let links = link_ids.iter().map(|&id| self.links[id].1).collect::<Vec<_>>();
+                    self.loopbacks.insert(route.src.clone(), combine_links(&links));
+                }
+                continue;
+            }
+            // The route is traversed from both sides, since the orientation of a link can be determined
+            // only if one of its ends is already matched with the adjacent node or link end.
+            let mut exits = vec![0; link_ids.len()];
+            let (mut left, mut right) = (get_node_elem(&route.src), get_node_elem(&route.dst));
+            let (mut i, mut j) = (0, link_ids.len());
+            while i < j {
+                let (a, b) = (link_end(link_ids[i], 0), link_end(link_ids[i], 1));
+                let left_match = classes.matches(left, a) || classes.matches(left, b);
+                let (c, d) = (link_end(link_ids[j - 1], 0), link_end(link_ids[j - 1], 1));
+                let right_match = classes.matches(right, c) || classes.matches(right, d);
+                if left_match || classes.is_fresh(a) && classes.is_fresh(b) || !right_match {
+                    let forward = !classes.matches(left, b);
+                    let (enter, exit) = if forward { (a, b) } else { (b, a) };
+                    classes.union(left, enter);
+                    if forward {
+                        used_forward[link_ids[i]] = true;
+                    } else {
+                        used_backward[link_ids[i]] = true;
+                    }
+                    exits[i] = exit;
+                    left = exit;
+                    i += 1;
+                } else {
+                    let forward = classes.matches(right, d);
+                    let (enter, exit) = if forward { (c, d) } else { (d, c) };
+                    classes.union(right, exit);
+                    if forward {
+                        used_forward[link_ids[j - 1]] = true;
+                    } else {
+                        used_backward[link_ids[j - 1]] = true;
+                    }
+                    exits[j - 1] = exit;
+                    right = enter;
+                    j -= 1;
+                }
+            }
+            classes.union(left, right);
+            let route_traversals = exits;
+            traversals.push((route.src.clone(), route.dst.clone(), route_traversals));
+        }
+
+        // Assign names to the classes of link ends.
+        let mut class_names: HashMap<usize, String> = HashMap::new();
+        for (elem, name) in node_names.iter().enumerate() {
+            class_names.insert(classes.find(elem), name.clone());
+        }
+        let mut aux_routers = Vec::new();
+        for link_id in 0..self.links.len() {
+            if !used_forward[link_id] && !used_backward[link_id] {
+                continue;
+            }
+            for end in 0..2 {
+                let class = classes.find(link_end(link_id, end));
+                class_names.entry(class).or_insert_with(|| {
+                    let name = format!("{}#{}", self.links[link_id].0, end);
+                    aux_routers.push(name.clone());
+                    name
+                });
+            }
+        }
+
+        let mut links = Vec::new();
+        let mut directed_pairs = HashSet::new();
+        for (link_id, (name, link)) in self.links.iter().enumerate() {
+            if !used_forward[link_id] && !used_backward[link_id] {
+                continue;
+            }
+            let mut src = class_names[&classes.find(link_end(link_id, 0))].clone();
+            let mut dst = class_names[&classes.find(link_end(link_id, 1))].clone();
+            assert!(src != dst, "Link {} connects node {} with itself", name, src);
+            let bidirectional = used_forward[link_id] && used_backward[link_id];
+            if !used_forward[link_id] {
+                std::mem::swap(&mut src, &mut dst);
+            }
+            let mut pairs = vec![(src.clone(), dst.clone())];
+            if bidirectional {
+                pairs.push((dst.clone(), src.clone()));
+            }
+            for pair in pairs {
+                assert!(
+                    directed_pairs.insert(pair.clone()),
+                    "Multiple links from {} to {} are not supported",
+                    pair.0,
+                    pair.1
+                );
+            }
+            links.push(PlatformLink {
+                name: name.clone(),
+                src,
+                dst,
+                link: *link,
+                bidirectional,
+            });
+        }
+
+        let routes = traversals
+            .into_iter()
+            .map(|(src, dst, route_traversals)| {
+                let nodes = std::iter::once(src.clone())
+                    .chain(
+                        route_traversals
+                            .into_iter()
+                            .map(|elem| class_names[&classes.find(elem)].clone()),
+                    )
+                    .collect();
+                PlatformRoute { src, dst, nodes }
+            })
+            .collect();
+
+        self.routers.extend(aux_routers);
+        Platform {
+            hosts: self.hosts,
+            routers: self.routers,
+            links,
+            routes,
+            loopbacks: self.loopbacks,
+        }
+    }
+}
+
+/// Combines a sequence of links into a single link with bottleneck bandwidth and total latency.
+fn combine_links(links: &[Link]) -> Link {
+    Link {
+        bandwidth: links.iter().map(|l| l.bandwidth).fold(f64::INFINITY, f64::min),
+        latency: links.iter().map(|l| l.latency).sum(),
+        sharing_policy: links[0].sharing_policy,
+    }
+}
+
+/// Disjoint sets of nodes and link ends, which checks that distinct named nodes are never merged.
+struct EndClasses<'a> {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    named_count: usize,
+    node_names: &'a [String],
+}
+
+impl<'a> EndClasses<'a> {
+    fn new(size: usize, named_count: usize, node_names: &'a [String]) -> Self {
+        Self {
+            parent: (0..size).collect(),
+            size: vec![1; size],
+            named_count,
+            node_names,
+        }
+    }
+
+    fn find(&mut self, elem: usize) -> usize {
+        let mut root = elem;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut cur = elem;
+        while self.parent[cur] != root {
+            let next = self.parent[cur];
+            self.parent[cur] = root;
+            cur = next;
+        }
+        root
+    }
+
+    fn matches(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    fn is_fresh(&mut self, elem: usize) -> bool {
+        let root = self.find(elem);
+        self.size[root] == 1
+    }
+
+    fn union(&mut self, x: usize, y: usize) {
+        let (x, y) = (self.find(x), self.find(y));
+        if x == y {
+            return;
+        }
+        // named nodes are always the roots of their classes
+        if x < self.named_count && y < self.named_count {
+            panic!(
+                "Platform routes are inconsistent: nodes {} and {} are connected without a link",
+                self.node_names[x], self.node_names[y]
+            );
+        }
+        let (child, root) = if y < self.named_count { (x, y) } else { (y, x) };
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+    }
+}
@@ -0,0 +1,360 @@
+//! Loader of SimGrid platform XML files.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::platform::xml::{parse_document, Element};
+use crate::platform::{Platform, PlatformBuilder, PlatformHost};
+use crate::Link;
+
+const BANDWIDTH_UNITS: &[(&str, f64)] = &[
+    ("", 1.),
+    ("Bps", 1.),
+    ("kBps", 1e3),
+    ("KBps", 1e3),
+    ("MBps", 1e6),
+    ("GBps", 1e9),
+    ("TBps", 1e12),
+    ("KiBps", 1024.),
+    ("MiBps", 1024. * 1024.),
+    ("GiBps", 1024. * 1024. * 1024.),
+    ("TiBps", 1024. * 1024. * 1024. * 1024.),
+    ("bps", 1. / 8.),
+    ("kbps", 1e3 / 8.),
+    ("Kbps", 1e3 / 8.),
+    ("Mbps", 1e6 / 8.),
+    ("Gbps", 1e9 / 8.),
+    ("Tbps", 1e12 / 8.),
+    ("Kibps", 1024. / 8.),
+    ("Mibps", 1024. * 1024. / 8.),
+    ("Gibps", 1024. * 1024. * 1024. / 8.),
+    ("Tibps", 1024. * 1024. * 1024. * 1024. / 8.),
+];
+
+const TIME_UNITS: &[(&str, f64)] = &[
+    ("", 1.),
+    ("w", 7. * 24. * 60. * 60.),
+    ("d", 24. * 60. * 60.),
+    ("h", 60. * 60.),
+    ("m", 60.),
+    ("s", 1.),
+    ("ms", 1e-3),
+    ("us", 1e-6),
+    ("ns", 1e-9),
+    ("ps", 1e-12),
+];
+
+const SPEED_UNITS: &[(&str, f64)] = &[
+    ("", 1.),
+    ("f", 1.),
+    ("kf", 1e3),
+    ("Mf", 1e6),
+    ("Gf", 1e9),
+    ("Tf", 1e12),
+    ("Pf", 1e15),
+    ("Ef", 1e18),
+    ("Zf", 1e21),
+];
+
+/// Parses a value with optional unit suffix, e.g. `10ms` or `1.25GBps`.
+fn parse_value(value: &str, units: &[(&str, f64)]) -> f64 {
+    let value = value.trim();
+    let number_len = value.trim_end_matches(|c: char| c.is_ascii_alphabetic()).len();
+    let (number, unit) = value.split_at(number_len);
+    let unit = unit.strip_suffix("lops").unwrap_or(unit);
+    let number: f64 = number
+        .parse()
+        .unwrap_or_else(|_| panic!("Can't parse value '{}'", value));
+    let (_, factor) = units
+        .iter()
+        .find(|(name, _)| *name == unit)
+        .unwrap_or_else(|| panic!("Unknown unit in value '{}'", value));
+    number * factor
+}
+
+/// Parses the list of integers like `0-3,7,9-10`.
+fn parse_radical(radical: &str) -> Vec<u64> {
+    let mut values = Vec::new();
+    for part in radical.split(',') {
+        let parse = |s: &str| -> u64 {
+            s.trim()
+                .parse()
+                .unwrap_or_else(|_| panic!("Can't parse cluster radical '{}'", radical))
+        };
+        match part.split_once('-') {
+            Some((from, to)) => values.extend(parse(from)..=parse(to)),
+            None => values.push(parse(part)),
+        }
+    }
+    values
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SharingPolicy {
+    Shared,
+    FatPipe,
+    SplitDuplex,
+}
+
+fn parse_sharing_policy(element: &Element, attr: &str, default: SharingPolicy) -> SharingPolicy {
+    match element.attr(attr) {
+        None => default,
+        Some("SHARED") => SharingPolicy::Shared,
+        Some("FATPIPE") => SharingPolicy::FatPipe,
+        Some("SPLITDUPLEX") => SharingPolicy::SplitDuplex,
+        Some(policy) => panic!("Unknown sharing policy {}", policy),
+    }
+}
+
+fn make_link(bandwidth: f64, latency: f64, policy: SharingPolicy) -> Link {
+    match policy {
+        SharingPolicy::FatPipe => Link::non_shared(bandwidth, latency),
+        _ => Link::shared(bandwidth, latency),
+    }
+}
+
+/// Route with references to links as specified in the platform file.
+struct RawRoute {
+    src: String,
+    dst: String,
+    symmetrical: bool,
+    links: Vec<(String, Option<String>)>,
+}
+
+#[derive(Default)]
+struct SimGridLoader {
+    builder: PlatformBuilder,
+    split_duplex_links: Vec<String>,
+    routes: Vec<RawRoute>,
+}
+
+impl SimGridLoader {
+    fn add_link(&mut self, name: &str, bandwidth: f64, latency: f64, policy: SharingPolicy) {
+        if policy == SharingPolicy::SplitDuplex {
+            self.builder
+                .add_link(&format!("{}_UP", name), make_link(bandwidth, latency, policy));
+            self.builder
+                .add_link(&format!("{}_DOWN", name), make_link(bandwidth, latency, policy));
+            self.split_duplex_links.push(name.to_string());
+        } else {
+            self.builder.add_link(name, make_link(bandwidth, latency, policy));
+        }
+    }
+
+    fn add_route(&mut self, src: &str, dst: &str, symmetrical: bool, links: Vec<(String, Option<String>)>) {
+        self.routes.push(RawRoute {
+            src: src.to_string(),
+            dst: dst.to_string(),
+            symmetrical,
+            links,
+        });
+    }
+
+    fn process_zone(&mut self, zone: &Element) {
+        for element in zone.children.iter() {
+            match element.name.as_str() {
+                "zone" | "AS" => self.process_zone(element),
+                "host" => self.process_host(element),
+                "router" => self.builder.add_router(element.required_attr("id")),
+                "link" => {
+                    let bandwidth = parse_value(element.required_attr("bandwidth"), BANDWIDTH_UNITS);
+                    let latency = parse_value(element.attr("latency").unwrap_or("0"), TIME_UNITS);
+                    let policy = parse_sharing_policy(element, "sharing_policy", SharingPolicy::Shared);
+                    self.add_link(element.required_attr("id"), bandwidth, latency, policy);
+                }
+                "route" | "bypassRoute" => self.process_route(element, "src", "dst"),
+                "zoneRoute" | "ASroute" | "bypassZoneRoute" | "bypassASroute" => {
+                    self.process_route(element, "gw_src", "gw_dst")
+                }
+                "cluster" => self.process_cluster(element),
+                _ => {
+                    log::warn!("Unsupported platform element <{}> is ignored", element.name);
+                }
+            }
+        }
+    }
+
+    fn process_host(&mut self, element: &Element) {
+        let speed = element.required_attr("speed").split(',').next().unwrap();
+        let properties = element
+            .children_named("prop")
+            .map(|prop| {
+                (
+                    prop.required_attr("id").to_string(),
+                    prop.required_attr("value").to_string(),
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+        self.builder.add_host(PlatformHost {
+            name: element.required_attr("id").to_string(),
+            speed: parse_value(speed, SPEED_UNITS),
+            cores: element.attr("core").map_or(1, |c| {
+                c.parse().unwrap_or_else(|_| panic!("Can't parse core count '{}'", c))
+            }),
+            properties,
+        });
+    }
+
+    fn process_route(&mut self, element: &Element, src_attr: &str, dst_attr: &str) {
+        let symmetrical = !matches!(element.attr("symmetrical"), Some("NO") | Some("no"));
+        let links = element
+            .children_named("link_ctn")
+            .map(|link| {
+                (
+                    link.required_attr("id").to_string(),
+                    link.attr("direction").map(|d| d.to_string()),
+                )
+            })
+            .collect();
+        self.add_route(
+            element.required_attr(src_attr),
+            element.required_attr(dst_attr),
+            symmetrical,
+            links,
+        );
+    }
+
+    fn process_cluster(&mut self, element: &Element) {
+        let id = element.required_attr("id");
+        let prefix = element.attr("prefix").unwrap_or("");
+        let suffix = element.attr("suffix").unwrap_or("");
+        let speed = parse_value(element.required_attr("speed").split(',').next().unwrap(), SPEED_UNITS);
+        let cores = element.attr("core").map_or(1, |c| {
+            c.parse().unwrap_or_else(|_| panic!("Can't parse core count '{}'", c))
+        });
+        let bandwidth = parse_value(element.required_attr("bw"), BANDWIDTH_UNITS);
+        let latency = parse_value(element.attr("lat").unwrap_or("0"), TIME_UNITS);
+        let policy = parse_sharing_policy(element, "sharing_policy", SharingPolicy::SplitDuplex);
+        let router = element
+            .attr("router_id")
+            .map(|r| r.to_string())
+            .unwrap_or_else(|| format!("{}{}_router{}", prefix, id, suffix));
+        self.builder.add_router(&router);
+
+        let backbone = element.attr("bb_bw").map(|bb_bw| {
+            let bb_bandwidth = parse_value(bb_bw, BANDWIDTH_UNITS);
+            let bb_latency = parse_value(element.attr("bb_lat").unwrap_or("0"), TIME_UNITS);
+            let bb_policy = parse_sharing_policy(element, "bb_sharing_policy", SharingPolicy::Shared);
+            make_link(bb_bandwidth, bb_latency, bb_policy)
+        });
+        let (bb_in, bb_out) = match backbone {
+            Some(backbone) => {
+                // Backbone is traversed by all routes between cluster hosts, so it is modeled as a link
+                // between two auxiliary nodes, which are connected with host uplinks and downlinks respectively.
+                let bb_in = format!("{}_backbone_in", id);
+                let bb_out = format!("{}_backbone_out", id);
+                let bb_name = format!("{}_backbone", id);
+                self.builder.add_router(&bb_in);
+                self.builder.add_router(&bb_out);
+                self.builder.add_link(&bb_name, backbone);
+                self.builder.add_route(&bb_in, &bb_out, vec![bb_name]);
+                let router_in = format!("{}_router_in", id);
+                let router_out = format!("{}_router_out", id);
+                self.builder.add_link(&router_in, Link::non_shared(f64::MAX, 0.));
+                self.builder.add_link(&router_out, Link::non_shared(f64::MAX, 0.));
+                self.builder.add_route(&router, &bb_in, vec![router_in]);
+                self.builder.add_route(&bb_out, &router, vec![router_out]);
+                (bb_in, bb_out)
+            }
+            None => (router.clone(), router.clone()),
+        };
+
+        let loopback = element.attr("loopback_bw").map(|lb_bw| {
+            let lb_latency = parse_value(element.attr("loopback_lat").unwrap_or("0"), TIME_UNITS);
+            Link::non_shared(parse_value(lb_bw, BANDWIDTH_UNITS), lb_latency)
+        });
+
+        for i in parse_radical(element.required_attr("radical")) {
+            let host = format!("{}{}{}", prefix, i, suffix);
+            self.builder.add_host(PlatformHost {
+                name: host.clone(),
+                speed,
+                cores,
+                properties: BTreeMap::new(),
+            });
+            if let Some(loopback) = loopback {
+                self.builder.add_loopback(&host, loopback);
+            }
+            let link_name = format!("{}_link_{}", id, i);
+            let link = make_link(bandwidth, latency, policy);
+            if policy == SharingPolicy::SplitDuplex || backbone.is_some() {
+                // With backbone, the shared link is approximated by two independent links,
+                // since uplink and downlink are connected to different sides of the backbone.
+                let (up, down) = (format!("{}_UP", link_name), format!("{}_DOWN", link_name));
+                self.builder.add_link(&up, link);
+                self.builder.add_link(&down, link);
+                self.builder.add_route(&host, &bb_in, vec![up]);
+                self.builder.add_route(&bb_out, &host, vec![down]);
+            } else {
+                self.builder.add_link(&link_name, link);
+                self.builder.add_route(&host, &router, vec![link_name.clone()]);
+                self.builder.add_route(&router, &host, vec![link_name]);
+            }
+        }
+    }
+
+    fn resolve_link(&self, name: &str, direction: Option<&str>) -> String {
+        if self.split_duplex_links.iter().any(|l| l == name) {
+            match direction {
+                Some("UP") => format!("{}_UP", name),
+                Some("DOWN") => format!("{}_DOWN", name),
+                _ => panic!("Direction (UP or DOWN) must be specified for SPLITDUPLEX link {}", name),
+            }
+        } else {
+            assert!(self.builder.has_link(name), "Link {} is not found", name);
+            name.to_string()
+        }
+    }
+
+    fn finish(mut self) -> Platform {
+        let routes = std::mem::take(&mut self.routes);
+        for route in routes {
+            let links = route
+                .links
+                .iter()
+                .map(|(name, direction)| self.resolve_link(name, direction.as_deref()))
+                .collect();
+            self.builder.add_route(&route.src, &route.dst, links);
+            if route.symmetrical && route.src != route.dst {
+                let links = route
+                    .links
+                    .iter()
+                    .rev()
+                    .map(|(name, direction)| {
+                        let direction = direction.as_deref().map(|d| if d == "UP" { "DOWN" } else { "UP" });
+                        self.resolve_link(name, direction)
+                    })
+                    .collect();
+                self.builder.add_route(&route.dst, &route.src, links);
+            }
+        }
+        self.builder.build()
+    }
+}
+
+impl Platform {
+    /// Reads platform from a file in [SimGrid platform format](https://simgrid.org/doc/latest/Platform.html).
+    ///
+    /// Supports hosts (including their properties), routers, links, routes and clusters located in arbitrarily
+    /// nested zones. Zone routes are treated as routes between the specified gateways. Since each link in the
+    /// resulting platform connects two nodes, the link endpoints are inferred from the routes. The routes which
+    /// can not be represented this way, e.g. the ones connecting two nodes without a link, are reported via panic.
+    ///
+    /// Bandwidth is converted to bytes/s, latency to seconds and host speed to flop/s.
+    pub fn from_simgrid_xml<P: AsRef<Path>>(file: P) -> Self {
+        Self::from_simgrid_xml_str(
+            &std::fs::read_to_string(&file).unwrap_or_else(|_| panic!("Can't read file {}", file.as_ref().display())),
+        )
+    }
+
+    /// Reads platform from a string in [SimGrid platform format](https://simgrid.org/doc/latest/Platform.html).
+    ///
+    /// See [`Self::from_simgrid_xml`].
+    pub fn from_simgrid_xml_str(content: &str) -> Self {
+        let root = parse_document(content).unwrap_or_else(|e| panic!("Can't parse platform XML: {}", e));
+        assert!(root.name == "platform", "Root element must be <platform>");
+        let mut loader = SimGridLoader::default();
+        loader.process_zone(&root);
+        loader.finish()
+    }
+}
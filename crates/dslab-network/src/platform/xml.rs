@@ -0,0 +1,59 @@
+//! Minimal XML element tree used by platform loaders.
+
+use std::collections::HashMap;
+
+use xml::reader::{EventReader, XmlEvent};
+
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: HashMap<String, String>,
+    pub children: Vec<Element>,
+    pub text: String,
+}
+
+impl Element {
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(|s| s.as_str())
+    }
+
+    pub fn required_attr(&self, name: &str) -> &str {
+        self.attr(name)
+            .unwrap_or_else(|| panic!("Element <{}> misses required attribute '{}'", self.name, name))
+    }
+
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+/// Parses XML document and returns its root element.
+pub(crate) fn parse_document(content: &str) -> Result<Element, String> {
+    let mut stack: Vec<Element> = Vec::new();
+    for event in EventReader::from_str(content) {
+        match event.map_err(|e| e.to_string())? {
+            XmlEvent::StartElement { name, attributes, .. } => stack.push(Element {
+                name: name.local_name,
+                attributes: attributes
+                    .into_iter()
+                    .map(|attr| (attr.name.local_name, attr.value))
+                    .collect(),
+                children: Vec::new(),
+                text: String::new(),
+            }),
+            XmlEvent::EndElement { .. } => {
+                let element = stack.pop().unwrap();
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element),
+                }
+            }
+            XmlEvent::Characters(text) => {
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&text);
+                }
+            }
+            _ => {}
+        }
+    }
+    Err("Document has no root element".to_string())
+}
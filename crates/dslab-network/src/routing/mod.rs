@@ -0,0 +1,479 @@
+//! Routing algorithms.
+
+mod static_routes;
+mod zones;
+
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
+
+use crate::topology::NodeLinksMap;
+use crate::{LinkId, NodeId, Topology};
+
+pub use static_routes::StaticRouting;
+pub use zones::{HierarchicalRouting, Zone, ZoneRouting};
+
+const INVALID_NODE_ID: usize = usize::MAX;
+
+/// Calculates the paths between pairs of nodes in a network.
+pub trait RoutingAlgorithm {
+    /// Performs initialization of the routing algorithm based on the provided network topology.
+    fn init(&mut self, topology: &Topology);
+
+    /// Returns a path iterator from node `src` to node `dst`, or `None` if there is no path.
+    ///
+    /// Can be used only after calling [`Self::init`].
+    fn get_path_iter<'a>(&'a self, src: NodeId, dst: NodeId, topology: &'a Topology) -> Option<PathIterator<'a>>;
+
+    /// Updates the routing after a new node is added to the topology.
+    ///
+    /// By default performs the full recomputation via [`Self::init`].
+    fn on_node_added(&mut self, _node_id: NodeId, topology: &Topology) {
+        self.init(topology);
+    }
+
+    /// Updates the routing after a new link is added between the nodes which were not directly connected before.
+    ///
+    /// By default performs the full recomputation via [`Self::init`].
+    fn on_link_added(&mut self, _link_id: LinkId, topology: &Topology) {
+        self.init(topology);
+    }
+
+    /// Updates the routing after the link is removed from the topology.
+    ///
+    /// By default performs the full recomputation via [`Self::init`].
+    fn on_link_removed(&mut self, _link_id: LinkId, topology: &Topology) {
+        self.init(topology);
+    }
+}
+
+/// Iterator which returns links on a path.
+pub struct PathIterator<'a> {
+    inner: PathIteratorInner<'a>,
+}
+
+enum PathIteratorInner<'a> {
+    ParentPath {
+        src: NodeId,
+        dst: NodeId,
+        node_links_map: &'a NodeLinksMap,
+        parent_path: &'a Vec<Vec<NodeId>>,
+    },
+    Links(std::slice::Iter<'a, LinkId>),
+    OwnedLinks(std::vec::IntoIter<LinkId>),
+}
+
+impl<'a> PathIterator<'a> {
+    /// Creates an iterator over the explicitly specified sequence of links.
+    pub fn from_links(links: &'a [LinkId]) -> Self {
+        Self {
+            inner: PathIteratorInner::Links(links.iter()),
+        }
+    }
+
+    /// Creates an iterator over the path computed on the fly.
+    pub fn from_vec(links: Vec<LinkId>) -> Self {
+        Self {
+            inner: PathIteratorInner::OwnedLinks(links.into_iter()),
+        }
+    }
+
+    fn from_parent_path(
+        src: NodeId,
+        dst: NodeId,
+        node_links_map: &'a NodeLinksMap,
+        parent_path: &'a Vec<Vec<NodeId>>,
+    ) -> Self {
+        Self {
+            inner: PathIteratorInner::ParentPath {
+                src,
+                dst,
+                node_links_map,
+                parent_path,
+            },
+        }
+    }
+}
+
+impl Iterator for PathIterator<'_> {
+    type Item = LinkId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            PathIteratorInner::ParentPath {
+                src,
+                dst,
+                node_links_map,
+                parent_path,
+            } => {
+                if src == dst {
+                    return None;
+                }
+                let next = parent_path[*dst][*src];
+                let link_id = node_links_map[src][&next];
+                *src = next;
+                Some(link_id)
+            }
+            PathIteratorInner::Links(links) => links.next().copied(),
+            PathIteratorInner::OwnedLinks(links) => links.next(),
+        }
+    }
+}
+
+// Incremental updates ------------------------------------------------------------------------------------------------
+//
+// The shortest paths are computed in the inverted graph, i.e. `parent_path[dst][node]` is the next node
+// on the path from `node` to `dst` and `distance[dst][node]` is the latency of this path.
+
+#[derive(PartialEq)]
+struct HeapItem {
+    latency: f64,
+    node: usize,
+}
+
+impl Ord for HeapItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .latency
+            .total_cmp(&self.latency)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for HeapItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Eq for HeapItem {}
+
+/// Returns the edges of the link in the inverted graph along with the link latency.
+fn inverted_edges(link_id: LinkId, topology: &Topology) -> Vec<(NodeId, NodeId, f64)> {
+    let latency = topology.link(link_id).latency;
+    topology
+        .link_directions(link_id)
+        .into_iter()
+        .map(|(node_from, node_to)| (node_to, node_from, latency))
+        .collect()
+}
+
+/// Returns the ids of incoming and outgoing links of the node.
+fn node_links(node_id: NodeId, topology: &Topology) -> BTreeSet<LinkId> {
+    topology.node_links_map()[&node_id]
+        .values()
+        .chain(topology.inv_node_links_map()[&node_id].values())
+        .copied()
+        .collect()
+}
+
+/// Extends the distance and parent matrices to include the nodes added to the topology.
+fn resize_tables(
+    distance: &mut Vec<Vec<f64>>,
+    parent_path: &mut Vec<Vec<NodeId>>,
+    topology: &Topology,
+    self_parent: bool,
+) {
+    let node_count = topology.node_count();
+    for (dist_row, parent_row) in distance.iter_mut().zip(parent_path.iter_mut()) {
+        dist_row.resize(node_count, f64::INFINITY);
+        parent_row.resize(node_count, INVALID_NODE_ID);
+    }
+    while distance.len() < node_count {
+        let node = distance.len();
+        let mut dist_row = vec![f64::INFINITY; node_count];
+        let mut parent_row = vec![INVALID_NODE_ID; node_count];
+        dist_row[node] = 0.;
+        if self_parent {
+            parent_row[node] = node;
+        }
+        distance.push(dist_row);
+        parent_path.push(parent_row);
+    }
+}
+
+/// Relaxes the edges of the inverted graph starting from the nodes in the heap until no distance can be improved.
+fn propagate(mut heap: BinaryHeap<HeapItem>, distance: &mut [f64], parent: &mut [NodeId], topology: &Topology) {
+    let inv_node_links_map = topology.inv_node_links_map();
+    while let Some(HeapItem { latency, node }) = heap.pop() {
+        if latency > distance[node] {
+            continue;
+        }
+        for (&next, &link_id) in inv_node_links_map[&node].iter() {
+            let next_latency = latency + topology.link(link_id).latency;
+            if next_latency < distance[next] {
+                distance[next] = next_latency;
+                parent[next] = node;
+                heap.push(HeapItem {
+                    latency: next_latency,
+                    node: next,
+                });
+            }
+        }
+    }
+}
+
+/// Updates the shortest paths to a single node after adding the edge of the inverted graph.
+fn relax_added_edge(distance: &mut [f64], parent: &mut [NodeId], edge: (NodeId, NodeId, f64), topology: &Topology) {
+    let (node_from, node_to, edge_latency) = edge;
+    let latency = distance[node_from] + edge_latency;
+    if latency < distance[node_to] {
+        distance[node_to] = latency;
+        parent[node_to] = node_from;
+        propagate(
+            BinaryHeap::from([HeapItem { latency, node: node_to }]),
+            distance,
+            parent,
+            topology,
+        );
+    }
+}
+
+/// Repairs the shortest paths to `root` after removing the edges of the inverted graph.
+///
+/// Only the distances of nodes in the subtrees below the removed edges are recomputed.
+fn repair_removed_edges(
+    distance: &mut [f64],
+    parent: &mut [NodeId],
+    root: NodeId,
+    edges: &[(NodeId, NodeId, f64)],
+    topology: &Topology,
+) {
+    let mut stack = edges
+        .iter()
+        .filter(|(node_from, node_to, _)| *node_to != root && parent[*node_to] == *node_from)
+        .map(|(_, node_to, _)| *node_to)
+        .collect::<Vec<_>>();
+    if stack.is_empty() {
+        return;
+    }
+    let mut children = vec![Vec::new(); distance.len()];
+    for (node, &prev) in parent.iter().enumerate() {
+        if node != root && prev != INVALID_NODE_ID {
+            children[prev].push(node);
+        }
+    }
+    let mut affected = Vec::new();
+    let mut is_affected = vec![false; distance.len()];
+    while let Some(node) = stack.pop() {
+        if !is_affected[node] {
+            is_affected[node] = true;
+            affected.push(node);
+            stack.extend(children[node].iter().copied());
+        }
+    }
+
+    for &node in affected.iter() {
+        distance[node] = f64::INFINITY;
+        parent[node] = INVALID_NODE_ID;
+    }
+    // the incoming edges of the inverted graph are the outgoing links of the topology
+    let node_links_map = topology.node_links_map();
+    let mut heap = BinaryHeap::new();
+    for &node in affected.iter() {
+        for (&prev, &link_id) in node_links_map[&node].iter() {
+            let latency = distance[prev] + topology.link(link_id).latency;
+            if latency < distance[node] {
+                distance[node] = latency;
+                parent[node] = prev;
+            }
+        }
+        if distance[node] < f64::INFINITY {
+            heap.push(HeapItem {
+                latency: distance[node],
+                node,
+            });
+        }
+    }
+    propagate(heap, distance, parent, topology);
+}
+
+// Shortest Path (Floyd–Warshall) --------------------------------------------------------------------------------------
+
+/// Static routing algorithm which returns shortest paths (by latency) computed using the Floyd–Warshall algorithm.
+///
+/// Supports incremental updates: adding a link updates the paths in O(n^2) time, removing a link recomputes
+/// only the paths which were going through it.
+#[derive(Default)]
+pub struct ShortestPathFloydWarshall {
+    parent_path: Vec<Vec<NodeId>>,
+    distance: Vec<Vec<f64>>,
+}
+
+impl RoutingAlgorithm for ShortestPathFloydWarshall {
+    fn init(&mut self, topology: &Topology) {
+        let node_count = topology.node_count();
+        self.parent_path = vec![vec![INVALID_NODE_ID; node_count]; node_count];
+        let mut current_paths = vec![vec![f64::INFINITY; node_count]; node_count];
+        #[allow(clippy::needless_range_loop)]
+        for node in 0..node_count {
+            current_paths[node][node] = 0.0;
+            self.parent_path[node][node] = node;
+        }
+
+        for (node1, intermap) in topology.inv_node_links_map() {
+            for (node2, link_id) in intermap {
+                current_paths[*node1][*node2] = topology.link(*link_id).latency;
+                self.parent_path[*node1][*node2] = *node1;
+            }
+        }
+
+        for k in 0..node_count {
+            for i in 0..node_count {
+                for j in 0..node_count {
+                    if current_paths[i][k] < f64::INFINITY
+                        && current_paths[k][j] < f64::INFINITY
+                        && current_paths[i][k] + current_paths[k][j] < current_paths[i][j]
+                    {
+                        current_paths[i][j] = current_paths[i][k] + current_paths[k][j];
+                        let prev = self.parent_path[k][j];
+                        self.parent_path[i][j] = prev;
+                    }
+                }
+            }
+        }
+        self.distance = current_paths;
+    }
+
+    fn get_path_iter<'a>(&'a self, src: NodeId, dst: NodeId, topology: &'a Topology) -> Option<PathIterator<'a>> {
+        if self.parent_path[dst][src] == INVALID_NODE_ID {
+            None
+        } else {
+            Some(PathIterator::from_parent_path(
+                src,
+                dst,
+                topology.node_links_map(),
+                &self.parent_path,
+            ))
+        }
+    }
+
+    fn on_node_added(&mut self, node_id: NodeId, topology: &Topology) {
+        resize_tables(&mut self.distance, &mut self.parent_path, topology, true);
+        for link_id in node_links(node_id, topology) {
+            self.on_link_added(link_id, topology);
+        }
+    }
+
+    fn on_link_added(&mut self, link_id: LinkId, topology: &Topology) {
+        let node_count = self.distance.len();
+        for (node_from, node_to, edge_latency) in inverted_edges(link_id, topology) {
+            let to_distance = self.distance[node_to].clone();
+            let to_parent = self.parent_path[node_to].clone();
+            for i in 0..node_count {
+                let base_latency = self.distance[i][node_from] + edge_latency;
+                if base_latency == f64::INFINITY {
+                    continue;
+                }
+                for j in 0..node_count {
+                    let latency = base_latency + to_distance[j];
+                    if latency < self.distance[i][j] {
+                        self.distance[i][j] = latency;
+                        self.parent_path[i][j] = if j == node_to { node_from } else { to_parent[j] };
+                    }
+                }
+            }
+        }
+    }
+
+    fn on_link_removed(&mut self, link_id: LinkId, topology: &Topology) {
+        let edges = inverted_edges(link_id, topology);
+        for (root, (distance, parent)) in self.distance.iter_mut().zip(self.parent_path.iter_mut()).enumerate() {
+            repair_removed_edges(distance, parent, root, &edges, topology);
+        }
+    }
+}
+
+// Shortest Path (Dijkstra) --------------------------------------------------------------------------------------------
+
+/// Static routing algorithm which returns shortest paths (by latency) computed using the Dijkstra's algorithm.
+///
+/// Supports incremental updates: after adding or removing a link, only the affected paths are recomputed.
+#[derive(Default)]
+pub struct ShortestPathDijkstra {
+    parent_path: Vec<Vec<NodeId>>,
+    distance: Vec<Vec<f64>>,
+}
+
+impl ShortestPathDijkstra {
+    fn dijkstra_for_node(&mut self, node: NodeId, topology: &Topology) {
+        let node_links_map = topology.inv_node_links_map();
+        let mut latency: HashMap<NodeId, f64> = HashMap::new();
+        for n in node_links_map.keys() {
+            latency.insert(*n, f64::INFINITY);
+        }
+        latency.insert(node, 0.0);
+        let mut visited: HashSet<NodeId> = HashSet::new();
+        for _ in 0..node_links_map.len() {
+            let mut relax_node = usize::MAX;
+            for next_node in node_links_map.keys() {
+                if !visited.contains(next_node)
+                    && (relax_node == usize::MAX || latency[next_node] < latency[&relax_node])
+                {
+                    relax_node = *next_node;
+                }
+            }
+
+            if latency[&relax_node] == f64::INFINITY {
+                break;
+            }
+
+            for (node_to, link_id) in node_links_map.get(&relax_node).unwrap() {
+                let link = topology.link(*link_id);
+                if latency[&relax_node] + link.latency < latency[node_to] {
+                    latency.insert(*node_to, latency[&relax_node] + link.latency);
+                    self.parent_path[node][*node_to] = relax_node;
+                }
+            }
+            visited.insert(relax_node);
+        }
+        for (node_to, node_latency) in latency {
+            self.distance[node][node_to] = node_latency;
+        }
+    }
+}
+
+impl RoutingAlgorithm for ShortestPathDijkstra {
+    fn init(&mut self, topology: &Topology) {
+        let node_count = topology.node_count();
+        self.parent_path = vec![vec![INVALID_NODE_ID; node_count]; node_count];
+        self.distance = vec![vec![f64::INFINITY; node_count]; node_count];
+        for node in 0..node_count {
+            self.dijkstra_for_node(node, topology);
+        }
+    }
+
+    fn get_path_iter<'a>(&'a self, src: NodeId, dst: NodeId, topology: &'a Topology) -> Option<PathIterator<'a>> {
+        if self.parent_path[dst][src] == INVALID_NODE_ID {
+            None
+        } else {
+            Some(PathIterator::from_parent_path(
+                src,
+                dst,
+                topology.node_links_map(),
+                &self.parent_path,
+            ))
+        }
+    }
+
+    fn on_node_added(&mut self, node_id: NodeId, topology: &Topology) {
+        resize_tables(&mut self.distance, &mut self.parent_path, topology, false);
+        for link_id in node_links(node_id, topology) {
+            self.on_link_added(link_id, topology);
+        }
+    }
+
+    fn on_link_added(&mut self, link_id: LinkId, topology: &Topology) {
+        for edge in inverted_edges(link_id, topology) {
+            for (distance, parent) in self.distance.iter_mut().zip(self.parent_path.iter_mut()) {
+                relax_added_edge(distance, parent, edge, topology);
+            }
+        }
+    }
+
+    fn on_link_removed(&mut self, link_id: LinkId, topology: &Topology) {
+        let edges = inverted_edges(link_id, topology);
+        for (root, (distance, parent)) in self.distance.iter_mut().zip(self.parent_path.iter_mut()).enumerate() {
+            repair_removed_edges(distance, parent, root, &edges, topology);
+        }
+    }
+}
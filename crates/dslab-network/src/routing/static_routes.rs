@@ -0,0 +1,247 @@
+//! Routing with user-specified static routes.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::routing::{PathIterator, RoutingAlgorithm, ShortestPathFloydWarshall};
+use crate::{LinkId, NodeId, Topology};
+
+#[derive(Clone, Debug)]
+enum RouteHops {
+    Nodes(Vec<String>),
+    Links(Vec<LinkId>),
+}
+
+#[derive(Clone, Debug)]
+struct StaticRoute {
+    src: String,
+    dst: String,
+    hops: RouteHops,
+}
+
+impl StaticRoute {
+    fn reverse(&self) -> Self {
+        let hops = match &self.hops {
+            RouteHops::Nodes(nodes) => RouteHops::Nodes(nodes.iter().rev().cloned().collect()),
+            RouteHops::Links(links) => RouteHops::Links(links.iter().rev().copied().collect()),
+        };
+        Self {
+            src: self.dst.clone(),
+            dst: self.src.clone(),
+            hops,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RouteRecord {
+    src: String,
+    dst: String,
+    path: String,
+}
+
+/// Routing algorithm which uses the explicitly specified paths between pairs of nodes
+/// and falls back to another routing algorithm for the remaining pairs.
+///
+/// The routes can be specified programmatically via [`Self::with_route`] and [`Self::with_link_route`]
+/// or loaded from a file via [`Self::from_file`]. The routes are validated against the topology
+/// in [`RoutingAlgorithm::init`], which panics with the list of all found errors if some routes are invalid.
+pub struct StaticRouting {
+    routes: Vec<StaticRoute>,
+    symmetric: bool,
+    fallback: Option<Box<dyn RoutingAlgorithm>>,
+    paths: HashMap<(NodeId, NodeId), Vec<LinkId>>,
+}
+
+impl Default for StaticRouting {
+    fn default() -> Self {
+        Self {
+            routes: Vec::new(),
+            symmetric: false,
+            fallback: Some(Box::<ShortestPathFloydWarshall>::default()),
+            paths: HashMap::new(),
+        }
+    }
+}
+
+impl StaticRouting {
+    /// Creates routing without routes, which uses [`ShortestPathFloydWarshall`] as a fallback.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates routing with the routes loaded from CSV file.
+    ///
+    /// The file must contain `src`, `dst` and `path` columns, where `path` contains the names of nodes on the route
+    /// separated by spaces, including the source and destination nodes.
+    pub fn from_file<P: AsRef<Path>>(file: P) -> Self {
+        let mut reader =
+            csv::Reader::from_path(&file).unwrap_or_else(|_| panic!("Can't read file {}", file.as_ref().display()));
+        let routes = reader
+            .deserialize()
+            .map(|record| {
+                let record: RouteRecord = record.unwrap_or_else(|e| panic!("Can't parse route record: {}", e));
+                StaticRoute {
+                    src: record.src,
+                    dst: record.dst,
+                    hops: RouteHops::Nodes(record.path.split_whitespace().map(String::from).collect()),
+                }
+            })
+            .collect();
+        Self {
+            routes,
+            ..Self::default()
+        }
+    }
+
+    /// Adds the route passing through the given nodes, from the first node to the last one.
+    pub fn with_route(mut self, nodes: &[&str]) -> Self {
+        assert!(nodes.len() >= 2, "Route must contain at least two nodes");
+        self.routes.push(StaticRoute {
+            src: nodes[0].to_string(),
+            dst: nodes[nodes.len() - 1].to_string(),
+            hops: RouteHops::Nodes(nodes.iter().map(|node| node.to_string()).collect()),
+        });
+        self
+    }
+
+    /// Adds the route from node `src` to node `dst` consisting of the given links.
+    pub fn with_link_route(mut self, src: &str, dst: &str, links: &[LinkId]) -> Self {
+        self.routes.push(StaticRoute {
+            src: src.to_string(),
+            dst: dst.to_string(),
+            hops: RouteHops::Links(links.to_vec()),
+        });
+        self
+    }
+
+    /// Enables adding the reverse route for each route, unless the reverse route is specified explicitly.
+    pub fn with_symmetric_routes(mut self, symmetric: bool) -> Self {
+        self.symmetric = symmetric;
+        self
+    }
+
+    /// Sets the routing algorithm used for the pairs of nodes without explicit route.
+    pub fn with_fallback(mut self, fallback: Box<dyn RoutingAlgorithm>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Disables the fallback, so that there is no path between the nodes without explicit route.
+    pub fn without_fallback(mut self) -> Self {
+        self.fallback = None;
+        self
+    }
+
+    /// Resolves the route to the sequence of links, returns the error description if the route is invalid.
+    fn resolve_route(
+        route: &StaticRoute,
+        node_ids: &HashMap<&str, NodeId>,
+        topology: &Topology,
+    ) -> Result<(NodeId, NodeId, Vec<LinkId>), String> {
+        let get_node_id = |name: &str| {
+            node_ids
+                .get(name)
+                .copied()
+                .ok_or_else(|| format!("node {} is not found", name))
+        };
+        let src = get_node_id(&route.src)?;
+        let dst = get_node_id(&route.dst)?;
+        let path = match &route.hops {
+            RouteHops::Nodes(nodes) => {
+                if nodes.first() != Some(&route.src) || nodes.last() != Some(&route.dst) {
+                    return Err("path must start at the source node and end at the destination node".to_string());
+                }
+                let mut path = Vec::new();
+                for pair in nodes.windows(2) {
+                    let (from, to) = (get_node_id(&pair[0])?, get_node_id(&pair[1])?);
+                    let link_id = topology.node_links_map()[&from]
+                        .get(&to)
+                        .ok_or_else(|| format!("no link from {} to {}", pair[0], pair[1]))?;
+                    path.push(*link_id);
+                }
+                path
+            }
+            RouteHops::Links(links) => {
+                let mut node = src;
+                for &link_id in links {
+                    if link_id >= topology.link_count() || topology.is_link_removed(link_id) {
+                        return Err(format!("link {} is not found", link_id));
+                    }
+                    node = topology.node_links_map()[&node]
+                        .iter()
+                        .find(|(_, &id)| id == link_id)
+                        .map(|(&next, _)| next)
+                        .ok_or_else(|| {
+                            format!("link {} does not go from node {}", link_id, topology.node(node).name)
+                        })?;
+                }
+                if node != dst {
+                    return Err(format!("path ends at node {}", topology.node(node).name));
+                }
+                links.clone()
+            }
+        };
+        Ok((src, dst, path))
+    }
+}
+
+impl RoutingAlgorithm for StaticRouting {
+    fn init(&mut self, topology: &Topology) {
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.init(topology);
+        }
+        let node_ids: HashMap<&str, NodeId> = (0..topology.node_count())
+            .map(|node_id| (topology.node(node_id).name.as_str(), node_id))
+            .collect();
+
+        let mut errors = Vec::new();
+        self.paths.clear();
+        for route in self.routes.iter() {
+            match Self::resolve_route(route, &node_ids, topology) {
+                Ok((src, dst, path)) => {
+                    if self.paths.insert((src, dst), path).is_some() {
+                        errors.push(format!("duplicate route from {} to {}", route.src, route.dst));
+                    }
+                }
+                Err(error) => errors.push(format!("route from {} to {}: {}", route.src, route.dst, error)),
+            }
+        }
+        if self.symmetric {
+            for route in self.routes.iter() {
+                let reverse = route.reverse();
+                let (Some(&src), Some(&dst)) = (node_ids.get(reverse.src.as_str()), node_ids.get(reverse.dst.as_str()))
+                else {
+                    continue;
+                };
+                if self.paths.contains_key(&(src, dst)) {
+                    continue;
+                }
+                match Self::resolve_route(&reverse, &node_ids, topology) {
+                    Ok((src, dst, path)) => {
+                        self.paths.insert((src, dst), path);
+                    }
+                    Err(error) => errors.push(format!(
+                        "reverse route from {} to {}: {}",
+                        reverse.src, reverse.dst, error
+                    )),
+                }
+            }
+        }
+        if !errors.is_empty() {
+            panic!("Invalid static routes:\n{}", errors.join("\n"));
+        }
+    }
+
+    fn get_path_iter<'a>(&'a self, src: NodeId, dst: NodeId, topology: &'a Topology) -> Option<PathIterator<'a>> {
+        match self.paths.get(&(src, dst)) {
+            Some(path) => Some(PathIterator::from_links(path)),
+            None => self
+                .fallback
+                .as_ref()
+                .and_then(|fallback| fallback.get_path_iter(src, dst, topology)),
+        }
+    }
+}
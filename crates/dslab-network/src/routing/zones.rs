@@ -0,0 +1,459 @@
+//! Hierarchical routing based on network zones.
+
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::routing::{HeapItem, PathIterator, RoutingAlgorithm};
+use crate::{LinkId, NodeId, Topology};
+
+const NO_ZONE: usize = usize::MAX;
+const NO_HOP: usize = usize::MAX;
+
+/// Routing algorithm used inside a zone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZoneRouting {
+    /// Routes are specified explicitly via [`Zone::with_route`].
+    Full,
+    /// Shortest paths (by latency) are computed using the Floyd–Warshall algorithm.
+    Floyd,
+    /// Shortest paths (by latency) are computed using the Dijkstra's algorithm.
+    Dijkstra,
+    /// All zone nodes are connected directly to the zone gateway, so no routing tables are stored.
+    Cluster,
+}
+
+/// Network zone, i.e. a group of nodes and nested zones with its own routing.
+///
+/// The nodes of nested zones are reachable from the outside only through the zone gateway,
+/// which must be one of the zone nodes. Inside the zone, the routes are computed between the zone nodes
+/// and the gateways of nested zones using only the links between these nodes.
+#[derive(Clone, Debug)]
+pub struct Zone {
+    name: String,
+    routing: ZoneRouting,
+    gateway: Option<String>,
+    nodes: Vec<String>,
+    zones: Vec<Zone>,
+    routes: Vec<Vec<String>>,
+}
+
+impl Zone {
+    /// Creates a new empty zone with specified routing.
+    pub fn new(name: &str, routing: ZoneRouting) -> Self {
+        Self {
+            name: name.to_string(),
+            routing,
+            gateway: None,
+            nodes: Vec::new(),
+            zones: Vec::new(),
+            routes: Vec::new(),
+        }
+    }
+
+    /// Sets the zone gateway. The gateway is added to the zone nodes if needed.
+    ///
+    /// Required for nested zones and zones with [`ZoneRouting::Cluster`] routing.
+    pub fn with_gateway(mut self, node: &str) -> Self {
+        if !self.nodes.iter().any(|n| n == node) {
+            self.nodes.push(node.to_string());
+        }
+        self.gateway = Some(node.to_string());
+        self
+    }
+
+    /// Adds node to the zone.
+    pub fn with_node(mut self, node: &str) -> Self {
+        self.nodes.push(node.to_string());
+        self
+    }
+
+    /// Adds nodes to the zone.
+    pub fn with_nodes<I, S>(mut self, nodes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.nodes.extend(nodes.into_iter().map(|n| n.into()));
+        self
+    }
+
+    /// Adds nested zone.
+    pub fn with_zone(mut self, zone: Zone) -> Self {
+        self.zones.push(zone);
+        self
+    }
+
+    /// Adds explicit route for zone with [`ZoneRouting::Full`] routing.
+    ///
+    /// The route is specified as a sequence of zone nodes (or gateways of nested zones), the reverse route
+    /// is added automatically unless it is specified explicitly.
+    pub fn with_route(mut self, nodes: &[&str]) -> Self {
+        assert!(nodes.len() >= 2, "Route must contain at least two nodes");
+        self.routes.push(nodes.iter().map(|n| n.to_string()).collect());
+        self
+    }
+
+    /// Returns the zone name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+// Routing inside a zone -----------------------------------------------------------------------------------------------
+
+enum LocalRouting {
+    /// Matrix with the next hop on the path between each pair of local nodes.
+    NextHop(Vec<Vec<usize>>),
+    Full(HashMap<(usize, usize), Vec<LinkId>>),
+    Cluster(usize),
+}
+
+struct CompiledZone {
+    parent: usize,
+    depth: usize,
+    gateway: Option<NodeId>,
+    local_nodes: Vec<NodeId>,
+    local_index: HashMap<NodeId, usize>,
+    routing: LocalRouting,
+}
+
+impl CompiledZone {
+    /// Returns the links between local nodes as adjacency lists with link latencies.
+    fn local_graph(&self, topology: &Topology) -> Vec<Vec<(usize, f64)>> {
+        self.local_nodes
+            .iter()
+            .map(|node| {
+                topology.node_links_map()[node]
+                    .iter()
+                    .filter_map(|(next, link_id)| {
+                        self.local_index
+                            .get(next)
+                            .map(|&next| (next, topology.link(*link_id).latency))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn floyd(&self, topology: &Topology) -> Vec<Vec<usize>> {
+        let n = self.local_nodes.len();
+        let mut next_hop = vec![vec![NO_HOP; n]; n];
+        let mut dist = vec![vec![f64::INFINITY; n]; n];
+        for (i, edges) in self.local_graph(topology).into_iter().enumerate() {
+            dist[i][i] = 0.;
+            next_hop[i][i] = i;
+            for (j, latency) in edges {
+                if latency < dist[i][j] {
+                    dist[i][j] = latency;
+                    next_hop[i][j] = j;
+                }
+            }
+        }
+        for k in 0..n {
+            for i in 0..n {
+                if dist[i][k] == f64::INFINITY {
+                    continue;
+                }
+                for j in 0..n {
+                    if dist[i][k] + dist[k][j] < dist[i][j] {
+                        dist[i][j] = dist[i][k] + dist[k][j];
+                        next_hop[i][j] = next_hop[i][k];
+                    }
+                }
+            }
+        }
+        next_hop
+    }
+
+    fn dijkstra(&self, topology: &Topology) -> Vec<Vec<usize>> {
+        let n = self.local_nodes.len();
+        let graph = self.local_graph(topology);
+        let mut next_hop = vec![vec![NO_HOP; n]; n];
+        for (src, src_next_hop) in next_hop.iter_mut().enumerate() {
+            let mut dist = vec![f64::INFINITY; n];
+            let mut heap = BinaryHeap::new();
+            dist[src] = 0.;
+            src_next_hop[src] = src;
+            heap.push(HeapItem { latency: 0., node: src });
+            while let Some(HeapItem { latency, node }) = heap.pop() {
+                if latency > dist[node] {
+                    continue;
+                }
+                for &(next, link_latency) in graph[node].iter() {
+                    if latency + link_latency < dist[next] {
+                        dist[next] = latency + link_latency;
+                        src_next_hop[next] = if node == src { next } else { src_next_hop[node] };
+                        heap.push(HeapItem {
+                            latency: dist[next],
+                            node: next,
+                        });
+                    }
+                }
+            }
+        }
+        next_hop
+    }
+
+    /// Appends the links of the path between two local nodes to `path`, returns false if there is no path.
+    fn append_route(&self, src: NodeId, dst: NodeId, topology: &Topology, path: &mut Vec<LinkId>) -> bool {
+        if src == dst {
+            return true;
+        }
+        let links = topology.node_links_map();
+        let (src_idx, dst_idx) = (self.local_index[&src], self.local_index[&dst]);
+        match &self.routing {
+            LocalRouting::NextHop(next_hop) => {
+                if next_hop[src_idx][dst_idx] == NO_HOP {
+                    return false;
+                }
+                let mut cur = src_idx;
+                while cur != dst_idx {
+                    let next = next_hop[cur][dst_idx];
+                    path.push(links[&self.local_nodes[cur]][&self.local_nodes[next]]);
+                    cur = next;
+                }
+                true
+            }
+            LocalRouting::Full(routes) => match routes.get(&(src_idx, dst_idx)) {
+                Some(route) => {
+                    path.extend(route);
+                    true
+                }
+                None => false,
+            },
+            LocalRouting::Cluster(hub) => {
+                let hub = self.local_nodes[*hub];
+                if src != hub {
+                    path.push(links[&src][&hub]);
+                }
+                if dst != hub {
+                    path.push(links[&hub][&dst]);
+                }
+                true
+            }
+        }
+    }
+}
+
+// Hierarchical routing ------------------------------------------------------------------------------------------------
+
+/// Routing algorithm which composes the routes across the hierarchy of network zones,
+/// similar to the zones in [SimGrid](https://simgrid.org/doc/latest/Platform_routing.html).
+///
+/// The route between two nodes is built by going up from the zones of both nodes via zone gateways to the closest
+/// common zone and connecting the obtained gateways inside this zone. Since the routing tables are stored only for
+/// the nodes of each zone, the memory usage and initialization time are much smaller than for the flat routing over
+/// the whole topology. The nodes not included in any zone are added to the root zone.
+pub struct HierarchicalRouting {
+    root: Zone,
+    zones: Vec<CompiledZone>,
+    node_zone: Vec<usize>,
+}
+
+impl HierarchicalRouting {
+    /// Creates routing algorithm with the specified root zone.
+    pub fn new(root: Zone) -> Self {
+        Self {
+            root,
+            zones: Vec::new(),
+            node_zone: Vec::new(),
+        }
+    }
+
+    fn node_id(&self, name: &str, names: &HashMap<&str, NodeId>) -> NodeId {
+        *names.get(name).unwrap_or_else(|| panic!("Node {} is not found", name))
+    }
+
+    /// Registers the zone and its nested zones, returns the zone index.
+    fn add_zone(&mut self, zone: &Zone, parent: usize, depth: usize, names: &HashMap<&str, NodeId>) -> usize {
+        let index = self.zones.len();
+        let gateway = zone.gateway.as_ref().map(|gw| self.node_id(gw, names));
+        if parent != NO_ZONE {
+            assert!(gateway.is_some(), "Nested zone {} must have a gateway", zone.name);
+        }
+        self.zones.push(CompiledZone {
+            parent,
+            depth,
+            gateway,
+            local_nodes: Vec::new(),
+            local_index: HashMap::new(),
+            routing: LocalRouting::Full(HashMap::new()),
+        });
+        let mut local_nodes = Vec::new();
+        for name in zone.nodes.iter() {
+            let node = self.node_id(name, names);
+            if self.node_zone[node] == index {
+                continue;
+            }
+            assert!(
+                self.node_zone[node] == NO_ZONE,
+                "Node {} belongs to several zones",
+                name
+            );
+            self.node_zone[node] = index;
+            local_nodes.push(node);
+        }
+        for child in zone.zones.iter() {
+            let child_index = self.add_zone(child, index, depth + 1, names);
+            local_nodes.push(self.zones[child_index].gateway.unwrap());
+        }
+        let compiled = &mut self.zones[index];
+        compiled.local_index = local_nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        compiled.local_nodes = local_nodes;
+        index
+    }
+
+    fn init_zone_routing(&mut self, index: usize, zone: &Zone, topology: &Topology, names: &HashMap<&str, NodeId>) {
+        let routing = {
+            let compiled = &self.zones[index];
+            match zone.routing {
+                ZoneRouting::Floyd => LocalRouting::NextHop(compiled.floyd(topology)),
+                ZoneRouting::Dijkstra => LocalRouting::NextHop(compiled.dijkstra(topology)),
+                ZoneRouting::Cluster => {
+                    let hub = compiled
+                        .gateway
+                        .unwrap_or_else(|| panic!("Cluster zone {} must have a gateway", zone.name));
+                    for &node in compiled.local_nodes.iter() {
+                        assert!(
+                            node == hub
+                                || (topology.node_links_map()[&node].contains_key(&hub)
+                                    && topology.node_links_map()[&hub].contains_key(&node)),
+                            "Node {} is not connected to the gateway of cluster zone {}",
+                            topology.node(node).name,
+                            zone.name
+                        );
+                    }
+                    LocalRouting::Cluster(compiled.local_index[&hub])
+                }
+                ZoneRouting::Full => {
+                    let mut routes = HashMap::new();
+                    let mut add_route = |nodes: Vec<NodeId>| {
+                        let links = nodes
+                            .windows(2)
+                            .map(|pair| {
+                                *topology.node_links_map()[&pair[0]].get(&pair[1]).unwrap_or_else(|| {
+                                    panic!(
+                                        "Route in zone {} uses missing link from {} to {}",
+                                        zone.name,
+                                        topology.node(pair[0]).name,
+                                        topology.node(pair[1]).name
+                                    )
+                                })
+                            })
+                            .collect::<Vec<_>>();
+                        let key = (
+                            compiled.local_index[&nodes[0]],
+                            compiled.local_index[nodes.last().unwrap()],
+                        );
+                        routes.entry(key).or_insert(links);
+                    };
+                    let node_routes = zone
+                        .routes
+                        .iter()
+                        .map(|route| {
+                            route
+                                .iter()
+                                .map(|name| {
+                                    let node = self.node_id(name, names);
+                                    assert!(
+                                        compiled.local_index.contains_key(&node),
+                                        "Route in zone {} uses node {} from another zone",
+                                        zone.name,
+                                        name
+                                    );
+                                    node
+                                })
+                                .collect::<Vec<_>>()
+                        })
+                        .collect::<Vec<_>>();
+                    // explicit routes take precedence over the reversed ones
+                    for route in node_routes.iter() {
+                        add_route(route.clone());
+                    }
+                    for route in node_routes {
+                        add_route(route.into_iter().rev().collect());
+                    }
+                    LocalRouting::Full(routes)
+                }
+            }
+        };
+        self.zones[index].routing = routing;
+    }
+
+    /// Computes the path by composing the routes inside zones.
+    fn compose_path(&self, src: NodeId, dst: NodeId, topology: &Topology) -> Option<Vec<LinkId>> {
+        let (mut src_node, mut src_zone) = (src, *self.node_zone.get(src)?);
+        let (mut dst_node, mut dst_zone) = (dst, *self.node_zone.get(dst)?);
+        let mut path = Vec::new();
+        let mut down_path = Vec::new();
+        while src_zone != dst_zone {
+            if self.zones[src_zone].depth >= self.zones[dst_zone].depth {
+                let zone = &self.zones[src_zone];
+                let gateway = zone.gateway.unwrap();
+                if !zone.append_route(src_node, gateway, topology, &mut path) {
+                    return None;
+                }
+                src_node = gateway;
+                src_zone = zone.parent;
+            } else {
+                let zone = &self.zones[dst_zone];
+                let gateway = zone.gateway.unwrap();
+                let mut segment = Vec::new();
+                if !zone.append_route(gateway, dst_node, topology, &mut segment) {
+                    return None;
+                }
+                down_path.push(segment);
+                dst_node = gateway;
+                dst_zone = zone.parent;
+            }
+        }
+        if !self.zones[src_zone].append_route(src_node, dst_node, topology, &mut path) {
+            return None;
+        }
+        for segment in down_path.into_iter().rev() {
+            path.extend(segment);
+        }
+        Some(path)
+    }
+}
+
+impl RoutingAlgorithm for HierarchicalRouting {
+    fn init(&mut self, topology: &Topology) {
+        let names = (0..topology.node_count())
+            .map(|node| (topology.node(node).name.as_str(), node))
+            .collect::<HashMap<_, _>>();
+        let mut root = self.root.clone();
+        self.zones.clear();
+        self.node_zone = vec![NO_ZONE; topology.node_count()];
+
+        // add the nodes not included in any zone to the root zone
+        let mut listed = vec![false; topology.node_count()];
+        let mut stack = vec![&root];
+        while let Some(zone) = stack.pop() {
+            for name in zone.nodes.iter() {
+                listed[self.node_id(name, &names)] = true;
+            }
+            stack.extend(zone.zones.iter());
+        }
+        let unlisted = (0..topology.node_count())
+            .filter(|&node| !listed[node])
+            .map(|node| topology.node(node).name.clone())
+            .collect::<Vec<_>>();
+        root.nodes.extend(unlisted);
+
+        self.add_zone(&root, NO_ZONE, 0, &names);
+        // zones are numbered in the same depth-first order as they were added
+        let mut stack = vec![&root];
+        let mut order = Vec::new();
+        while let Some(zone) = stack.pop() {
+            order.push(zone);
+            stack.extend(zone.zones.iter().rev());
+        }
+        for (index, zone) in order.into_iter().enumerate() {
+            self.init_zone_routing(index, zone, topology, &names);
+        }
+    }
+
+    fn get_path_iter<'a>(&'a self, src: NodeId, dst: NodeId, topology: &'a Topology) -> Option<PathIterator<'a>> {
+        self.compose_path(src, dst, topology).map(PathIterator::from_vec)
+    }
+}
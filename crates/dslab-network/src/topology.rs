@@ -2,19 +2,32 @@
 
 use std::collections::BTreeMap;
 
+use dslab_models::power::network::{LinkPowerModel, SwitchPowerModel};
+
 use crate::routing::PathIterator;
 use crate::{Link, LinkId, Node, NodeId};
 
 /// Stores for each node a map with its neighbors and corresponding outgoing links.
 pub type NodeLinksMap = BTreeMap<NodeId, BTreeMap<NodeId, LinkId>>;
 
+#[derive(Clone, Copy)]
+struct LinkEnds {
+    node1: NodeId,
+    node2: NodeId,
+    bidirectional: bool,
+    removed: bool,
+}
+
 /// Represents a network topology consisting of nodes connected with links.
 #[derive(Default)]
 pub struct Topology {
     nodes: Vec<Node>,
     links: Vec<Link>,
+    link_ends: Vec<LinkEnds>,
     node_links_map: NodeLinksMap,
     inv_node_links_map: NodeLinksMap,
+    node_power_models: BTreeMap<NodeId, Box<dyn SwitchPowerModel>>,
+    link_power_models: BTreeMap<LinkId, Box<dyn LinkPowerModel>>,
 }
 
 impl Topology {
@@ -32,6 +45,13 @@ impl Topology {
         node_id
     }
 
+    /// Returns the node by its id.
+    pub fn node(&self, node_id: NodeId) -> &Node {
+        self.nodes
+            .get(node_id)
+            .unwrap_or_else(|| panic!("Node {} is not found", node_id))
+    }
+
     /// Returns the number of nodes.
     pub fn node_count(&self) -> usize {
         self.nodes.len()
@@ -63,10 +83,90 @@ impl Topology {
     }
 
     /// Returns the number of links.
+    ///
+    /// Removed links are also counted since the link ids are not reused.
     pub fn link_count(&self) -> usize {
         self.links.len()
     }
 
+    /// Removes the link from the topology.
+    ///
+    /// The link id remains reserved, so the link parameters are still available via [`Self::link`].
+    pub fn remove_link(&mut self, link_id: LinkId) {
+        let ends = self
+            .link_ends
+            .get_mut(link_id)
+            .unwrap_or_else(|| panic!("Link {} is not found", link_id));
+        assert!(!ends.removed, "Link {} is already removed", link_id);
+        ends.removed = true;
+        let ends = *ends;
+        let mut directions = vec![(ends.node1, ends.node2)];
+        if ends.bidirectional {
+            directions.push((ends.node2, ends.node1));
+        }
+        for (node_from, node_to) in directions {
+            // the link could be replaced by another link between the same nodes
+            if self.node_links_map[&node_from].get(&node_to) == Some(&link_id) {
+                self.node_links_map.get_mut(&node_from).unwrap().remove(&node_to);
+                self.inv_node_links_map.get_mut(&node_to).unwrap().remove(&node_from);
+            }
+        }
+    }
+
+    /// Returns true if the link was removed from the topology.
+    pub fn is_link_removed(&self, link_id: LinkId) -> bool {
+        self.link_ends
+            .get(link_id)
+            .unwrap_or_else(|| panic!("Link {} is not found", link_id))
+            .removed
+    }
+
+    /// Sets the power model of network device (e.g. switch) represented by the node.
+    pub fn set_node_power_model(&mut self, node_id: NodeId, power_model: Box<dyn SwitchPowerModel>) {
+        assert!(node_id < self.nodes.len(), "Node {} is not found", node_id);
+        self.node_power_models.insert(node_id, power_model);
+    }
+
+    /// Sets the power model of the link.
+    pub fn set_link_power_model(&mut self, link_id: LinkId, power_model: Box<dyn LinkPowerModel>) {
+        assert!(link_id < self.links.len(), "Link {} is not found", link_id);
+        self.link_power_models.insert(link_id, power_model);
+    }
+
+    /// Returns the nodes with power models along with their models.
+    pub fn node_power_models(&self) -> impl Iterator<Item = (NodeId, &dyn SwitchPowerModel)> {
+        self.node_power_models
+            .iter()
+            .map(|(&node_id, model)| (node_id, model.as_ref()))
+    }
+
+    /// Returns the links with power models along with their models.
+    pub fn link_power_models(&self) -> impl Iterator<Item = (LinkId, &dyn LinkPowerModel)> {
+        self.link_power_models
+            .iter()
+            .map(|(&link_id, model)| (link_id, model.as_ref()))
+    }
+
+    /// Returns the pairs of nodes `(from, to)` connected by the link in each direction it can be traversed.
+    pub fn link_directions(&self, link_id: LinkId) -> Vec<(NodeId, NodeId)> {
+        let ends = self
+            .link_ends
+            .get(link_id)
+            .unwrap_or_else(|| panic!("Link {} is not found", link_id));
+        if ends.bidirectional {
+            vec![(ends.node1, ends.node2), (ends.node2, ends.node1)]
+        } else {
+            vec![(ends.node1, ends.node2)]
+        }
+    }
+
+    /// Returns true if there is a link going from `node_from` to `node_to`.
+    pub fn has_link_between(&self, node_from: NodeId, node_to: NodeId) -> bool {
+        self.node_links_map
+            .get(&node_from)
+            .is_some_and(|links| links.contains_key(&node_to))
+    }
+
     /// Returns an immutable reference to the stored [`NodeLinksMap`].
     pub fn node_links_map(&self) -> &NodeLinksMap {
         &self.node_links_map
@@ -94,6 +194,12 @@ impl Topology {
         assert!(link.bandwidth > 0.0, "Link bandwidth must be > 0");
         let link_id = self.links.len();
         self.links.push(link);
+        self.link_ends.push(LinkEnds {
+            node1,
+            node2,
+            bidirectional,
+            removed: false,
+        });
         self.node_links_map.get_mut(&node1).unwrap().insert(node2, link_id);
         self.inv_node_links_map.get_mut(&node2).unwrap().insert(node1, link_id);
         if bidirectional {
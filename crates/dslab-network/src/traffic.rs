@@ -0,0 +1,409 @@
+//! Generator of background network traffic.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use dslab_core::context::SimulationContext;
+use dslab_core::event::Event;
+use dslab_core::handler::EventHandler;
+use dslab_core::{cast, log_debug};
+
+use crate::{DataTransferCompleted, Network};
+
+/// Flow record of a trace replayed by [`TrafficGenerator`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TraceFlow {
+    /// Flow start time.
+    pub time: f64,
+    /// Name of source node.
+    pub src: String,
+    /// Name of destination node.
+    pub dst: String,
+    /// Flow size.
+    pub size: f64,
+}
+
+/// Reads flow trace from CSV file with `time`, `src`, `dst` and `size` columns.
+pub fn read_flow_trace<P: AsRef<Path>>(file: P) -> Vec<TraceFlow> {
+    let mut reader =
+        csv::Reader::from_path(&file).unwrap_or_else(|_| panic!("Can't read file {}", file.as_ref().display()));
+    reader
+        .deserialize()
+        .map(|record| record.unwrap_or_else(|e| panic!("Can't parse flow trace record: {}", e)))
+        .collect()
+}
+
+/// Defines the pairs of nodes connected by the generated flows.
+#[derive(Clone, Debug)]
+pub enum TrafficPattern {
+    /// Each arrival starts a flow between a random pair of distinct nodes.
+    UniformRandom,
+    /// Each arrival starts flows between all ordered pairs of distinct nodes.
+    AllToAll,
+    /// Each arrival starts flows from `senders` random nodes to `dst` node.
+    Incast {
+        /// Name of destination node.
+        dst: String,
+        /// Number of sending nodes.
+        senders: usize,
+    },
+    /// Nodes are connected according to a random permutation without fixed points chosen at start,
+    /// each arrival starts a flow from a random node to its counterpart.
+    Permutation,
+    /// Flows are started according to the trace ignoring the configured arrivals and flow sizes.
+    ///
+    /// The configured flow limit and stop time still apply, i.e. the trace flows starting after the stop time
+    /// or after the limit is reached are skipped.
+    Trace(Vec<TraceFlow>),
+}
+
+/// Defines the times of flow arrivals.
+#[derive(Clone, Copy, Debug)]
+pub enum ArrivalProcess {
+    /// Poisson process with specified rate (arrivals per time unit).
+    Poisson(f64),
+    /// Arrivals at fixed time intervals.
+    Periodic(f64),
+}
+
+/// Defines the distribution of flow sizes.
+#[derive(Clone, Copy, Debug)]
+pub enum FlowSizeDistribution {
+    /// All flows have the same size.
+    Fixed(f64),
+    /// Size is uniformly distributed between min and max.
+    Uniform(f64, f64),
+    /// Size is exponentially distributed with specified mean.
+    Exponential(f64),
+    /// Size has Pareto distribution with specified scale (minimum size) and shape.
+    Pareto(f64, f64),
+}
+
+/// Information about a flow started by [`TrafficGenerator`].
+#[derive(Clone, Debug, Serialize)]
+pub struct FlowRecord {
+    /// Id of corresponding data transfer.
+    pub transfer_id: usize,
+    /// Name of source node.
+    pub src: String,
+    /// Name of destination node.
+    pub dst: String,
+    /// Flow size.
+    pub size: f64,
+    /// Flow start time.
+    pub start_time: f64,
+    /// Flow completion time, if the flow is completed.
+    pub completion_time: Option<f64>,
+}
+
+impl FlowRecord {
+    /// Returns the flow duration (FCT), if the flow is completed.
+    pub fn duration(&self) -> Option<f64> {
+        self.completion_time.map(|time| time - self.start_time)
+    }
+}
+
+/// Summary of flow completion time (FCT) distribution.
+#[derive(Clone, Debug, Serialize)]
+pub struct FlowCompletionStats {
+    /// Number of completed flows.
+    pub count: usize,
+    /// Mean FCT.
+    pub mean: f64,
+    /// Minimum FCT.
+    pub min: f64,
+    /// Median FCT.
+    pub p50: f64,
+    /// 90th percentile of FCT.
+    pub p90: f64,
+    /// 99th percentile of FCT.
+    pub p99: f64,
+    /// Maximum FCT.
+    pub max: f64,
+}
+
+#[derive(Clone, Serialize)]
+struct GenerateFlows {}
+
+#[derive(Clone, Serialize)]
+struct StartTraceFlow {
+    index: usize,
+}
+
+/// Simulation component which generates background traffic in the network.
+///
+/// The generator starts data transfers (flows) between the network nodes according to the configured
+/// [`TrafficPattern`], [`ArrivalProcess`] and [`FlowSizeDistribution`], and records their completion times.
+/// The generation of flows is started by calling [`Self::start`] and continues until the configured flow limit
+/// or stop time is reached, otherwise indefinitely.
+pub struct TrafficGenerator {
+    network: Rc<RefCell<Network>>,
+    nodes: Vec<String>,
+    pattern: TrafficPattern,
+    arrivals: ArrivalProcess,
+    flow_size: FlowSizeDistribution,
+    flow_limit: Option<usize>,
+    stop_time: Option<f64>,
+    permutation: Vec<usize>,
+    flows: Vec<FlowRecord>,
+    flow_index: HashMap<usize, usize>,
+    ctx: SimulationContext,
+}
+
+impl TrafficGenerator {
+    /// Creates generator of flows between the specified network nodes.
+    ///
+    /// By default, the flows of size 1 arrive according to Poisson process with rate 1.
+    pub fn new(
+        network: Rc<RefCell<Network>>,
+        nodes: Vec<String>,
+        pattern: TrafficPattern,
+        ctx: SimulationContext,
+    ) -> Self {
+        if !matches!(pattern, TrafficPattern::Trace(_)) {
+            assert!(nodes.len() >= 2, "Traffic generator requires at least two nodes");
+        }
+        Self {
+            network,
+            nodes,
+            pattern,
+            arrivals: ArrivalProcess::Poisson(1.),
+            flow_size: FlowSizeDistribution::Fixed(1.),
+            flow_limit: None,
+            stop_time: None,
+            permutation: Vec::new(),
+            flows: Vec::new(),
+            flow_index: HashMap::new(),
+            ctx,
+        }
+    }
+
+    /// Sets the process of flow arrivals.
+    pub fn with_arrivals(mut self, arrivals: ArrivalProcess) -> Self {
+        self.arrivals = arrivals;
+        self
+    }
+
+    /// Sets the distribution of flow sizes.
+    pub fn with_flow_size(mut self, flow_size: FlowSizeDistribution) -> Self {
+        self.flow_size = flow_size;
+        self
+    }
+
+    /// Sets the maximum number of generated flows.
+    pub fn with_flow_limit(mut self, flow_limit: usize) -> Self {
+        self.flow_limit = Some(flow_limit);
+        self
+    }
+
+    /// Sets the time after which no new flows are generated.
+    pub fn with_stop_time(mut self, stop_time: f64) -> Self {
+        self.stop_time = Some(stop_time);
+        self
+    }
+
+    /// Starts the generation of flows.
+    pub fn start(&mut self) {
+        match &self.pattern {
+            TrafficPattern::Trace(trace) => {
+                let time = self.ctx.time();
+                for (index, flow) in trace.iter().enumerate() {
+                    if self.stop_time.is_some_and(|stop_time| flow.time > stop_time) {
+                        continue;
+                    }
+                    self.ctx.emit_self(StartTraceFlow { index }, (flow.time - time).max(0.));
+                }
+            }
+            _ => {
+                if let TrafficPattern::Permutation = self.pattern {
+                    self.permutation = self.random_derangement();
+                }
+                self.schedule_arrival();
+            }
+        }
+    }
+
+    /// Returns the records of all generated flows.
+    pub fn flows(&self) -> &[FlowRecord] {
+        &self.flows
+    }
+
+    /// Returns the sorted completion times (durations) of completed flows.
+    pub fn completion_times(&self) -> Vec<f64> {
+        let mut times = self.flows.iter().filter_map(|f| f.duration()).collect::<Vec<_>>();
+        times.sort_by(|a, b| a.total_cmp(b));
+        times
+    }
+
+    /// Returns the summary of flow completion time distribution, or `None` if there are no completed flows.
+    pub fn completion_stats(&self) -> Option<FlowCompletionStats> {
+        let times = self.completion_times();
+        if times.is_empty() {
+            return None;
+        }
+        let percentile = |p: f64| times[((p * times.len() as f64).ceil() as usize).clamp(1, times.len()) - 1];
+        Some(FlowCompletionStats {
+            count: times.len(),
+            mean: times.iter().sum::<f64>() / times.len() as f64,
+            min: times[0],
+            p50: percentile(0.5),
+            p90: percentile(0.9),
+            p99: percentile(0.99),
+            max: times[times.len() - 1],
+        })
+    }
+
+    /// Saves the records of generated flows to CSV file.
+    pub fn save_flows_csv<P: AsRef<Path>>(&self, file: P) {
+        let mut writer =
+            csv::Writer::from_path(&file).unwrap_or_else(|_| panic!("Can't write file {}", file.as_ref().display()));
+        for flow in self.flows.iter() {
+            writer.serialize(flow).unwrap();
+        }
+        writer.flush().unwrap();
+    }
+
+    fn limit_reached(&self) -> bool {
+        self.flow_limit.is_some_and(|limit| self.flows.len() >= limit)
+    }
+
+    fn schedule_arrival(&mut self) {
+        let delay = match self.arrivals {
+            ArrivalProcess::Poisson(rate) => -(1. - self.ctx.rand()).ln() / rate,
+            ArrivalProcess::Periodic(interval) => interval,
+        };
+        if !self
+            .stop_time
+            .is_some_and(|stop_time| self.ctx.time() + delay > stop_time)
+        {
+            self.ctx.emit_self(GenerateFlows {}, delay);
+        }
+    }
+
+    fn sample_size(&self) -> f64 {
+        match self.flow_size {
+            FlowSizeDistribution::Fixed(size) => size,
+            FlowSizeDistribution::Uniform(min, max) => min + (max - min) * self.ctx.rand(),
+            FlowSizeDistribution::Exponential(mean) => -(1. - self.ctx.rand()).ln() * mean,
+            FlowSizeDistribution::Pareto(scale, shape) => scale / (1. - self.ctx.rand()).powf(1. / shape),
+        }
+    }
+
+    fn random_node(&self) -> usize {
+        self.ctx.gen_range(0..self.nodes.len())
+    }
+
+    /// Returns random permutation of node indices without fixed points.
+    fn random_derangement(&self) -> Vec<usize> {
+        loop {
+            let mut permutation = (0..self.nodes.len()).collect::<Vec<_>>();
+            for i in (1..permutation.len()).rev() {
+                permutation.swap(i, self.ctx.gen_range(0..=i));
+            }
+            if permutation.iter().enumerate().all(|(i, &j)| i != j) {
+                return permutation;
+            }
+        }
+    }
+
+    fn generate_flows(&mut self) {
+        let pairs = match &self.pattern {
+            TrafficPattern::UniformRandom => {
+                let src = self.random_node();
+                let mut dst = self.ctx.gen_range(0..self.nodes.len() - 1);
+                if dst >= src {
+                    dst += 1;
+                }
+                vec![(src, dst)]
+            }
+            TrafficPattern::AllToAll => (0..self.nodes.len())
+                .flat_map(|src| {
+                    (0..self.nodes.len())
+                        .filter(move |&dst| dst != src)
+                        .map(move |dst| (src, dst))
+                })
+                .collect(),
+            TrafficPattern::Incast { dst, senders } => {
+                let dst = self
+                    .nodes
+                    .iter()
+                    .position(|node| node == dst)
+                    .unwrap_or_else(|| panic!("Incast destination {} is not in the list of nodes", dst));
+                let mut candidates = (0..self.nodes.len()).filter(|&i| i != dst).collect::<Vec<_>>();
+                let senders = (*senders).min(candidates.len());
+                for i in 0..senders {
+                    let j = self.ctx.gen_range(i..candidates.len());
+                    candidates.swap(i, j);
+                }
+                candidates[..senders].iter().map(|&src| (src, dst)).collect()
+            }
+            TrafficPattern::Permutation => {
+                let src = self.random_node();
+                vec![(src, self.permutation[src])]
+            }
+            TrafficPattern::Trace(_) => unreachable!(),
+        };
+        for (src, dst) in pairs {
+            if self.limit_reached() {
+                return;
+            }
+            let size = self.sample_size();
+            let (src, dst) = (self.nodes[src].clone(), self.nodes[dst].clone());
+            self.start_flow(src, dst, size);
+        }
+    }
+
+    fn start_flow(&mut self, src: String, dst: String, size: f64) {
+        let transfer_id = self
+            .network
+            .borrow_mut()
+            .transfer_data_between_nodes(&src, &dst, size, self.ctx.id());
+        log_debug!(
+            self.ctx,
+            "started flow {} from {} to {} of size {}",
+            transfer_id,
+            src,
+            dst,
+            size
+        );
+        self.flow_index.insert(transfer_id, self.flows.len());
+        self.flows.push(FlowRecord {
+            transfer_id,
+            src,
+            dst,
+            size,
+            start_time: self.ctx.time(),
+            completion_time: None,
+        });
+    }
+}
+
+impl EventHandler for TrafficGenerator {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            GenerateFlows {} => {
+                self.generate_flows();
+                if !self.limit_reached() {
+                    self.schedule_arrival();
+                }
+            }
+            StartTraceFlow { index } => {
+                if self.limit_reached() {
+                    return;
+                }
+                if let TrafficPattern::Trace(trace) = &self.pattern {
+                    let flow = trace[index].clone();
+                    self.start_flow(flow.src, flow.dst, flow.size);
+                }
+            }
+            DataTransferCompleted { dt } => {
+                let index = self.flow_index.remove(&dt.id).unwrap();
+                self.flows[index].completion_time = Some(self.ctx.time());
+            }
+        })
+    }
+}
@@ -0,0 +1,99 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dslab_core::simulation::Simulation;
+use dslab_core::EPSILON;
+use dslab_models::power::network_models::constant::ConstantSwitchPowerModel;
+use dslab_models::power::network_models::linear::{LinearLinkPowerModel, LinearSwitchPowerModel};
+
+use dslab_network::models::{ConstantBandwidthNetworkModel, PacketLevelNetworkModel, TopologyAwareNetworkModel};
+use dslab_network::{Link, LinkId, Network, NetworkModel};
+
+fn assert_float_eq(x: f64, y: f64) {
+    assert!((x - y).abs() < EPSILON, "Values do not match: {:.15} vs {:.15}", x, y);
+}
+
+/// Creates the network of two hosts connected to the switch with power models attached to the switch and links.
+fn make_network(sim: &mut Simulation, model: Box<dyn NetworkModel>) -> (Rc<RefCell<Network>>, [LinkId; 2]) {
+    let mut network = Network::new(model, sim.create_context("net"));
+    for node in ["host1", "host2", "switch"] {
+        network.add_node(node, Box::new(ConstantBandwidthNetworkModel::new(1000., 0.)));
+    }
+    let links = [
+        network.add_link("host1", "switch", Link::shared(100., 0.)),
+        network.add_link("host2", "switch", Link::shared(100., 0.)),
+    ];
+    network.init_topology();
+    network.set_node_power_model("switch", Box::new(LinearSwitchPowerModel::new(100., 1., 2.)));
+    for link_id in links {
+        network.set_link_power_model(link_id, Box::new(LinearLinkPowerModel::new(1., 3.)));
+    }
+    let network = Rc::new(RefCell::new(network));
+    sim.add_handler("net", network.clone());
+    (network, links)
+}
+
+#[test]
+fn test_energy_accounting() {
+    let mut sim = Simulation::new(123);
+    let (network, links) = make_network(&mut sim, Box::new(TopologyAwareNetworkModel::new()));
+    let client = sim.create_context("client").id();
+    assert_float_eq(network.borrow().total_power(), 102. + 2.);
+
+    network
+        .borrow_mut()
+        .transfer_data_between_nodes("host1", "host2", 1000., client);
+    sim.step_until_no_events();
+    assert_float_eq(sim.time(), 10.);
+    // both ports and links are fully utilized during the transfer
+    let network = network.borrow();
+    assert_float_eq(network.node_energy("switch"), 106. * 10.);
+    assert_float_eq(network.link_energy(links[0]), 30.);
+    assert_float_eq(network.link_energy(links[1]), 30.);
+    assert_float_eq(network.total_energy(), 1120.);
+    assert_float_eq(network.node_power("switch"), 102.);
+    assert_float_eq(network.link_power(links[0]), 1.);
+    assert_float_eq(network.node_power("host1"), 0.);
+    assert_float_eq(network.node_energy("host1"), 0.);
+}
+
+#[test]
+fn test_energy_of_turned_off_link() {
+    let mut sim = Simulation::new(123);
+    let (network, links) = make_network(&mut sim, Box::new(TopologyAwareNetworkModel::new()));
+    sim.step_until_time(10.);
+    // the idle link is turned off along with the switch port
+    network.borrow_mut().remove_link(links[1]);
+    assert_float_eq(network.borrow().node_power("switch"), 101.);
+    assert_float_eq(network.borrow().link_power(links[1]), 0.);
+    sim.step_until_time(20.);
+    let network = network.borrow();
+    assert_float_eq(network.node_energy("switch"), 1020. + 1010.);
+    assert_float_eq(network.link_energy(links[0]), 20.);
+    assert_float_eq(network.link_energy(links[1]), 10.);
+    assert_float_eq(network.total_energy(), 2030. + 30.);
+}
+
+#[test]
+fn test_energy_accounting_packet_level() {
+    let mut sim = Simulation::new(123);
+    let model = PacketLevelNetworkModel::new().with_mtu(100.).with_initial_window(100.);
+    let (network, links) = make_network(&mut sim, Box::new(model));
+    network
+        .borrow_mut()
+        .set_node_power_model("host1", Box::new(ConstantSwitchPowerModel::new(5.)));
+    let client = sim.create_context("client").id();
+    network
+        .borrow_mut()
+        .transfer_data_between_nodes("host1", "host2", 1000., client);
+    sim.step_until_no_events();
+    // the link utilization changes with each packet transmission
+    let time = sim.time();
+    let network = network.borrow();
+    assert_float_eq(network.node_energy("host1"), 5. * time);
+    let switch_energy = network.node_energy("switch");
+    assert!(switch_energy > 102. * time && switch_energy < 106. * time);
+    let link_energy = links.iter().map(|&link_id| network.link_energy(link_id)).sum::<f64>();
+    assert!(link_energy > 2. * time && link_energy < 6. * time);
+    assert_float_eq(network.total_energy(), 5. * time + switch_energy + link_energy);
+}
@@ -0,0 +1,252 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dslab_core::cast;
+use dslab_core::component::Id;
+use dslab_core::context::SimulationContext;
+use dslab_core::event::Event;
+use dslab_core::handler::EventHandler;
+use dslab_core::simulation::Simulation;
+use dslab_core::EPSILON;
+
+use dslab_network::models::{ConstantBandwidthNetworkModel, TopologyAwareNetworkModel};
+use dslab_network::platform::Platform;
+use dslab_network::{DataTransferCompleted, Link, Network};
+
+fn assert_float_eq(x: f64, y: f64, eps: f64) {
+    assert!(
+        (x - y).abs() < eps || (x.max(y) - x.min(y)) / x.min(y) < eps,
+        "Values do not match: {:.15} vs {:.15}",
+        x,
+        y
+    );
+}
+
+fn make_network(platform: &Platform, sim: &mut Simulation) -> Rc<RefCell<Network>> {
+    let model = TopologyAwareNetworkModel::new().with_routing(platform.routing());
+    let mut network = Network::new(Box::new(model), sim.create_context("net"));
+    platform.add_to_network(&mut network, |_| Box::new(ConstantBandwidthNetworkModel::new(1e12, 0.)));
+    network.init_topology();
+    let network = Rc::new(RefCell::new(network));
+    sim.add_handler("net", network.clone());
+    network
+}
+
+/// Binds a new component to each host and returns the component ids.
+fn bind_hosts(hosts: &[&str], network: &Rc<RefCell<Network>>, sim: &mut Simulation) -> Vec<Id> {
+    hosts
+        .iter()
+        .map(|host| {
+            let id = sim.create_context(*host).id();
+            network.borrow_mut().set_location(id, host);
+            id
+        })
+        .collect()
+}
+
+struct TransferObserver {
+    completions: Vec<(usize, f64)>,
+    ctx: SimulationContext,
+}
+
+impl EventHandler for TransferObserver {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            DataTransferCompleted { dt } => {
+                self.completions.push((dt.id, self.ctx.time()));
+            }
+        })
+    }
+}
+
+/// Runs the given transfers (index of source, index of destination, size) and returns their completion times.
+fn run_transfers(
+    transfers: &[(usize, usize, f64)],
+    ids: &[Id],
+    network: &Rc<RefCell<Network>>,
+    sim: &mut Simulation,
+) -> Vec<f64> {
+    let observer = Rc::new(RefCell::new(TransferObserver {
+        completions: Vec::new(),
+        ctx: sim.create_context("observer"),
+    }));
+    let observer_id = sim.add_handler("observer", observer.clone());
+    let transfer_ids = transfers
+        .iter()
+        .map(|&(src, dst, size)| {
+            network
+                .borrow_mut()
+                .transfer_data(ids[src], ids[dst], size, observer_id)
+        })
+        .collect::<Vec<_>>();
+    sim.step_until_no_events();
+    let completions = &observer.borrow().completions;
+    transfer_ids
+        .iter()
+        .map(|id| completions.iter().find(|(dt_id, _)| dt_id == id).unwrap().1)
+        .collect()
+}
+
+const SIMPLE_PLATFORM: &str = r#"<?xml version='1.0'?>
+<!DOCTYPE platform SYSTEM "https://simgrid.org/simgrid.dtd">
+<platform version="4.1">
+    <zone id="zone0" routing="Full">
+        <host id="host1" speed="1Gf" core="4">
+            <prop id="memory" value="8192"/>
+        </host>
+        <host id="host2" speed="2Gf" />
+        <host id="host3" speed="500Mf" />
+
+        <link id="loopback1" latency="0" bandwidth="100GBps" sharing_policy="FATPIPE"/>
+        <link id="link1" latency="10ms" bandwidth="100MBps"/>
+        <link id="link2" latency="20ms" bandwidth="50MBps"/>
+        <link id="link3" latency="1ms" bandwidth="800Mbps" sharing_policy="SPLITDUPLEX"/>
+
+        <route src="host1" dst="host1">
+            <link_ctn id="loopback1" />
+        </route>
+        <route src="host1" dst="host2">
+            <link_ctn id="link1" />
+            <link_ctn id="link2" />
+        </route>
+        <route src="host1" dst="host3">
+            <link_ctn id="link1" />
+            <link_ctn id="link3" direction="UP" />
+        </route>
+    </zone>
+</platform>"#;
+
+#[test]
+fn test_simgrid_platform() {
+    let platform = Platform::from_simgrid_xml_str(SIMPLE_PLATFORM);
+
+    assert_eq!(platform.hosts.len(), 3);
+    let host1 = platform.host("host1").unwrap();
+    assert_float_eq(host1.speed, 1e9, EPSILON);
+    assert_eq!(host1.cores, 4);
+    assert_eq!(host1.properties["memory"], "8192");
+    assert_float_eq(platform.loopbacks["host1"].bandwidth, 1e11, EPSILON);
+
+    // link1 is shared by both routes, so its end becomes an auxiliary router
+    assert_eq!(platform.routers, vec!["link1#1"]);
+    assert_eq!(platform.links.len(), 4);
+    let route = platform
+        .routes
+        .iter()
+        .find(|r| r.src == "host3" && r.dst == "host1")
+        .unwrap();
+    assert_eq!(route.nodes, vec!["host3", "link1#1", "host1"]);
+
+    let mut sim = Simulation::new(123);
+    let network = make_network(&platform, &mut sim);
+    let ids = bind_hosts(&["host1", "host2", "host3"], &network, &mut sim);
+    assert_float_eq(network.borrow().latency(ids[0], ids[1]), 0.03, EPSILON);
+    assert_float_eq(network.borrow().bandwidth(ids[0], ids[1]), 5e7, EPSILON);
+    assert_float_eq(network.borrow().bandwidth(ids[2], ids[0]), 1e8, EPSILON);
+
+    // transfers from host1 share link1 (100 MB/s), while the transfer to host2 is limited by link2 (50 MB/s),
+    // the transfer to host3 starts earlier due to lower latency and gets the full link1 bandwidth until 0.03
+    let times = run_transfers(&[(0, 1, 1e8), (0, 2, 1e8)], &ids, &network, &mut sim);
+    assert_float_eq(times[0], 0.03 + 2., EPSILON);
+    assert_float_eq(times[1], 0.03 + (1e8 - 0.019 * 1e8) / 5e7, EPSILON);
+}
+
+const CLUSTER_PLATFORM: &str = r#"<?xml version='1.0'?>
+<platform version="4.1">
+    <zone id="world" routing="Full">
+        <cluster id="c1" prefix="node-" suffix=".c1" radical="0-2" speed="1Gf"
+                 bw="100MBps" lat="1ms" bb_bw="150MBps" bb_lat="2ms" loopback_bw="1GBps" loopback_lat="0"/>
+        <cluster id="c2" prefix="node-" suffix=".c2" radical="0,1" speed="1Gf" bw="100MBps" lat="1ms"
+                 sharing_policy="SHARED"/>
+        <link id="wan" bandwidth="10MBps" latency="50ms"/>
+        <zoneRoute src="c1" dst="c2" gw_src="node-c1_router.c1" gw_dst="node-c2_router.c2">
+            <link_ctn id="wan"/>
+        </zoneRoute>
+    </zone>
+</platform>"#;
+
+#[test]
+fn test_simgrid_cluster() {
+    let platform = Platform::from_simgrid_xml_str(CLUSTER_PLATFORM);
+    assert_eq!(platform.hosts.len(), 5);
+    assert!(platform.loopbacks.contains_key("node-0.c1"));
+    assert!(!platform.loopbacks.contains_key("node-0.c2"));
+
+    let mut sim = Simulation::new(123);
+    let network = make_network(&platform, &mut sim);
+    let ids = bind_hosts(
+        &["node-0.c1", "node-1.c1", "node-2.c1", "node-0.c2"],
+        &network,
+        &mut sim,
+    );
+
+    // uplink, backbone and downlink
+    assert_float_eq(network.borrow().latency(ids[0], ids[1]), 0.004, EPSILON);
+    // uplink, backbone, WAN link and downlink of the other cluster
+    assert_float_eq(network.borrow().latency(ids[0], ids[3]), 0.054, EPSILON);
+    assert_float_eq(network.borrow().bandwidth(ids[0], ids[3]), 1e7, EPSILON);
+
+    // two transfers share the backbone (150 MB/s)
+    let times = run_transfers(&[(0, 1, 1.5e8), (1, 2, 1.5e8)], &ids, &network, &mut sim);
+    assert_float_eq(times[0], 0.004 + 2., EPSILON);
+    assert_float_eq(times[1], 0.004 + 2., EPSILON);
+}
+
+#[test]
+#[should_panic(expected = "connected without a link")]
+fn test_simgrid_inconsistent_routes() {
+    Platform::from_simgrid_xml_str(
+        r#"<platform version="4.1">
+            <zone id="zone0" routing="Full">
+                <host id="host1" speed="1Gf"/>
+                <host id="host2" speed="1Gf"/>
+                <route src="host1" dst="host2"></route>
+            </zone>
+        </platform>"#,
+    );
+}
+
+const GRAPHML_PLATFORM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+    <key id="d0" for="node" attr.name="type" attr.type="string"/>
+    <key id="d1" for="node" attr.name="speed" attr.type="double"/>
+    <key id="d2" for="edge" attr.name="bandwidth" attr.type="double"/>
+    <key id="d3" for="edge" attr.name="latency" attr.type="double"/>
+    <graph id="G" edgedefault="undirected">
+        <node id="h1"><data key="d1">2e9</data></node>
+        <node id="h2"/>
+        <node id="s1"><data key="d0">switch</data></node>
+        <edge source="h1" target="s1"><data key="d2">200</data><data key="d3">0.5</data></edge>
+        <edge source="s1" target="h2"/>
+    </graph>
+</graphml>"#;
+
+#[test]
+fn test_graphml_platform() {
+    let platform = Platform::from_graphml_str(GRAPHML_PLATFORM, Link::shared(100., 0.1));
+    assert_eq!(platform.hosts.len(), 2);
+    assert_float_eq(platform.host("h1").unwrap().speed, 2e9, EPSILON);
+    assert_eq!(platform.routers, vec!["s1"]);
+    assert_eq!(platform.links.len(), 2);
+    assert!(platform.links.iter().all(|l| l.bidirectional));
+
+    let mut sim = Simulation::new(123);
+    let network = make_network(&platform, &mut sim);
+    let ids = bind_hosts(&["h1", "h2"], &network, &mut sim);
+    assert_float_eq(network.borrow().latency(ids[1], ids[0]), 0.6, EPSILON);
+    assert_float_eq(network.borrow().bandwidth(ids[0], ids[1]), 100., EPSILON);
+}
+
+#[test]
+fn test_simgrid_example_platform() {
+    let platform = Platform::from_simgrid_xml(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../examples-other/simgrid/ping-pong/platform.xml"
+    ));
+    assert_eq!(platform.hosts.len(), 2);
+    assert_eq!(platform.loopbacks.len(), 2);
+    assert_eq!(platform.links.len(), 1);
+    assert!(platform.links[0].bidirectional);
+    assert_float_eq(platform.links[0].link.bandwidth, 12.5e9, EPSILON);
+    assert_float_eq(platform.links[0].link.latency, 0.01, EPSILON);
+}
@@ -0,0 +1,292 @@
+use rand::prelude::*;
+use rand_pcg::Pcg64;
+use rstest::rstest;
+
+use dslab_core::simulation::Simulation;
+use dslab_core::EPSILON;
+
+use dslab_network::models::{ConstantBandwidthNetworkModel, TopologyAwareNetworkModel};
+use dslab_network::routing::{RoutingAlgorithm, ShortestPathDijkstra, ShortestPathFloydWarshall, StaticRouting};
+use dslab_network::{Link, LinkId, Network, NetworkModel, Node, NodeId, Topology};
+
+#[derive(Clone, Copy)]
+enum RoutingImpl {
+    Dijkstra,
+    FloydWarshall,
+}
+
+fn make_routing(routing: RoutingImpl) -> Box<dyn RoutingAlgorithm> {
+    match routing {
+        RoutingImpl::Dijkstra => Box::<ShortestPathDijkstra>::default(),
+        RoutingImpl::FloydWarshall => Box::<ShortestPathFloydWarshall>::default(),
+    }
+}
+
+/// Checks that the path goes from `src` to `dst` over the existing links.
+fn check_path(topology: &Topology, src: NodeId, dst: NodeId, path: &[LinkId]) {
+    let mut node = src;
+    for &link_id in path {
+        let next = topology.node_links_map()[&node]
+            .iter()
+            .find(|(_, &id)| id == link_id)
+            .map(|(&next, _)| next);
+        node = next.unwrap_or_else(|| panic!("Link {} does not go from node {}", link_id, node));
+    }
+    assert_eq!(node, dst);
+}
+
+/// Compares the paths returned by the incrementally updated routing with the full recomputation.
+fn check_equivalence(incremental: &dyn RoutingAlgorithm, routing: RoutingImpl, topology: &Topology) {
+    let mut full = make_routing(routing);
+    full.init(topology);
+    for src in 0..topology.node_count() {
+        for dst in 0..topology.node_count() {
+            let expected = full.get_path_iter(src, dst, topology).map(|p| p.collect::<Vec<_>>());
+            let actual = incremental
+                .get_path_iter(src, dst, topology)
+                .map(|p| p.collect::<Vec<_>>());
+            assert_eq!(
+                actual.is_some(),
+                expected.is_some(),
+                "Path existence from {} to {} differs",
+                src,
+                dst
+            );
+            if let (Some(actual), Some(expected)) = (actual, expected) {
+                check_path(topology, src, dst, &actual);
+                let actual_latency: f64 = actual.iter().map(|&id| topology.link(id).latency).sum();
+                let expected_latency: f64 = expected.iter().map(|&id| topology.link(id).latency).sum();
+                assert!(
+                    (actual_latency - expected_latency).abs() < EPSILON,
+                    "Path latencies from {} to {} differ: {} vs {}",
+                    src,
+                    dst,
+                    actual_latency,
+                    expected_latency
+                );
+            }
+        }
+    }
+}
+
+fn add_node(topology: &mut Topology) -> NodeId {
+    let name = format!("node{}", topology.node_count());
+    topology.add_node(Node { name })
+}
+
+/// Adds a random link between the nodes which are not directly connected.
+fn add_random_link(topology: &mut Topology, rng: &mut Pcg64) -> Option<LinkId> {
+    let node_count = topology.node_count();
+    for _ in 0..100 {
+        let node1 = rng.gen_range(0..node_count);
+        let node2 = rng.gen_range(0..node_count);
+        if node1 == node2 || topology.has_link_between(node1, node2) || topology.has_link_between(node2, node1) {
+            continue;
+        }
+        let link = Link::shared(100., rng.gen_range(1.0..10.0));
+        return Some(if rng.gen_bool(0.5) {
+            topology.add_link(node1, node2, link)
+        } else {
+            topology.add_unidirectional_link(node1, node2, link)
+        });
+    }
+    None
+}
+
+#[rstest]
+fn test_incremental_routing(
+    #[values(RoutingImpl::Dijkstra, RoutingImpl::FloydWarshall)] routing_impl: RoutingImpl,
+    #[values(1, 2, 3)] seed: u64,
+) {
+    let mut rng = Pcg64::seed_from_u64(seed);
+    let mut topology = Topology::new();
+    for _ in 0..10 {
+        add_node(&mut topology);
+    }
+    let mut links = Vec::new();
+    for _ in 0..15 {
+        links.extend(add_random_link(&mut topology, &mut rng));
+    }
+    let mut routing = make_routing(routing_impl);
+    routing.init(&topology);
+    check_equivalence(routing.as_ref(), routing_impl, &topology);
+
+    for _ in 0..40 {
+        match rng.gen_range(0..3) {
+            0 => {
+                let node_id = add_node(&mut topology);
+                routing.on_node_added(node_id, &topology);
+            }
+            1 => {
+                if let Some(link_id) = add_random_link(&mut topology, &mut rng) {
+                    links.push(link_id);
+                    routing.on_link_added(link_id, &topology);
+                }
+            }
+            _ => {
+                if !links.is_empty() {
+                    let link_id = links.swap_remove(rng.gen_range(0..links.len()));
+                    topology.remove_link(link_id);
+                    routing.on_link_removed(link_id, &topology);
+                }
+            }
+        }
+        check_equivalence(routing.as_ref(), routing_impl, &topology);
+    }
+}
+
+#[rstest]
+fn test_link_flap(#[values(RoutingImpl::Dijkstra, RoutingImpl::FloydWarshall)] routing_impl: RoutingImpl) {
+    // ring of 6 nodes with a fast chord between the opposite nodes
+    let mut topology = Topology::new();
+    for _ in 0..6 {
+        add_node(&mut topology);
+    }
+    for i in 0..6 {
+        topology.add_link(i, (i + 1) % 6, Link::shared(100., 1.));
+    }
+    let chord = topology.add_link(0, 3, Link::shared(100., 0.5));
+    let mut routing = make_routing(routing_impl);
+    routing.init(&topology);
+    let path = |routing: &dyn RoutingAlgorithm, topology: &Topology| {
+        routing.get_path_iter(1, 4, topology).unwrap().collect::<Vec<_>>()
+    };
+    assert_eq!(path(routing.as_ref(), &topology), vec![0, chord, 3]);
+
+    topology.remove_link(chord);
+    routing.on_link_removed(chord, &topology);
+    check_equivalence(routing.as_ref(), routing_impl, &topology);
+    assert_eq!(path(routing.as_ref(), &topology).len(), 3);
+    assert!(!path(routing.as_ref(), &topology).contains(&chord));
+
+    let chord = topology.add_link(0, 3, Link::shared(100., 0.5));
+    routing.on_link_added(chord, &topology);
+    check_equivalence(routing.as_ref(), routing_impl, &topology);
+    assert_eq!(path(routing.as_ref(), &topology), vec![0, chord, 3]);
+}
+
+#[test]
+fn test_network_topology_update() {
+    let mut sim = Simulation::new(123);
+    let model: Box<dyn NetworkModel> = Box::new(TopologyAwareNetworkModel::new());
+    let mut network = Network::new(model, sim.create_context("net"));
+    for host in ["host1", "host2", "host3"] {
+        network.add_node(host, Box::new(ConstantBandwidthNetworkModel::new(100., 0.)));
+    }
+    network.add_link("host1", "host2", Link::shared(100., 10.));
+    network.add_link("host2", "host3", Link::shared(100., 1.));
+    network.init_topology();
+    let src = sim.create_context("src").id();
+    let dst = sim.create_context("dst").id();
+    network.set_location(src, "host1");
+    network.set_location(dst, "host3");
+    assert!((network.latency(src, dst) - 11.).abs() < EPSILON);
+
+    // the new node provides a faster path
+    network.add_node("switch", Box::new(ConstantBandwidthNetworkModel::new(100., 0.)));
+    network.add_link("host1", "switch", Link::shared(100., 1.));
+    let link = network.add_link("switch", "host3", Link::shared(100., 1.));
+    assert!((network.latency(src, dst) - 2.).abs() < EPSILON);
+
+    network.remove_link(link);
+    assert!((network.latency(src, dst) - 11.).abs() < EPSILON);
+
+    // replacing the existing link triggers the full recomputation
+    network.add_link("host1", "host2", Link::shared(100., 20.));
+    assert!((network.latency(src, dst) - 21.).abs() < EPSILON);
+}
+
+/// Creates the ring of 4 nodes with the unidirectional chord from node0 to node2.
+fn make_ring() -> (Topology, LinkId) {
+    let mut topology = Topology::new();
+    for _ in 0..4 {
+        add_node(&mut topology);
+    }
+    for i in 0..4 {
+        topology.add_link(i, (i + 1) % 4, Link::shared(100., 1.));
+    }
+    let chord = topology.add_unidirectional_link(0, 2, Link::shared(100., 1.));
+    (topology, chord)
+}
+
+#[test]
+fn test_static_routing() {
+    let (topology, chord) = make_ring();
+    let mut routing = StaticRouting::new()
+        .with_route(&["node0", "node3", "node2", "node1"])
+        .with_link_route("node3", "node1", &[3, 0])
+        .with_symmetric_routes(true);
+    routing.init(&topology);
+    let path = |src: NodeId, dst: NodeId| {
+        routing
+            .get_path_iter(src, dst, &topology)
+            .map(|path| path.collect::<Vec<_>>())
+    };
+    assert_eq!(path(0, 1), Some(vec![3, 2, 1]));
+    // reverse routes are added automatically
+    assert_eq!(path(1, 0), Some(vec![1, 2, 3]));
+    assert_eq!(path(3, 1), Some(vec![3, 0]));
+    assert_eq!(path(1, 3), Some(vec![0, 3]));
+    // fallback to shortest path
+    assert_eq!(path(0, 2), Some(vec![chord]));
+    assert_eq!(path(2, 0).unwrap().len(), 2);
+
+    let mut routing = StaticRouting::new().with_route(&["node0", "node1"]).without_fallback();
+    routing.init(&topology);
+    assert!(routing.get_path_iter(0, 1, &topology).is_some());
+    assert!(routing.get_path_iter(1, 0, &topology).is_none());
+}
+
+#[test]
+fn test_static_routing_from_file() {
+    let (topology, chord) = make_ring();
+    let file = std::env::temp_dir().join("dslab_network_static_routes.csv");
+    std::fs::write(
+        &file,
+        "src,dst,path\nnode0,node2,node0 node1 node2\nnode3,node1,node3 node0 node1\n",
+    )
+    .unwrap();
+    let mut routing = StaticRouting::from_file(&file);
+    std::fs::remove_file(&file).unwrap();
+    routing.init(&topology);
+    let path = routing.get_path_iter(0, 2, &topology).unwrap().collect::<Vec<_>>();
+    assert_eq!(path, vec![0, 1]);
+    assert!(!path.contains(&chord));
+    let path = routing.get_path_iter(3, 1, &topology).unwrap().collect::<Vec<_>>();
+    assert_eq!(path, vec![3, 0]);
+}
+
+#[test]
+fn test_static_routing_validation() {
+    let (topology, chord) = make_ring();
+    let mut routing = StaticRouting::new()
+        .with_route(&["node0", "node2"])
+        .with_route(&["node0", "node5"])
+        .with_link_route("node2", "node1", &[chord])
+        .with_link_route("node0", "node3", &[0, 1])
+        .with_link_route("node1", "node2", &[42])
+        .with_route(&["node0", "node1", "node2"]);
+    let error = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| routing.init(&topology)))
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+    let expected = [
+        "Invalid static routes:",
+        "route from node0 to node5: node node5 is not found",
+        "route from node2 to node1: link 4 does not go from node node2",
+        "route from node0 to node3: path ends at node node2",
+        "route from node1 to node2: link 42 is not found",
+        "duplicate route from node0 to node2",
+    ];
+    assert_eq!(error.lines().collect::<Vec<_>>(), expected);
+
+    // the reverse of route with unidirectional link is invalid
+    let mut routing = StaticRouting::new()
+        .with_route(&["node1", "node0", "node2"])
+        .with_symmetric_routes(true);
+    let error = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| routing.init(&topology)))
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+    assert!(error.ends_with("reverse route from node2 to node1: no link from node2 to node0"));
+}
@@ -12,9 +12,15 @@ use dslab_core::handler::EventHandler;
 use dslab_core::simulation::Simulation;
 use dslab_core::EPSILON;
 
-use dslab_network::models::{ConstantBandwidthNetworkModel, TopologyAwareNetworkModel};
+use dslab_network::models::{
+    ConstantBandwidthNetworkModel, PacketLevelNetworkModel, SharedBandwidthNetworkModel, TcpAwareNetworkModel,
+    TopologyAwareNetworkModel,
+};
+use dslab_network::monitoring::LinkSamplingMode;
 use dslab_network::routing::{RoutingAlgorithm, ShortestPathDijkstra, ShortestPathFloydWarshall};
-use dslab_network::{DataTransferCompleted, Link, Network};
+use dslab_network::{
+    DataTransferCompleted, Link, MessageDelivered, MessageDeliveryMode, MulticastCompleted, Network, NetworkModel,
+};
 
 #[derive(Clone, Copy)]
 enum RoutingImpl {
@@ -297,3 +303,482 @@ fn test_diamond(#[values(RoutingImpl::Dijkstra, RoutingImpl::FloydWarshall)] rou
 
     assert_float_eq(sim.time(), 10.2, EPSILON);
 }
+
+pub struct MessageReceiver {
+    delivery_times: Vec<f64>,
+    ctx: SimulationContext,
+}
+
+impl EventHandler for MessageReceiver {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            MessageDelivered { msg: _ } => {
+                self.delivery_times.push(self.ctx.time());
+            }
+            DataTransferCompleted { dt: _ } => {}
+        })
+    }
+}
+
+#[rstest]
+#[case(MessageDeliveryMode::CutThrough, false, 1.2)]
+#[case(MessageDeliveryMode::StoreAndForward, false, 2.2)]
+#[case(MessageDeliveryMode::Contended, false, 1.2)]
+#[case(MessageDeliveryMode::CutThrough, true, 1.2)]
+#[case(MessageDeliveryMode::Contended, true, 2.2)]
+fn test_sized_messages(
+    #[case] mode: MessageDeliveryMode,
+    #[case] with_background_transfer: bool,
+    #[case] expected_delivery_time: f64,
+) {
+    let mut sim = Simulation::new(123);
+
+    let network_model = Box::new(TopologyAwareNetworkModel::new());
+    let mut network = Network::new(network_model, sim.create_context("net"));
+
+    network.add_node("host1", Box::new(ConstantBandwidthNetworkModel::new(100.0, 0.0)));
+    network.add_node("host2", Box::new(ConstantBandwidthNetworkModel::new(100.0, 0.0)));
+    network.add_node("host3", Box::new(ConstantBandwidthNetworkModel::new(100.0, 0.0)));
+
+    network.add_link("host1", "host2", Link::shared(100., 0.1));
+    network.add_link("host2", "host3", Link::shared(100., 0.1));
+
+    network.init_topology();
+    network.set_message_delivery_mode(mode);
+    let network_rc = Rc::new(RefCell::new(network));
+    sim.add_handler("net", network_rc.clone());
+
+    let sender_ctx = sim.create_context("sender");
+    let sender_id = sender_ctx.id();
+    let receiver = Rc::new(RefCell::new(MessageReceiver {
+        delivery_times: Vec::new(),
+        ctx: sim.create_context("receiver"),
+    }));
+    let receiver_id = sim.add_handler("receiver", receiver.clone());
+
+    network_rc.borrow_mut().set_location(sender_id, "host1");
+    network_rc.borrow_mut().set_location(receiver_id, "host3");
+
+    assert_float_eq(
+        network_rc.borrow().message_delay(sender_id, receiver_id, 100.),
+        if mode == MessageDeliveryMode::StoreAndForward {
+            2.2
+        } else {
+            1.2
+        },
+        EPSILON,
+    );
+
+    if with_background_transfer {
+        network_rc
+            .borrow_mut()
+            .transfer_data(sender_id, receiver_id, 1000., receiver_id);
+    }
+    network_rc
+        .borrow_mut()
+        .send_sized_msg("hello".to_string(), sender_id, receiver_id, 100.);
+
+    sim.step_until_no_events();
+
+    let delivery_times = &receiver.borrow().delivery_times;
+    assert_eq!(delivery_times.len(), 1);
+    assert_float_eq(delivery_times[0], expected_delivery_time, EPSILON);
+}
+
+#[rstest]
+fn test_link_monitoring(
+    #[values(false, true)] full_mesh_optimization: bool,
+    #[values(LinkSamplingMode::OnChange, LinkSamplingMode::Interval(3.))] mode: LinkSamplingMode,
+) {
+    let mut sim = Simulation::new(123);
+
+    let network_model = TopologyAwareNetworkModel::new().with_full_mesh_optimization(full_mesh_optimization);
+    let mut network = Network::new(Box::new(network_model), sim.create_context("net"));
+
+    network.add_node("host1", Box::new(ConstantBandwidthNetworkModel::new(100.0, 0.0)));
+    network.add_node("host2", Box::new(ConstantBandwidthNetworkModel::new(100.0, 0.0)));
+    network.add_node("host3", Box::new(ConstantBandwidthNetworkModel::new(100.0, 0.0)));
+
+    let link1 = network.add_link("host1", "host2", Link::shared(100., 0.));
+    let link2 = network.add_link("host2", "host3", Link::shared(200., 0.));
+
+    network.init_topology();
+    network.enable_link_monitoring(mode);
+    let network_rc = Rc::new(RefCell::new(network));
+    sim.add_handler("net", network_rc.clone());
+
+    let node1 = Node::new(network_rc.clone(), sim.create_context("node1"));
+    let node1_id = sim.add_handler("node1", Rc::new(RefCell::new(node1)));
+    let node3 = Node::new(network_rc.clone(), sim.create_context("node3"));
+    let node3_id = sim.add_handler("node3", Rc::new(RefCell::new(node3)));
+
+    network_rc.borrow_mut().set_location(node1_id, "host1");
+    network_rc.borrow_mut().set_location(node3_id, "host3");
+
+    network_rc
+        .borrow_mut()
+        .transfer_data(node1_id, node3_id, 1000., node3_id);
+
+    sim.step_for_duration(5.);
+    let stats = network_rc.borrow().link_stats(link1);
+    assert_eq!(stats.active_transfers, 1);
+    assert_float_eq(stats.utilization, 1., EPSILON);
+    assert_float_eq(stats.data_transferred, 500., EPSILON);
+    let stats = network_rc.borrow().link_stats(link2);
+    assert_float_eq(stats.utilization, 0.5, EPSILON);
+    assert_float_eq(stats.utilization_integral, 2.5, EPSILON);
+
+    // the last transfer completes at 10, while the last sample can be recorded later
+    sim.step_until_no_events();
+    let stats = network_rc.borrow().all_link_stats();
+    assert_eq!(stats[link1].active_transfers, 0);
+    assert_float_eq(stats[link1].data_transferred, 1000., EPSILON);
+    assert_float_eq(stats[link1].average_utilization(10.), 1., EPSILON);
+    assert_float_eq(stats[link2].average_utilization(10.), 0.5, EPSILON);
+
+    let net = network_rc.borrow();
+    let samples = net.link_utilization_log().unwrap().samples();
+    let link1_samples = samples
+        .iter()
+        .filter(|s| s.link_id == link1)
+        .map(|s| (s.time, s.utilization))
+        .collect::<Vec<_>>();
+    match mode {
+        LinkSamplingMode::OnChange => assert_eq!(link1_samples, vec![(0., 0.), (0., 1.), (10., 0.)]),
+        LinkSamplingMode::Interval(_) => {
+            assert_eq!(
+                link1_samples,
+                vec![(0., 0.), (0., 1.), (3., 1.), (6., 1.), (9., 1.), (12., 0.)]
+            )
+        }
+    }
+}
+
+pub struct TransferCollector {
+    completion_times: Vec<(usize, f64)>,
+    ctx: SimulationContext,
+}
+
+impl EventHandler for TransferCollector {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            DataTransferCompleted { dt } => {
+                self.completion_times.push((dt.id, self.ctx.time()));
+            }
+        })
+    }
+}
+
+/// Runs transfers (index of source host, index of destination host, size) over the chain of hosts
+/// connected by given links, returns the transfer completion times and the amounts of data carried by the links.
+fn run_chain_test(
+    model: Box<dyn NetworkModel>,
+    links: &[Link],
+    transfers: &[(usize, usize, f64)],
+) -> (Vec<f64>, Vec<f64>) {
+    let mut sim = Simulation::new(123);
+
+    let mut network = Network::new(model, sim.create_context("net"));
+    let hosts = (0..=links.len()).map(|i| format!("host{}", i + 1)).collect::<Vec<_>>();
+    for host in hosts.iter() {
+        network.add_node(host, Box::new(ConstantBandwidthNetworkModel::new(100.0, 0.0)));
+    }
+    for (i, link) in links.iter().enumerate() {
+        network.add_link(&hosts[i], &hosts[i + 1], *link);
+    }
+    network.init_topology();
+    let network_rc = Rc::new(RefCell::new(network));
+    sim.add_handler("net", network_rc.clone());
+
+    let ids = hosts
+        .iter()
+        .map(|host| {
+            let id = sim.create_context(host).id();
+            network_rc.borrow_mut().set_location(id, host);
+            id
+        })
+        .collect::<Vec<_>>();
+    let collector = Rc::new(RefCell::new(TransferCollector {
+        completion_times: Vec::new(),
+        ctx: sim.create_context("collector"),
+    }));
+    let collector_id = sim.add_handler("collector", collector.clone());
+
+    let transfer_ids = transfers
+        .iter()
+        .map(|&(src, dst, size)| {
+            network_rc
+                .borrow_mut()
+                .transfer_data(ids[src], ids[dst], size, collector_id)
+        })
+        .collect::<Vec<_>>();
+    sim.step_until_no_events();
+
+    let completion_times = &collector.borrow().completion_times;
+    let times = transfer_ids
+        .iter()
+        .map(|id| completion_times.iter().find(|(dt_id, _)| dt_id == id).unwrap().1)
+        .collect();
+    let link_stats = network_rc.borrow().all_link_stats();
+    assert!(link_stats.iter().all(|stats| stats.active_transfers == 0));
+    (times, link_stats.iter().map(|stats| stats.data_transferred).collect())
+}
+
+fn chain_links() -> [Link; 2] {
+    [Link::shared(100., 0.5), Link::shared(100., 0.5)]
+}
+
+#[test]
+fn test_packet_level_pipelining() {
+    // 10 packets are transmitted back-to-back over the first link and pipelined over the second one
+    let model = PacketLevelNetworkModel::new().with_mtu(100.).with_initial_window(100.);
+    let (times, data_transferred) = run_chain_test(Box::new(model), &chain_links(), &[(0, 2, 1000.)]);
+    assert_float_eq(times[0], 10. + 0.5 + 1. + 0.5, EPSILON);
+    assert_float_eq(data_transferred[0], 1000., EPSILON);
+    assert_float_eq(data_transferred[1], 1000., EPSILON);
+}
+
+#[test]
+fn test_packet_level_window() {
+    // window of one packet, each packet is sent after receiving the acknowledgement of the previous one
+    let model = PacketLevelNetworkModel::new()
+        .with_mtu(100.)
+        .with_initial_window(1.)
+        .with_max_window(1.);
+    let (times, _) = run_chain_test(Box::new(model), &chain_links(), &[(0, 1, 1000.)]);
+    assert_float_eq(times[0], 9. * 2. + 1.5, EPSILON);
+}
+
+#[test]
+fn test_packet_level_drop_tail() {
+    let transfers = [(0, 2, 1000.), (1, 2, 1000.)];
+    // the queues are large enough, so the second link is always busy
+    let model = PacketLevelNetworkModel::new()
+        .with_mtu(100.)
+        .with_min_retransmission_timeout(30.);
+    let (times, _) = run_chain_test(Box::new(model), &chain_links(), &transfers);
+    assert_float_eq(times[0], 20.5, EPSILON);
+    assert_float_eq(times[1], 10.5, EPSILON);
+
+    // the initial window exceeds the queue size, so the dropped packets are retransmitted after timeout
+    let model = PacketLevelNetworkModel::new().with_mtu(100.).with_queue_size(2);
+    let (times_with_drops, data_transferred) = run_chain_test(Box::new(model), &chain_links(), &transfers);
+    assert!(times_with_drops[0] > times[0]);
+    assert!(times_with_drops[1] > times[1]);
+    assert_float_eq(data_transferred[0], 1000., EPSILON);
+    assert_float_eq(data_transferred[1], 2000., EPSILON);
+}
+
+fn tcp_aware_model() -> TcpAwareNetworkModel {
+    TcpAwareNetworkModel::new()
+        .with_latency_factor(1.)
+        .with_bandwidth_factor(1.)
+        .with_weight_s(0.)
+        .with_tcp_window(f64::INFINITY)
+}
+
+#[test]
+fn test_tcp_aware_rtt_weights() {
+    // both transfers share the first link, the second transfer has 3 times larger latency and gets 1/4 of bandwidth
+    let links = [Link::shared(100., 1.), Link::shared(1000., 2.)];
+    let (times, _) = run_chain_test(Box::new(tcp_aware_model()), &links, &[(0, 1, 300.), (2, 0, 200.)]);
+    // the first transfer runs alone from 1 to 3, then gets 75 until completion
+    assert_float_eq(times[0], 3. + 100. / 75., EPSILON);
+    assert_float_eq(times[1], times[0] + (200. - 100. / 3.) / 100., EPSILON);
+
+    // without RTT-dependent weights the bandwidth is shared equally
+    let (times, _) = run_chain_test(
+        Box::new(TopologyAwareNetworkModel::new()),
+        &links,
+        &[(0, 1, 300.), (2, 0, 200.)],
+    );
+    assert_float_eq(times[0], 3. + 100. / 50., EPSILON);
+}
+
+#[test]
+fn test_tcp_aware_window_bound() {
+    let links = [Link::shared(100., 1.)];
+    // throughput is bounded by window / RTT = 100 / 2
+    let model = tcp_aware_model().with_tcp_window(100.);
+    let (times, data_transferred) = run_chain_test(Box::new(model), &links, &[(0, 1, 500.)]);
+    assert_float_eq(times[0], 1. + 500. / 50., EPSILON);
+    assert_float_eq(data_transferred[0], 500., EPSILON);
+}
+
+#[test]
+fn test_tcp_aware_factors() {
+    let links = [Link::shared(100., 1.)];
+    let model = tcp_aware_model().with_latency_factor(2.).with_bandwidth_factor(0.5);
+    let (times, _) = run_chain_test(Box::new(model), &links, &[(0, 1, 300.)]);
+    assert_float_eq(times[0], 2. + 300. / 50., EPSILON);
+
+    // LV08 parameters
+    let model = TcpAwareNetworkModel::new();
+    let (times, _) = run_chain_test(Box::new(model), &links, &[(0, 1, 300.)]);
+    assert_float_eq(times[0], 13.01 + 300. / 97., EPSILON);
+}
+
+#[derive(Default)]
+struct MulticastResult {
+    deliveries: Vec<(Id, f64)>,
+    completion_time: Option<f64>,
+}
+
+pub struct MulticastCollector {
+    result: MulticastResult,
+    ctx: SimulationContext,
+}
+
+impl EventHandler for MulticastCollector {
+    fn on(&mut self, event: Event) {
+        cast!(match event.data {
+            DataTransferCompleted { dt } => {
+                self.result.deliveries.push((dt.dst, self.ctx.time()));
+            }
+            MulticastCompleted { dsts, .. } => {
+                assert_eq!(dsts.len(), self.result.deliveries.len());
+                self.result.completion_time = Some(self.ctx.time());
+            }
+        })
+    }
+}
+
+/// Runs multicast from `host0` to the hosts connected to the switch, `host0` is connected to the switch
+/// via the uplink with latency 1, the other hosts are connected via the links with given latencies.
+/// Returns the multicast results with destinations replaced by indices of hosts and the network.
+fn run_multicast_test(
+    model: Box<dyn NetworkModel>,
+    latencies: &[f64],
+    broadcast: bool,
+) -> (Vec<(usize, f64)>, f64, Rc<RefCell<Network>>) {
+    let mut sim = Simulation::new(123);
+    let topology_aware = model.is_topology_aware();
+    let mut network = Network::new(model, sim.create_context("net"));
+    network.add_node("switch", Box::new(ConstantBandwidthNetworkModel::new(100., 0.)));
+    let hosts = (0..=latencies.len()).map(|i| format!("host{}", i)).collect::<Vec<_>>();
+    for host in hosts.iter() {
+        network.add_node(host, Box::new(ConstantBandwidthNetworkModel::new(100., 0.)));
+    }
+    if topology_aware {
+        network.add_link("host0", "switch", Link::shared(100., 1.));
+        for (host, &latency) in hosts.iter().skip(1).zip(latencies) {
+            network.add_link(host, "switch", Link::shared(100., latency));
+        }
+        network.init_topology();
+    }
+    let network_rc = Rc::new(RefCell::new(network));
+    sim.add_handler("net", network_rc.clone());
+    let ids = hosts
+        .iter()
+        .map(|host| {
+            let id = sim.create_context(host).id();
+            network_rc.borrow_mut().set_location(id, host);
+            id
+        })
+        .collect::<Vec<_>>();
+    let collector = Rc::new(RefCell::new(MulticastCollector {
+        result: MulticastResult::default(),
+        ctx: sim.create_context("collector"),
+    }));
+    let collector_id = sim.add_handler("collector", collector.clone());
+
+    if broadcast {
+        network_rc.borrow_mut().broadcast_data(ids[0], 1000., collector_id);
+    } else {
+        network_rc
+            .borrow_mut()
+            .multicast_data(ids[0], &ids[1..], 1000., collector_id);
+    }
+    sim.step_until_no_events();
+
+    let result = &collector.borrow().result;
+    let deliveries = result
+        .deliveries
+        .iter()
+        .map(|(dst, time)| (ids.iter().position(|id| id == dst).unwrap(), *time))
+        .collect();
+    (deliveries, result.completion_time.unwrap(), network_rc)
+}
+
+#[test]
+fn test_multicast_tree() {
+    let (deliveries, completion_time, network) =
+        run_multicast_test(Box::new(TopologyAwareNetworkModel::new()), &[0.5, 1., 2.], false);
+    // the data crosses the uplink once at full bandwidth
+    assert_eq!(deliveries.len(), 3);
+    for (host, time) in deliveries {
+        assert_float_eq(time, 10. + 1. + [0.5, 1., 2.][host - 1], EPSILON);
+    }
+    assert_float_eq(completion_time, 13., EPSILON);
+    let link_stats = network.borrow().all_link_stats();
+    for stats in link_stats {
+        assert_float_eq(stats.data_transferred, 1000., EPSILON);
+    }
+}
+
+#[test]
+fn test_multicast_tree_sharing() {
+    // multicast transfer shares the uplink with a regular transfer as a single transfer
+    let mut sim = Simulation::new(123);
+    let mut network = Network::new(Box::new(TopologyAwareNetworkModel::new()), sim.create_context("net"));
+    for host in ["host0", "host1", "host2", "switch"] {
+        network.add_node(host, Box::new(ConstantBandwidthNetworkModel::new(100., 0.)));
+    }
+    for host in ["host0", "host1", "host2"] {
+        network.add_link(host, "switch", Link::shared(100., 0.));
+    }
+    network.init_topology();
+    let network_rc = Rc::new(RefCell::new(network));
+    sim.add_handler("net", network_rc.clone());
+    let ids = ["host0", "host1", "host2"].map(|host| {
+        let id = sim.create_context(host).id();
+        network_rc.borrow_mut().set_location(id, host);
+        id
+    });
+    let collector = Rc::new(RefCell::new(TransferCollector {
+        completion_times: Vec::new(),
+        ctx: sim.create_context("collector"),
+    }));
+    let collector_id = sim.add_handler("collector", collector.clone());
+    let multicast_id = network_rc
+        .borrow_mut()
+        .multicast_data(ids[0], &ids[1..], 1000., collector_id);
+    let transfer_id = network_rc
+        .borrow_mut()
+        .transfer_data(ids[0], ids[1], 500., collector_id);
+    sim.step_until_no_events();
+
+    let completion_times = &collector.borrow().completion_times;
+    let times = |id: usize| {
+        completion_times
+            .iter()
+            .filter(|(dt_id, _)| *dt_id == id)
+            .map(|(_, time)| *time)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(times(transfer_id), vec![10.]);
+    assert_eq!(times(multicast_id), vec![15., 15.]);
+}
+
+#[test]
+fn test_multicast_without_tree() {
+    // topology-unaware model does not support multicast, so separate transfers are used
+    let (deliveries, completion_time, _) =
+        run_multicast_test(Box::new(SharedBandwidthNetworkModel::new(100., 1.)), &[0., 0.], false);
+    assert_eq!(deliveries.len(), 2);
+    for (_, time) in deliveries {
+        assert_float_eq(time, 21., EPSILON);
+    }
+    assert_float_eq(completion_time, 21., EPSILON);
+}
+
+#[test]
+fn test_broadcast() {
+    let (mut deliveries, completion_time, _) =
+        run_multicast_test(Box::new(TopologyAwareNetworkModel::new()), &[1., 3.], true);
+    deliveries.sort_by_key(|(host, _)| *host);
+    assert_eq!(deliveries.iter().map(|(host, _)| *host).collect::<Vec<_>>(), vec![1, 2]);
+    assert_float_eq(deliveries[0].1, 12., EPSILON);
+    assert_float_eq(deliveries[1].1, 14., EPSILON);
+    assert_float_eq(completion_time, 14., EPSILON);
+}
@@ -0,0 +1,194 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use dslab_core::simulation::Simulation;
+use dslab_core::EPSILON;
+
+use dslab_network::models::{ConstantBandwidthNetworkModel, TopologyAwareNetworkModel};
+use dslab_network::traffic::{
+    read_flow_trace, ArrivalProcess, FlowSizeDistribution, TraceFlow, TrafficGenerator, TrafficPattern,
+};
+use dslab_network::{Link, Network};
+
+fn assert_float_eq(x: f64, y: f64, eps: f64) {
+    assert!(
+        (x - y).abs() < eps || (x.max(y) - x.min(y)) / x.min(y) < eps,
+        "Values do not match: {:.15} vs {:.15}",
+        x,
+        y
+    );
+}
+
+const HOSTS: [&str; 4] = ["host1", "host2", "host3", "host4"];
+
+/// Creates the star network of four hosts connected to the switch.
+fn make_network(sim: &mut Simulation) -> Rc<RefCell<Network>> {
+    let mut network = Network::new(Box::new(TopologyAwareNetworkModel::new()), sim.create_context("net"));
+    network.add_node("switch", Box::new(ConstantBandwidthNetworkModel::new(1000., 0.)));
+    for host in HOSTS {
+        network.add_node(host, Box::new(ConstantBandwidthNetworkModel::new(1000., 0.)));
+        network.add_link(host, "switch", Link::shared(100., 0.));
+    }
+    network.init_topology();
+    let network = Rc::new(RefCell::new(network));
+    sim.add_handler("net", network.clone());
+    network
+}
+
+fn run_generator<F>(pattern: TrafficPattern, configure: F) -> Rc<RefCell<TrafficGenerator>>
+where
+    F: FnOnce(TrafficGenerator) -> TrafficGenerator,
+{
+    let mut sim = Simulation::new(123);
+    let network = make_network(&mut sim);
+    let nodes = HOSTS.iter().map(|h| h.to_string()).collect();
+    let generator = configure(TrafficGenerator::new(
+        network,
+        nodes,
+        pattern,
+        sim.create_context("generator"),
+    ));
+    let generator = Rc::new(RefCell::new(generator));
+    sim.add_handler("generator", generator.clone());
+    generator.borrow_mut().start();
+    sim.step_until_no_events();
+    generator
+}
+
+#[test]
+fn test_all_to_all() {
+    let generator = run_generator(TrafficPattern::AllToAll, |g| {
+        g.with_arrivals(ArrivalProcess::Periodic(1.))
+            .with_flow_size(FlowSizeDistribution::Fixed(100.))
+            .with_flow_limit(12)
+    });
+    let generator = generator.borrow();
+    assert_eq!(generator.flows().len(), 12);
+    // each host link is used by 3 outgoing and 3 incoming flows
+    for flow in generator.flows() {
+        assert_float_eq(flow.start_time, 1., EPSILON);
+        assert_float_eq(flow.duration().unwrap(), 6., EPSILON);
+    }
+    let stats = generator.completion_stats().unwrap();
+    assert_eq!(stats.count, 12);
+    assert_float_eq(stats.mean, 6., EPSILON);
+    assert_float_eq(stats.p99, 6., EPSILON);
+}
+
+#[test]
+fn test_incast() {
+    let pattern = TrafficPattern::Incast {
+        dst: "host1".to_string(),
+        senders: 2,
+    };
+    let generator = run_generator(pattern, |g| {
+        g.with_arrivals(ArrivalProcess::Periodic(10.))
+            .with_flow_size(FlowSizeDistribution::Fixed(100.))
+            .with_stop_time(25.)
+    });
+    let generator = generator.borrow();
+    // two arrivals at 10 and 20, each with two flows sharing the link of destination
+    assert_eq!(generator.flows().len(), 4);
+    for flow in generator.flows() {
+        assert_eq!(flow.dst, "host1");
+        assert_ne!(flow.src, "host1");
+        assert_float_eq(flow.duration().unwrap(), 2., EPSILON);
+    }
+}
+
+#[test]
+fn test_permutation() {
+    let generator = run_generator(TrafficPattern::Permutation, |g| {
+        g.with_arrivals(ArrivalProcess::Poisson(0.1))
+            .with_flow_size(FlowSizeDistribution::Exponential(10.))
+            .with_flow_limit(20)
+    });
+    let generator = generator.borrow();
+    assert_eq!(generator.flows().len(), 20);
+    let mut destinations = HashMap::new();
+    for flow in generator.flows() {
+        assert_ne!(flow.src, flow.dst);
+        assert_eq!(
+            destinations.entry(flow.src.clone()).or_insert(flow.dst.clone()),
+            &flow.dst
+        );
+        assert!(flow.completion_time.is_some());
+    }
+}
+
+#[test]
+fn test_uniform_random() {
+    let generator = run_generator(TrafficPattern::UniformRandom, |g| {
+        g.with_arrivals(ArrivalProcess::Poisson(2.))
+            .with_flow_size(FlowSizeDistribution::Pareto(10., 1.5))
+            .with_flow_limit(100)
+    });
+    let generator = generator.borrow();
+    assert_eq!(generator.flows().len(), 100);
+    assert!(generator
+        .flows()
+        .iter()
+        .all(|flow| flow.src != flow.dst && flow.size >= 10.));
+    let stats = generator.completion_stats().unwrap();
+    assert_eq!(stats.count, 100);
+    assert!(stats.min <= stats.p50 && stats.p50 <= stats.p90 && stats.p90 <= stats.p99 && stats.p99 <= stats.max);
+    assert!(stats.min >= 0.1 - EPSILON);
+}
+
+#[test]
+fn test_trace_replay() {
+    let file = std::env::temp_dir().join("dslab_network_flow_trace.csv");
+    std::fs::write(
+        &file,
+        "time,src,dst,size\n0,host1,host2,100\n0.5,host3,host2,100\n5,host4,host1,50\n",
+    )
+    .unwrap();
+    let trace = read_flow_trace(&file);
+    std::fs::remove_file(&file).unwrap();
+    assert_eq!(trace.len(), 3);
+
+    let generator = run_generator(TrafficPattern::Trace(trace), |g| g);
+    let generator = generator.borrow();
+    let flows = generator.flows();
+    assert_eq!(flows.len(), 3);
+    // the first two flows share the link of host2 from 0.5 until 1.5
+    assert_float_eq(flows[0].completion_time.unwrap(), 1.5, EPSILON);
+    assert_float_eq(flows[1].completion_time.unwrap(), 2., EPSILON);
+    assert_float_eq(flows[2].start_time, 5., EPSILON);
+    assert_float_eq(flows[2].duration().unwrap(), 0.5, EPSILON);
+    assert_eq!(generator.completion_times(), vec![0.5, 1.5, 1.5]);
+}
+
+#[test]
+fn test_trace_replay_limits() {
+    let trace = [(0., "host1", "host2"), (1., "host3", "host2"), (5., "host4", "host1")]
+        .into_iter()
+        .map(|(time, src, dst)| TraceFlow {
+            time,
+            src: src.to_string(),
+            dst: dst.to_string(),
+            size: 100.,
+        })
+        .collect::<Vec<_>>();
+
+    // the flow starting after the stop time is skipped
+    let generator = run_generator(TrafficPattern::Trace(trace.clone()), |g| g.with_stop_time(2.));
+    let flows = generator
+        .borrow()
+        .flows()
+        .iter()
+        .map(|f| f.src.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(flows, vec!["host1", "host3"]);
+
+    // only the first flow is started
+    let generator = run_generator(TrafficPattern::Trace(trace), |g| g.with_flow_limit(1));
+    let flows = generator
+        .borrow()
+        .flows()
+        .iter()
+        .map(|f| f.src.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(flows, vec!["host1"]);
+}
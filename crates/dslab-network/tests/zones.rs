@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use rstest::rstest;
+
+use dslab_core::EPSILON;
+
+use dslab_network::routing::{
+    HierarchicalRouting, PathIterator, RoutingAlgorithm, ShortestPathFloydWarshall, Zone, ZoneRouting,
+};
+use dslab_network::{Link, LinkId, Node, NodeId, Topology};
+
+struct Datacenter {
+    topology: Topology,
+    nodes: HashMap<String, NodeId>,
+    hosts: Vec<String>,
+}
+
+impl Datacenter {
+    fn add_node(&mut self, name: &str) {
+        let id = self.topology.add_node(Node { name: name.to_string() });
+        self.nodes.insert(name.to_string(), id);
+    }
+
+    fn add_link(&mut self, node1: &str, node2: &str, latency: f64) -> LinkId {
+        self.topology
+            .add_link(self.nodes[node1], self.nodes[node2], Link::shared(100., latency))
+    }
+}
+
+/// Creates a datacenter with `racks` racks of `hosts` hosts connected to the top-of-rack switch,
+/// the rack switches are connected to two core switches.
+fn make_datacenter(racks: usize, hosts: usize) -> Datacenter {
+    let mut dc = Datacenter {
+        topology: Topology::new(),
+        nodes: HashMap::new(),
+        hosts: Vec::new(),
+    };
+    dc.add_node("core1");
+    dc.add_node("core2");
+    for rack in 0..racks {
+        let switch = format!("tor{}", rack);
+        dc.add_node(&switch);
+        dc.add_link(&switch, "core1", 1.);
+        // the second core switch is slower for odd racks
+        dc.add_link(&switch, "core2", if rack % 2 == 0 { 2. } else { 0.5 });
+        for host in 0..hosts {
+            let name = format!("host{}-{}", rack, host);
+            dc.add_node(&name);
+            dc.add_link(&name, &switch, 0.1);
+            dc.hosts.push(name);
+        }
+    }
+    dc
+}
+
+fn make_zones(racks: usize, hosts: usize, root_routing: ZoneRouting, rack_routing: ZoneRouting) -> Zone {
+    let mut root = Zone::new("dc", root_routing).with_nodes(["core1", "core2"]);
+    for rack in 0..racks {
+        let zone = Zone::new(&format!("rack{}", rack), rack_routing)
+            .with_gateway(&format!("tor{}", rack))
+            .with_nodes((0..hosts).map(|host| format!("host{}-{}", rack, host)));
+        root = root.with_zone(zone);
+    }
+    root
+}
+
+fn get_path(routing: &dyn RoutingAlgorithm, dc: &Datacenter, src: &str, dst: &str) -> Option<Vec<LinkId>> {
+    routing
+        .get_path_iter(dc.nodes[src], dc.nodes[dst], &dc.topology)
+        .map(|path| path.collect())
+}
+
+#[rstest]
+fn test_hierarchical_routing_matches_flat(
+    #[values(ZoneRouting::Floyd, ZoneRouting::Dijkstra)] root_routing: ZoneRouting,
+    #[values(ZoneRouting::Floyd, ZoneRouting::Dijkstra, ZoneRouting::Cluster)] rack_routing: ZoneRouting,
+) {
+    let dc = make_datacenter(4, 3);
+    let mut flat = ShortestPathFloydWarshall::default();
+    flat.init(&dc.topology);
+    let mut hierarchical = HierarchicalRouting::new(make_zones(4, 3, root_routing, rack_routing));
+    hierarchical.init(&dc.topology);
+
+    let mut nodes = dc.hosts.clone();
+    nodes.extend(["core1", "core2", "tor0", "tor3"].map(String::from));
+    for src in nodes.iter() {
+        for dst in nodes.iter() {
+            assert_eq!(
+                get_path(&hierarchical, &dc, src, dst),
+                get_path(&flat, &dc, src, dst),
+                "Paths from {} to {} differ",
+                src,
+                dst
+            );
+        }
+    }
+}
+
+#[test]
+fn test_hierarchical_routing_path() {
+    let mut dc = make_datacenter(2, 2);
+    // the rack switches belong to the root zone, so the direct link between them is used
+    let direct_link = dc.add_link("tor0", "tor1", 0.1);
+    let mut routing = HierarchicalRouting::new(make_zones(2, 2, ZoneRouting::Floyd, ZoneRouting::Cluster));
+    routing.init(&dc.topology);
+    let path = get_path(&routing, &dc, "host0-0", "host1-1").unwrap();
+    assert_eq!(path, vec![2, direct_link, 7]);
+    assert!((dc.topology.get_path_latency(PathIterator::from_links(&path)) - 0.3).abs() < EPSILON);
+
+    // route from host inside rack to core switch goes through the rack gateway
+    let path = get_path(&routing, &dc, "host1-0", "core2").unwrap();
+    assert_eq!(path, vec![6, 5]);
+}
+
+#[test]
+fn test_full_zone_routes() {
+    let mut dc = make_datacenter(2, 1);
+    dc.add_link("tor0", "tor1", 0.1);
+    // explicit routes between racks go through the slow core switch
+    let mut root = Zone::new("dc", ZoneRouting::Full)
+        .with_nodes(["core1", "core2"])
+        .with_route(&["tor0", "core2", "tor1"]);
+    for rack in 0..2 {
+        root = root.with_zone(
+            Zone::new(&format!("rack{}", rack), ZoneRouting::Cluster)
+                .with_gateway(&format!("tor{}", rack))
+                .with_node(&format!("host{}-0", rack)),
+        );
+    }
+    let mut routing = HierarchicalRouting::new(root);
+    routing.init(&dc.topology);
+    let latency = |src: &str, dst: &str| {
+        dc.topology.get_path_latency(
+            routing
+                .get_path_iter(dc.nodes[src], dc.nodes[dst], &dc.topology)
+                .unwrap(),
+        )
+    };
+    assert!((latency("host0-0", "host1-0") - 2.7).abs() < EPSILON);
+    // reverse route is added automatically
+    assert!((latency("host1-0", "host0-0") - 2.7).abs() < EPSILON);
+    // there is no route between core switches
+    assert!(get_path(&routing, &dc, "core1", "core2").is_none());
+}
+
+#[test]
+fn test_unlisted_nodes_in_root_zone() {
+    let dc = make_datacenter(2, 2);
+    // core switches are not listed in zones
+    let root = Zone::new("dc", ZoneRouting::Dijkstra)
+        .with_zone(
+            Zone::new("rack0", ZoneRouting::Cluster)
+                .with_gateway("tor0")
+                .with_nodes(["host0-0", "host0-1"]),
+        )
+        .with_zone(
+            Zone::new("rack1", ZoneRouting::Cluster)
+                .with_gateway("tor1")
+                .with_nodes(["host1-0", "host1-1"]),
+        );
+    let mut routing = HierarchicalRouting::new(root);
+    routing.init(&dc.topology);
+    let mut flat = ShortestPathFloydWarshall::default();
+    flat.init(&dc.topology);
+    assert_eq!(
+        get_path(&routing, &dc, "host0-1", "host1-0"),
+        get_path(&flat, &dc, "host0-1", "host1-0")
+    );
+}
+
+#[test]
+#[should_panic(expected = "Nested zone rack0 must have a gateway")]
+fn test_nested_zone_without_gateway() {
+    let dc = make_datacenter(1, 1);
+    let root =
+        Zone::new("dc", ZoneRouting::Floyd).with_zone(Zone::new("rack0", ZoneRouting::Floyd).with_node("host0-0"));
+    HierarchicalRouting::new(root).init(&dc.topology);
+}
+
+#[test]
+#[should_panic(expected = "is not connected to the gateway of cluster zone")]
+fn test_cluster_zone_without_links() {
+    let dc = make_datacenter(2, 1);
+    let root = Zone::new("dc", ZoneRouting::Floyd).with_zone(
+        Zone::new("rack0", ZoneRouting::Cluster)
+            .with_gateway("tor0")
+            .with_nodes(["host0-0", "host1-0"]),
+    );
+    HierarchicalRouting::new(root).init(&dc.topology);
+}
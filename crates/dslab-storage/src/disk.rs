@@ -14,11 +14,13 @@ use dslab_core::component::Id;
 use dslab_core::event::Event;
 use dslab_core::handler::EventHandler;
 use dslab_core::{cast, context::SimulationContext, log_debug, log_error};
+use dslab_models::power::ssd::{SsdPowerModel, SsdState};
 use dslab_models::throughput_sharing::{
     make_constant_throughput_fn, ActivityFactorFn, ConstantFactorFn, FairThroughputSharingModel, ResourceThroughputFn,
 };
 
 use crate::events::{DataReadCompleted, DataReadFailed, DataWriteCompleted, DataWriteFailed};
+use crate::power::{DiskPowerState, PowerManager, PowerStateTimeout, PowerStateTransitionCompleted};
 use crate::scheduler::{FifoScheduler, Scheduler};
 use crate::storage::{Storage, StorageInfo};
 
@@ -63,6 +65,8 @@ pub struct DiskBuilder {
     concurrent_ops_limit: Option<u64>,
     concurrent_read_ops_limit: Option<u64>,
     concurrent_write_ops_limit: Option<u64>,
+    ssd_power_model: Option<Box<dyn SsdPowerModel>>,
+    low_power_timeouts: Vec<(usize, f64)>,
 }
 
 impl Default for DiskBuilder {
@@ -79,6 +83,8 @@ impl Default for DiskBuilder {
             concurrent_ops_limit: None,
             concurrent_read_ops_limit: None,
             concurrent_write_ops_limit: None,
+            ssd_power_model: None,
+            low_power_timeouts: Vec::new(),
         }
     }
 }
@@ -167,10 +173,26 @@ impl DiskBuilder {
         self
     }
 
+    /// Sets SSD power model used to compute the disk power and energy consumption.
+    pub fn ssd_power_model(mut self, ssd_power_model: Box<dyn SsdPowerModel>) -> Self {
+        self.ssd_power_model.replace(ssd_power_model);
+        self
+    }
+
+    /// Enables transition into the low-power state with given index after the disk stays idle for given time.
+    ///
+    /// Can be called multiple times to enable transitions into deeper low-power states after longer idle times.
+    /// Requires SSD power model supporting this low-power state.
+    pub fn low_power_timeout(mut self, state: usize, idle_time: f64) -> Self {
+        assert!(idle_time >= 0., "Idle time must be non-negative");
+        self.low_power_timeouts.push((state, idle_time));
+        self
+    }
+
     /// Builds disk from given builder and simulation context.
     ///
     /// Panics on invalid or incomplete disk settings.
-    pub fn build(self, ctx: SimulationContext) -> Disk {
+    pub fn build(self, mut ctx: SimulationContext) -> Disk {
         let read_throughput_model =
             FairThroughputSharingModel::new(self.read_throughput_fn.unwrap(), self.read_factor_fn);
 
@@ -185,10 +207,33 @@ impl DiskBuilder {
             self.concurrent_write_ops_limit,
         ));
 
+        let mut low_power_timeouts = self.low_power_timeouts;
+        low_power_timeouts.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let power_manager = match self.ssd_power_model {
+            Some(model) => {
+                for (state, _) in low_power_timeouts.iter() {
+                    assert!(
+                        model.low_power_state(*state).is_some(),
+                        "SSD power model does not support low-power state {}",
+                        state
+                    );
+                }
+                Some(PowerManager::new(model, low_power_timeouts, &mut ctx))
+            }
+            None => {
+                assert!(
+                    low_power_timeouts.is_empty(),
+                    "Low-power timeouts require SSD power model"
+                );
+                None
+            }
+        };
+
         Disk {
             capacity: self.capacity.unwrap(),
             used: 0,
             scheduler,
+            power_manager,
             next_request_id: 0,
             ctx,
         }
@@ -202,11 +247,13 @@ impl DiskBuilder {
 /// Disk is characterized by its capacity and read/write throughput models.
 ///
 /// Disk state includes the amount of used disk space and state of throughput models.
+/// If SSD power model is set, the disk also tracks its power state and energy consumption.
 /// Should be created using [`DiskBuilder`].
 pub struct Disk {
     pub(in crate::disk) capacity: u64,
     pub(in crate::disk) used: u64,
     pub(in crate::disk) scheduler: Box<dyn Scheduler>,
+    pub(in crate::disk) power_manager: Option<PowerManager>,
     pub(in crate::disk) next_request_id: u64,
     pub(in crate::disk) ctx: SimulationContext,
 }
@@ -217,6 +264,58 @@ impl Disk {
         self.next_request_id += 1;
         request_id
     }
+
+    /// Submits the operation to the scheduler, or delays it until the disk returns from low-power state.
+    fn submit(&mut self, operation: DiskOperation) {
+        let operation = match self.power_manager.as_mut() {
+            Some(power_manager) => power_manager.admit(operation, &mut self.ctx),
+            None => Some(operation),
+        };
+        if let Some(operation) = operation {
+            self.scheduler.submit(operation, &mut self.ctx);
+            self.update_power();
+        }
+    }
+
+    /// Updates the disk power consumption after the change of running operations.
+    fn update_power(&mut self) {
+        if let Some(power_manager) = self.power_manager.as_mut() {
+            power_manager.update_throughput(
+                self.scheduler.read_throughput(),
+                self.scheduler.write_throughput(),
+                self.ctx.time(),
+            );
+        }
+    }
+
+    /// Returns the current disk power state or `None` if SSD power model is not set.
+    pub fn power_state(&self) -> Option<DiskPowerState> {
+        self.power_manager.as_ref().map(|power_manager| power_manager.state())
+    }
+
+    /// Returns the current state passed to SSD power model or `None` if SSD power model is not set.
+    pub fn ssd_state(&self) -> Option<SsdState> {
+        self.power_manager
+            .as_ref()
+            .map(|power_manager| power_manager.ssd_state())
+    }
+
+    /// Returns the current disk power consumption in Watts or zero if SSD power model is not set.
+    ///
+    /// During the transition into low-power state the disk consumes the power of its previous state,
+    /// and during the transition from low-power state the disk consumes the idle power.
+    pub fn current_power(&self) -> f64 {
+        self.power_manager
+            .as_ref()
+            .map_or(0., |power_manager| power_manager.current_power())
+    }
+
+    /// Returns the total disk energy consumption in Joules or zero if SSD power model is not set.
+    pub fn energy_consumed(&self) -> f64 {
+        self.power_manager
+            .as_ref()
+            .map_or(0., |power_manager| power_manager.energy_consumed(self.ctx.time()))
+    }
 }
 
 /// Storage model implementation for disk.
@@ -237,15 +336,12 @@ impl Storage for Disk {
             log_error!(self.ctx, "Failed reading: {}", error,);
             self.ctx.emit_now(DataReadFailed { request_id, error }, requester);
         } else {
-            self.scheduler.submit(
-                DiskOperation {
-                    request_id,
-                    requester,
-                    op_type: DiskOperationType::Read,
-                    size,
-                },
-                &mut self.ctx,
-            );
+            self.submit(DiskOperation {
+                request_id,
+                requester,
+                op_type: DiskOperationType::Read,
+                size,
+            });
         }
         request_id
     }
@@ -265,15 +361,12 @@ impl Storage for Disk {
             self.ctx.emit_now(DataWriteFailed { request_id, error }, requester);
         } else {
             self.used += size;
-            self.scheduler.submit(
-                DiskOperation {
-                    request_id,
-                    requester,
-                    op_type: DiskOperationType::Write,
-                    size,
-                },
-                &mut self.ctx,
-            );
+            self.submit(DiskOperation {
+                request_id,
+                requester,
+                op_type: DiskOperationType::Write,
+                size,
+            });
         }
         request_id
     }
@@ -316,6 +409,12 @@ impl EventHandler for Disk {
         cast!(match event.data {
             DiskOperationCompleted { request_id } => {
                 let operation = self.scheduler.complete(request_id, &mut self.ctx);
+                self.update_power();
+                if self.scheduler.is_idle() {
+                    if let Some(power_manager) = self.power_manager.as_mut() {
+                        power_manager.on_idle(&mut self.ctx);
+                    }
+                }
                 match operation.op_type {
                     DiskOperationType::Read => {
                         self.ctx.emit_now(
@@ -337,6 +436,20 @@ impl EventHandler for Disk {
                     }
                 }
             }
+            PowerStateTimeout {} => {
+                self.power_manager.as_mut().unwrap().on_timeout(&mut self.ctx);
+            }
+            PowerStateTransitionCompleted {} => {
+                let operations = self
+                    .power_manager
+                    .as_mut()
+                    .unwrap()
+                    .on_transition_completed(&mut self.ctx);
+                for operation in operations {
+                    self.scheduler.submit(operation, &mut self.ctx);
+                }
+                self.update_power();
+            }
         })
     }
 }
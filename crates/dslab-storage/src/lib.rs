@@ -4,6 +4,7 @@
 pub mod disk;
 pub mod events;
 pub mod fs;
+pub mod power;
 pub mod scheduler;
 pub mod storage;
 
@@ -0,0 +1,207 @@
+//! Disk power management.
+//!
+//! Tracks the power state and energy consumption of disk using SSD power model from the `dslab-models` crate.
+//! The disk can autonomously enter non-operational low-power states after staying idle for the configured time,
+//! similar to the autonomous power state transitions (APST) in NVMe drives. The operations arriving while the disk
+//! is in low-power state are delayed until the disk returns to the operational state.
+
+use std::collections::VecDeque;
+
+use serde::Serialize;
+
+use dslab_core::event::EventId;
+use dslab_core::SimulationContext;
+use dslab_models::power::ssd::{SsdPowerModel, SsdState};
+
+use crate::disk::DiskOperation;
+
+/// Internal event used to start the transition into the next low-power state.
+#[derive(Clone, Serialize)]
+pub(crate) struct PowerStateTimeout {}
+
+/// Internal event used to complete the transition between power states.
+#[derive(Clone, Serialize)]
+pub(crate) struct PowerStateTransitionCompleted {}
+
+/// Disk power state.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiskPowerState {
+    /// The disk is in operational state and can serve operations.
+    Operational,
+    /// The disk is entering the low-power state with the given index.
+    Entering(usize),
+    /// The disk is in the low-power state with the given index.
+    LowPower(usize),
+    /// The disk is returning to operational state.
+    Exiting,
+}
+
+pub(crate) struct PowerManager {
+    model: Box<dyn SsdPowerModel>,
+    low_power_timeouts: Vec<(usize, f64)>,
+    state: DiskPowerState,
+    ssd_state: SsdState,
+    next_timeout: usize,
+    idle_since: f64,
+    timer: Option<EventId>,
+    wake_requested: bool,
+    pending_ops: VecDeque<DiskOperation>,
+    energy_consumed: f64,
+    current_power: f64,
+    prev_time: f64,
+}
+
+impl PowerManager {
+    /// Creates power manager for initially idle disk.
+    ///
+    /// The `low_power_timeouts` contain the pairs of low-power state index and idle time after which the disk
+    /// enters this state, sorted by idle time.
+    pub fn new(
+        model: Box<dyn SsdPowerModel>,
+        low_power_timeouts: Vec<(usize, f64)>,
+        ctx: &mut SimulationContext,
+    ) -> Self {
+        let current_power = model.get_power(SsdState::Idle);
+        let mut manager = Self {
+            model,
+            low_power_timeouts,
+            state: DiskPowerState::Operational,
+            ssd_state: SsdState::Idle,
+            next_timeout: 0,
+            idle_since: ctx.time(),
+            timer: None,
+            wake_requested: false,
+            pending_ops: VecDeque::new(),
+            energy_consumed: 0.,
+            current_power,
+            prev_time: ctx.time(),
+        };
+        manager.schedule_timeout(ctx);
+        manager
+    }
+
+    pub fn state(&self) -> DiskPowerState {
+        self.state
+    }
+
+    pub fn ssd_state(&self) -> SsdState {
+        self.ssd_state
+    }
+
+    pub fn current_power(&self) -> f64 {
+        self.current_power
+    }
+
+    /// Returns the total energy consumption up to the given time.
+    pub fn energy_consumed(&self, time: f64) -> f64 {
+        self.energy_consumed + (time - self.prev_time) * self.current_power
+    }
+
+    fn set_ssd_state(&mut self, ssd_state: SsdState, time: f64) {
+        self.energy_consumed += (time - self.prev_time) * self.current_power;
+        self.ssd_state = ssd_state;
+        self.current_power = self.model.get_power(ssd_state);
+        self.prev_time = time;
+    }
+
+    /// Updates the power consumption of operational disk using the current disk throughput.
+    pub fn update_throughput(&mut self, read_throughput: f64, write_throughput: f64, time: f64) {
+        if self.state != DiskPowerState::Operational {
+            return;
+        }
+        let ssd_state = if read_throughput > 0. || write_throughput > 0. {
+            SsdState::Active {
+                read_throughput,
+                write_throughput,
+            }
+        } else {
+            SsdState::Idle
+        };
+        self.set_ssd_state(ssd_state, time);
+    }
+
+    /// Checks whether the operation can be submitted immediately, otherwise delays the operation
+    /// and starts returning to the operational state.
+    pub fn admit(&mut self, operation: DiskOperation, ctx: &mut SimulationContext) -> Option<DiskOperation> {
+        match self.state {
+            DiskPowerState::Operational => {
+                if let Some(timer) = self.timer.take() {
+                    ctx.cancel_event(timer);
+                }
+                Some(operation)
+            }
+            DiskPowerState::Entering(_) => {
+                self.wake_requested = true;
+                self.pending_ops.push_back(operation);
+                None
+            }
+            DiskPowerState::LowPower(index) => {
+                self.pending_ops.push_back(operation);
+                self.start_exit(index, ctx);
+                None
+            }
+            DiskPowerState::Exiting => {
+                self.pending_ops.push_back(operation);
+                None
+            }
+        }
+    }
+
+    /// Invoked when the disk becomes idle to schedule the transitions into low-power states.
+    pub fn on_idle(&mut self, ctx: &mut SimulationContext) {
+        self.idle_since = ctx.time();
+        self.next_timeout = 0;
+        self.schedule_timeout(ctx);
+    }
+
+    /// Processes [`PowerStateTimeout`] event by starting the transition into the next low-power state.
+    pub fn on_timeout(&mut self, ctx: &mut SimulationContext) {
+        self.timer = None;
+        let index = self.low_power_timeouts[self.next_timeout].0;
+        let entry_latency = self.model.low_power_state(index).unwrap().entry_latency;
+        self.state = DiskPowerState::Entering(index);
+        ctx.emit_self(PowerStateTransitionCompleted {}, entry_latency);
+    }
+
+    /// Processes [`PowerStateTransitionCompleted`] event, returns the delayed operations
+    /// if the disk has returned to the operational state.
+    pub fn on_transition_completed(&mut self, ctx: &mut SimulationContext) -> Vec<DiskOperation> {
+        match self.state {
+            DiskPowerState::Entering(index) => {
+                self.state = DiskPowerState::LowPower(index);
+                self.set_ssd_state(SsdState::LowPower(index), ctx.time());
+                self.next_timeout += 1;
+                if self.wake_requested {
+                    self.wake_requested = false;
+                    self.start_exit(index, ctx);
+                } else {
+                    self.schedule_timeout(ctx);
+                }
+                Vec::new()
+            }
+            DiskPowerState::Exiting => {
+                self.state = DiskPowerState::Operational;
+                self.set_ssd_state(SsdState::Idle, ctx.time());
+                self.pending_ops.drain(..).collect()
+            }
+            _ => panic!("Unexpected power state transition in state {:?}", self.state),
+        }
+    }
+
+    fn start_exit(&mut self, index: usize, ctx: &mut SimulationContext) {
+        if let Some(timer) = self.timer.take() {
+            ctx.cancel_event(timer);
+        }
+        let exit_latency = self.model.low_power_state(index).unwrap().exit_latency;
+        self.state = DiskPowerState::Exiting;
+        self.set_ssd_state(SsdState::Idle, ctx.time());
+        ctx.emit_self(PowerStateTransitionCompleted {}, exit_latency);
+    }
+
+    fn schedule_timeout(&mut self, ctx: &mut SimulationContext) {
+        if let Some(&(_, idle_time)) = self.low_power_timeouts.get(self.next_timeout) {
+            let delay = (self.idle_since + idle_time - ctx.time()).max(0.);
+            self.timer = Some(ctx.emit_self(PowerStateTimeout {}, delay));
+        }
+    }
+}
@@ -22,6 +22,15 @@ pub(crate) trait Scheduler {
     ///
     /// Returns the corresponding completed operation.
     fn complete(&mut self, request_id: u64, ctx: &mut SimulationContext) -> DiskOperation;
+
+    /// Returns the current total throughput of read operations.
+    fn read_throughput(&self) -> f64;
+
+    /// Returns the current total throughput of write operations.
+    fn write_throughput(&self) -> f64;
+
+    /// Returns true if there are no running or pending operations.
+    fn is_idle(&self) -> bool;
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -107,6 +116,18 @@ impl Scheduler for FifoScheduler {
         self.try_schedule(ctx);
         operation
     }
+
+    fn read_throughput(&self) -> f64 {
+        self.read_model.inner_throughput_model.total_throughput()
+    }
+
+    fn write_throughput(&self) -> f64 {
+        self.write_model.inner_throughput_model.total_throughput()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.total_ops_count == 0 && self.pending_ops.is_empty()
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
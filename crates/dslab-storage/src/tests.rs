@@ -5,10 +5,13 @@ use sugars::{rc, refcell};
 
 use dslab_core::simulation::Simulation;
 use dslab_core::{cast, Event, EventHandler};
+use dslab_models::power::ssd::SsdState;
+use dslab_models::power::ssd_models::throughput::ThroughputSsdPowerModel;
 
 use crate::disk::{Disk, DiskBuilder};
 use crate::events::*;
 use crate::fs::FileSystem;
+use crate::power::DiskPowerState;
 use crate::storage::{Storage, StorageInfo};
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -37,6 +40,20 @@ fn make_simple_disk(sim: &mut Simulation, name: &str) -> Rc<RefCell<Disk>> {
     disk
 }
 
+fn make_disk_with_power_model(sim: &mut Simulation, name: &str, low_power: bool) -> Rc<RefCell<Disk>> {
+    let power_model = ThroughputSsdPowerModel::new(2., 6., DISK_READ_BW, 8., DISK_WRITE_BW)
+        .with_low_power_state(0.5, 0.1, 0.2)
+        .with_low_power_state(0.1, 0.5, 1.);
+    let mut builder =
+        DiskBuilder::simple(DISK_CAPACITY, DISK_READ_BW, DISK_WRITE_BW).ssd_power_model(Box::new(power_model));
+    if low_power {
+        builder = builder.low_power_timeout(1, 5.).low_power_timeout(0, 1.);
+    }
+    let disk = rc!(refcell!(builder.build(sim.create_context(name))));
+    sim.add_handler(name, disk.clone());
+    disk
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 
 #[derive(PartialEq)]
@@ -634,3 +651,90 @@ fn disk_with_read_write_ops_limit() {
     assert_eq!(write_checker.borrow().received_events_count(), 2);
     assert_eq!(read_checker.borrow().received_events_count(), 2);
 }
+
+#[test]
+fn disk_energy_consumption() {
+    let mut sim = Simulation::new(SEED);
+
+    let checker = rc!(refcell!(Checker::new(ExpectedEventType::DataReadCompleted)));
+    let checker_id = sim.add_handler("User", checker.clone());
+
+    let write_checker = rc!(refcell!(Checker::new(ExpectedEventType::DataWriteCompleted)));
+    let write_checker_id = sim.add_handler("Writer", write_checker);
+
+    let disk = make_disk_with_power_model(&mut sim, "Disk-1", false);
+    assert_eq!(disk.borrow().ssd_state(), Some(SsdState::Idle));
+    assert_eq!(disk.borrow().current_power(), 2.);
+
+    // read at full throughput consumes 6 W during 0.5 s
+    disk.borrow_mut().read(50, checker_id);
+    assert_eq!(disk.borrow().current_power(), 6.);
+    sim.step_until_no_events();
+    assert_eq!(sim.time(), 0.5);
+    assert_eq!(checker.borrow().received_events_count(), 1);
+    assert_eq!(disk.borrow().energy_consumed(), 3.);
+
+    // idle disk consumes 2 W
+    sim.step_until_time(1.5);
+    assert_eq!(disk.borrow().power_state(), Some(DiskPowerState::Operational));
+    assert_eq!(disk.borrow().energy_consumed(), 5.);
+
+    // concurrent read and write at full throughput consume 2 + 4 + 6 = 12 W during 0.5 s
+    disk.borrow_mut().read(50, checker_id);
+    disk.borrow_mut().write(50, write_checker_id);
+    assert_eq!(
+        disk.borrow().ssd_state(),
+        Some(SsdState::Active {
+            read_throughput: DISK_READ_BW,
+            write_throughput: DISK_WRITE_BW
+        })
+    );
+    assert_eq!(disk.borrow().current_power(), 12.);
+    sim.step_until_time(2.);
+    assert_eq!(disk.borrow().energy_consumed(), 11.);
+}
+
+#[test]
+fn disk_low_power_states() {
+    let mut sim = Simulation::new(SEED);
+
+    let checker = rc!(refcell!(Checker::new(ExpectedEventType::DataReadCompleted)));
+    let checker_id = sim.add_handler("User", checker.clone());
+
+    let disk = make_disk_with_power_model(&mut sim, "Disk-1", true);
+
+    // the first low-power state is entered at 1 + 0.1
+    sim.step_until_time(1.05);
+    assert_eq!(disk.borrow().power_state(), Some(DiskPowerState::Entering(0)));
+    assert_eq!(disk.borrow().current_power(), 2.);
+    sim.step_until_time(2.);
+    assert_eq!(disk.borrow().power_state(), Some(DiskPowerState::LowPower(0)));
+    assert_eq!(disk.borrow().current_power(), 0.5);
+
+    // the second low-power state is entered at 5 + 0.5
+    sim.step_until_time(10.);
+    assert_eq!(disk.borrow().power_state(), Some(DiskPowerState::LowPower(1)));
+    assert_eq!(disk.borrow().current_power(), 0.1);
+
+    // the read is delayed by exit latency of 1 s
+    disk.borrow_mut().read(50, checker_id);
+    assert_eq!(disk.borrow().power_state(), Some(DiskPowerState::Exiting));
+    sim.step_until_time(11.4);
+    assert_eq!(disk.borrow().power_state(), Some(DiskPowerState::Operational));
+    assert_eq!(checker.borrow().received_events_count(), 0);
+    sim.step_until_time(12.);
+    assert_eq!(checker.borrow().received_events_count(), 1);
+
+    let expected_energy = 2. * 1.1 + 0.5 * 4.4 + 0.1 * 4.5 + 2. * 1. + 6. * 0.5 + 2. * 0.5;
+    assert!((disk.borrow().energy_consumed() - expected_energy).abs() < 1e-12);
+
+    // the read arriving during the entry into low-power state is delayed until the entry and exit are completed
+    sim.step_until_time(12.55);
+    assert_eq!(disk.borrow().power_state(), Some(DiskPowerState::Entering(0)));
+    disk.borrow_mut().read(50, checker_id);
+    sim.step_until_time(13.);
+    assert_eq!(disk.borrow().power_state(), Some(DiskPowerState::Operational));
+    assert_eq!(checker.borrow().received_events_count(), 1);
+    sim.step_until_time(13.5);
+    assert_eq!(checker.borrow().received_events_count(), 2);
+}
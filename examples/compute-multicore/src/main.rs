@@ -68,7 +68,11 @@ impl EventHandler for Task {
                     self.id,
                 );
             }
-            CompStarted { id, cores } => {
+            CompStarted {
+                id,
+                core_group: _,
+                cores,
+            } => {
                 println!(
                     "{} [{}] received CompStarted from {} for {:?} on {} cores",
                     self.ctx.time(),
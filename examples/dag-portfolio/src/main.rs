@@ -159,6 +159,8 @@ fn run_experiments(args: &Args) {
                     speed,
                     cores,
                     memory,
+                    core_groups: Vec::new(),
+                    accelerator: None,
                 });
             }
         }
@@ -198,7 +198,11 @@ impl EventHandler for Worker {
             DataReadCompleted { request_id, size: _ } => {
                 self.on_data_read_completed(request_id);
             }
-            CompStarted { id, cores: _ } => {
+            CompStarted {
+                id,
+                core_group: _,
+                cores: _,
+            } => {
                 self.on_comp_started(id);
             }
             CompFinished { id } => {
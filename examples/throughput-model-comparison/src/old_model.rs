@@ -4,7 +4,7 @@ use std::collections::BinaryHeap;
 use sugars::boxed;
 
 use dslab_core::SimulationContext;
-use dslab_models::throughput_sharing::ThroughputSharingModel;
+use dslab_models::throughput_sharing::{ActivityId, ThroughputSharingModel};
 
 struct Activity<T> {
     position: f64,
@@ -95,7 +95,7 @@ impl<T> FairThroughputSharingModel<T> {
 }
 
 impl<T> ThroughputSharingModel<T> for FairThroughputSharingModel<T> {
-    fn insert(&mut self, item: T, volume: f64, ctx: &mut SimulationContext) {
+    fn insert(&mut self, item: T, volume: f64, ctx: &mut SimulationContext) -> ActivityId {
         if self.entries.is_empty() {
             self.last_throughput_per_item = (self.throughput_function)(1);
             let finish_time = ctx.time() + volume / self.last_throughput_per_item;
@@ -115,6 +115,7 @@ impl<T> ThroughputSharingModel<T> for FairThroughputSharingModel<T> {
             ));
         }
         self.next_id += 1;
+        self.next_id - 1
     }
 
     fn pop(&mut self) -> Option<(f64, T)> {